@@ -0,0 +1,23 @@
+//! A worker that solves the jobs submitted to a directory with `good_lp::batch`.
+//!
+//! Run it on as many machines as needed, with the same shared directory:
+//! `cargo run --release --example batch_worker -- /mnt/shared/jobs`
+use std::thread;
+use std::time::Duration;
+
+use good_lp::batch::{run_worker, DirectoryQueue};
+use good_lp::default_solver;
+
+fn main() -> std::io::Result<()> {
+    let directory = std::env::args()
+        .nth(1)
+        .expect("usage: batch_worker <queue directory>");
+    let queue = DirectoryQueue::new(directory)?;
+    loop {
+        let solved = run_worker(&queue, default_solver)?;
+        if solved > 0 {
+            println!("solved {} jobs", solved);
+        }
+        thread::sleep(Duration::from_secs(5));
+    }
+}
@@ -3,9 +3,7 @@
 //!
 use std::collections::{hash_map::Entry, HashMap};
 
-use good_lp::{
-    constraint, variable, variables, Expression, Solution, SolverModel, StaticSolver, Variable,
-};
+use good_lp::{constraint, variable, variables, Expression, SolverModel, StaticSolver};
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub enum Dish {
@@ -89,13 +87,7 @@ fn solve_diet_example<S: StaticSolver>(
     let mut vars = variables!();
 
     // Free Variables
-    let food_vars = Dish::FOODS
-        .iter()
-        .map(|f| {
-            let f_var = vars.add(variable().min(0.0));
-            (f, f_var)
-        })
-        .collect::<HashMap<&Dish, Variable>>();
+    let food_vars = vars.add_map(variable().min(0.0), Dish::FOODS.iter());
 
     println!("{:?}", food_vars);
 
@@ -140,7 +132,9 @@ fn solve_diet_example<S: StaticSolver>(
         match guide.limit.level {
             Quantity::Value => continue,
             Quantity::Min => {
-                p.add_constraint(constraint!(food_sum >= guide.limit.volume + 0.0001));
+                p.add_constraint(
+                    constraint!(food_sum > guide.limit.volume).set_strict_epsilon(0.0001),
+                );
             }
             Quantity::Max => {
                 p.add_constraint(constraint!(food_sum <= guide.limit.volume));
@@ -150,9 +144,9 @@ fn solve_diet_example<S: StaticSolver>(
 
     // Solve Problem
     let solution = p.solve().expect("Library test");
+    let counts = food_vars.values(&solution);
     for food in &Dish::FOODS {
-        let dish_var = food_vars.get(food).expect("Library test");
-        println!("Food {:?} Count {:?}", food, solution.value(*dish_var));
+        println!("Food {:?} Count {:?}", food, counts[&food]);
     }
 
     Ok(())
@@ -0,0 +1,319 @@
+//! The `LpVariables` derive macro of [good_lp](https://docs.rs/good_lp).
+//!
+//! It is re-exported by good_lp when its `derive` feature is enabled,
+//! and documented there, with the `LpVariables` trait that it implements.
+#![deny(missing_docs)]
+extern crate proc_macro;
+
+use std::iter::Peekable;
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Implements `good_lp::LpVariables` for a struct with named fields
+/// of type `f64`, `Vec<f64>` or `HashMap<K, f64>`,
+/// and defines the struct of their variables.
+#[proc_macro_derive(LpVariables, attributes(lp))]
+pub fn derive_lp_variables(input: TokenStream) -> TokenStream {
+    let code = match parse_struct(input) {
+        Ok(parsed) => parsed.expand(),
+        Err(message) => format!("compile_error!({:?});", message),
+    };
+    code.parse().expect("invalid generated code")
+}
+
+type Tokens = Peekable<std::vec::IntoIter<TokenTree>>;
+
+/// The struct the macro is derived for
+struct Struct {
+    visibility: String,
+    name: String,
+    fields: Vec<Field>,
+}
+
+struct Field {
+    visibility: String,
+    name: String,
+    kind: Kind,
+    /// The expression building the `VariableDefinition` of the field
+    definition: String,
+}
+
+enum Kind {
+    Scalar,
+    Vector,
+    Map { key: String },
+}
+
+impl Struct {
+    fn expand(&self) -> String {
+        let variables = format!("{}Variables", self.name);
+        let mut fields = String::new();
+        let mut added = String::new();
+        let mut values = String::new();
+        for Field {
+            visibility,
+            name,
+            kind,
+            definition,
+        } in &self.fields
+        {
+            let (field_type, add, value) = match kind {
+                Kind::Scalar => (
+                    "::good_lp::Variable".to_string(),
+                    format!("vars.add({})", definition),
+                    format!("solution.value(variables.{})", name),
+                ),
+                Kind::Vector => (
+                    "::std::vec::Vec<::good_lp::Variable>".to_string(),
+                    format!(
+                        "self.{}.iter().map(|_| vars.add({})).collect()",
+                        name, definition
+                    ),
+                    format!(
+                        "variables.{}.iter().map(|&variable| solution.value(variable)).collect()",
+                        name
+                    ),
+                ),
+                Kind::Map { key } => (
+                    format!(
+                        "::std::collections::HashMap<{}, ::good_lp::Variable>",
+                        key
+                    ),
+                    format!(
+                        "self.{}.keys().map(|key| (::std::clone::Clone::clone(key), vars.add({}))).collect()",
+                        name, definition
+                    ),
+                    format!(
+                        "variables.{}.iter().map(|(key, &variable)| (::std::clone::Clone::clone(key), solution.value(variable))).collect()",
+                        name
+                    ),
+                ),
+            };
+            fields += &format!(
+                "#[doc = \"The variables of `{}`\"] {} {}: {},",
+                name, visibility, name, field_type
+            );
+            added += &format!("{}: {},", name, add);
+            values += &format!("{}: {},", name, value);
+        }
+        format!(
+            "#[doc = \"The variables of [{name}], created by its `LpVariables` implementation\"]
+            #[derive(Clone)]
+            {visibility} struct {variables} {{ {fields} }}
+
+            impl ::good_lp::LpVariables for {name} {{
+                type Variables = {variables};
+
+                fn add_variables(&self, vars: &mut ::good_lp::ProblemVariables) -> {variables} {{
+                    {variables} {{ {added} }}
+                }}
+
+                fn from_solution<S: ::good_lp::Solution>(variables: &{variables}, solution: &S) -> Self {{
+                    {name} {{ {values} }}
+                }}
+            }}",
+            name = self.name,
+            visibility = self.visibility,
+            variables = variables,
+            fields = fields,
+            added = added,
+            values = values,
+        )
+    }
+}
+
+fn parse_struct(input: TokenStream) -> Result<Struct, String> {
+    let mut tokens: Tokens = input.into_iter().collect::<Vec<_>>().into_iter().peekable();
+    attributes(&mut tokens);
+    let visibility = visibility(&mut tokens);
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => {}
+        _ => return Err("LpVariables can only be derived for structs".to_string()),
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("expected the name of the struct".to_string()),
+    };
+    match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+            let fields = split_top_level(group.stream(), true)
+                .into_iter()
+                .map(parse_field)
+                .collect::<Result<_, _>>()?;
+            Ok(Struct {
+                visibility,
+                name,
+                fields,
+            })
+        }
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => Err(format!(
+            "LpVariables cannot be derived for the generic struct {}",
+            name
+        )),
+        _ => Err(format!(
+            "LpVariables can only be derived for structs with named fields, which {} does not have",
+            name
+        )),
+    }
+}
+
+fn parse_field(tokens: Vec<TokenTree>) -> Result<Field, String> {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut definition = "::good_lp::variable()".to_string();
+    for attribute in attributes(&mut tokens) {
+        let mut attribute = attribute.into_iter();
+        match (attribute.next(), attribute.next()) {
+            (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(arguments)))
+                if ident.to_string() == "lp" =>
+            {
+                definition += &bounds(arguments.stream())?
+            }
+            // Documentation and the attributes of other macros
+            _ => {}
+        }
+    }
+    let visibility = visibility(&mut tokens);
+    let name = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(colon)))
+            if colon.as_char() == ':' =>
+        {
+            ident.to_string()
+        }
+        _ => return Err("expected a named field".to_string()),
+    };
+    let field_type: Vec<TokenTree> = tokens.collect();
+    let kind = kind(&field_type).ok_or_else(|| {
+        format!(
+            "the field {} must be a f64, a Vec<f64> or a HashMap<K, f64>, not {}",
+            name,
+            field_type.into_iter().collect::<TokenStream>()
+        )
+    })?;
+    Ok(Field {
+        visibility,
+        name,
+        kind,
+        definition,
+    })
+}
+
+/// Parses the arguments of `#[lp(...)]` into calls to the methods of `VariableDefinition`
+fn bounds(arguments: TokenStream) -> Result<String, String> {
+    let mut calls = String::new();
+    for argument in split_top_level(arguments, false) {
+        let text = argument
+            .iter()
+            .cloned()
+            .collect::<TokenStream>()
+            .to_string();
+        let mut argument = argument.into_iter();
+        let key = match argument.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err(format!("invalid lp attribute: {}", text)),
+        };
+        calls += &match (key.as_str(), argument.next()) {
+            ("integer", None) | ("binary", None) => format!(".{}()", key),
+            ("min", Some(TokenTree::Punct(equals))) | ("max", Some(TokenTree::Punct(equals)))
+                if equals.as_char() == '=' =>
+            {
+                format!(".{}({})", key, argument.collect::<TokenStream>())
+            }
+            _ => {
+                return Err(format!(
+                    "invalid lp attribute: {}, expected integer, binary, min = ... or max = ...",
+                    text
+                ))
+            }
+        };
+    }
+    Ok(calls)
+}
+
+/// Recognizes `f64`, `Vec<f64>` and `HashMap<K, f64>`
+fn kind(field_type: &[TokenTree]) -> Option<Kind> {
+    let is_f64 = |tokens: &[TokenTree]| match tokens {
+        [TokenTree::Ident(ident)] => ident.to_string() == "f64",
+        _ => false,
+    };
+    if is_f64(field_type) {
+        return Some(Kind::Scalar);
+    }
+    let open = field_type.iter().position(|token| is_punct(token, '<'))?;
+    if open == 0 || !is_punct(field_type.last()?, '>') {
+        return None;
+    }
+    let container = field_type[open - 1].to_string();
+    let arguments = split_top_level(
+        field_type[open + 1..field_type.len() - 1]
+            .iter()
+            .cloned()
+            .collect(),
+        true,
+    );
+    match (container.as_str(), arguments.as_slice()) {
+        ("Vec", [value]) if is_f64(value) => Some(Kind::Vector),
+        ("HashMap", [key, value]) if is_f64(value) => Some(Kind::Map {
+            key: key.iter().cloned().collect::<TokenStream>().to_string(),
+        }),
+        _ => None,
+    }
+}
+
+/// Removes the attributes at the start of the tokens, and returns their contents
+fn attributes(tokens: &mut Tokens) -> Vec<TokenStream> {
+    let mut attributes = vec![];
+    while matches!(tokens.peek(), Some(token) if is_punct(token, '#')) {
+        tokens.next();
+        if let Some(TokenTree::Group(group)) = tokens.next() {
+            attributes.push(group.stream());
+        }
+    }
+    attributes
+}
+
+/// Removes the visibility at the start of the tokens, such as `pub` or `pub(crate)`, and returns it
+fn visibility(tokens: &mut Tokens) -> String {
+    let is_pub = match tokens.peek() {
+        Some(TokenTree::Ident(ident)) => ident.to_string() == "pub",
+        _ => false,
+    };
+    if !is_pub {
+        return String::new();
+    }
+    let mut visibility = tokens.next().unwrap().to_string();
+    let restricted = match tokens.peek() {
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Parenthesis,
+        _ => false,
+    };
+    if restricted {
+        visibility += &tokens.next().unwrap().to_string();
+    }
+    visibility
+}
+
+/// Splits the tokens at the commas that are not nested in a group,
+/// nor between angle brackets when `in_types` is true
+fn split_top_level(tokens: TokenStream, in_types: bool) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![vec![]];
+    let mut depth = 0;
+    for token in tokens {
+        if in_types && is_punct(&token, '<') {
+            depth += 1;
+        } else if in_types && is_punct(&token, '>') {
+            depth -= 1;
+        } else if depth == 0 && is_punct(&token, ',') {
+            parts.push(vec![]);
+            continue;
+        }
+        parts.last_mut().unwrap().push(token);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    match token {
+        TokenTree::Punct(punct) => punct.as_char() == c,
+        _ => false,
+    }
+}
@@ -62,7 +62,7 @@ pub trait IntoAffineExpression {
     /// let value = expr.eval_with(&var_mapping);
     /// assert_eq!(value, 8.);
     /// ```
-    fn eval_with<S: Solution>(self, values: &S) -> f64
+    fn eval_with<S: Solution + ?Sized>(self, values: &S) -> f64
     where
         Self: Sized,
     {
@@ -0,0 +1,236 @@
+//! Summaries of the values taken by groups of variables in a solution,
+//! to inspect large solutions without exporting every value,
+//! and [comparisons](compare_solutions) of solutions returned by different solvers.
+//!
+//! ```
+//! use good_lp::analysis::{describe, histogram};
+//! use good_lp::{variable, variables, Variable};
+//! use std::collections::HashMap;
+//!
+//! let mut vars = variables!();
+//! let utilization: Vec<Variable> = vars.add_vector(variable().clamp(0, 1), 4);
+//! // Usually, the solution comes from a solver
+//! let solution: HashMap<Variable, f64> =
+//!     utilization.iter().copied().zip(vec![0., 0.25, 0.75, 1.]).collect();
+//!
+//! let stats = describe(&solution, &utilization);
+//! assert_eq!(stats.mean, 0.5);
+//! let histogram = histogram(&solution, &utilization, 2);
+//! assert_eq!(histogram.counts, vec![2, 2]);
+//! ```
+use crate::{Expression, Solution, Variable};
+
+/// The number of values that fall into equal-width intervals, computed by [histogram]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// The lower bound of the first bin: the smallest value
+    pub min: f64,
+    /// The upper bound of the last bin: the largest value
+    pub max: f64,
+    /// The number of values in each bin
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// The width of each bin
+    pub fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+
+    /// The lower and upper bounds of each bin
+    pub fn bins(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let width = self.bin_width();
+        (0..self.counts.len()).map(move |i| {
+            let lower = self.min + i as f64 * width;
+            let upper = if i + 1 == self.counts.len() {
+                self.max
+            } else {
+                lower + width
+            };
+            (lower, upper)
+        })
+    }
+}
+
+/// Descriptive statistics of the values of a group of variables, computed by [describe]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    /// The number of variables
+    pub count: usize,
+    /// The sum of the values
+    pub sum: f64,
+    /// The smallest value
+    pub min: f64,
+    /// The largest value
+    pub max: f64,
+    /// The arithmetic mean of the values
+    pub mean: f64,
+    /// The median of the values
+    pub median: f64,
+    /// The population standard deviation of the values
+    pub std_dev: f64,
+}
+
+/// Counts the values of the variables in `bins` intervals of equal width,
+/// between the smallest and the largest value.
+/// Every bin includes its lower bound, and the last one also includes its upper bound.
+///
+/// If all the values are equal, they are all counted in the first bin.
+///
+/// Panics if `bins` is 0 or `variables` is empty.
+pub fn histogram<S: Solution>(solution: &S, variables: &[Variable], bins: usize) -> Histogram {
+    assert!(bins > 0, "a histogram needs at least one bin");
+    let values = values(solution, variables);
+    let (min, max) = min_max(&values);
+    let mut counts = vec![0; bins];
+    let width = (max - min) / bins as f64;
+    for value in values {
+        let bin = if width > 0. {
+            (((value - min) / width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        counts[bin] += 1;
+    }
+    Histogram { min, max, counts }
+}
+
+/// Computes descriptive statistics of the values of the variables.
+///
+/// Panics if `variables` is empty.
+pub fn describe<S: Solution>(solution: &S, variables: &[Variable]) -> Statistics {
+    let mut values = values(solution, variables);
+    let (min, max) = min_max(&values);
+    let count = values.len();
+    let sum: f64 = values.iter().sum();
+    let mean = sum / count as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count as f64;
+    values.sort_by(f64::total_cmp);
+    let median = if count % 2 == 1 {
+        values[count / 2]
+    } else {
+        (values[count / 2 - 1] + values[count / 2]) / 2.
+    };
+    Statistics {
+        count,
+        sum,
+        min,
+        max,
+        mean,
+        median,
+        std_dev: variance.sqrt(),
+    }
+}
+
+fn values<S: Solution>(solution: &S, variables: &[Variable]) -> Vec<f64> {
+    assert!(!variables.is_empty(), "no variable to analyse");
+    variables.iter().map(|&v| solution.value(v)).collect()
+}
+
+fn min_max(values: &[f64]) -> (f64, f64) {
+    values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+            (min.min(v), max.max(v))
+        })
+}
+
+/// Whether two objective values are equal, up to a relative or an absolute tolerance:
+/// `|a - b| <= max(rel_tol * max(|a|, |b|), abs_tol)`.
+///
+/// ```
+/// use good_lp::analysis::objective_close;
+/// assert!(objective_close(1e6, 1e6 + 0.5, 1e-6, 1e-9));
+/// assert!(objective_close(0., 1e-10, 1e-6, 1e-9));
+/// assert!(!objective_close(1., 1.1, 1e-6, 1e-9));
+/// ```
+pub fn objective_close(a: f64, b: f64, rel_tol: f64, abs_tol: f64) -> bool {
+    if a == b {
+        // Handles infinite values
+        return true;
+    }
+    let tolerance = (rel_tol * a.abs().max(b.abs())).max(abs_tol);
+    (a - b).abs() <= tolerance
+}
+
+/// The differences between several solutions of the same problem, computed by [compare_solutions]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionComparison {
+    /// The objective value of each solution
+    pub objectives: Vec<f64>,
+    /// Whether all objective values are close to the one of the first solution
+    pub objectives_close: bool,
+    /// The largest absolute difference between the value of a variable
+    /// in one of the solutions, and its value in the first solution
+    pub max_deviation: f64,
+    /// The variable with the largest deviation, if any variable has a different value
+    pub max_deviation_variable: Option<Variable>,
+    /// The index of the solution with the largest deviation, if any variable has a different value
+    pub max_deviation_solution: Option<usize>,
+}
+
+/// Compares solutions of the same problem, for instance returned by different solvers,
+/// to the first one.
+///
+/// Different solvers can return different optimal solutions to the same problem,
+/// so variables may deviate even when the objective values are close.
+///
+/// ```
+/// use good_lp::analysis::compare_solutions;
+/// use good_lp::{variable, variables, Expression, Solution, Variable};
+/// use std::collections::HashMap;
+///
+/// let mut vars = variables!();
+/// let x = vars.add(variable());
+/// let y = vars.add(variable());
+/// // Usually, the solutions come from solvers
+/// let a: HashMap<Variable, f64> = vec![(x, 1.), (y, 0.)].into_iter().collect();
+/// let b: HashMap<Variable, f64> = vec![(x, 0.), (y, 1.)].into_iter().collect();
+/// let comparison = compare_solutions(&[&a, &b], &[x, y], &(x + y), 1e-9, 1e-9);
+/// assert!(comparison.objectives_close);
+/// assert_eq!(comparison.max_deviation, 1.);
+/// ```
+pub fn compare_solutions(
+    solutions: &[&dyn Solution],
+    variables: &[Variable],
+    objective: &Expression,
+    rel_tol: f64,
+    abs_tol: f64,
+) -> SolutionComparison {
+    let objectives: Vec<f64> = solutions
+        .iter()
+        .map(|solution| {
+            objective.constant
+                + objective
+                    .linear
+                    .coefficients
+                    .iter()
+                    .map(|(&var, &coefficient)| coefficient * solution.value(var))
+                    .sum::<f64>()
+        })
+        .collect();
+    let objectives_close = objectives
+        .iter()
+        .all(|&o| objective_close(o, objectives[0], rel_tol, abs_tol));
+    let mut comparison = SolutionComparison {
+        objectives,
+        objectives_close,
+        max_deviation: 0.,
+        max_deviation_variable: None,
+        max_deviation_solution: None,
+    };
+    if let Some((reference, others)) = solutions.split_first() {
+        for &var in variables {
+            let expected = reference.value(var);
+            for (i, solution) in others.iter().enumerate() {
+                let deviation = (solution.value(var) - expected).abs();
+                if deviation > comparison.max_deviation {
+                    comparison.max_deviation = deviation;
+                    comparison.max_deviation_variable = Some(var);
+                    comparison.max_deviation_solution = Some(i + 1);
+                }
+            }
+        }
+    }
+    comparison
+}
@@ -0,0 +1,240 @@
+//! Getting the best solution that can be found within a fixed amount of time.
+//!
+//! Planning often has a deadline: the plan is needed by 3pm, whether it is optimal or not.
+//! [solve_anytime] solves a problem in rounds with increasing
+//! [time limits](crate::ModelWithTimeLimit), that all fit in a total time budget.
+//! Each round only looks for solutions at least as good as the best one found so far,
+//! which is reported to a callback as soon as it is found.
+//! The resolution stops when a round proves that the best solution is optimal,
+//! or when the budget is spent, and the best solution is returned.
+//!
+//! ```
+//! # // Not all solvers support time limits
+//! # #[cfg(feature = "coin_cbc")] {
+//! use good_lp::anytime::solve_anytime;
+//! use good_lp::{coin_cbc, constraint, variable, variables, Solution};
+//! use std::time::Duration;
+//!
+//! let mut vars = variables!();
+//! let x = vars.add(variable().integer().clamp(0, 10));
+//! let y = vars.add(variable().integer().clamp(0, 10));
+//! let problem = vars
+//!     .maximise(3 * x + 2 * y)
+//!     .with(constraint!(2 * x + 3 * y <= 17));
+//! let mut improvements = 0;
+//! let solution = solve_anytime(problem, coin_cbc, Duration::from_secs(10), |_| {
+//!     improvements += 1
+//! })?;
+//! assert!(solution.status().is_optimal());
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(solution.objective(), 24.);
+//! assert!(improvements >= 1);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::time::{Duration, Instant};
+
+use crate::solvers::{
+    ModelWithTimeLimit, ObjectiveDirection, ResolutionError, Solution, SolutionStatus, SolveStats,
+    Solver, SolverModel,
+};
+use crate::variable::UnsolvedProblem;
+use crate::Variable;
+
+/// The share of the budget given to the first round. Each next round gets twice as much time.
+const FIRST_ROUND_SHARE: u32 = 8;
+
+/// The best solution found by [solve_anytime]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnytimeSolution {
+    pub(crate) values: Vec<f64>,
+    pub(crate) objective: f64,
+    pub(crate) best_bound: Option<f64>,
+    pub(crate) status: SolutionStatus,
+    pub(crate) stats: SolveStats,
+    pub(crate) elapsed: Duration,
+}
+
+impl AnytimeSolution {
+    /// The value of the objective of the problem in this solution
+    pub fn objective(&self) -> f64 {
+        self.objective
+    }
+
+    /// The time elapsed between the start of the resolution and the moment
+    /// this solution was found
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl Solution for AnytimeSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+
+    /// [SolutionStatus::Optimal] if a round proved that the solution is optimal,
+    /// and [SolutionStatus::TimeLimit] otherwise
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+
+    /// The bound reported by the solver in the round that found the solution
+    fn best_bound(&self) -> Option<f64> {
+        self.best_bound
+    }
+
+    /// The statistics of the round that found the solution
+    fn stats(&self) -> SolveStats {
+        self.stats.clone()
+    }
+}
+
+/// Solves the problem in rounds until the budget is spent, and returns the best solution found.
+/// `on_incumbent` is called every time a better solution is found,
+/// for instance to save it or display it.
+///
+/// Every round is a new resolution, with a time limit that fits in what remains of the budget.
+/// The solvers stop close to their time limit, but not exactly on it,
+/// so the total time can slightly exceed the budget.
+///
+/// Returns an error if the problem is infeasible or unbounded,
+/// or if no solution was found within the budget. In that case,
+/// the error is the one returned by the solver in the last round.
+pub fn solve_anytime<S, F>(
+    problem: UnsolvedProblem,
+    solver: S,
+    budget: Duration,
+    on_incumbent: F,
+) -> Result<AnytimeSolution, ResolutionError>
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithTimeLimit,
+    F: FnMut(&AnytimeSolution),
+{
+    solve_from(problem, solver, budget, None, on_incumbent)
+}
+
+/// Like [solve_anytime], but starting from a known solution of the problem,
+/// which is returned if no better one is found
+pub(crate) fn solve_from<S, F>(
+    problem: UnsolvedProblem,
+    solver: S,
+    budget: Duration,
+    best: Option<AnytimeSolution>,
+    on_incumbent: F,
+) -> Result<AnytimeSolution, ResolutionError>
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithTimeLimit,
+    F: FnMut(&AnytimeSolution),
+{
+    let first_round = budget / FIRST_ROUND_SHARE;
+    solve_in_rounds(
+        problem,
+        solver,
+        budget,
+        first_round,
+        best,
+        on_incumbent,
+        || false,
+    )
+}
+
+/// Like [solve_from], with the time limit of the first round,
+/// and a function that is called before each round to know whether to stop early
+pub(crate) fn solve_in_rounds<S, F, C>(
+    problem: UnsolvedProblem,
+    solver: S,
+    budget: Duration,
+    first_round: Duration,
+    mut best: Option<AnytimeSolution>,
+    mut on_incumbent: F,
+    stopped: C,
+) -> Result<AnytimeSolution, ResolutionError>
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithTimeLimit,
+    F: FnMut(&AnytimeSolution),
+    C: Fn() -> bool,
+{
+    let start = Instant::now();
+    let variables: Vec<Variable> = problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(v, _)| v)
+        .collect();
+    let objective = problem.objective.clone();
+    let direction = problem.direction;
+    let mut proved_optimal = false;
+    let mut last_error = ResolutionError::Other("the time budget is empty");
+    let mut round_limit = first_round;
+    loop {
+        let remaining = budget.saturating_sub(start.elapsed());
+        if remaining.is_zero() || stopped() {
+            break;
+        }
+        let limit = round_limit.min(remaining);
+        round_limit = round_limit.saturating_mul(2);
+        let mut round = problem.clone();
+        if let Some(incumbent) = &best {
+            // Only look for solutions that are at least as good as the incumbent
+            let cutoff = match direction {
+                ObjectiveDirection::Maximisation => objective.clone().geq(incumbent.objective),
+                ObjectiveDirection::Minimisation => objective.clone().leq(incumbent.objective),
+            };
+            round.add_constraint(cutoff);
+        }
+        let round_start = Instant::now();
+        let solution = match round.using(solver.clone()).with_time_limit(limit).solve() {
+            Ok(solution) => solution,
+            // The incumbent is feasible in the round, so no better solution exists
+            Err(ResolutionError::Infeasible) if best.is_some() => {
+                proved_optimal = true;
+                break;
+            }
+            Err(error @ ResolutionError::Infeasible) | Err(error @ ResolutionError::Unbounded) => {
+                return Err(error);
+            }
+            // Without a solution, most solvers report reaching the time limit as an error
+            Err(error) if round_start.elapsed() >= limit / 2 => {
+                last_error = error;
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+        proved_optimal = solution.status().is_optimal();
+        let candidate = AnytimeSolution {
+            values: variables.iter().map(|&v| solution.value(v)).collect(),
+            objective: solution.eval(&objective),
+            best_bound: solution.best_bound(),
+            status: if proved_optimal {
+                SolutionStatus::Optimal
+            } else {
+                SolutionStatus::TimeLimit
+            },
+            stats: solution.stats(),
+            elapsed: start.elapsed(),
+        };
+        let improves = best.as_ref().is_none_or(|incumbent| match direction {
+            ObjectiveDirection::Maximisation => candidate.objective > incumbent.objective,
+            ObjectiveDirection::Minimisation => candidate.objective < incumbent.objective,
+        });
+        if improves {
+            on_incumbent(&candidate);
+            best = Some(candidate);
+        }
+        if proved_optimal {
+            break;
+        }
+    }
+    match best {
+        Some(mut solution) => {
+            if proved_optimal {
+                solution.status = SolutionStatus::Optimal;
+            }
+            Ok(solution)
+        }
+        None => Err(last_error),
+    }
+}
@@ -0,0 +1,360 @@
+//! Solving many problems on other machines, through a job queue.
+//!
+//! Teams that solve thousands of models every night run them on a pool of workers.
+//! Models are [submitted](submit) to a [JobQueue] in the MPS format, a worker
+//! takes them from the queue one by one and [solves them](run_worker), and the results are
+//! [collected](collect) once they are ready, as [BatchSolution]s.
+//! Workers can also be HTTP servers, that answer the requests of a
+//! [RemoteSolver](crate::solvers::remote::RemoteSolver) with [solve_job].
+//!
+//! [DirectoryQueue] is a queue stored in a directory, which can be shared by several machines
+//! on a network file system. Other stores, such as an S3-compatible object store,
+//! can be used by implementing [JobQueue].
+//! The `batch_worker` example is a worker that solves the jobs of a directory.
+//!
+//! ```
+//! use good_lp::batch::{collect, run_worker, submit, DirectoryQueue};
+//! use good_lp::{constraint, default_solver, variables, ResolutionError, Solution};
+//! use std::time::Duration;
+//!
+//! # let _ = std::fs::remove_dir_all(std::env::temp_dir().join("good_lp_batch_doctest"));
+//! let queue = DirectoryQueue::new(std::env::temp_dir().join("good_lp_batch_doctest"))?;
+//! variables! {vars: 0 <= x <= 10;}
+//! submit(&queue, "feasible", &vars.maximise(x).with(constraint!(x <= 3)))?;
+//! variables! {vars: 0 <= x <= 10;}
+//! submit(&queue, "infeasible", &vars.maximise(x).with(constraint!(x >= 11)))?;
+//! // This usually runs on other machines
+//! assert_eq!(run_worker(&queue, default_solver)?, 2);
+//! let results = collect(&queue, &["feasible", "infeasible"], Duration::from_millis(10))?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(results["feasible"].as_ref().unwrap().value(x), 3.);
+//! assert_eq!(results["infeasible"].as_ref().err(), Some(&ResolutionError::Infeasible));
+//! # std::fs::remove_dir_all(std::env::temp_dir().join("good_lp_batch_doctest"))?;
+//! # Ok::<_, std::io::Error>(())
+//! ```
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::formats::mps;
+use crate::solvers::{ResolutionError, Solution, SolutionStatus, Solver, SolverModel};
+use crate::variable::UnsolvedProblem;
+use crate::Variable;
+
+/// A store of jobs, shared by the code that submits problems and the workers that solve them.
+///
+/// Jobs are identified by a name chosen when they are submitted.
+/// Models and results are opaque bytes for the queue.
+pub trait JobQueue {
+    /// The error that can occur while accessing the queue
+    type Error: std::error::Error;
+
+    /// Adds a job to the queue. Its model is a problem in the free MPS format.
+    fn submit(&self, job: &str, model: &[u8]) -> Result<(), Self::Error>;
+
+    /// Takes a job from the queue, so that no other worker takes it,
+    /// and returns its name and its model. Returns `None` if there is no job to do.
+    fn claim(&self) -> Result<Option<(String, Vec<u8>)>, Self::Error>;
+
+    /// Stores the result of a job returned by [JobQueue::claim]
+    fn complete(&self, job: &str, result: &[u8]) -> Result<(), Self::Error>;
+
+    /// The result of a job, or `None` if it is not completed yet
+    fn result(&self, job: &str) -> Result<Option<Vec<u8>>, Self::Error>;
+}
+
+/// A [JobQueue] in a directory, with one file per job.
+///
+/// Submitted jobs are in the `pending` subdirectory, jobs being solved in `running`,
+/// and results in `done`. Files are moved between them by renaming,
+/// which is atomic on local and most network file systems,
+/// so several workers can share the same directory.
+#[derive(Debug, Clone)]
+pub struct DirectoryQueue {
+    root: PathBuf,
+}
+
+impl DirectoryQueue {
+    /// Uses the given directory as a queue, creating it if needed
+    pub fn new<P: Into<PathBuf>>(root: P) -> io::Result<Self> {
+        let queue = DirectoryQueue { root: root.into() };
+        for directory in &["pending", "running", "done"] {
+            fs::create_dir_all(queue.root.join(directory))?;
+        }
+        Ok(queue)
+    }
+
+    fn path(&self, directory: &str, job: &str, extension: &str) -> io::Result<PathBuf> {
+        if job.is_empty() || job.starts_with('.') || job.contains(['/', '\\']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid job name: {:?}", job),
+            ));
+        }
+        Ok(self
+            .root
+            .join(directory)
+            .join(format!("{}.{}", job, extension)))
+    }
+}
+
+/// Writes a file under a temporary name, then renames it,
+/// so that it is never read while it is only partly written
+fn write_atomically(path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, content)?;
+    fs::rename(&temporary, path)
+}
+
+impl JobQueue for DirectoryQueue {
+    type Error = io::Error;
+
+    fn submit(&self, job: &str, model: &[u8]) -> io::Result<()> {
+        write_atomically(&self.path("pending", job, "mps")?, model)
+    }
+
+    fn claim(&self) -> io::Result<Option<(String, Vec<u8>)>> {
+        let mut jobs: Vec<String> = fs::read_dir(self.root.join("pending"))?
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                Some(name.strip_suffix(".mps")?.to_string())
+            })
+            .collect();
+        jobs.sort();
+        for job in jobs {
+            let running = self.path("running", &job, "mps")?;
+            match fs::rename(self.path("pending", &job, "mps")?, &running) {
+                Ok(()) => return Ok(Some((job, fs::read(running)?))),
+                // Another worker took the job first
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+
+    fn complete(&self, job: &str, result: &[u8]) -> io::Result<()> {
+        write_atomically(&self.path("done", job, "result")?, result)?;
+        fs::remove_file(self.path("running", job, "mps")?)
+    }
+
+    fn result(&self, job: &str) -> io::Result<Option<Vec<u8>>> {
+        match fs::read(self.path("done", job, "result")?) {
+            Ok(result) => Ok(Some(result)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// A solution computed by a worker, returned by [collect]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchSolution {
+    pub(crate) values: Vec<f64>,
+    pub(crate) objective: f64,
+    pub(crate) status: SolutionStatus,
+}
+
+impl BatchSolution {
+    /// The value of the objective, computed by the worker
+    pub fn objective(&self) -> f64 {
+        self.objective
+    }
+}
+
+impl Solution for BatchSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+/// Adds a problem to the queue, under the given job name
+pub fn submit<Q: JobQueue>(
+    queue: &Q,
+    job: &str,
+    problem: &UnsolvedProblem,
+) -> Result<(), Q::Error> {
+    queue.submit(job, problem.to_mps().as_bytes())
+}
+
+/// Solves the jobs of the queue with the given solver until there is none left,
+/// and returns the number of jobs that were solved.
+/// Problems that cannot be read or solved are completed with an error.
+pub fn run_worker<Q, S>(queue: &Q, solver: S) -> Result<usize, Q::Error>
+where
+    Q: JobQueue,
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    let mut solved = 0;
+    while let Some((job, model)) = queue.claim()? {
+        queue.complete(&job, &solve_job(&model, solver.clone()))?;
+        solved += 1;
+    }
+    Ok(solved)
+}
+
+/// Solves a model in the free MPS format, and returns the result in the format
+/// expected by [collect] and by [RemoteSolver](crate::solvers::remote::RemoteSolver).
+/// This is what a worker does for each job, and what an HTTP server
+/// answers to the requests of a remote solver.
+///
+/// The result is text: either an `error` line, or a `status` line, an `objective` line,
+/// and a `value` line for each variable, in the order of the columns of the model.
+///
+/// ```
+/// use good_lp::batch::solve_job;
+/// use good_lp::{default_solver, variables};
+///
+/// variables! {vars: 0 <= x <= 3;}
+/// let result = solve_job(vars.maximise(x).to_mps().as_bytes(), default_solver);
+/// assert_eq!(String::from_utf8(result).unwrap(), "status optimal\nobjective 3.0\nvalue 3.0\n");
+/// ```
+pub fn solve_job<S>(model: &[u8], solver: S) -> Vec<u8>
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    encode_result(&solve_model(model, solver)).into_bytes()
+}
+
+/// Waits until all the given jobs are completed, checking the queue every `poll_interval`,
+/// and returns their results by job name
+pub fn collect<Q: JobQueue>(
+    queue: &Q,
+    jobs: &[&str],
+    poll_interval: Duration,
+) -> Result<HashMap<String, Result<BatchSolution, ResolutionError>>, Q::Error> {
+    let mut results = HashMap::new();
+    while results.len() < jobs.len() {
+        for &job in jobs {
+            if results.contains_key(job) {
+                continue;
+            }
+            if let Some(result) = queue.result(job)? {
+                results.insert(job.to_string(), decode_result(&result));
+            }
+        }
+        if results.len() < jobs.len() {
+            thread::sleep(poll_interval);
+        }
+    }
+    Ok(results)
+}
+
+fn solve_model<S>(model: &[u8], solver: S) -> Result<BatchSolution, ResolutionError>
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    let parsed = mps::read(model).map_err(|e| ResolutionError::Str(e.to_string()))?;
+    let variables: Vec<Variable> = parsed
+        .problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(v, _)| v)
+        .collect();
+    let objective = parsed.problem.objective.clone();
+    let solution = parsed.problem.using(solver).solve()?;
+    Ok(BatchSolution {
+        values: variables.iter().map(|&v| solution.value(v)).collect(),
+        objective: solution.eval(&objective),
+        status: solution.status(),
+    })
+}
+
+const STATUSES: [(SolutionStatus, &str); 4] = [
+    (SolutionStatus::Optimal, "optimal"),
+    (SolutionStatus::TimeLimit, "time_limit"),
+    (SolutionStatus::GapLimit, "gap_limit"),
+    (SolutionStatus::Interrupted, "interrupted"),
+];
+
+/// Writes a result as text: either a status, an objective value and one value per line,
+/// or an error
+fn encode_result(result: &Result<BatchSolution, ResolutionError>) -> String {
+    match result {
+        Ok(solution) => {
+            let status = STATUSES
+                .iter()
+                .find(|(status, _)| *status == solution.status)
+                .map_or("optimal", |&(_, name)| name);
+            let mut text = format!("status {}\nobjective {:?}\n", status, solution.objective);
+            for value in &solution.values {
+                text += &format!("value {:?}\n", value);
+            }
+            text
+        }
+        Err(ResolutionError::Infeasible) => "error infeasible\n".to_string(),
+        Err(ResolutionError::Unbounded) => "error unbounded\n".to_string(),
+        Err(ResolutionError::Other(message)) => format!("error {}\n", message.replace('\n', " ")),
+        Err(ResolutionError::Str(message)) => format!("error {}\n", message.replace('\n', " ")),
+    }
+}
+
+pub(crate) fn decode_result(result: &[u8]) -> Result<BatchSolution, ResolutionError> {
+    let invalid = || ResolutionError::Other("the result of the job is invalid");
+    let text = std::str::from_utf8(result).map_err(|_| invalid())?;
+    let mut solution = BatchSolution {
+        values: vec![],
+        objective: f64::NAN,
+        status: SolutionStatus::Optimal,
+    };
+    for line in text.lines() {
+        let (key, value) = line.split_once(' ').ok_or_else(invalid)?;
+        match key {
+            "error" => {
+                return Err(match value {
+                    "infeasible" => ResolutionError::Infeasible,
+                    "unbounded" => ResolutionError::Unbounded,
+                    message => ResolutionError::Str(message.to_string()),
+                })
+            }
+            "status" => {
+                solution.status = STATUSES
+                    .iter()
+                    .find(|&&(_, name)| name == value)
+                    .ok_or_else(invalid)?
+                    .0
+            }
+            "objective" => solution.objective = value.parse().map_err(|_| invalid())?,
+            "value" => solution.values.push(value.parse().map_err(|_| invalid())?),
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_round_trip() {
+        let solution = BatchSolution {
+            values: vec![0.1 + 0.2, -0., 1e-300],
+            objective: 7.5,
+            status: SolutionStatus::TimeLimit,
+        };
+        let encoded = encode_result(&Ok(solution.clone()));
+        assert_eq!(decode_result(encoded.as_bytes()), Ok(solution));
+        let error = ResolutionError::Str("line 3: bad\nnumber".to_string());
+        assert_eq!(
+            decode_result(encode_result(&Err(error)).as_bytes()),
+            Err(ResolutionError::Str("line 3: bad number".to_string()))
+        );
+        let infeasible = encode_result(&Err(ResolutionError::Infeasible));
+        assert_eq!(
+            decode_result(infeasible.as_bytes()),
+            Err(ResolutionError::Infeasible)
+        );
+    }
+}
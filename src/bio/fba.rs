@@ -0,0 +1,240 @@
+//! [Flux balance analysis](https://en.wikipedia.org/wiki/Flux_balance_analysis) (FBA)
+//! of metabolic networks.
+//!
+//! A metabolic network is described by its stoichiometric matrix `S`,
+//! where `S[m][r]` is the quantity of metabolite `m` produced (if positive)
+//! or consumed (if negative) by reaction `r`.
+//! FBA looks for the reaction fluxes `v` that optimise an objective
+//! (usually the production of biomass) while keeping all metabolites at steady state: `S v = 0`.
+//!
+//! ```
+//! use good_lp::{bio::fba::FluxBalance, default_solver};
+//! // Reactions: uptake of A, A -> B, B -> biomass
+//! let stoichiometry = vec![
+//!     vec![1., -1., 0.], // A
+//!     vec![0., 1., -1.], // B
+//! ];
+//! let fba = FluxBalance::new(stoichiometry)
+//!     .bounds(0, 0., 10.) // at most 10 units of A can be taken up
+//!     .maximise_reaction(2);
+//! let fluxes = fba.solve(default_solver)?;
+//! assert_eq!(fluxes.objective_value, 10.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel, Variable,
+};
+
+/// A flux balance analysis problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluxBalance {
+    pub(crate) stoichiometry: Vec<Vec<f64>>,
+    pub(crate) lower_bounds: Vec<f64>,
+    pub(crate) upper_bounds: Vec<f64>,
+    pub(crate) objective: Vec<f64>,
+    pub(crate) reaction_names: Vec<String>,
+    pub(crate) metabolite_names: Vec<String>,
+}
+
+/// The fluxes through all reactions of an optimal solution
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluxDistribution {
+    /// The optimal value of the objective
+    pub objective_value: f64,
+    /// The flux through each reaction, in the order of the columns of the stoichiometric matrix
+    pub fluxes: Vec<f64>,
+}
+
+/// The range of fluxes each reaction can take while keeping the objective close to its optimum.
+/// Computed by [FluxBalance::variability].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluxVariability {
+    /// The smallest possible flux through each reaction
+    pub minimum: Vec<f64>,
+    /// The largest possible flux through each reaction
+    pub maximum: Vec<f64>,
+}
+
+impl FluxBalance {
+    /// Creates a problem from a stoichiometric matrix with one row per metabolite
+    /// and one column per reaction.
+    ///
+    /// All reactions are initially irreversible (their flux is non-negative and unbounded),
+    /// and the objective is empty.
+    ///
+    /// Panics if the rows of the matrix don't all have the same length.
+    pub fn new(stoichiometry: Vec<Vec<f64>>) -> Self {
+        let n_reactions = stoichiometry.first().map_or(0, Vec::len);
+        assert!(
+            stoichiometry.iter().all(|row| row.len() == n_reactions),
+            "all rows of the stoichiometric matrix should have one coefficient per reaction"
+        );
+        FluxBalance {
+            lower_bounds: vec![0.; n_reactions],
+            upper_bounds: vec![f64::INFINITY; n_reactions],
+            objective: vec![0.; n_reactions],
+            reaction_names: (0..n_reactions).map(|r| format!("R{}", r)).collect(),
+            metabolite_names: (0..stoichiometry.len())
+                .map(|m| format!("M{}", m))
+                .collect(),
+            stoichiometry,
+        }
+    }
+
+    /// The number of reactions
+    pub fn n_reactions(&self) -> usize {
+        self.objective.len()
+    }
+
+    /// The number of metabolites
+    pub fn n_metabolites(&self) -> usize {
+        self.stoichiometry.len()
+    }
+
+    /// Set the minimum and maximum flux through a reaction.
+    /// Use a negative minimum for reversible reactions.
+    pub fn bounds(mut self, reaction: usize, min: f64, max: f64) -> Self {
+        self.lower_bounds[reaction] = min;
+        self.upper_bounds[reaction] = max;
+        self
+    }
+
+    /// Set the objective coefficient of every reaction. The objective is maximised.
+    ///
+    /// Panics if there is not exactly one coefficient per reaction.
+    pub fn objective(mut self, coefficients: Vec<f64>) -> Self {
+        assert_eq!(
+            coefficients.len(),
+            self.n_reactions(),
+            "there should be one objective coefficient per reaction"
+        );
+        self.objective = coefficients;
+        self
+    }
+
+    /// Maximise the flux through a single reaction, usually the biomass reaction
+    pub fn maximise_reaction(mut self, reaction: usize) -> Self {
+        self.objective = vec![0.; self.n_reactions()];
+        self.objective[reaction] = 1.;
+        self
+    }
+
+    /// Set the names of the reactions. They are used as variable names.
+    pub fn reaction_names(mut self, names: Vec<String>) -> Self {
+        assert_eq!(names.len(), self.n_reactions(), "one name per reaction");
+        self.reaction_names = names;
+        self
+    }
+
+    /// Set the names of the metabolites. They are used as constraint names.
+    pub fn metabolite_names(mut self, names: Vec<String>) -> Self {
+        assert_eq!(names.len(), self.n_metabolites(), "one name per metabolite");
+        self.metabolite_names = names;
+        self
+    }
+
+    /// The names of the reactions
+    pub fn get_reaction_names(&self) -> &[String] {
+        &self.reaction_names
+    }
+
+    /// Builds the problem optimising the given function of the fluxes,
+    /// subject to the steady-state constraints.
+    ///
+    /// `objective` receives the flux variables and returns the expression to optimise.
+    /// The returned problem can be extended with additional constraints,
+    /// or exported to a file.
+    pub fn problem<F>(
+        &self,
+        direction: ObjectiveDirection,
+        objective: F,
+    ) -> (UnsolvedProblem, Vec<Variable>)
+    where
+        F: FnOnce(&[Variable]) -> Expression,
+    {
+        let mut vars = ProblemVariables::new();
+        let fluxes: Vec<Variable> = (0..self.n_reactions())
+            .map(|r| {
+                vars.add(
+                    variable()
+                        .name(self.reaction_names[r].clone())
+                        .bounds(self.lower_bounds[r]..=self.upper_bounds[r]),
+                )
+            })
+            .collect();
+        let objective = objective(&fluxes);
+        let mut problem = vars.optimise(direction, objective);
+        for (row, name) in self.stoichiometry.iter().zip(&self.metabolite_names) {
+            let balance: Expression = fluxes
+                .iter()
+                .zip(row)
+                .filter(|(_, &s)| s != 0.)
+                .map(|(&v, &s)| s * v)
+                .sum();
+            problem.add_constraint(constraint!(balance == 0).set_name(name.clone()));
+        }
+        (problem, fluxes)
+    }
+
+    fn objective_expression(&self, fluxes: &[Variable]) -> Expression {
+        fluxes
+            .iter()
+            .zip(&self.objective)
+            .filter(|(_, &c)| c != 0.)
+            .map(|(&v, &c)| c * v)
+            .sum()
+    }
+
+    /// Finds fluxes that maximise the objective
+    pub fn solve<S: Solver>(
+        &self,
+        solver: S,
+    ) -> Result<FluxDistribution, <S::Model as SolverModel>::Error> {
+        let (problem, fluxes) = self.problem(ObjectiveDirection::Maximisation, |f| {
+            self.objective_expression(f)
+        });
+        let solution = problem.using(solver).solve()?;
+        let fluxes: Vec<f64> = fluxes.iter().map(|&v| solution.value(v)).collect();
+        let objective_value = fluxes.iter().zip(&self.objective).map(|(v, c)| v * c).sum();
+        Ok(FluxDistribution {
+            objective_value,
+            fluxes,
+        })
+    }
+
+    /// Flux variability analysis (FVA): computes the minimum and maximum flux through
+    /// each reaction, among the solutions whose objective is at least
+    /// `fraction_of_optimum` times the optimal objective.
+    ///
+    /// This solves `1 + 2 * n_reactions` linear problems.
+    pub fn variability<S: Solver + Clone>(
+        &self,
+        fraction_of_optimum: f64,
+        solver: S,
+    ) -> Result<FluxVariability, <S::Model as SolverModel>::Error> {
+        assert!(
+            (0. ..=1.).contains(&fraction_of_optimum),
+            "the fraction of the optimum should be between 0 and 1"
+        );
+        let optimum = self.solve(solver.clone())?.objective_value;
+        let threshold = optimum - (1. - fraction_of_optimum) * optimum.abs();
+        let extreme_flux = |reaction: usize,
+                            direction: ObjectiveDirection|
+         -> Result<f64, <S::Model as SolverModel>::Error> {
+            let (mut problem, fluxes) = self.problem(direction, |f| f[reaction].into());
+            let objective = self.objective_expression(&fluxes);
+            problem.add_constraint(constraint!(objective >= threshold));
+            let solution = problem.using(solver.clone()).solve()?;
+            Ok(solution.value(fluxes[reaction]))
+        };
+        let mut minimum = Vec::with_capacity(self.n_reactions());
+        let mut maximum = Vec::with_capacity(self.n_reactions());
+        for reaction in 0..self.n_reactions() {
+            minimum.push(extreme_flux(reaction, ObjectiveDirection::Minimisation)?);
+            maximum.push(extreme_flux(reaction, ObjectiveDirection::Maximisation)?);
+        }
+        Ok(FluxVariability { minimum, maximum })
+    }
+}
@@ -0,0 +1,7 @@
+//! Modelling helpers for computational biology.
+
+pub mod fba;
+
+#[cfg(feature = "sbml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sbml")))]
+pub mod sbml;
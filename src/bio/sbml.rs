@@ -0,0 +1,237 @@
+//! Read metabolic networks from [SBML](https://sbml.org/) files.
+//!
+//! Flux bounds and objectives are read from the
+//! [flux balance constraints](https://sbml.org/documents/specifications/level-3/version-1/fbc/)
+//! (`fbc`) package, or from the `LOWER_BOUND`, `UPPER_BOUND` and `OBJECTIVE_COEFFICIENT`
+//! kinetic law parameters used by older COBRA models.
+//! Boundary species are not subject to the steady-state constraint.
+//!
+//! This module is activated by the `sbml` cargo feature.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use roxmltree::{Document, Node};
+
+use crate::bio::fba::FluxBalance;
+
+/// An error that occurred while reading an SBML document
+#[derive(Debug)]
+pub enum SbmlError {
+    /// The document is not valid XML
+    Xml(roxmltree::Error),
+    /// The document is valid XML, but not a valid SBML model
+    Invalid(String),
+}
+
+impl Display for SbmlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SbmlError::Xml(e) => write!(f, "Invalid XML in SBML document: {}", e),
+            SbmlError::Invalid(s) => write!(f, "Invalid SBML model: {}", s),
+        }
+    }
+}
+
+impl Error for SbmlError {}
+
+impl From<roxmltree::Error> for SbmlError {
+    fn from(e: roxmltree::Error) -> Self {
+        SbmlError::Xml(e)
+    }
+}
+
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &'static str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+/// Attributes are looked up by local name, so that the version of the fbc package doesn't matter
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|a| a.name() == name)
+        .map(|a| a.value())
+}
+
+fn number(value: &str, context: &str) -> Result<f64, SbmlError> {
+    match value.trim() {
+        "INF" | "inf" | "Infinity" => Ok(f64::INFINITY),
+        "-INF" | "-inf" | "-Infinity" => Ok(f64::NEG_INFINITY),
+        v => v
+            .parse()
+            .map_err(|_| SbmlError::Invalid(format!("invalid number {:?} in {}", v, context))),
+    }
+}
+
+impl FluxBalance {
+    /// Reads a metabolic network from the content of an SBML file
+    ///
+    /// ```
+    /// use good_lp::bio::fba::FluxBalance;
+    /// let sbml = r#"<sbml><model>
+    ///   <listOfSpecies><species id="A"/></listOfSpecies>
+    ///   <listOfReactions>
+    ///     <reaction id="uptake" reversible="false">
+    ///       <listOfProducts><speciesReference species="A"/></listOfProducts>
+    ///     </reaction>
+    ///     <reaction id="consume" reversible="false">
+    ///       <listOfReactants><speciesReference species="A" stoichiometry="2"/></listOfReactants>
+    ///     </reaction>
+    ///   </listOfReactions>
+    /// </model></sbml>"#;
+    /// let fba = FluxBalance::from_sbml(sbml)?;
+    /// assert_eq!(fba.get_reaction_names(), &["uptake", "consume"]);
+    /// # Ok::<_, good_lp::bio::sbml::SbmlError>(())
+    /// ```
+    pub fn from_sbml(xml: &str) -> Result<Self, SbmlError> {
+        let document = Document::parse(xml)?;
+        let model = document
+            .descendants()
+            .find(|n| n.tag_name().name() == "model")
+            .ok_or_else(|| SbmlError::Invalid("no model element".into()))?;
+
+        let mut metabolite_index = HashMap::new();
+        let mut metabolite_names = vec![];
+        for species in child(model, "listOfSpecies")
+            .into_iter()
+            .flat_map(|list| children(list, "species"))
+        {
+            if attribute(species, "boundaryCondition") == Some("true") {
+                continue;
+            }
+            let id = attribute(species, "id")
+                .ok_or_else(|| SbmlError::Invalid("species without id".into()))?;
+            metabolite_index.insert(id, metabolite_names.len());
+            metabolite_names.push(id.to_string());
+        }
+
+        let mut parameters = HashMap::new();
+        for parameter in child(model, "listOfParameters")
+            .into_iter()
+            .flat_map(|list| children(list, "parameter"))
+        {
+            if let (Some(id), Some(value)) =
+                (attribute(parameter, "id"), attribute(parameter, "value"))
+            {
+                parameters.insert(id, number(value, id)?);
+            }
+        }
+        let bound = |reaction: Node, name: &str| -> Result<Option<f64>, SbmlError> {
+            attribute(reaction, name)
+                .map(|id| {
+                    parameters.get(id).copied().ok_or_else(|| {
+                        SbmlError::Invalid(format!("unknown flux bound parameter {:?}", id))
+                    })
+                })
+                .transpose()
+        };
+
+        let mut stoichiometry: Vec<Vec<f64>> = vec![vec![]; metabolite_names.len()];
+        let mut reaction_names = vec![];
+        let mut lower_bounds = vec![];
+        let mut upper_bounds = vec![];
+        let mut objective = vec![];
+        let mut reaction_index = HashMap::new();
+        for reaction in child(model, "listOfReactions")
+            .into_iter()
+            .flat_map(|list| children(list, "reaction"))
+        {
+            let id = attribute(reaction, "id")
+                .ok_or_else(|| SbmlError::Invalid("reaction without id".into()))?;
+            let r = reaction_names.len();
+            reaction_index.insert(id, r);
+            reaction_names.push(id.to_string());
+            for row in stoichiometry.iter_mut() {
+                row.push(0.);
+            }
+            for (list, sign) in [("listOfReactants", -1.), ("listOfProducts", 1.)] {
+                for reference in child(reaction, list)
+                    .into_iter()
+                    .flat_map(|l| children(l, "speciesReference"))
+                {
+                    let species = attribute(reference, "species").ok_or_else(|| {
+                        SbmlError::Invalid(format!("species reference without species in {}", id))
+                    })?;
+                    let coefficient = attribute(reference, "stoichiometry")
+                        .map(|s| number(s, id))
+                        .transpose()?
+                        .unwrap_or(1.);
+                    if let Some(&m) = metabolite_index.get(species) {
+                        stoichiometry[m][r] += sign * coefficient;
+                    }
+                }
+            }
+
+            let reversible = attribute(reaction, "reversible") != Some("false");
+            let mut lower = bound(reaction, "lowerFluxBound")?;
+            let mut upper = bound(reaction, "upperFluxBound")?;
+            let mut coefficient = 0.;
+            let local_parameters = child(reaction, "kineticLaw").into_iter().flat_map(|law| {
+                ["listOfParameters", "listOfLocalParameters"]
+                    .iter()
+                    .filter_map(move |list| child(law, list))
+                    .flat_map(|list| list.children().filter(|n| n.is_element()))
+            });
+            for parameter in local_parameters {
+                let value = match attribute(parameter, "value") {
+                    Some(value) => number(value, id)?,
+                    None => continue,
+                };
+                match attribute(parameter, "id") {
+                    Some("LOWER_BOUND") => lower = lower.or(Some(value)),
+                    Some("UPPER_BOUND") => upper = upper.or(Some(value)),
+                    Some("OBJECTIVE_COEFFICIENT") => coefficient = value,
+                    _ => {}
+                }
+            }
+            let default_lower = if reversible { f64::NEG_INFINITY } else { 0. };
+            lower_bounds.push(lower.unwrap_or(default_lower));
+            upper_bounds.push(upper.unwrap_or(f64::INFINITY));
+            objective.push(coefficient);
+        }
+
+        if let Some(objectives) = child(model, "listOfObjectives") {
+            let active = attribute(objectives, "activeObjective");
+            let selected = children(objectives, "objective")
+                .find(|o| active.is_none() || attribute(*o, "id") == active);
+            if let Some(selected) = selected {
+                objective = vec![0.; reaction_names.len()];
+                let sign = if attribute(selected, "type") == Some("minimize") {
+                    -1.
+                } else {
+                    1.
+                };
+                for flux_objective in child(selected, "listOfFluxObjectives")
+                    .into_iter()
+                    .flat_map(|list| children(list, "fluxObjective"))
+                {
+                    let reaction = attribute(flux_objective, "reaction").unwrap_or_default();
+                    let &r = reaction_index.get(reaction).ok_or_else(|| {
+                        SbmlError::Invalid(format!("unknown objective reaction {:?}", reaction))
+                    })?;
+                    let coefficient = attribute(flux_objective, "coefficient")
+                        .map(|c| number(c, reaction))
+                        .transpose()?
+                        .unwrap_or(1.);
+                    objective[r] = sign * coefficient;
+                }
+            }
+        }
+
+        Ok(FluxBalance {
+            stoichiometry,
+            lower_bounds,
+            upper_bounds,
+            objective,
+            reaction_names,
+            metabolite_names,
+        })
+    }
+}
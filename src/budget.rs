@@ -0,0 +1,277 @@
+//! Budget allocation across marketing channels with diminishing returns
+//! ([marketing mix](https://en.wikipedia.org/wiki/Marketing_mix_modeling) optimisation).
+//!
+//! The response of each channel to the money spent on it is a concave curve:
+//! every additional unit of spend brings less than the previous one.
+//! Curves are approximated by piecewise linear functions,
+//! with one variable per segment, equal to the spend within that segment.
+//! Since the slopes of the segments decrease, maximising the total response
+//! fills the segments in order, and the problem stays a linear program.
+//!
+//! ```
+//! use good_lp::budget::{BudgetAllocation, Channel};
+//! use good_lp::default_solver;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//!
+//! let allocation = BudgetAllocation::new(100.)
+//!     // (spend, response) breakpoints of the response curve
+//!     .channel(Channel::new("search", vec![(0., 0.), (40., 80.), (100., 110.)]))
+//!     .channel(Channel::new("social", vec![(0., 0.), (50., 60.), (100., 70.)]).min_spend(10.))
+//!     .solve(default_solver)?;
+//! // The last 10 are spent in search, where they bring more than in social
+//! assert_float_eq(allocation.spend(0), 50.);
+//! assert_float_eq(allocation.spend(1), 50.);
+//! assert_float_eq(allocation.total_response(), 145.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel, Variable,
+};
+
+/// A channel in which money can be spent, with its response curve
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channel {
+    name: String,
+    /// `(spend, response)` breakpoints, starting at a spend of 0
+    curve: Vec<(f64, f64)>,
+    min_spend: f64,
+    max_spend: f64,
+}
+
+impl Channel {
+    /// Creates a channel from the `(spend, response)` breakpoints of its response curve.
+    /// The response between two breakpoints is interpolated linearly,
+    /// and nothing can be spent beyond the last breakpoint.
+    ///
+    /// Panics if the first breakpoint is not at a spend of 0,
+    /// if the spends are not increasing, or if the curve is not concave.
+    pub fn new<S: Into<String>>(name: S, curve: Vec<(f64, f64)>) -> Self {
+        assert!(
+            curve.first().map(|&(spend, _)| spend) == Some(0.),
+            "the response curve must start at a spend of 0"
+        );
+        let mut previous_slope = f64::INFINITY;
+        for pair in curve.windows(2) {
+            let ((s0, r0), (s1, r1)) = (pair[0], pair[1]);
+            assert!(s1 > s0, "the spends of the breakpoints must be increasing");
+            let slope = (r1 - r0) / (s1 - s0);
+            assert!(
+                slope <= previous_slope + 1e-9,
+                "the response curve must be concave (diminishing returns)"
+            );
+            previous_slope = slope;
+        }
+        let max_spend = curve.last().map_or(0., |&(spend, _)| spend);
+        Channel {
+            name: name.into(),
+            curve,
+            min_spend: 0.,
+            max_spend,
+        }
+    }
+
+    /// Creates a channel with a saturating response curve,
+    /// `response = max_response * spend / (spend + half_saturation)`,
+    /// sampled at `segments + 1` evenly spaced spends between 0 and `max_spend`.
+    ///
+    /// ```
+    /// use good_lp::budget::Channel;
+    /// let tv = Channel::saturating("tv", 1000., 50., 200., 8);
+    /// assert_eq!(tv.curve().len(), 9);
+    /// assert_eq!(tv.curve()[8], (200., 800.));
+    /// ```
+    pub fn saturating<S: Into<String>>(
+        name: S,
+        max_response: f64,
+        half_saturation: f64,
+        max_spend: f64,
+        segments: usize,
+    ) -> Self {
+        assert!(segments > 0, "the curve needs at least one segment");
+        let curve = (0..=segments)
+            .map(|i| {
+                let spend = max_spend * i as f64 / segments as f64;
+                (spend, max_response * spend / (spend + half_saturation))
+            })
+            .collect();
+        Channel::new(name, curve)
+    }
+
+    /// At least `spend` must be spent in this channel
+    pub fn min_spend(mut self, spend: f64) -> Self {
+        self.min_spend = spend;
+        self
+    }
+
+    /// At most `spend` can be spent in this channel
+    pub fn max_spend(mut self, spend: f64) -> Self {
+        self.max_spend = self.max_spend.min(spend);
+        self
+    }
+
+    /// The name of the channel
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The `(spend, response)` breakpoints of the response curve
+    pub fn curve(&self) -> &[(f64, f64)] {
+        &self.curve
+    }
+}
+
+/// A budget allocation problem, built with [BudgetAllocation::new]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetAllocation {
+    budget: f64,
+    spend_all: bool,
+    channels: Vec<Channel>,
+}
+
+impl BudgetAllocation {
+    /// Creates an allocation problem with the given total budget, and no channels
+    pub fn new(budget: f64) -> Self {
+        BudgetAllocation {
+            budget,
+            spend_all: false,
+            channels: vec![],
+        }
+    }
+
+    /// Adds a channel
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.channels.push(channel);
+        self
+    }
+
+    /// Require the whole budget to be spent, even when additional spend brings no response
+    pub fn spend_all(mut self) -> Self {
+        self.spend_all = true;
+        self
+    }
+
+    /// Builds the problem. Its variables and constraints can be inspected and extended
+    /// before it is solved.
+    pub fn problem(&self) -> BudgetProblem {
+        let mut vars = ProblemVariables::new();
+        let mut spend = vec![];
+        let mut response = vec![];
+        for channel in &self.channels {
+            let mut channel_spend = Expression::default();
+            let mut channel_response = Expression::from(channel.curve[0].1);
+            for (i, pair) in channel.curve.windows(2).enumerate() {
+                let ((s0, r0), (s1, r1)) = (pair[0], pair[1]);
+                let segment: Variable = vars.add(
+                    variable()
+                        .clamp(0, s1 - s0)
+                        .name(format!("{}_segment_{}", channel.name, i)),
+                );
+                channel_spend += segment;
+                channel_response.add_mul((r1 - r0) / (s1 - s0), segment);
+            }
+            spend.push(channel_spend);
+            response.push(channel_response);
+        }
+        let total_response: Expression = response.iter().sum();
+        let mut problem = vars.maximise(total_response);
+        let total_spend: Expression = spend.iter().sum();
+        if self.spend_all {
+            problem.add_constraint(constraint!(total_spend == self.budget).set_name("budget"));
+        } else {
+            problem.add_constraint(constraint!(total_spend <= self.budget).set_name("budget"));
+        }
+        for (channel, spend) in self.channels.iter().zip(&spend) {
+            if channel.min_spend > 0. {
+                problem.add_constraint(
+                    constraint!(spend.clone() >= channel.min_spend)
+                        .set_name(format!("{}_min_spend", channel.name)),
+                );
+            }
+            if channel.max_spend < channel.curve.last().map_or(0., |&(s, _)| s) {
+                problem.add_constraint(
+                    constraint!(spend.clone() <= channel.max_spend)
+                        .set_name(format!("{}_max_spend", channel.name)),
+                );
+            }
+        }
+        BudgetProblem {
+            problem,
+            spend,
+            response,
+        }
+    }
+
+    /// Finds the allocation with the highest total response
+    pub fn solve<S: Solver>(
+        &self,
+        solver: S,
+    ) -> Result<Allocation, <S::Model as SolverModel>::Error> {
+        self.problem().solve(solver)
+    }
+}
+
+/// The optimisation problem built from a [BudgetAllocation]
+pub struct BudgetProblem {
+    problem: UnsolvedProblem,
+    spend: Vec<Expression>,
+    response: Vec<Expression>,
+}
+
+impl BudgetProblem {
+    /// The amount spent in a channel, as an expression
+    pub fn spend_expression(&self, channel: usize) -> &Expression {
+        &self.spend[channel]
+    }
+
+    /// The response of a channel, as an expression
+    pub fn response_expression(&self, channel: usize) -> &Expression {
+        &self.response[channel]
+    }
+
+    /// The underlying problem, to which additional constraints can be added
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Finds the optimal allocation
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<Allocation, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        Ok(Allocation {
+            spend: self.spend.iter().map(|e| solution.eval(e)).collect(),
+            response: self.response.iter().map(|e| solution.eval(e)).collect(),
+        })
+    }
+}
+
+/// The amount spent in each channel, and the response it brings
+#[derive(Debug, Clone, PartialEq)]
+pub struct Allocation {
+    spend: Vec<f64>,
+    response: Vec<f64>,
+}
+
+impl Allocation {
+    /// The amount spent in the given channel
+    pub fn spend(&self, channel: usize) -> f64 {
+        self.spend[channel]
+    }
+
+    /// The response of the given channel
+    pub fn response(&self, channel: usize) -> f64 {
+        self.response[channel]
+    }
+
+    /// The amount spent in all channels
+    pub fn total_spend(&self) -> f64 {
+        self.spend.iter().sum()
+    }
+
+    /// The response of all channels
+    pub fn total_response(&self) -> f64 {
+        self.response.iter().sum()
+    }
+}
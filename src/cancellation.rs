@@ -0,0 +1,273 @@
+//! Cancelling a resolution that takes too long.
+//!
+//! [solve_with_cancellation] solves a problem in a background thread,
+//! and returns as soon as the resolution ends or a [CancellationToken] is cancelled,
+//! whichever comes first. This lets a service stop waiting for a solver
+//! when a request is aborted, or when it shuts down.
+//!
+//! Most solvers cannot be interrupted from the outside: a resolution cancelled by
+//! [solve_with_cancellation] goes on in its thread until it ends, and its result is discarded.
+//! The thread, and the memory and processor time it uses, are only released then.
+//!
+//! With solvers that accept a [time limit](crate::ModelWithTimeLimit),
+//! [solve_until_cancelled] actually stops the resolution: it solves the problem in rounds
+//! with increasing time limits, like [solve_anytime](crate::anytime::solve_anytime),
+//! and no new round is started once the token is cancelled.
+//! It returns the best solution found before the cancellation.
+//!
+//! ```
+//! use good_lp::cancellation::{solve_with_cancellation, CancellationToken};
+//! use good_lp::{default_solver, variables, ResolutionError, Solution};
+//!
+//! variables! {vars: 0 <= x <= 2;}
+//! let token = CancellationToken::new();
+//! let solution = solve_with_cancellation(vars.maximise(x), default_solver, &token)?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(solution.value(x), 2.);
+//!
+//! variables! {vars: 0 <= x <= 2;}
+//! token.cancel();
+//! let cancelled = solve_with_cancellation(vars.maximise(x), default_solver, &token);
+//! assert_eq!(cancelled.err(), Some(ResolutionError::Other("the resolution was cancelled")));
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use crate::anytime::{solve_in_rounds, AnytimeSolution};
+use crate::solvers::{
+    relative_gap, ModelWithTimeLimit, ResolutionError, Solution, SolutionStatus, SolveStats,
+    Solver, SolverModel,
+};
+use crate::variable::UnsolvedProblem;
+use crate::Variable;
+
+/// How often a waiting resolution checks whether it was cancelled
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The time limit of the first round of [solve_until_cancelled].
+/// Each next round gets twice as much time.
+const FIRST_ROUND: Duration = Duration::from_millis(100);
+
+/// A flag shared between the code that waits for a resolution and the code that can cancel it.
+/// Clones of a token share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the resolutions that use this token, or any of its clones
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the token was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// The solution of a resolution that could have been cancelled,
+/// returned by [solve_with_cancellation]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CancellableSolution {
+    values: Vec<f64>,
+    status: SolutionStatus,
+    best_bound: Option<f64>,
+    mip_gap: Option<f64>,
+    stats: SolveStats,
+}
+
+impl From<AnytimeSolution> for CancellableSolution {
+    fn from(solution: AnytimeSolution) -> Self {
+        CancellableSolution {
+            mip_gap: solution
+                .best_bound
+                .map(|bound| relative_gap(solution.objective, bound)),
+            best_bound: solution.best_bound,
+            values: solution.values,
+            status: solution.status,
+            stats: solution.stats,
+        }
+    }
+}
+
+impl Solution for CancellableSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+
+    /// With [solve_until_cancelled], [SolutionStatus::Interrupted]
+    /// if the token was cancelled before the solution was proved to be optimal
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+
+    fn best_bound(&self) -> Option<f64> {
+        self.best_bound
+    }
+
+    fn mip_gap(&self) -> Option<f64> {
+        self.mip_gap
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.stats.clone()
+    }
+}
+
+/// Solves the problem in a background thread, unless the token is cancelled first.
+///
+/// When the token is cancelled, this returns
+/// `ResolutionError::Other("the resolution was cancelled")` without waiting for the solver,
+/// which keeps running in the background until it ends: the thread is not stopped,
+/// so cancelled resolutions of long problems accumulate.
+/// Use [solve_until_cancelled] to stop them, with solvers that accept a time limit.
+/// The token is checked every few milliseconds.
+pub fn solve_with_cancellation<S>(
+    problem: UnsolvedProblem,
+    solver: S,
+    token: &CancellationToken,
+) -> Result<CancellableSolution, ResolutionError>
+where
+    S: Solver + Send + 'static,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    let cancelled = Err(ResolutionError::Other("the resolution was cancelled"));
+    if token.is_cancelled() {
+        return cancelled;
+    }
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let variables: Vec<Variable> = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(v, _)| v)
+            .collect();
+        let result = problem
+            .using(solver)
+            .solve()
+            .map(|solution| CancellableSolution {
+                values: variables.iter().map(|&v| solution.value(v)).collect(),
+                status: solution.status(),
+                best_bound: solution.best_bound(),
+                mip_gap: solution.mip_gap(),
+                stats: solution.stats(),
+            });
+        // The receiver is gone if the resolution was cancelled
+        let _ = sender.send(result);
+    });
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(result) => return result,
+            Err(mpsc::RecvTimeoutError::Timeout) if !token.is_cancelled() => continue,
+            Err(mpsc::RecvTimeoutError::Timeout) => return cancelled,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(ResolutionError::Other("the solver panicked"))
+            }
+        }
+    }
+}
+
+/// What the resolution thread of [solve_until_cancelled] sends to the waiting thread
+enum Progress {
+    Incumbent(CancellableSolution),
+    Done(Result<CancellableSolution, ResolutionError>),
+}
+
+/// Solves the problem in a background thread, in rounds with increasing time limits,
+/// until the best solution is proved to be optimal or the token is cancelled.
+///
+/// When the token is cancelled, this returns the best solution found so far
+/// without waiting for the solver, with a [SolutionStatus::Interrupted] status,
+/// or `ResolutionError::Other("the resolution was cancelled")` if none was found.
+/// The background thread stops at the end of the current round, so after the cancellation,
+/// the solver runs at most for the time limit of that round.
+///
+/// Every round is a new resolution, that only looks for solutions better than the best one
+/// found so far: proving optimality takes longer than with a single resolution.
+///
+/// ```
+/// # // Not all solvers support time limits
+/// # #[cfg(feature = "coin_cbc")] {
+/// use good_lp::cancellation::{solve_until_cancelled, CancellationToken};
+/// use good_lp::{coin_cbc, constraint, variable, variables, Solution};
+///
+/// let mut vars = variables!();
+/// let x = vars.add(variable().integer().clamp(0, 10));
+/// let problem = vars.maximise(x).with(constraint!(2 * x <= 7));
+/// let token = CancellationToken::new();
+/// let solution = solve_until_cancelled(problem, coin_cbc, &token)?;
+/// assert!(solution.status().is_optimal());
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(solution.value(x), 3.);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn solve_until_cancelled<S>(
+    problem: UnsolvedProblem,
+    solver: S,
+    token: &CancellationToken,
+) -> Result<CancellableSolution, ResolutionError>
+where
+    S: Solver + Clone + Send + 'static,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithTimeLimit,
+{
+    if token.is_cancelled() {
+        return Err(ResolutionError::Other("the resolution was cancelled"));
+    }
+    let (sender, receiver) = mpsc::channel();
+    let stopped = token.clone();
+    thread::spawn(move || {
+        let incumbents = sender.clone();
+        let result = solve_in_rounds(
+            problem,
+            solver,
+            Duration::MAX,
+            FIRST_ROUND,
+            None,
+            |incumbent| {
+                let incumbent = CancellableSolution::from(incumbent.clone());
+                let _ = incumbents.send(Progress::Incumbent(incumbent));
+            },
+            || stopped.is_cancelled(),
+        );
+        // The receiver is gone if the resolution was cancelled
+        let _ = sender.send(Progress::Done(result.map(CancellableSolution::from)));
+    });
+    let mut best = None;
+    loop {
+        let progress = match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(progress) => Some(progress),
+            Err(mpsc::RecvTimeoutError::Timeout) => None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(ResolutionError::Other("the solver panicked"))
+            }
+        };
+        match progress {
+            Some(Progress::Incumbent(incumbent)) => best = Some(incumbent),
+            Some(Progress::Done(Ok(solution))) if solution.status.is_optimal() => {
+                return Ok(solution)
+            }
+            Some(Progress::Done(result)) if !token.is_cancelled() => return result,
+            _ if token.is_cancelled() => {
+                return match best {
+                    Some(mut solution) => {
+                        solution.status = SolutionStatus::Interrupted;
+                        Ok(solution)
+                    }
+                    None => Err(ResolutionError::Other("the resolution was cancelled")),
+                };
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Long resolutions that save their progress to disk, and can be resumed after a crash.
+//!
+//! A mixed integer problem that takes a day to solve should not start from scratch
+//! when the machine it runs on is restarted.
+//! [solve_with_checkpoints] works like [solve_anytime](crate::anytime::solve_anytime),
+//! and writes a [Checkpoint] to a file every time it finds a better solution.
+//! [resume_from_checkpoint] reads that file, and continues the resolution
+//! from the saved solution: only better solutions are searched for,
+//! and the saved one is returned if none is found.
+//!
+//! The checkpoint contains the best solution and the best bound on the objective.
+//! None of the solvers used by good_lp can save their branch-and-bound tree,
+//! so a resumed resolution explores the tree again, with the saved solution as a cutoff.
+//!
+//! ```
+//! # // Not all solvers support time limits
+//! # #[cfg(feature = "coin_cbc")] {
+//! use good_lp::checkpoint::{resume_from_checkpoint, solve_with_checkpoints, Checkpoint};
+//! use good_lp::{coin_cbc, constraint, variable, variables};
+//! use std::time::Duration;
+//!
+//! let file = format!("good_lp_checkpoint_doctest_{}.txt", std::process::id());
+//! let path = std::env::temp_dir().join(file);
+//! let mut vars = variables!();
+//! let x = vars.add(variable().integer().clamp(0, 10));
+//! let y = vars.add(variable().integer().clamp(0, 10));
+//! let problem = vars
+//!     .maximise(3 * x + 2 * y)
+//!     .with(constraint!(2 * x + 3 * y <= 17));
+//! let result = solve_with_checkpoints(problem.clone(), coin_cbc, Duration::from_secs(10), &path)?;
+//! assert!(result.save_error.is_none());
+//! let checkpoint = Checkpoint::load(&path).unwrap();
+//! assert_eq!(checkpoint.objective, result.solution.objective());
+//! // After a crash, the same problem is built again, and the resolution goes on
+//! let resumed = resume_from_checkpoint(problem, coin_cbc, Duration::from_secs(10), &path)?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(resumed.solution.objective(), 24.);
+//! # std::fs::remove_file(&path).unwrap();
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::anytime::{solve_from, AnytimeSolution};
+use crate::solvers::{
+    ModelWithTimeLimit, ObjectiveDirection, ResolutionError, SolutionStatus, Solver, SolverModel,
+};
+use crate::variable::UnsolvedProblem;
+
+/// The first line of checkpoint files, with the version of the format
+const HEADER: &str = "good_lp checkpoint 1";
+
+/// The progress of a resolution, as saved by [solve_with_checkpoints]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    /// The value of the objective in the best solution found
+    pub objective: f64,
+    /// The best bound on the objective reported by the solver, if any
+    pub best_bound: Option<f64>,
+    /// The values of all the variables of the problem in the best solution, in order
+    pub values: Vec<f64>,
+}
+
+impl Checkpoint {
+    fn from_solution(solution: &AnytimeSolution) -> Self {
+        Checkpoint {
+            objective: solution.objective,
+            best_bound: solution.best_bound,
+            values: solution.values.clone(),
+        }
+    }
+
+    /// Writes the checkpoint as text.
+    /// The file is written next to its destination, then renamed,
+    /// so that a crash while saving does not corrupt the previous checkpoint.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut text = format!("{}\nobjective {:?}\n", HEADER, self.objective);
+        if let Some(bound) = self.best_bound {
+            text += &format!("bound {:?}\n", bound);
+        }
+        for value in &self.values {
+            text += &format!("value {:?}\n", value);
+        }
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        fs::write(&temporary, text)?;
+        fs::rename(&temporary, path)
+    }
+
+    /// Reads a checkpoint written by [Checkpoint::save]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("this is not a good_lp checkpoint".to_string()));
+        }
+        let mut objective = None;
+        let mut checkpoint = Checkpoint {
+            objective: f64::NAN,
+            best_bound: None,
+            values: vec![],
+        };
+        for line in lines {
+            let (key, value) = line
+                .split_once(' ')
+                .ok_or_else(|| invalid(format!("invalid line: {:?}", line)))?;
+            let value: f64 = value
+                .parse()
+                .map_err(|_| invalid(format!("invalid number: {:?}", value)))?;
+            match key {
+                "objective" => objective = Some(value),
+                "bound" => checkpoint.best_bound = Some(value),
+                "value" => checkpoint.values.push(value),
+                _ => return Err(invalid(format!("unknown field: {:?}", key))),
+            }
+        }
+        checkpoint.objective = objective.ok_or_else(|| invalid("no objective".to_string()))?;
+        Ok(checkpoint)
+    }
+}
+
+/// The best solution found by [solve_with_checkpoints] or [resume_from_checkpoint],
+/// and whether it could be saved
+#[derive(Debug)]
+pub struct CheckpointedSolution {
+    /// The best solution found
+    pub solution: AnytimeSolution,
+    /// The first error that occurred while writing a checkpoint, if any.
+    /// The resolution goes on when a checkpoint cannot be written,
+    /// but the file may then hold an older solution, or none.
+    pub save_error: Option<io::Error>,
+}
+
+/// Solves the problem like [solve_anytime](crate::anytime::solve_anytime),
+/// and saves a [Checkpoint] to `path` every time a better solution is found.
+///
+/// The solution is returned even if a checkpoint cannot be written:
+/// the error is reported in [CheckpointedSolution::save_error].
+pub fn solve_with_checkpoints<S, P>(
+    problem: UnsolvedProblem,
+    solver: S,
+    budget: Duration,
+    path: P,
+) -> Result<CheckpointedSolution, ResolutionError>
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithTimeLimit,
+    P: AsRef<Path>,
+{
+    checkpointed(problem, solver, budget, None, path.as_ref())
+}
+
+/// Continues a resolution started with [solve_with_checkpoints], from the checkpoint
+/// saved at `path`, for at most `budget`. The checkpoint is updated when a better solution
+/// is found. The problem must be the same as the one the checkpoint was saved for.
+///
+/// Fails if the checkpoint cannot be read, or if it doesn't have as many values
+/// as the problem has variables.
+pub fn resume_from_checkpoint<S, P>(
+    mut problem: UnsolvedProblem,
+    solver: S,
+    budget: Duration,
+    path: P,
+) -> Result<CheckpointedSolution, ResolutionError>
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithTimeLimit,
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let checkpoint = Checkpoint::load(path).map_err(|error| {
+        ResolutionError::Str(format!("cannot read {}: {}", path.display(), error))
+    })?;
+    if checkpoint.values.len() != problem.variables.len() {
+        return Err(ResolutionError::Str(format!(
+            "the checkpoint has {} values, but the problem has {} variables",
+            checkpoint.values.len(),
+            problem.variables.len()
+        )));
+    }
+    if let Some(bound) = checkpoint.best_bound {
+        // No solution is better than the bound, so the resumed resolution can start from it
+        let objective = problem.objective.clone();
+        problem.add_constraint(match problem.direction {
+            ObjectiveDirection::Maximisation => objective.leq(bound),
+            ObjectiveDirection::Minimisation => objective.geq(bound),
+        });
+    }
+    let initial = AnytimeSolution {
+        values: checkpoint.values,
+        objective: checkpoint.objective,
+        best_bound: checkpoint.best_bound,
+        status: SolutionStatus::TimeLimit,
+        stats: Default::default(),
+        elapsed: Duration::ZERO,
+    };
+    checkpointed(problem, solver, budget, Some(initial), path)
+}
+
+fn checkpointed<S>(
+    problem: UnsolvedProblem,
+    solver: S,
+    budget: Duration,
+    initial: Option<AnytimeSolution>,
+    path: &Path,
+) -> Result<CheckpointedSolution, ResolutionError>
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithTimeLimit,
+{
+    let mut save_error = None;
+    let solution = solve_from(problem, solver, budget, initial, |incumbent| {
+        if let Err(error) = Checkpoint::from_solution(incumbent).save(path) {
+            save_error.get_or_insert(error);
+        }
+    })?;
+    Ok(CheckpointedSolution {
+        solution,
+        save_error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Checkpoint;
+
+    #[test]
+    fn save_and_load() {
+        let file = format!("good_lp_checkpoint_unit_test_{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(file);
+        let checkpoint = Checkpoint {
+            objective: 0.1 + 0.2,
+            best_bound: Some(-1e300),
+            values: vec![1., f64::MIN_POSITIVE, -0.],
+        };
+        checkpoint.save(&path).unwrap();
+        assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+        std::fs::write(&path, "good_lp checkpoint 1\nvalue 2\n").unwrap();
+        assert!(Checkpoint::load(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
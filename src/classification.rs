@@ -0,0 +1,243 @@
+//! Structural analysis of problems, to know what kind of solver they need.
+//!
+//! ```
+//! use good_lp::classification::{ProblemKind, Structure};
+//! use good_lp::{constraint, variable, variables};
+//!
+//! let mut vars = variables!();
+//! let items = vars.add_vector(variable().binary(), 3);
+//! let problem = vars
+//!     .maximise(3 * items[0] + 4 * items[1] + 5 * items[2])
+//!     .with(constraint!(2 * items[0] + 3 * items[1] + 4 * items[2] <= 5));
+//! let classification = problem.classify();
+//! assert_eq!(classification.kind, ProblemKind::Binary);
+//! assert!(classification.has(Structure::Knapsack));
+//! ```
+use crate::constraint::Constraint;
+use crate::variable::{UnsolvedProblem, VariableDefinition};
+
+/// The type of the variables of a problem
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ProblemKind {
+    /// All variables are continuous: a linear program (LP)
+    Linear,
+    /// Some variables are integer, and some are continuous: a mixed integer program (MIP)
+    MixedInteger,
+    /// All variables are integer, and some of them are not binary
+    Integer,
+    /// All variables are binary (integer between 0 and 1)
+    Binary,
+}
+
+/// A well-known structure of the constraints of a problem
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Structure {
+    /// Every coefficient is 1 or -1, and every variable appears with a coefficient of 1
+    /// in at most one constraint and with a coefficient of -1 in at most one constraint.
+    /// The constraint matrix is the incidence matrix of a graph, so linear relaxations
+    /// of these problems have integer solutions.
+    NetworkFlow,
+    /// All variables are binary, every constraint says that exactly one of its variables is 1,
+    /// and every variable appears in exactly two constraints
+    Assignment,
+    /// All variables are integer, and all constraints are capacity constraints:
+    /// a weighted sum of variables with positive weights is at most a positive capacity
+    Knapsack,
+    /// All variables are binary, and every constraint says that at least one of its variables is 1
+    SetCovering,
+    /// All variables are binary, and every constraint says that at most one of its variables is 1
+    SetPacking,
+    /// All variables are binary, and every constraint says that exactly one of its variables is 1
+    SetPartitioning,
+}
+
+/// The result of [UnsolvedProblem::classify]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    /// The type of the variables
+    pub kind: ProblemKind,
+    /// The structures that were detected in the constraints
+    pub structures: Vec<Structure>,
+    /// The number of variables
+    pub variables: usize,
+    /// The number of integer variables, including binary ones
+    pub integer_variables: usize,
+    /// The number of binary variables
+    pub binary_variables: usize,
+    /// The number of constraints
+    pub constraints: usize,
+}
+
+impl Classification {
+    /// Whether the given structure was detected
+    pub fn has(&self, structure: Structure) -> bool {
+        self.structures.contains(&structure)
+    }
+
+    /// Whether a solver needs to support integer variables to solve the problem
+    pub fn needs_integer_support(&self) -> bool {
+        self.kind != ProblemKind::Linear
+    }
+
+    /// The cargo feature of the most suitable solver among the ones that are compiled in,
+    /// or `None` if none of them can solve this kind of problem.
+    ///
+    /// HiGHS is preferred for linear programs and Cbc for problems with integer variables.
+    pub fn suggested_solver(&self) -> Option<&'static str> {
+        let linear = [
+            ("highs", cfg!(feature = "highs")),
+            ("coin_cbc", cfg!(feature = "coin_cbc")),
+            ("lpsolve", cfg!(feature = "lpsolve")),
+            ("minilp", cfg!(feature = "minilp")),
+            ("lp-solvers", cfg!(feature = "lp-solvers")),
+        ];
+        let integer = [
+            ("coin_cbc", cfg!(feature = "coin_cbc")),
+            ("lpsolve", cfg!(feature = "lpsolve")),
+            ("lp-solvers", cfg!(feature = "lp-solvers")),
+        ];
+        let candidates: &[_] = if self.needs_integer_support() {
+            &integer
+        } else {
+            &linear
+        };
+        candidates
+            .iter()
+            .find(|(_, enabled)| *enabled)
+            .map(|&(name, _)| name)
+    }
+}
+
+fn is_binary(def: &VariableDefinition) -> bool {
+    def.is_integer && def.min >= 0. && def.max <= 1.
+}
+
+/// The non-zero coefficients of a constraint
+fn coefficients(constraint: &Constraint) -> impl Iterator<Item = (usize, f64)> + '_ {
+    constraint
+        .expression
+        .linear
+        .coefficients
+        .iter()
+        .filter(|(_, &c)| c != 0.)
+        .map(|(var, &c)| (var.index(), c))
+}
+
+/// Whether the constraint is `sum(variables) <kind> 1`.
+/// Constraints are stored as `expression <= 0`, so `sum >= 1` is stored as `1 - sum <= 0`.
+fn is_cardinality_one(constraint: &Constraint, kind: Structure) -> bool {
+    let (coefficient, constant, equality) = match kind {
+        Structure::SetCovering => (-1., 1., false),
+        Structure::SetPacking => (1., -1., false),
+        _ => (1., -1., true),
+    };
+    let expression = &constraint.expression;
+    let matches =
+        |c: f64, k: f64| coefficients(constraint).all(|(_, x)| x == c) && expression.constant == k;
+    if equality {
+        constraint.is_equality && (matches(1., -1.) || matches(-1., 1.))
+    } else {
+        !constraint.is_equality && matches(coefficient, constant)
+    }
+}
+
+fn is_network(problem: &UnsolvedProblem) -> bool {
+    let n = problem.variables.len();
+    let mut positive = vec![false; n];
+    let mut negative = vec![false; n];
+    for constraint in &problem.constraints {
+        for (var, c) in coefficients(constraint) {
+            let seen = if c == 1. {
+                &mut positive[var]
+            } else if c == -1. {
+                &mut negative[var]
+            } else {
+                return false;
+            };
+            if *seen {
+                return false;
+            }
+            *seen = true;
+        }
+    }
+    true
+}
+
+fn is_knapsack(problem: &UnsolvedProblem) -> bool {
+    problem.constraints.iter().all(|constraint| {
+        !constraint.is_equality
+            && constraint.expression.constant < 0.
+            && coefficients(constraint).all(|(_, c)| c > 0.)
+    })
+}
+
+impl UnsolvedProblem {
+    /// Analyses the variables and constraints of the problem,
+    /// to find what kind of problem it is, and which solver is best suited for it.
+    /// Only the constraints that were added to the problem itself are analysed,
+    /// not the ones added later to the solver model.
+    pub fn classify(&self) -> Classification {
+        let definitions: Vec<&VariableDefinition> = self
+            .variables
+            .iter_variables_with_def()
+            .map(|(_, def)| def)
+            .collect();
+        let variables = definitions.len();
+        let integer_variables = definitions.iter().filter(|d| d.is_integer).count();
+        let binary_variables = definitions.iter().filter(|d| is_binary(d)).count();
+        let kind = if integer_variables == 0 {
+            ProblemKind::Linear
+        } else if binary_variables == variables {
+            ProblemKind::Binary
+        } else if integer_variables == variables {
+            ProblemKind::Integer
+        } else {
+            ProblemKind::MixedInteger
+        };
+
+        let mut structures = vec![];
+        let has_constraints = !self.constraints.is_empty();
+        if has_constraints && is_network(self) {
+            structures.push(Structure::NetworkFlow);
+        }
+        if has_constraints && kind == ProblemKind::Binary {
+            for set_structure in [
+                Structure::SetCovering,
+                Structure::SetPacking,
+                Structure::SetPartitioning,
+            ] {
+                if self
+                    .constraints
+                    .iter()
+                    .all(|c| is_cardinality_one(c, set_structure))
+                {
+                    structures.push(set_structure);
+                }
+            }
+            if structures.contains(&Structure::SetPartitioning) {
+                let mut occurrences = vec![0; variables];
+                for constraint in &self.constraints {
+                    for (var, _) in coefficients(constraint) {
+                        occurrences[var] += 1;
+                    }
+                }
+                if occurrences.iter().all(|&o| o == 2) {
+                    structures.push(Structure::Assignment);
+                }
+            }
+        }
+        let all_integer = matches!(kind, ProblemKind::Binary | ProblemKind::Integer);
+        if has_constraints && all_integer && is_knapsack(self) {
+            structures.push(Structure::Knapsack);
+        }
+
+        Classification {
+            kind,
+            structures,
+            variables,
+            integer_variables,
+            binary_variables,
+            constraints: self.constraints.len(),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! [Graph coloring](https://en.wikipedia.org/wiki/Graph_coloring) and frequency assignment:
+//! giving a color to every vertex of a conflict graph,
+//! so that vertices in conflict get different colors.
+//!
+//! The problem uses one binary variable per vertex and color,
+//! equal to 1 when the vertex gets that color, and one binary variable per color,
+//! equal to 1 when the color or a higher one is used.
+//! The objective minimises the number of colors.
+//!
+//! In frequency assignment, colors are frequencies, and two transmitters
+//! can require frequencies that are not only different, but far enough apart
+//! ([Coloring::separation]). The objective then minimises the span of the frequencies used.
+//!
+//! This is a mixed integer program: it requires a solver that supports integer variables.
+//!
+//! ```no_run
+//! use good_lp::coloring::Coloring;
+//! use good_lp::default_solver;
+//! // A cycle of 5 vertices needs 3 colors
+//! let coloring = Coloring::new(5, 5)
+//!     .conflicts(vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)])
+//!     .solve(default_solver)?;
+//! assert_eq!(coloring.n_colors(), 3);
+//! assert_ne!(coloring.color(0), coloring.color(1));
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel, Variable,
+};
+
+/// A coloring problem, built with [Coloring::new]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coloring {
+    n_vertices: usize,
+    max_colors: usize,
+    /// The minimum distance between the colors of two vertices, by pair of vertices `(u, v)`, `u < v`
+    separations: BTreeMap<(usize, usize), usize>,
+    symmetry_breaking: bool,
+    clique_constraints: bool,
+}
+
+impl Coloring {
+    /// Creates a coloring problem with the given number of vertices,
+    /// that can use at most `max_colors` colors, numbered from 0.
+    /// Initially, no vertices are in conflict.
+    pub fn new(n_vertices: usize, max_colors: usize) -> Self {
+        Coloring {
+            n_vertices,
+            max_colors,
+            separations: BTreeMap::new(),
+            symmetry_breaking: true,
+            clique_constraints: false,
+        }
+    }
+
+    /// The two vertices must have different colors
+    pub fn conflict(self, u: usize, v: usize) -> Self {
+        self.separation(u, v, 1)
+    }
+
+    /// Adds a conflict between the vertices of each edge
+    pub fn conflicts<I: IntoIterator<Item = (usize, usize)>>(mut self, edges: I) -> Self {
+        for (u, v) in edges {
+            self = self.conflict(u, v);
+        }
+        self
+    }
+
+    /// The colors of the two vertices must differ by at least `distance`.
+    /// A distance of 1 is a simple conflict.
+    pub fn separation(mut self, u: usize, v: usize, distance: usize) -> Self {
+        assert_ne!(u, v, "a vertex cannot be in conflict with itself");
+        let key = (u.min(v), u.max(v));
+        let current = self.separations.entry(key).or_insert(0);
+        *current = (*current).max(distance);
+        self
+    }
+
+    /// Whether to remove symmetric solutions, obtained by permuting the colors,
+    /// by allowing vertex `i` to only use the colors `0..=i`. Enabled by default.
+    ///
+    /// It is only applied when all separations are simple conflicts:
+    /// when distances between colors matter, colors cannot be permuted.
+    pub fn symmetry_breaking(mut self, enabled: bool) -> Self {
+        self.symmetry_breaking = enabled;
+        self
+    }
+
+    /// Whether to strengthen the formulation with clique constraints. Disabled by default.
+    ///
+    /// Conflicts are grouped into cliques, found greedily,
+    /// and each clique gets a single constraint per color saying that at most one of its vertices
+    /// has that color, instead of one constraint per conflict.
+    /// This gives a tighter linear relaxation, which usually speeds up the resolution
+    /// of dense conflict graphs.
+    pub fn clique_constraints(mut self, enabled: bool) -> Self {
+        self.clique_constraints = enabled;
+        self
+    }
+
+    /// Groups the simple conflicts into cliques, each conflict being in at least one clique
+    fn cliques(&self) -> Vec<Vec<usize>> {
+        let mut neighbors = vec![BTreeSet::new(); self.n_vertices];
+        for (&(u, v), &distance) in &self.separations {
+            if distance == 1 {
+                neighbors[u].insert(v);
+                neighbors[v].insert(u);
+            }
+        }
+        let mut covered = BTreeSet::new();
+        let mut cliques = vec![];
+        for (&(u, v), &distance) in &self.separations {
+            if distance != 1 || covered.contains(&(u, v)) {
+                continue;
+            }
+            let mut clique = vec![u, v];
+            for &w in &neighbors[u] {
+                if w != v && clique.iter().all(|c| neighbors[w].contains(c)) {
+                    clique.push(w);
+                }
+            }
+            clique.sort_unstable();
+            for (i, &a) in clique.iter().enumerate() {
+                for &b in &clique[i + 1..] {
+                    covered.insert((a, b));
+                }
+            }
+            cliques.push(clique);
+        }
+        cliques
+    }
+
+    /// Builds the problem. Its variables and constraints can be inspected and extended
+    /// before it is solved.
+    pub fn problem(&self) -> ColoringProblem {
+        let mut vars = ProblemVariables::new();
+        let assignments: Vec<Vec<Variable>> = (0..self.n_vertices)
+            .map(|v| {
+                (0..self.max_colors)
+                    .map(|c| vars.add(variable().binary().name(format!("x_{}_{}", v, c))))
+                    .collect()
+            })
+            .collect();
+        let used: Vec<Variable> = (0..self.max_colors)
+            .map(|c| vars.add(variable().binary().name(format!("used_{}", c))))
+            .collect();
+        let objective: Expression = used.iter().sum();
+        let mut problem = vars.minimise(objective);
+
+        for (v, colors) in assignments.iter().enumerate() {
+            let n_colors: Expression = colors.iter().sum();
+            problem.add_constraint(constraint!(n_colors == 1).set_name(format!("color_{}", v)));
+            for (&x, &used) in colors.iter().zip(&used) {
+                problem.add_constraint(constraint!(x <= used));
+            }
+        }
+        // A color is counted as used when a higher color is used, so the objective is the span
+        for (c, pair) in used.windows(2).enumerate() {
+            problem.add_constraint(
+                constraint!(pair[1] <= pair[0]).set_name(format!("used_order_{}", c)),
+            );
+        }
+        let simple = self.separations.values().all(|&d| d == 1);
+        if self.symmetry_breaking && simple {
+            for (v, colors) in assignments.iter().enumerate() {
+                for &x in colors.iter().skip(v + 1) {
+                    problem.add_constraint(constraint!(x == 0));
+                }
+            }
+        }
+
+        let cliques = if self.clique_constraints {
+            self.cliques()
+        } else {
+            vec![]
+        };
+        for (k, clique) in cliques.iter().enumerate() {
+            for (c, &used) in used.iter().enumerate() {
+                let with_color: Expression = clique.iter().map(|&v| assignments[v][c]).sum();
+                problem.add_constraint(
+                    constraint!(with_color <= used).set_name(format!("clique_{}_{}", k, c)),
+                );
+            }
+        }
+        for (&(u, v), &distance) in &self.separations {
+            if distance == 1 && self.clique_constraints {
+                // Covered by a clique
+                continue;
+            }
+            for c in 0..self.max_colors {
+                let (x, y) = (assignments[u][c], assignments[v][c]);
+                problem.add_constraint(
+                    constraint!(x + y <= used[c]).set_name(format!("conflict_{}_{}_{}", u, v, c)),
+                );
+                let far = (c + 1..self.max_colors).take(distance - 1);
+                for other in far {
+                    let (x2, y2) = (assignments[u][other], assignments[v][other]);
+                    problem.add_constraint(constraint!(x + y2 <= 1));
+                    problem.add_constraint(constraint!(x2 + y <= 1));
+                }
+            }
+        }
+        ColoringProblem {
+            problem,
+            assignments,
+            used,
+        }
+    }
+
+    /// Finds a coloring with the smallest number of colors
+    pub fn solve<S: Solver>(
+        &self,
+        solver: S,
+    ) -> Result<ColoringSolution, <S::Model as SolverModel>::Error> {
+        self.problem().solve(solver)
+    }
+}
+
+/// The optimisation problem built from a [Coloring]
+pub struct ColoringProblem {
+    problem: UnsolvedProblem,
+    /// `assignments[vertex][color]`
+    assignments: Vec<Vec<Variable>>,
+    used: Vec<Variable>,
+}
+
+impl ColoringProblem {
+    /// The binary variable that is 1 when the vertex has the given color
+    pub fn assignment_variable(&self, vertex: usize, color: usize) -> Variable {
+        self.assignments[vertex][color]
+    }
+
+    /// The binary variable that is 1 when the given color or a higher one is used
+    pub fn used_variable(&self, color: usize) -> Variable {
+        self.used[color]
+    }
+
+    /// The underlying problem, to which additional constraints can be added
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Finds the optimal coloring
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<ColoringSolution, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        let colors = self
+            .assignments
+            .iter()
+            .map(|colors| {
+                (0..colors.len())
+                    .max_by(|&a, &b| {
+                        solution
+                            .value(colors[a])
+                            .total_cmp(&solution.value(colors[b]))
+                    })
+                    .expect("there is at least one color")
+            })
+            .collect();
+        Ok(ColoringSolution { colors })
+    }
+}
+
+/// The color of each vertex
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColoringSolution {
+    colors: Vec<usize>,
+}
+
+impl ColoringSolution {
+    /// The color of the given vertex
+    pub fn color(&self, vertex: usize) -> usize {
+        self.colors[vertex]
+    }
+
+    /// The color of every vertex
+    pub fn colors(&self) -> &[usize] {
+        &self.colors
+    }
+
+    /// The vertices with the given color
+    pub fn vertices_with(&self, color: usize) -> Vec<usize> {
+        (0..self.colors.len())
+            .filter(|&v| self.colors[v] == color)
+            .collect()
+    }
+
+    /// The number of distinct colors used
+    pub fn n_colors(&self) -> usize {
+        self.colors.iter().collect::<BTreeSet<_>>().len()
+    }
+
+    /// The difference between the highest and the lowest color used
+    pub fn span(&self) -> usize {
+        let max = self.colors.iter().max();
+        let min = self.colors.iter().min();
+        match (min, max) {
+            (Some(min), Some(max)) => max - min,
+            _ => 0,
+        }
+    }
+}
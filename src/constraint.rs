@@ -1,22 +1,207 @@
 //! Constraints define the inequalities that must hold in the solution.
+use crate::affine_expression_trait::IntoAffineExpression;
 use crate::expression::Expression;
+use crate::solvers::ResolutionError;
 use crate::variable::{FormatWithVars, Variable};
 use core::fmt::{Debug, Formatter};
 use std::ops::{Shl, Shr, Sub};
 
 /// A constraint represents a single (in)equality that must hold in the solution.
+///
+/// Constraints are stored, and passed to the solvers, in a canonical form:
+/// `linear + constant <= 0` or `linear + constant == 0`.
+/// `a <= b` and `b >= a` are both stored as `a - b <= 0`.
+/// A variable that appears several times in a constraint, as in `x + 2 * x <= 3`,
+/// appears only once, with the sum of its coefficients,
+/// and variables whose coefficients cancel out, as in `x + y - y <= 3`, are removed.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Constraint {
     /// The expression that is constrained to be null or negative
     pub(crate) expression: Expression,
     /// if is_equality, represents expression == 0, otherwise, expression <= 0
     pub(crate) is_equality: bool,
+    /// The name of the constraint, used when exporting the problem to a file
+    pub(crate) name: Option<String>,
+    /// For strict inequalities, the margin that was added to the expression
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) strict_epsilon: Option<f64>,
+}
+
+/// The margin by which strict inequalities such as `constraint!(a > b)` are satisfied,
+/// unless another one is set with [Constraint::set_strict_epsilon]
+/// or with a [StrictInequality] policy.
+pub const DEFAULT_STRICT_EPSILON: f64 = 1e-6;
+
+/// How the strict inequalities of a problem are handled, set with
+/// [UnsolvedProblem::set_strict_inequality](crate::variable::UnsolvedProblem::set_strict_inequality).
+///
+/// Linear programming solvers only handle non-strict inequalities:
+/// `x > 0` has no optimal solution when minimising `x`.
+/// A strict inequality is either turned into a non-strict one with a margin,
+/// or rejected, for models in which it would be a mistake.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrictInequality {
+    /// Strict inequalities are not allowed: the resolution of a problem that contains one fails
+    Error,
+    /// `a < b` becomes `a + epsilon <= b`
+    EpsilonRelax(f64),
+}
+
+/// The default is to relax strict inequalities by [DEFAULT_STRICT_EPSILON]
+impl Default for StrictInequality {
+    fn default() -> Self {
+        StrictInequality::EpsilonRelax(DEFAULT_STRICT_EPSILON)
+    }
 }
 
 impl Constraint {
-    fn new(expression: Expression, is_equality: bool) -> Constraint {
+    pub(crate) fn new(mut expression: Expression, is_equality: bool) -> Constraint {
+        // Some solvers reject explicit zeros in their constraint matrix
+        expression
+            .linear
+            .coefficients
+            .retain(|_, coefficient| *coefficient != 0.);
         Constraint {
             expression,
             is_equality,
+            name: None,
+            strict_epsilon: None,
+        }
+    }
+
+    /// Give a name to the constraint.
+    /// Names are not used by the solvers, but they are written when exporting the problem to a file.
+    ///
+    /// ```
+    /// # use good_lp::*;
+    /// variables! {vars: x <= 10;}
+    /// let capacity = constraint!(2 * x <= 5).set_name("capacity");
+    /// assert_eq!(capacity.get_name(), Some("capacity"));
+    /// ```
+    pub fn set_name<N: Into<String>>(mut self, name: N) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The name of the constraint, if one was set using [Constraint::set_name]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Changes the margin of a strict inequality: `a < b` becomes `a + epsilon <= b`.
+    /// Has no effect on other constraints.
+    ///
+    /// ```
+    /// # use good_lp::*;
+    /// variables! {vars: x <= 10;}
+    /// let below = constraint!(x < 5).set_strict_epsilon(0.01);
+    /// assert_eq!(below.get_strict_epsilon(), Some(0.01));
+    /// ```
+    pub fn set_strict_epsilon(mut self, epsilon: f64) -> Self {
+        if let Some(previous) = self.strict_epsilon {
+            self.expression.constant += epsilon - previous;
+            self.strict_epsilon = Some(epsilon);
+        }
+        self
+    }
+
+    /// The margin of a strict inequality, or `None` if the constraint is not strict
+    pub fn get_strict_epsilon(&self) -> Option<f64> {
+        self.strict_epsilon
+    }
+
+    /// Applies a policy to the constraint if it is a strict inequality:
+    /// its margin is changed, or an error is returned.
+    /// Other constraints are returned unchanged.
+    ///
+    /// ```
+    /// # use good_lp::*;
+    /// use good_lp::constraint::StrictInequality;
+    /// variables! {vars: x <= 10;}
+    /// let relaxed = constraint!(x > 0).with_strict_policy(StrictInequality::EpsilonRelax(0.1));
+    /// assert_eq!(relaxed.unwrap().get_strict_epsilon(), Some(0.1));
+    /// assert!(constraint!(x > 0).with_strict_policy(StrictInequality::Error).is_err());
+    /// assert!(constraint!(x >= 0).with_strict_policy(StrictInequality::Error).is_ok());
+    /// ```
+    pub fn with_strict_policy(
+        self,
+        policy: StrictInequality,
+    ) -> Result<Self, StrictInequalityError> {
+        match (self.strict_epsilon, policy) {
+            (None, _) => Ok(self),
+            (Some(_), StrictInequality::EpsilonRelax(epsilon)) => {
+                Ok(self.set_strict_epsilon(epsilon))
+            }
+            (Some(_), StrictInequality::Error) => Err(StrictInequalityError {
+                name: self.name.clone(),
+            }),
+        }
+    }
+}
+
+/// The error returned when a strict inequality is used where they are not allowed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictInequalityError {
+    /// The name of the constraint, if it has one
+    pub name: Option<String>,
+}
+
+impl std::fmt::Display for StrictInequalityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the strict inequality ")?;
+        if let Some(name) = &self.name {
+            write!(f, "{:?} ", name)?;
+        }
+        write!(
+            f,
+            "is not allowed: linear solvers only handle non-strict inequalities, \
+            use <= or >=, or allow strict inequalities with a margin"
+        )
+    }
+}
+
+impl std::error::Error for StrictInequalityError {}
+
+/// The [StrictInequality] policy of a solver model, taken from the problem it was created from.
+/// It is applied to every constraint added to the model, and a forbidden strict inequality
+/// makes the resolution fail.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StrictInequalities {
+    policy: Option<StrictInequality>,
+    violation: Option<StrictInequalityError>,
+}
+
+impl StrictInequalities {
+    pub(crate) fn new(policy: Option<StrictInequality>) -> Self {
+        StrictInequalities {
+            policy,
+            violation: None,
+        }
+    }
+
+    /// Changes the margin of a strict inequality, or records it if it is forbidden
+    pub(crate) fn apply(&mut self, constraint: Constraint) -> Constraint {
+        match (self.policy, constraint.strict_epsilon) {
+            (Some(StrictInequality::EpsilonRelax(epsilon)), Some(_)) => {
+                constraint.set_strict_epsilon(epsilon)
+            }
+            (Some(StrictInequality::Error), Some(_)) => {
+                self.violation.get_or_insert(StrictInequalityError {
+                    name: constraint.name.clone(),
+                });
+                constraint
+            }
+            _ => constraint,
+        }
+    }
+
+    /// An error if a forbidden strict inequality was added
+    pub(crate) fn check(&self) -> Result<(), ResolutionError> {
+        match &self.violation {
+            Some(error) => Err(ResolutionError::Str(error.to_string())),
+            None => Ok(()),
         }
     }
 }
@@ -39,20 +224,143 @@ impl Debug for Constraint {
 }
 
 /// equals
-pub fn eq<B, A: Sub<B, Output = Expression>>(a: A, b: B) -> Constraint {
-    Constraint::new(a - b, true)
+pub fn eq<B, A: Sub<B>>(a: A, b: B) -> Constraint
+where
+    A::Output: IntoAffineExpression,
+{
+    Constraint::new((a - b).into_expression(), true)
 }
 
 /// less than or equal
-pub fn leq<B, A: Sub<B, Output = Expression>>(a: A, b: B) -> Constraint {
-    Constraint::new(a - b, false)
+pub fn leq<B, A: Sub<B>>(a: A, b: B) -> Constraint
+where
+    A::Output: IntoAffineExpression,
+{
+    Constraint::new((a - b).into_expression(), false)
 }
 
 /// greater than or equal
-pub fn geq<A, B: Sub<A, Output = Expression>>(a: A, b: B) -> Constraint {
+pub fn geq<A, B: Sub<A>>(a: A, b: B) -> Constraint
+where
+    B::Output: IntoAffineExpression,
+{
     leq(b, a)
 }
 
+/// strictly less than, by a margin of [DEFAULT_STRICT_EPSILON]
+pub fn lt<B, A: Sub<B>>(a: A, b: B) -> Constraint
+where
+    A::Output: IntoAffineExpression,
+{
+    let mut constraint = leq(a, b);
+    constraint.expression.constant += DEFAULT_STRICT_EPSILON;
+    constraint.strict_epsilon = Some(DEFAULT_STRICT_EPSILON);
+    constraint
+}
+
+/// strictly greater than, by a margin of [DEFAULT_STRICT_EPSILON]
+pub fn gt<A, B: Sub<A>>(a: A, b: B) -> Constraint
+where
+    B::Output: IntoAffineExpression,
+{
+    lt(b, a)
+}
+
+/// Constrains `part` to be between `min_pct` and `max_pct` percent of `whole`,
+/// for instance the share of proteins in a diet, or of a sector in a portfolio.
+///
+/// The ratio `part / whole` is not linear, so both sides are multiplied by `whole`:
+/// `100 * part >= min_pct * whole` and `100 * part <= max_pct * whole`.
+/// This is only equivalent when `whole` is nonnegative.
+/// When `whole` is zero, `part` has to be zero too.
+///
+/// Panics if the percentages are not between 0 and 100, or if `min_pct > max_pct`.
+///
+/// ```
+/// use good_lp::constraint::share_constraint;
+/// use good_lp::{default_solver, variables, Solution, SolverModel};
+///
+/// variables! {vars: 0 <= stocks; 0 <= bonds;}
+/// let [at_least, at_most] = share_constraint(stocks, stocks + bonds, 20., 60.);
+/// let solution = vars
+///     .maximise(stocks)
+///     .using(default_solver)
+///     .with(at_least)
+///     .with(at_most)
+///     .with(good_lp::constraint!(stocks + bonds == 1000))
+///     .solve()?;
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(solution.value(stocks), 600.);
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn share_constraint<P: IntoAffineExpression, W: IntoAffineExpression>(
+    part: P,
+    whole: W,
+    min_pct: f64,
+    max_pct: f64,
+) -> [Constraint; 2] {
+    assert!(
+        (0. ..=100.).contains(&min_pct) && (0. ..=100.).contains(&max_pct),
+        "percentages must be between 0 and 100, got {} and {}",
+        min_pct,
+        max_pct
+    );
+    assert!(
+        min_pct <= max_pct,
+        "the minimum share ({}%) is above the maximum share ({}%)",
+        min_pct,
+        max_pct
+    );
+    let part = part.into_expression();
+    let whole = whole.into_expression();
+    let scaled_part = 100. * part;
+    [
+        geq(scaled_part.clone(), min_pct * whole.clone()),
+        leq(scaled_part, max_pct * whole),
+    ]
+}
+
+/// Exactly one of the binary variables is 1, as in "each task is given to one worker".
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::constraint::exactly_one;
+/// use good_lp::{default_solver, variable, variables, Expression, Solution, SolverModel};
+///
+/// let mut vars = variables!();
+/// let options = vars.add_vector(variable().binary(), 3);
+/// let solution = vars
+///     .maximise(options[0] + 2 * options[1] + 3 * options[2])
+///     .using(default_solver)
+///     .with(exactly_one(&options).set_name("choice"))
+///     .solve()?;
+/// let chosen: Expression = options.iter().sum();
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(solution.eval(&chosen), 1.);
+/// assert_float_eq(solution.value(options[2]), 1.);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn exactly_one(variables: &[Variable]) -> Constraint {
+    eq(sum_of(variables), 1.)
+}
+
+/// At most `k` of the binary variables are 1
+pub fn at_most_k(variables: &[Variable], k: usize) -> Constraint {
+    leq(sum_of(variables), k as f64)
+}
+
+/// At least `k` of the binary variables are 1.
+/// The constraint cannot be satisfied when there are fewer than `k` variables.
+pub fn at_least_k(variables: &[Variable], k: usize) -> Constraint {
+    geq(sum_of(variables), k as f64)
+}
+
+fn sum_of(variables: &[Variable]) -> Expression {
+    variables.iter().sum()
+}
+
 macro_rules! impl_shifts {
     ($($t:ty)*) => {$(
         impl< RHS> Shl<RHS> for $t where Self: Sub<RHS, Output=Expression> {
@@ -78,6 +386,11 @@ impl_shifts!(Expression Variable);
 /// This macro allows defining constraints using `a + b <= c + d`
 /// instead of `(a + b).leq(c + d)` or `a + b << c + d`
 ///
+/// Strict inequalities, `a < b` and `a > b`, are turned into `a + epsilon <= b`
+/// and `a >= b + epsilon`, with an epsilon of [DEFAULT_STRICT_EPSILON](crate::constraint::DEFAULT_STRICT_EPSILON)
+/// that can be changed for one constraint with [Constraint::set_strict_epsilon],
+/// or for a whole problem with [UnsolvedProblem::set_strict_epsilon](crate::variable::UnsolvedProblem::set_strict_epsilon).
+///
 /// # Example
 ///
 /// ## Create a constraint
@@ -110,28 +423,195 @@ impl_shifts!(Expression Variable);
 /// assert_float_eq(10., solution.value(a));
 /// assert_float_eq(10., solution.value(b));
 /// ```
+///
+/// ## Sums
+///
+/// `sum(iterator)` is the sum of the items of the iterator,
+/// that can be variables, expressions or numbers: see [sum](crate::sum).
+/// `sum(expression for pattern in iterator)` sums the expression for each item of the iterator.
+/// On the right hand side of the comparison, a sum is only recognized at the start.
+///
+/// ```
+/// use good_lp::*;
+/// use std::collections::HashMap;
+///
+/// let mut vars = variables!();
+/// let foods: HashMap<&str, Variable> = ["bread", "rice"]
+///     .iter()
+///     .map(|&food| (food, vars.add(variable().min(0))))
+///     .collect();
+/// let calories: HashMap<&str, f64> = vec![("bread", 250.), ("rice", 130.)].into_iter().collect();
+/// let at_most_ten = constraint!(sum(foods.values()) <= 10);
+/// let enough_energy = constraint!(sum(calories[food] * var for (food, &var) in &foods) >= 2000);
+/// ```
+///
+/// ## Invalid constraints
+///
+/// A constraint between two numbers is always true or always false,
+/// and is rejected at compile time:
+///
+/// ```compile_fail
+/// # use good_lp::*;
+/// let always_false = constraint!(3 <= -1.5);
+/// ```
+///
+/// So is a constraint without a comparison operator:
+///
+/// ```compile_fail
+/// # use good_lp::*;
+/// # let mut vars = variables!();
+/// # let a = vars.add(variable());
+/// let missing_operator = constraint!(a + 3);
+/// ```
 #[macro_export]
 macro_rules! constraint {
+    // `sum(expression for pattern in iterator)`: a comprehension
+    (@sum [$($body:tt)+] for $pattern:pat in $($iterator:tt)+) => {
+        ::std::iter::IntoIterator::into_iter($($iterator)+)
+            .map(|$pattern| $($body)+)
+            .sum::<$crate::Expression>()
+    };
+    (@sum [$($body:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::constraint!(@sum [$($body)* $next] $($rest)*)
+    };
+    // `sum(iterator)`
+    (@sum [$($iterator:tt)*]) => {
+        $crate::sum($($iterator)*)
+    };
+    // The right hand side is not processed token by token, so that it can be long.
+    // Only a `sum(...)` at its start is replaced
+    (@right sum ($($arguments:tt)*) $($rest:tt)*) => {
+        $crate::constraint!(@sum [] $($arguments)*) $($rest)*
+    };
+    (@right $($right:tt)*) => { $($right)* };
+    // A number on each side of the comparison, such as `3 <= -1.5`.
+    // The sides are captured as token trees first: a `literal` fragment cannot fail
+    // on a side that starts with `-`, such as `-x`, without aborting the whole macro
+    (@start $all:tt $left:tt $operator:tt $right:tt) => {
+        $crate::constraint!(@numbers [$left] $operator [$right] $all)
+    };
+    (@start $all:tt - $left:tt $operator:tt $right:tt) => {
+        $crate::constraint!(@numbers [$left] $operator [$right] $all)
+    };
+    (@start $all:tt $left:tt $operator:tt - $right:tt) => {
+        $crate::constraint!(@numbers [$left] $operator [$right] $all)
+    };
+    (@start $all:tt - $left:tt $operator:tt - $right:tt) => {
+        $crate::constraint!(@numbers [$left] $operator [$right] $all)
+    };
+    (@start [$($all:tt)*] $($ignored:tt)*) => {
+        $crate::constraint!([] $($all)*)
+    };
+    (@numbers [-] $operator:tt $right:tt [$($all:tt)*]) => { $crate::constraint!([] $($all)*) };
+    (@numbers $left:tt $operator:tt [-] [$($all:tt)*]) => { $crate::constraint!([] $($all)*) };
+    (@numbers [$left:literal] <= [$right:literal] $all:tt) => { $crate::constraint!(@constants) };
+    (@numbers [$left:literal] >= [$right:literal] $all:tt) => { $crate::constraint!(@constants) };
+    (@numbers [$left:literal] == [$right:literal] $all:tt) => { $crate::constraint!(@constants) };
+    (@numbers [$left:literal] < [$right:literal] $all:tt) => { $crate::constraint!(@constants) };
+    (@numbers [$left:literal] > [$right:literal] $all:tt) => { $crate::constraint!(@constants) };
+    (@numbers $left:tt $operator:tt $right:tt [$($all:tt)*]) => { $crate::constraint!([] $($all)*) };
+    (@constants) => {
+        compile_error!(
+            "this constraint compares two numbers, so it does not depend on any variable: \
+            it is always true or always false"
+        )
+    };
     ([$($left:tt)*] <= $($right:tt)*) => {
-        $crate::constraint::leq($($left)*, $($right)*)
+        $crate::constraint::leq($($left)*, $crate::constraint!(@right $($right)*))
     };
     ([$($left:tt)*] >= $($right:tt)*) => {
-        $crate::constraint::geq($($left)*, $($right)*)
+        $crate::constraint::geq($($left)*, $crate::constraint!(@right $($right)*))
     };
     ([$($left:tt)*] == $($right:tt)*) => {
-        $crate::constraint::eq($($left)*, $($right)*)
+        $crate::constraint::eq($($left)*, $crate::constraint!(@right $($right)*))
+    };
+    ([$($left:tt)*] < $($right:tt)*) => {
+        $crate::constraint::lt($($left)*, $crate::constraint!(@right $($right)*))
+    };
+    ([$($left:tt)*] > $($right:tt)*) => {
+        $crate::constraint::gt($($left)*, $crate::constraint!(@right $($right)*))
     };
-    // Stop condition: all token have been processed
+    // Stop condition: all token have been processed without finding a comparison operator
     ([$($left:tt)*]) => {
-        $($left:tt)*
+        compile_error!("a constraint needs a comparison operator: <=, >=, ==, < or >")
+    };
+    // `sum(...)` on the left hand side
+    ([$($left:tt)*] . $method:ident $($right:tt)*) => {
+        $crate::constraint!([$($left)* . $method] $($right)*)
+    };
+    ([$($left:tt)*] :: $name:ident $($right:tt)*) => {
+        $crate::constraint!([$($left)* :: $name] $($right)*)
+    };
+    // The `<` and `>` of a turbofish, as in `x.iter().sum::<Expression>()`, are not comparisons
+    ([$($left:tt)*] :: < $($generic:ty),+ > $($right:tt)*) => {
+        $crate::constraint!([$($left)* :: < $($generic),+ >] $($right)*)
+    };
+    ([$($left:tt)*] sum ($($arguments:tt)*) $($right:tt)*) => {
+        $crate::constraint!([$($left)* $crate::constraint!(@sum [] $($arguments)*)] $($right)*)
     };
     // The next token is not a special one
     ([$($left:tt)*] $next:tt $($right:tt)*) => {
-        constraint!([$($left)* $next] $($right)*)
+        $crate::constraint!([$($left)* $next] $($right)*)
     };
-    // Initial rule: start the recursive calls
+    // Initial rule: check the constraint, and start the recursive calls
     ($($all:tt)*) => {
-        constraint!([] $($all)*)
+        $crate::constraint!(@start [$($all)*] $($all)*)
+    };
+}
+
+/// Adds several constraints to a problem or a model, and returns their references in an array.
+///
+/// The first argument is the problem, as an [UnsolvedProblem](crate::variable::UnsolvedProblem)
+/// or a [SolverModel](crate::SolverModel), followed by a semicolon.
+/// Then come the constraints, written as in [constraint!], separated by semicolons.
+/// A constraint can be given a name, as a string literal followed by a colon.
+///
+/// ```
+/// use good_lp::{constraints, default_solver, variables, Solution, SolverModel};
+///
+/// variables! {vars: 0 <= a; 0 <= b;}
+/// let mut problem = vars.maximise(a + b);
+/// let references = constraints!(problem;
+///     "capacity": a + 2 * b <= 10;
+///     a <= 4;
+/// );
+/// assert_eq!(references.len(), 2);
+/// let solution = problem.using(default_solver).solve()?;
+/// # fn assert_float_eq(x:f64, y:f64) { assert!((x-y).abs() <= 1e-6, "{} != {}", x, y); }
+/// assert_float_eq(solution.value(b), 3.);
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+#[macro_export]
+macro_rules! constraints {
+    // Adds a single constraint, with or without a name
+    (@add $model:ident $name:literal : $($constraint:tt)+) => {
+        $model.add_constraint(
+            $crate::constraint!($($constraint)+).set_name($name)
+        )
+    };
+    (@add $model:ident $($constraint:tt)+) => {
+        $model.add_constraint($crate::constraint!($($constraint)+))
+    };
+    // Splits the constraints on semicolons, ignoring empty ones
+    (@split [$model:expr] [$($done:tt)*] [] ; $($rest:tt)*) => {
+        $crate::constraints!(@split [$model] [$($done)*] [] $($rest)*)
+    };
+    (@split [$model:expr] [$($done:tt)*] [$($current:tt)+] ; $($rest:tt)*) => {
+        $crate::constraints!(@split [$model] [$($done)* [$($current)+]] [] $($rest)*)
+    };
+    (@split [$model:expr] [$($done:tt)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::constraints!(@split [$model] [$($done)*] [$($current)* $next] $($rest)*)
+    };
+    (@split [$model:expr] [$($done:tt)*] [$($current:tt)+]) => {
+        $crate::constraints!(@split [$model] [$($done)* [$($current)+]] [])
+    };
+    // All the constraints are split: add them in order
+    (@split [$model:expr] [$([$($constraint:tt)+])*] []) => {{
+        let model = &mut $model;
+        [$($crate::constraints!(@add model $($constraint)+)),*]
+    }};
+    ($model:expr; $($constraints:tt)*) => {
+        $crate::constraints!(@split [$model] [] [] $($constraints)*)
     };
 }
 
@@ -150,6 +630,212 @@ mod tests {
         let v0 = vars.add_variable();
         let v1 = vars.add_variable();
         let f = format!("{:?}", (3. - v0) >> v1);
-        assert!(vec!["v0 + v1 <= 3", "v1 + v0 <= 3"].contains(&&*f), "{}", f)
+        assert!(["v0 + v1 <= 3", "v1 + v0 <= 3"].contains(&&*f), "{}", f)
+    }
+
+    #[test]
+    fn strict_inequalities() {
+        let mut vars = variables!();
+        let x = vars.add_variable();
+        let above = constraint!(2 * x > 3).set_strict_epsilon(0.5);
+        assert!((above.expression.constant - 3.5).abs() < 1e-12);
+        assert_eq!(above.expression.linear.coefficients[&x], -2.);
+        let below = constraint!(x < 1);
+        assert_eq!(
+            below.get_strict_epsilon(),
+            Some(super::DEFAULT_STRICT_EPSILON)
+        );
+        assert_eq!(
+            constraint!(x <= 1)
+                .set_strict_epsilon(1.)
+                .get_strict_epsilon(),
+            None
+        );
+    }
+
+    #[test]
+    fn left_side_starting_with_minus() {
+        let mut vars = variables!();
+        let x = vars.add_variable();
+        let y = vars.add_variable();
+        let c = constraint!(-x <= 3);
+        assert_eq!(c.expression.linear.coefficients[&x], -1.);
+        assert_eq!(c.expression.constant, -3.);
+        let c = constraint!(-x - y == -4);
+        assert_eq!(c.expression.linear.coefficients[&y], -1.);
+        assert_eq!(c.expression.constant, 4.);
+        let c = constraint!(-2 <= x);
+        assert_eq!(c.expression.linear.coefficients[&x], -1.);
+        assert_eq!(c.expression.constant, -2.);
+    }
+
+    #[test]
+    fn turbofish_in_left_side() {
+        use crate::Expression;
+        let mut vars = variables!();
+        let x = vars.add_vector(crate::variable(), 2);
+        let c = constraint!(x.iter().sum::<Expression>() <= 3);
+        assert_eq!(c.expression.linear.coefficients[&x[1]], 1.);
+        assert_eq!(c.expression.constant, -3.);
+        let c = constraint!(x.iter().sum::<Expression>() > x.iter().sum::<Expression>() / 2);
+        assert_eq!(c.expression.linear.coefficients[&x[0]], -0.5);
+    }
+
+    #[test]
+    fn share_of_an_empty_whole_is_empty() {
+        let mut vars = variables!();
+        let part = vars.add_variable();
+        let whole = vars.add_variable();
+        let [at_least, at_most] = super::share_constraint(part, whole, 0., 100.);
+        // 100 * part >= 0 and 100 * part <= 100 * whole: part = 0 when whole = 0
+        assert_eq!(at_least.expression.linear.coefficients[&part], -100.);
+        assert_eq!(at_most.expression.linear.coefficients[&part], 100.);
+        assert_eq!(at_most.expression.linear.coefficients[&whole], -100.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn share_bounds_must_be_ordered() {
+        let mut vars = variables!();
+        let part = vars.add_variable();
+        super::share_constraint(part, 10, 50., 40.);
+    }
+
+    #[test]
+    fn constraints_are_normalized() {
+        let mut vars = variables!();
+        let x = vars.add_variable();
+        let y = vars.add_variable();
+        let c = constraint!(x + 2 * x + y + 3 >= y + 1);
+        assert_eq!(c.expression.linear.coefficients.len(), 1);
+        assert_eq!(c.expression.linear.coefficients[&x], -3.);
+        assert_eq!(c.expression.constant, -2.);
+        assert!(!c.is_equality);
+        assert_eq!(constraint!(x + y == y + 1), constraint!(x == 1));
+    }
+
+    #[test]
+    fn cardinality_constraints() {
+        let mut vars = variables!();
+        let x = vars.add_vector(crate::variable().binary(), 3);
+        let one = super::exactly_one(&x);
+        assert!(one.is_equality);
+        assert_eq!(one.expression.constant, -1.);
+        let at_most = super::at_most_k(&x, 2);
+        assert!(!at_most.is_equality);
+        assert_eq!(at_most.expression.constant, -2.);
+        assert!(x
+            .iter()
+            .all(|v| at_most.expression.linear.coefficients[v] == 1.));
+        // k - sum <= 0
+        let at_least = super::at_least_k(&x, 2);
+        assert_eq!(at_least.expression.constant, 2.);
+        assert!(x
+            .iter()
+            .all(|v| at_least.expression.linear.coefficients[v] == -1.));
+        assert!(super::at_most_k(&[], 0)
+            .expression
+            .linear
+            .coefficients
+            .is_empty());
+    }
+
+    #[test]
+    fn cardinality_bounds() {
+        use crate::Solution;
+        use std::collections::HashMap;
+        let mut vars = variables!();
+        let x = vars.add_vector(crate::variable().binary(), 3);
+        // The values of x when `ones` of the variables are 1
+        let with_ones = |ones: usize| -> HashMap<_, _> {
+            x.iter()
+                .enumerate()
+                .map(|(i, &v)| (v, if i < ones { 1. } else { 0. }))
+                .collect()
+        };
+        let satisfied = |constraint: &super::Constraint| -> Vec<bool> {
+            (0..=3)
+                .map(|ones| with_ones(ones).is_satisfied(constraint, 1e-9))
+                .collect()
+        };
+        let all = vec![true; 4];
+        assert_eq!(
+            satisfied(&super::at_most_k(&x, 2)),
+            [true, true, true, false]
+        );
+        assert_eq!(
+            satisfied(&super::at_least_k(&x, 2)),
+            [false, false, true, true]
+        );
+        // k = 0 forces every variable to 0, or leaves them free
+        assert_eq!(
+            satisfied(&super::at_most_k(&x, 0)),
+            [true, false, false, false]
+        );
+        assert_eq!(satisfied(&super::at_least_k(&x, 0)), all);
+        // k > n leaves the variables free, or cannot be satisfied
+        assert_eq!(satisfied(&super::at_most_k(&x, 5)), all);
+        assert_eq!(satisfied(&super::at_least_k(&x, 4)), vec![false; 4]);
+    }
+
+    #[test]
+    fn several_constraints_with_names() {
+        let mut vars = variables!();
+        let x = vars.add_variable();
+        let y = vars.add_variable();
+        let mut problem = vars.minimise(x + y);
+        let [first, second, third] = crate::constraints!(problem;
+            x >= 1;;
+            "limit": x + y <= 3;
+            -2 <= y
+        );
+        assert_eq!((first.index, second.index, third.index), (0, 1, 2));
+        let names: Vec<_> = problem.constraints.iter().map(|c| c.get_name()).collect();
+        assert_eq!(names, [None, Some("limit"), None]);
+        assert_eq!(problem.constraints[2], constraint!(y >= -2));
+    }
+
+    #[test]
+    fn sums_in_constraints() {
+        use crate::Expression;
+        let mut vars = variables!();
+        let x = vars.add_vector(crate::variable(), 3);
+        let total: Expression = x.iter().sum();
+        assert_eq!(constraint!(sum(&x) <= 3), constraint!(total.clone() <= 3));
+        assert_eq!(constraint!(3 >= sum(&x)), constraint!(total.clone() <= 3));
+        let weighted = constraint!(sum(2 * x[i] for i in 0..3) == sum(x[i] for i in 0..2) + 1);
+        assert_eq!(
+            weighted,
+            constraint!(2 * x[0] + 2 * x[1] + 2 * x[2] == 1 + x[0] + x[1])
+        );
+        // Methods and functions named `sum` are not replaced
+        struct Group(Vec<crate::Variable>);
+        impl Group {
+            fn sum(&self) -> Expression {
+                self.0.iter().skip(1).sum()
+            }
+        }
+        let group = Group(x.clone());
+        assert_eq!(
+            constraint!(group.sum() >= crate::sum(&x)),
+            constraint!(x[1] + x[2] >= total)
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn long_right_hand_side() {
+        let mut vars = variables!();
+        let x = vars.add_variable();
+        let long = constraint!(x <=
+            1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 +
+            1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 +
+            1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 +
+            1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 +
+            1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 +
+            1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 +
+            1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0 + 1.0
+        );
+        assert_eq!(long, constraint!(x <= 70));
     }
 }
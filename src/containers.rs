@@ -0,0 +1,314 @@
+//! Containers for families of variables, created by [ProblemVariables].
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Deref, Index};
+
+use crate::{Expression, ProblemVariables, Solution, Variable, VariableDefinition};
+
+/// A vector of variables, that can be built from the `Vec<Variable>`
+/// returned by [ProblemVariables::add_vector].
+/// It gives access to the slice methods of the variables, such as `len` and `iter`.
+///
+/// ```
+/// use good_lp::{variable, variables, VariableVec};
+///
+/// let mut vars = variables!();
+/// let x = VariableVec::from(vars.add_vector(variable().min(0), 4));
+/// let first_half = x[..2].iter().sum::<good_lp::Expression>();
+/// let total = x.sum();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VariableVec(Vec<Variable>);
+
+impl VariableVec {
+    /// The sum of all the variables
+    pub fn sum(&self) -> Expression {
+        self.0.iter().sum()
+    }
+
+    /// The values of the variables in the solution
+    pub fn values<S: Solution>(&self, solution: &S) -> Vec<f64> {
+        solution.extract_vec(&self.0)
+    }
+}
+
+impl From<Vec<Variable>> for VariableVec {
+    fn from(variables: Vec<Variable>) -> Self {
+        VariableVec(variables)
+    }
+}
+
+impl From<VariableVec> for Vec<Variable> {
+    fn from(variables: VariableVec) -> Self {
+        variables.0
+    }
+}
+
+impl Deref for VariableVec {
+    type Target = [Variable];
+
+    fn deref(&self) -> &[Variable] {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a VariableVec {
+    type Item = &'a Variable;
+    type IntoIter = std::slice::Iter<'a, Variable>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// A matrix of variables, created by [ProblemVariables::add_matrix],
+/// indexed by `(row, column)`
+///
+/// ```
+/// use good_lp::{constraint, variable, variables};
+///
+/// let mut vars = variables!();
+/// // How much each of 2 factories ships to each of 3 shops
+/// let shipped = vars.add_matrix(variable().min(0), 2, 3);
+/// let capacity = constraint!(shipped.row_sum(0) <= 100);
+/// let demand = constraint!(shipped.column_sum(2) >= 30);
+/// let direct = constraint!(shipped[(1, 2)] <= 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableMatrix {
+    n_columns: usize,
+    /// The variables, row after row
+    variables: Vec<Variable>,
+}
+
+impl VariableMatrix {
+    /// The number of rows
+    pub fn n_rows(&self) -> usize {
+        self.variables
+            .len()
+            .checked_div(self.n_columns)
+            .unwrap_or(0)
+    }
+
+    /// The number of columns
+    pub fn n_columns(&self) -> usize {
+        self.n_columns
+    }
+
+    /// The variables of the row
+    pub fn row(&self, row: usize) -> &[Variable] {
+        &self.variables[row * self.n_columns..(row + 1) * self.n_columns]
+    }
+
+    /// The variables of the column
+    pub fn column(&self, column: usize) -> VariableVec {
+        assert!(
+            column < self.n_columns,
+            "column {} is out of bounds",
+            column
+        );
+        self.variables
+            .iter()
+            .skip(column)
+            .step_by(self.n_columns)
+            .copied()
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// The sum of the variables of the row
+    pub fn row_sum(&self, row: usize) -> Expression {
+        self.row(row).iter().sum()
+    }
+
+    /// The sum of the variables of the column
+    pub fn column_sum(&self, column: usize) -> Expression {
+        self.column(column).sum()
+    }
+
+    /// The sum of all the variables
+    pub fn sum(&self) -> Expression {
+        self.variables.iter().sum()
+    }
+
+    /// The values of the variables in the solution, as a vector of rows
+    pub fn values<S: Solution>(&self, solution: &S) -> Vec<Vec<f64>> {
+        (0..self.n_rows())
+            .map(|row| solution.extract_vec(self.row(row)))
+            .collect()
+    }
+}
+
+impl Index<(usize, usize)> for VariableMatrix {
+    type Output = Variable;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Variable {
+        assert!(
+            column < self.n_columns,
+            "column {} is out of bounds",
+            column
+        );
+        &self.variables[row * self.n_columns + column]
+    }
+}
+
+/// Variables indexed by keys, created by [ProblemVariables::add_map].
+/// The variables are iterated over in the order of their keys when the map was created.
+///
+/// ```
+/// use good_lp::{constraint, variable, variables};
+///
+/// let mut vars = variables!();
+/// let quantity = vars.add_map(variable().min(0), vec!["bread", "milk"]);
+/// let enough = constraint!(quantity.sum() >= 2);
+/// let little_milk = constraint!(quantity["milk"] <= 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VariableMap<K> {
+    keys: Vec<K>,
+    variables: HashMap<K, Variable>,
+}
+
+impl<K: Eq + Hash + Clone> VariableMap<K> {
+    /// The variable of the key, if there is one
+    pub fn get<Q>(&self, key: &Q) -> Option<&Variable>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.variables.get(key)
+    }
+
+    /// The number of variables
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether the map has no variable
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The keys, in order
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// The keys and their variables, in order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Variable)> {
+        self.keys.iter().map(move |key| (key, self.variables[key]))
+    }
+
+    /// The sum of all the variables
+    pub fn sum(&self) -> Expression {
+        self.variables.values().sum()
+    }
+
+    /// The values of the variables in the solution, by key
+    pub fn values<S: Solution>(&self, solution: &S) -> HashMap<K, f64> {
+        solution.extract(&self.variables)
+    }
+}
+
+impl<K, Q> Index<&Q> for VariableMap<K>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+{
+    type Output = Variable;
+
+    fn index(&self, key: &Q) -> &Variable {
+        &self.variables[key]
+    }
+}
+
+impl ProblemVariables {
+    /// Adds a `rows` by `columns` matrix of variables with the given definition
+    pub fn add_matrix(
+        &mut self,
+        var_def: VariableDefinition,
+        rows: usize,
+        columns: usize,
+    ) -> VariableMatrix {
+        VariableMatrix {
+            n_columns: columns,
+            variables: self.add_vector(var_def, rows * columns),
+        }
+    }
+
+    /// Adds a variable with the given definition for each key.
+    ///
+    /// Panics if a key appears twice.
+    pub fn add_map<K, I>(&mut self, var_def: VariableDefinition, keys: I) -> VariableMap<K>
+    where
+        K: Eq + Hash + Clone,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        let mut variables = HashMap::with_capacity(keys.len());
+        for key in &keys {
+            let previous = variables.insert(key.clone(), self.add(var_def.clone()));
+            assert!(
+                previous.is_none(),
+                "a key appears twice in the map of variables"
+            );
+        }
+        VariableMap { keys, variables }
+    }
+}
+
+/// A struct whose values are decision variables of a problem.
+///
+/// It is implemented by `#[derive(LpVariables)]`, with the `derive` feature,
+/// for structs whose fields are `f64`, `Vec<f64>` or `HashMap<K, f64>`.
+/// The derive macro defines the struct of the variables, named after the struct
+/// with a `Variables` suffix: it has the same fields, with variables instead of values.
+/// The bounds of the variables of a field are set with the `lp` attribute,
+/// that takes `integer`, `binary`, `min = ...` and `max = ...`, applied in order.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use std::collections::HashMap;
+/// use good_lp::{constraint, default_solver, LpVariables, SolverModel};
+///
+/// #[derive(LpVariables)]
+/// struct Plan {
+///     #[lp(min = 0, max = 10)]
+///     bread: f64,
+///     #[lp(min = 0)]
+///     cheese: Vec<f64>,
+///     #[lp(min = 0)]
+///     wine: HashMap<&'static str, f64>,
+/// }
+///
+/// // Only the lengths of the vectors and the keys of the maps of the template matter
+/// let template = Plan {
+///     bread: 0.,
+///     cheese: vec![0.; 2],
+///     wine: vec![("red", 0.)].into_iter().collect(),
+/// };
+/// let mut vars = good_lp::variables!();
+/// let plan = template.add_variables(&mut vars);
+/// let solution = vars
+///     .maximise(plan.bread + plan.cheese[1] + plan.wine["red"])
+///     .using(default_solver)
+///     .with(constraint!(plan.cheese[1] + plan.wine["red"] <= 5))
+///     .solve()?;
+/// let plan = Plan::from_solution(&plan, &solution);
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(plan.bread, 10.);
+/// assert_eq!(plan.cheese.len(), 2);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub trait LpVariables: Sized {
+    /// The struct of the variables
+    type Variables;
+
+    /// Adds to `vars` a variable for every value of the struct
+    fn add_variables(&self, vars: &mut ProblemVariables) -> Self::Variables;
+
+    /// Reads the values of the variables in the solution back into the struct
+    fn from_solution<S: Solution>(variables: &Self::Variables, solution: &S) -> Self;
+}
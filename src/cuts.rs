@@ -0,0 +1,197 @@
+//! Management of the cuts generated by a
+//! [cutting-plane method](https://en.wikipedia.org/wiki/Cutting-plane_method).
+//!
+//! A cutting-plane loop repeatedly solves a relaxation of a problem,
+//! and adds constraints (cuts) that are violated by the current solution.
+//! Most cuts stop being useful after a few rounds, but keeping them all
+//! makes every re-solve slower.
+//! A [CutPool] keeps track of which cuts are binding in the successive solutions,
+//! and removes the ones that have not been binding for too many rounds.
+//!
+//! ```
+//! use good_lp::cuts::CutPool;
+//! use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+//!
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+//! let relaxation = vars.maximise(x + y);
+//! // The cuts we can generate, in a real application they come from a separation routine
+//! let candidates = [constraint!(x + y <= 15), constraint!(x <= 6)];
+//! let mut pool = CutPool::new().max_age(3);
+//! let solution = loop {
+//!     let solution = pool.add_to(relaxation.clone()).using(default_solver).solve()?;
+//!     pool.update(&solution);
+//!     let violated = candidates.iter().find(|c| CutPool::is_violated(c, &solution, 1e-6));
+//!     match violated {
+//!         Some(cut) => pool.add(cut.clone()),
+//!         None => break solution,
+//!     };
+//! };
+//! assert_eq!(solution.eval(x + y), 15.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::constraint::Constraint;
+use crate::variable::UnsolvedProblem;
+use crate::Solution;
+
+/// A cut stored in a [CutPool]
+#[derive(Clone, PartialEq)]
+struct PooledCut {
+    constraint: Constraint,
+    /// The number of consecutive solutions in which the cut was not binding
+    age: usize,
+    /// The number of solutions in which the cut was binding
+    times_binding: usize,
+}
+
+/// A set of cuts that ages and removes the cuts that are not binding anymore.
+/// See the [module documentation](crate::cuts).
+#[derive(Clone)]
+pub struct CutPool {
+    cuts: Vec<PooledCut>,
+    max_age: usize,
+    tolerance: f64,
+    removed: usize,
+}
+
+impl Default for CutPool {
+    fn default() -> Self {
+        CutPool {
+            cuts: vec![],
+            max_age: 10,
+            tolerance: 1e-6,
+            removed: 0,
+        }
+    }
+}
+
+impl CutPool {
+    /// Creates an empty pool. By default, cuts are removed after
+    /// not being binding in 10 consecutive solutions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of consecutive solutions in which a cut can be non-binding
+    /// before it is removed from the pool
+    pub fn max_age(mut self, max_age: usize) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Set the tolerance used to decide whether a cut is binding or violated. Defaults to `1e-6`.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Adds a cut to the pool. Returns false if the same cut was already in the pool.
+    pub fn add(&mut self, cut: Constraint) -> bool {
+        if self.cuts.iter().any(|c| c.constraint == cut) {
+            return false;
+        }
+        self.cuts.push(PooledCut {
+            constraint: cut,
+            age: 0,
+            times_binding: 0,
+        });
+        true
+    }
+
+    /// The number of cuts in the pool
+    pub fn len(&self) -> usize {
+        self.cuts.len()
+    }
+
+    /// Whether the pool contains no cut
+    pub fn is_empty(&self) -> bool {
+        self.cuts.is_empty()
+    }
+
+    /// The number of cuts that were removed from the pool because they were too old
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+
+    /// The cuts currently in the pool, from the oldest to the most recently added
+    pub fn cuts(&self) -> impl Iterator<Item = &Constraint> {
+        self.cuts.iter().map(|c| &c.constraint)
+    }
+
+    /// Returns the problem, with all the cuts of the pool added to it
+    pub fn add_to(&self, mut problem: UnsolvedProblem) -> UnsolvedProblem {
+        for cut in self.cuts() {
+            problem.add_constraint(cut.clone());
+        }
+        problem
+    }
+
+    /// Whether the constraint is violated by more than `tolerance` in the given solution
+    pub fn is_violated<S: Solution>(constraint: &Constraint, solution: &S, tolerance: f64) -> bool {
+        !solution.is_satisfied(constraint, tolerance)
+    }
+
+    /// Updates the age of the cuts after a new solution is found:
+    /// binding cuts become young again, the other ones get older.
+    /// The cuts that are older than the maximum age are removed,
+    /// and their number is returned.
+    pub fn update<S: Solution>(&mut self, solution: &S) -> usize {
+        for cut in self.cuts.iter_mut() {
+            // All constraints are stored as `expression <= 0` or `expression = 0`
+            let binding = cut.constraint.is_equality
+                || solution.eval(&cut.constraint.expression) >= -self.tolerance;
+            if binding {
+                cut.age = 0;
+                cut.times_binding += 1;
+            } else {
+                cut.age += 1;
+            }
+        }
+        let before = self.cuts.len();
+        let max_age = self.max_age;
+        self.cuts.retain(|c| c.age <= max_age);
+        let removed = before - self.cuts.len();
+        self.removed += removed;
+        removed
+    }
+
+    /// The number of solutions in which each cut of the pool was binding,
+    /// in the same order as [CutPool::cuts]
+    pub fn activity(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cuts.iter().map(|c| c.times_binding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::CutPool;
+    use crate::{constraint, variable, variables};
+
+    #[test]
+    fn inactive_cuts_are_removed() {
+        variables! {vars: x; y;}
+        let mut pool = CutPool::new().max_age(1);
+        assert!(pool.add(constraint!(x <= 1)));
+        assert!(pool.add(constraint!(y <= 1)));
+        assert!(!pool.add(constraint!(x <= 1)));
+        let solution: HashMap<_, f64> = [(x, 1.), (y, 0.)].iter().copied().collect();
+        assert_eq!(pool.update(&solution), 0);
+        assert_eq!(pool.update(&solution), 1);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.removed(), 1);
+        assert_eq!(pool.activity().collect::<Vec<_>>(), vec![2]);
+        let problem = pool.add_to(vars.maximise(x));
+        assert_eq!(problem.constraints.len(), 1);
+    }
+
+    #[test]
+    fn violated_cuts() {
+        let mut vars = variables!();
+        let x = vars.add(variable());
+        let solution: HashMap<_, f64> = [(x, 2.)].iter().copied().collect();
+        assert!(CutPool::is_violated(&constraint!(x <= 1), &solution, 1e-6));
+        assert!(!CutPool::is_violated(&constraint!(x >= 1), &solution, 1e-6));
+        assert!(CutPool::is_violated(&constraint!(x == 1), &solution, 1e-6));
+    }
+}
@@ -0,0 +1,191 @@
+//! [Data envelopment analysis](https://en.wikipedia.org/wiki/Data_envelopment_analysis) (DEA)
+//! measures the relative efficiency of a set of decision-making units (DMUs)
+//! that each consume some inputs to produce some outputs.
+//!
+//! One linear program is built and solved for every DMU. They are independent from one another,
+//! so they are solved in parallel.
+//!
+//! ```
+//! use good_lp::{default_solver, dea::Dea};
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! // Three hospitals, each with a single input (doctors) and a single output (patients)
+//! let doctors = vec![vec![2.], vec![4.], vec![5.]];
+//! let patients = vec![vec![1.], vec![4.], vec![3.]];
+//! let scores = Dea::new(doctors, patients).solve(default_solver)?;
+//! assert_float_eq(scores[0], 0.5);
+//! assert_float_eq(scores[1], 1.);
+//! assert_float_eq(scores[2], 0.6);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::solvers::ObjectiveDirection;
+use crate::{constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel};
+
+/// How the production frontier behaves when the size of a DMU changes
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ReturnsToScale {
+    /// Constant returns to scale, also known as the CCR model (Charnes, Cooper and Rhodes)
+    Constant,
+    /// Variable returns to scale, also known as the BCC model (Banker, Charnes and Cooper)
+    Variable,
+}
+
+/// Whether efficiency is measured as a possible reduction of inputs
+/// or as a possible increase of outputs
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Orientation {
+    /// By how much can the inputs be reduced while producing the same outputs
+    Input,
+    /// By how much can the outputs be increased while consuming the same inputs
+    Output,
+}
+
+/// A data envelopment analysis problem.
+///
+/// `inputs[j][i]` is the quantity of input `i` consumed by DMU `j`,
+/// and `outputs[j][r]` is the quantity of output `r` it produces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dea {
+    inputs: Vec<Vec<f64>>,
+    outputs: Vec<Vec<f64>>,
+    returns_to_scale: ReturnsToScale,
+    orientation: Orientation,
+}
+
+impl Dea {
+    /// Creates an input-oriented problem with constant returns to scale (CCR).
+    ///
+    /// Panics if `inputs` and `outputs` don't describe the same number of DMUs,
+    /// or if the DMUs don't all have the same number of inputs and outputs.
+    pub fn new(inputs: Vec<Vec<f64>>, outputs: Vec<Vec<f64>>) -> Self {
+        assert_eq!(
+            inputs.len(),
+            outputs.len(),
+            "inputs and outputs should contain one row per decision-making unit"
+        );
+        assert!(
+            inputs.windows(2).all(|w| w[0].len() == w[1].len()),
+            "all decision-making units should have the same number of inputs"
+        );
+        assert!(
+            outputs.windows(2).all(|w| w[0].len() == w[1].len()),
+            "all decision-making units should have the same number of outputs"
+        );
+        Dea {
+            inputs,
+            outputs,
+            returns_to_scale: ReturnsToScale::Constant,
+            orientation: Orientation::Input,
+        }
+    }
+
+    /// Set the returns to scale assumption. Use [ReturnsToScale::Variable] for the BCC model.
+    pub fn returns_to_scale(mut self, returns_to_scale: ReturnsToScale) -> Self {
+        self.returns_to_scale = returns_to_scale;
+        self
+    }
+
+    /// Set the orientation of the model
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// The number of decision-making units
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Returns true when there is no decision-making unit
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Computes the efficiency score of a single decision-making unit, between 0 and 1.
+    /// A score of 1 means that the DMU is on the efficient frontier.
+    ///
+    /// The envelopment form of the model is used:
+    /// the score is the optimal contraction of the DMU's inputs (input orientation),
+    /// or the inverse of the optimal expansion of its outputs (output orientation).
+    pub fn efficiency<S: Solver>(
+        &self,
+        dmu: usize,
+        solver: S,
+    ) -> Result<f64, <S::Model as SolverModel>::Error> {
+        let mut vars = ProblemVariables::new();
+        let lambdas = vars.add_vector(variable().min(0), self.len());
+        let factor = vars.add(variable().name("efficiency"));
+        let direction = match self.orientation {
+            Orientation::Input => ObjectiveDirection::Minimisation,
+            Orientation::Output => ObjectiveDirection::Maximisation,
+        };
+        let mut model = vars.optimise(direction, factor).using(solver);
+        for i in 0..self.inputs[dmu].len() {
+            let used: Expression = lambdas
+                .iter()
+                .zip(&self.inputs)
+                .map(|(&lambda, row)| lambda * row[i])
+                .sum();
+            let available = match self.orientation {
+                Orientation::Input => factor * self.inputs[dmu][i],
+                Orientation::Output => Expression::from(self.inputs[dmu][i]),
+            };
+            model.add_constraint(constraint!(used <= available));
+        }
+        for r in 0..self.outputs[dmu].len() {
+            let produced: Expression = lambdas
+                .iter()
+                .zip(&self.outputs)
+                .map(|(&lambda, row)| lambda * row[r])
+                .sum();
+            let required = match self.orientation {
+                Orientation::Input => Expression::from(self.outputs[dmu][r]),
+                Orientation::Output => factor * self.outputs[dmu][r],
+            };
+            model.add_constraint(constraint!(produced >= required));
+        }
+        if self.returns_to_scale == ReturnsToScale::Variable {
+            let total: Expression = lambdas.iter().sum();
+            model.add_constraint(constraint!(total == 1));
+        }
+        let solution = model.solve()?;
+        Ok(match self.orientation {
+            Orientation::Input => solution.value(factor),
+            Orientation::Output => 1. / solution.value(factor),
+        })
+    }
+
+    /// Computes the efficiency scores of all decision-making units, in the order they were given.
+    ///
+    /// The problems are split between as many threads as there are available CPUs.
+    /// If several problems fail, the error of the first one is returned.
+    pub fn solve<S>(&self, solver: S) -> Result<Vec<f64>, <S::Model as SolverModel>::Error>
+    where
+        S: Solver + Clone + Send,
+        <S::Model as SolverModel>::Error: Send,
+    {
+        let n = self.len();
+        let threads = std::thread::available_parallelism()
+            .map(|t| t.get())
+            .unwrap_or(1)
+            .clamp(1, n.max(1));
+        let chunk_size = n.div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..n)
+                .step_by(chunk_size)
+                .map(|start| {
+                    let solver = solver.clone();
+                    scope.spawn(move || {
+                        (start..n.min(start + chunk_size))
+                            .map(|dmu| self.efficiency(dmu, solver.clone()))
+                            .collect::<Result<Vec<f64>, _>>()
+                    })
+                })
+                .collect();
+            let mut scores = Vec::with_capacity(n);
+            for worker in workers {
+                scores.extend(worker.join().expect("a DEA worker thread panicked")?);
+            }
+            Ok(scores)
+        })
+    }
+}
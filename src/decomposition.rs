@@ -0,0 +1,549 @@
+//! [Dantzig-Wolfe decomposition](https://en.wikipedia.org/wiki/Dantzig%E2%80%93Wolfe_decomposition)
+//! of problems with a block structure.
+//!
+//! Many large problems are made of independent blocks (factories, vehicles, time periods, ...),
+//! each with its own variables and constraints, tied together by a few linking constraints.
+//! Once the blocks are declared, [DantzigWolfe::solve] builds the reformulation:
+//!  - a master problem, whose variables choose a combination of solutions of each block,
+//!    and which contains the linking constraints;
+//!  - one subproblem per block, which finds the block solutions (the columns)
+//!    that can improve the master, given its dual values.
+//!
+//! The two are solved alternately until no subproblem finds an improving column.
+//! When the problem has integer variables, the subproblems keep them,
+//! and the master is then solved once more, choosing a single column per block
+//! (price-and-branch): the result is a good integer solution, with a bound on the optimum,
+//! but it is not proved to be optimal.
+//!
+//! The master problem requires a solver that returns dual values.
+//!
+//! ```
+//! # #[cfg(feature = "highs")] {
+//! use good_lp::decomposition::dantzig_wolfe;
+//! use good_lp::{constraint, variables, Solution};
+//!
+//! variables! {vars: 0 <= x[2] <= 3; 0 <= y[2] <= 4;}
+//! let mut problem = vars.maximise(3 * x[0] + 2 * x[1] + 4 * y[0] + y[1]);
+//! let first_factory = problem.add_constraint(constraint!(x[0] + x[1] <= 4));
+//! let second_factory = problem.add_constraint(constraint!(y[0] + y[1] <= 5));
+//! // the first product of both factories uses a shared resource
+//! problem.add_constraint(constraint!(x[0] + y[0] <= 5));
+//! let solution = dantzig_wolfe(problem)
+//!     .block(vec![first_factory])
+//!     .block(vec![second_factory])
+//!     .solve(good_lp::highs)?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(solution.objective(), 26.);
+//! assert_float_eq(solution.value(y[0]), 4.);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::constraint::ConstraintReference;
+use crate::solvers::{DualValues, ObjectiveDirection, SolutionStatus, SolutionWithDual};
+use crate::variable::UnsolvedProblem;
+use crate::{
+    variable, Constraint, Expression, ProblemVariables, ResolutionError, Solution, Solver,
+    SolverModel, Variable, VariableDefinition,
+};
+
+/// Reduced costs above this value do not improve the master problem
+const TOLERANCE: f64 = 1e-9;
+
+/// A problem with a block structure, created by [dantzig_wolfe]
+pub struct DantzigWolfe {
+    problem: UnsolvedProblem,
+    blocks: Vec<Vec<ConstraintReference>>,
+    penalty: f64,
+    max_iterations: usize,
+}
+
+/// Prepares the decomposition of the problem.
+/// Its blocks are then declared with [DantzigWolfe::block].
+pub fn dantzig_wolfe(problem: UnsolvedProblem) -> DantzigWolfe {
+    DantzigWolfe {
+        problem,
+        blocks: vec![],
+        penalty: 1e6,
+        max_iterations: 1000,
+    }
+}
+
+impl DantzigWolfe {
+    /// Declares a block, made of the given constraints and of the variables that appear in them.
+    /// The constraints that are not part of any block are the linking constraints,
+    /// and the variables that are not part of any block stay in the master problem.
+    ///
+    /// Panics when solving if a constraint is part of several blocks,
+    /// or if a variable appears in the constraints of several blocks.
+    pub fn block<I: IntoIterator<Item = ConstraintReference>>(mut self, constraints: I) -> Self {
+        self.blocks.push(constraints.into_iter().collect());
+        self
+    }
+
+    /// The cost of violating a linking constraint by one unit in the master problem.
+    /// The master problem can violate them until the subproblems found enough columns
+    /// to satisfy them, so it has to be higher than the cost of any solution.
+    /// Defaults to 1e6.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = penalty;
+        self
+    }
+
+    /// The maximum number of times the master problem is solved before the generation
+    /// of columns stops. The solution is then not optimal, but its bound is still valid.
+    /// Defaults to 1000.
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Generates columns until the master problem is optimal, and returns its solution.
+    ///
+    /// Returns [ResolutionError::Infeasible] if the linking constraints are still violated
+    /// in the end, and [ResolutionError::Unbounded] if a block is unbounded.
+    pub fn solve<S>(self, solver: S) -> Result<DecomposedSolution, ResolutionError>
+    where
+        S: Solver + Clone,
+        S::Model: SolverModel<Error = ResolutionError>,
+        for<'a> <S::Model as SolverModel>::Solution: SolutionWithDual<'a>,
+    {
+        let structure = Structure::new(&self.problem, &self.blocks);
+        let mut columns: Vec<Vec<Column>> = Vec::with_capacity(structure.blocks.len());
+        for block in 0..structure.blocks.len() {
+            let no_prices = vec![0.; structure.linking.len()];
+            let (column, _) = structure.price(block, &no_prices, solver.clone())?;
+            columns.push(vec![column]);
+        }
+        let mut bound = f64::NEG_INFINITY;
+        let mut iterations = 0;
+        let mut master = loop {
+            iterations += 1;
+            let master = structure.master(&columns, self.penalty, true);
+            let mut solution = master.problem.using(solver.clone()).solve()?;
+            let values = master.layout.values(&solution);
+            let duals = solution.compute_dual();
+            let prices: Vec<f64> = (0..structure.linking.len())
+                .map(|index| duals.dual(ConstraintReference { index }))
+                .collect();
+            let convexity: Vec<f64> = (0..structure.blocks.len())
+                .map(|block| {
+                    let index = structure.linking.len() + block;
+                    duals.dual(ConstraintReference { index })
+                })
+                .collect();
+            let mut lagrangian = values.objective;
+            let mut added = false;
+            for block in 0..structure.blocks.len() {
+                let (column, value) = structure.price(block, &prices, solver.clone())?;
+                let reduced_cost = value - convexity[block];
+                lagrangian += reduced_cost.min(0.);
+                if reduced_cost < -TOLERANCE * (1. + value.abs())
+                    && !columns[block].contains(&column)
+                {
+                    columns[block].push(column);
+                    added = true;
+                }
+            }
+            bound = bound.max(lagrangian);
+            if !added || iterations >= self.max_iterations {
+                break values;
+            }
+        };
+        let is_integer = self
+            .problem
+            .variables
+            .iter_variables_with_def()
+            .any(|(_, definition)| definition.is_integer);
+        if is_integer {
+            let restricted = structure.master(&columns, self.penalty, false);
+            let solution = restricted.problem.using(solver).solve()?;
+            master = restricted.layout.values(&solution);
+        }
+        if master.violation > TOLERANCE * (1. + master.objective.abs()) {
+            return Err(ResolutionError::Infeasible);
+        }
+        let values = structure.original_values(&columns, &master);
+        let linear = &self.problem.objective.linear.coefficients;
+        let objective = self.problem.objective.constant
+            + linear
+                .iter()
+                .map(|(variable, coefficient)| coefficient * values[variable.index()])
+                .sum::<f64>();
+        let sign = structure.sign;
+        let bound = sign * bound + self.problem.objective.constant;
+        let gap = (objective - bound).abs();
+        let status = if gap <= 1e-6 * (1. + objective.abs()) && iterations < self.max_iterations {
+            SolutionStatus::Optimal
+        } else {
+            SolutionStatus::Interrupted
+        };
+        Ok(DecomposedSolution {
+            values,
+            objective,
+            bound,
+            status,
+            iterations,
+            columns: columns.iter().map(Vec::len).collect(),
+        })
+    }
+}
+
+/// A solution of a block, and its contribution to the master problem
+#[derive(Debug, Clone, PartialEq)]
+struct Column {
+    /// The values of the variables of the block
+    values: Vec<f64>,
+    /// The contribution of the block to the objective to minimise
+    cost: f64,
+    /// The contribution of the block to each linking constraint
+    linking: Vec<f64>,
+}
+
+struct Block {
+    constraints: Vec<usize>,
+    variables: Vec<Variable>,
+}
+
+/// The blocks, the linking constraints and the linking variables of a problem
+struct Structure<'a> {
+    problem: &'a UnsolvedProblem,
+    /// 1 for minimisation, -1 for maximisation: the decomposition always minimises
+    sign: f64,
+    blocks: Vec<Block>,
+    linking: Vec<usize>,
+    /// The variables that are not part of any block
+    master_variables: Vec<Variable>,
+    definitions: Vec<VariableDefinition>,
+}
+
+impl<'a> Structure<'a> {
+    fn new(problem: &'a UnsolvedProblem, declared: &[Vec<ConstraintReference>]) -> Self {
+        let mut constraint_block = vec![None; problem.constraints.len()];
+        let mut variable_block = vec![None; problem.variables.len()];
+        let mut blocks = Vec::with_capacity(declared.len());
+        for (block, references) in declared.iter().enumerate() {
+            let mut constraints = Vec::with_capacity(references.len());
+            let mut variables = vec![];
+            for reference in references {
+                let owner = &mut constraint_block[reference.index];
+                assert!(
+                    owner.is_none(),
+                    "constraint {} is part of several blocks",
+                    reference.index
+                );
+                *owner = Some(block);
+                constraints.push(reference.index);
+                let expression = &problem.constraints[reference.index].expression;
+                for &variable in expression.linear.coefficients.keys() {
+                    match variable_block[variable.index()] {
+                        None => {
+                            variable_block[variable.index()] = Some(block);
+                            variables.push(variable);
+                        }
+                        Some(other) => assert_eq!(
+                            other, block,
+                            "a variable appears in the constraints of blocks {} and {}",
+                            other, block
+                        ),
+                    }
+                }
+            }
+            variables.sort_by_key(|v| v.index());
+            blocks.push(Block {
+                constraints,
+                variables,
+            });
+        }
+        let linking = (0..problem.constraints.len())
+            .filter(|&index| constraint_block[index].is_none())
+            .collect();
+        let master_variables = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(variable, _)| variable)
+            .filter(|variable| variable_block[variable.index()].is_none())
+            .collect();
+        let sign = match problem.direction {
+            ObjectiveDirection::Minimisation => 1.,
+            ObjectiveDirection::Maximisation => -1.,
+        };
+        let definitions = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(_, definition)| definition.clone())
+            .collect();
+        Structure {
+            problem,
+            sign,
+            blocks,
+            linking,
+            master_variables,
+            definitions,
+        }
+    }
+
+    fn cost(&self, variable: Variable) -> f64 {
+        let coefficients = &self.problem.objective.linear.coefficients;
+        self.sign * coefficients.get(&variable).copied().unwrap_or(0.)
+    }
+
+    fn coefficient(&self, linking: usize, variable: Variable) -> f64 {
+        let constraint = &self.problem.constraints[self.linking[linking]];
+        let coefficients = &constraint.expression.linear.coefficients;
+        coefficients.get(&variable).copied().unwrap_or(0.)
+    }
+
+    fn definition(&self, variable: Variable) -> VariableDefinition {
+        self.definitions[variable.index()].clone()
+    }
+
+    /// Solves the subproblem of the block with the given prices of the linking constraints,
+    /// and returns its solution with the objective of the subproblem
+    fn price<S>(
+        &self,
+        block: usize,
+        prices: &[f64],
+        solver: S,
+    ) -> Result<(Column, f64), ResolutionError>
+    where
+        S: Solver,
+        S::Model: SolverModel<Error = ResolutionError>,
+    {
+        let Block {
+            constraints,
+            variables,
+        } = &self.blocks[block];
+        let mut local_variables = ProblemVariables::new();
+        let mut local = vec![None; self.problem.variables.len()];
+        let mut objective = Expression::with_capacity(variables.len());
+        for &variable in variables {
+            let copy = local_variables.add(self.definition(variable));
+            local[variable.index()] = Some(copy);
+            let reduced: f64 = (0..self.linking.len())
+                .map(|linking| prices[linking] * self.coefficient(linking, variable))
+                .sum();
+            objective.add_mul(self.cost(variable) - reduced, copy);
+        }
+        let mut subproblem = local_variables.minimise(objective.clone());
+        for &index in constraints {
+            let constraint = &self.problem.constraints[index];
+            let mut expression = Expression::from(constraint.expression.constant);
+            for (variable, &coefficient) in &constraint.expression.linear.coefficients {
+                let copy = local[variable.index()].expect("the variable is in the block");
+                expression.add_mul(coefficient, copy);
+            }
+            subproblem.add_constraint(Constraint::new(expression, constraint.is_equality));
+        }
+        let solution = subproblem.using(solver).solve()?;
+        let values: Vec<f64> = variables
+            .iter()
+            .map(|variable| solution.value(local[variable.index()].unwrap()))
+            .collect();
+        let cost = variables
+            .iter()
+            .zip(&values)
+            .map(|(&variable, value)| self.cost(variable) * value)
+            .sum();
+        let linking = (0..self.linking.len())
+            .map(|linking| {
+                variables
+                    .iter()
+                    .zip(&values)
+                    .map(|(&variable, value)| self.coefficient(linking, variable) * value)
+                    .sum()
+            })
+            .collect();
+        let value = solution.eval(&objective);
+        let column = Column {
+            values,
+            cost,
+            linking,
+        };
+        Ok((column, value))
+    }
+
+    /// The master problem with the given columns. The linking constraints come first,
+    /// followed by the convexity constraint of each block.
+    /// The relaxation is continuous, the restricted master chooses a single column per block.
+    fn master(&self, columns: &[Vec<Column>], penalty: f64, relaxation: bool) -> Master {
+        let mut vars = ProblemVariables::new();
+        let master_variables: Vec<Variable> = self
+            .master_variables
+            .iter()
+            .map(|&variable| {
+                let mut definition = self.definition(variable);
+                if relaxation {
+                    definition.is_integer = false;
+                }
+                vars.add(definition)
+            })
+            .collect();
+        let weight = if relaxation {
+            variable().min(0)
+        } else {
+            variable().binary()
+        };
+        let weights: Vec<Vec<Variable>> = columns
+            .iter()
+            .map(|block| vars.add_vector(weight.clone(), block.len()))
+            .collect();
+        let mut objective = Expression::from(0.);
+        let mut linking_rows: Vec<Expression> = self
+            .linking
+            .iter()
+            .map(|&index| Expression::from(self.problem.constraints[index].expression.constant))
+            .collect();
+        for (&variable, &copy) in self.master_variables.iter().zip(&master_variables) {
+            objective.add_mul(self.cost(variable), copy);
+            for (linking, row) in linking_rows.iter_mut().enumerate() {
+                row.add_mul(self.coefficient(linking, variable), copy);
+            }
+        }
+        for (block, block_weights) in columns.iter().zip(&weights) {
+            for (column, &weight) in block.iter().zip(block_weights) {
+                objective.add_mul(column.cost, weight);
+                for (row, &coefficient) in linking_rows.iter_mut().zip(&column.linking) {
+                    row.add_mul(coefficient, weight);
+                }
+            }
+        }
+        // Artificial variables let the master violate the linking constraints, at a cost
+        let mut artificial = vec![];
+        for (row, &index) in linking_rows.iter_mut().zip(&self.linking) {
+            let above = vars.add(variable().min(0));
+            row.add_mul(-1, above);
+            artificial.push(above);
+            if self.problem.constraints[index].is_equality {
+                let below = vars.add(variable().min(0));
+                row.add_mul(1, below);
+                artificial.push(below);
+            }
+        }
+        for &a in &artificial {
+            objective.add_mul(penalty, a);
+        }
+        let mut problem = vars.minimise(objective.clone());
+        for (row, &index) in linking_rows.into_iter().zip(&self.linking) {
+            let is_equality = self.problem.constraints[index].is_equality;
+            problem.add_constraint(Constraint::new(row, is_equality));
+        }
+        for block_weights in &weights {
+            let total: Expression = block_weights.iter().copied().sum();
+            problem.add_constraint(total.eq(1));
+        }
+        Master {
+            problem,
+            layout: MasterLayout {
+                master_variables,
+                weights,
+                artificial,
+                objective,
+            },
+        }
+    }
+
+    /// The values of the variables of the original problem
+    fn original_values(&self, columns: &[Vec<Column>], master: &MasterValues) -> Vec<f64> {
+        let mut values = vec![0.; self.problem.variables.len()];
+        for (&variable, &value) in self.master_variables.iter().zip(&master.master_variables) {
+            values[variable.index()] = value;
+        }
+        for ((block, block_columns), weights) in
+            self.blocks.iter().zip(columns).zip(&master.weights)
+        {
+            for (column, &weight) in block_columns.iter().zip(weights) {
+                for (&variable, &value) in block.variables.iter().zip(&column.values) {
+                    values[variable.index()] += weight * value;
+                }
+            }
+        }
+        values
+    }
+}
+
+struct Master {
+    problem: UnsolvedProblem,
+    layout: MasterLayout,
+}
+
+struct MasterLayout {
+    master_variables: Vec<Variable>,
+    weights: Vec<Vec<Variable>>,
+    artificial: Vec<Variable>,
+    objective: Expression,
+}
+
+impl MasterLayout {
+    fn values<S: Solution>(&self, solution: &S) -> MasterValues {
+        let violation: f64 = self.artificial.iter().map(|&a| solution.value(a)).sum();
+        let master_variables: Vec<f64> = self
+            .master_variables
+            .iter()
+            .map(|&v| solution.value(v))
+            .collect();
+        let weights = self
+            .weights
+            .iter()
+            .map(|block| block.iter().map(|&w| solution.value(w)).collect())
+            .collect();
+        MasterValues {
+            master_variables,
+            weights,
+            violation,
+            objective: solution.eval(&self.objective),
+        }
+    }
+}
+
+/// The values of the variables of a master problem
+struct MasterValues {
+    master_variables: Vec<f64>,
+    weights: Vec<Vec<f64>>,
+    /// The total amount by which the linking constraints are violated
+    violation: f64,
+    /// The objective of the master problem, including the penalty of the violations
+    objective: f64,
+}
+
+/// The solution found by [DantzigWolfe::solve]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecomposedSolution {
+    values: Vec<f64>,
+    objective: f64,
+    bound: f64,
+    status: SolutionStatus,
+    iterations: usize,
+    columns: Vec<usize>,
+}
+
+impl DecomposedSolution {
+    /// The value of the objective of the original problem
+    pub fn objective(&self) -> f64 {
+        self.objective
+    }
+
+    /// The number of times the master problem was solved
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// The number of columns that were generated for each block
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+}
+
+impl Solution for DecomposedSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+
+    /// The best Lagrangian bound found during the generation of columns
+    fn best_bound(&self) -> Option<f64> {
+        Some(self.bound)
+    }
+}
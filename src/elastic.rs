@@ -0,0 +1,185 @@
+//! Solutions that violate as few requirements as possible, when a problem is infeasible.
+//!
+//! An infeasible problem gives nothing to show to the user of an application,
+//! except an error. [solve_or_explain] solves the problem, and when it is infeasible,
+//! solves its elastic relaxation instead: every constraint is allowed to be violated,
+//! and the total amount of violation is minimised. Among the solutions with the
+//! smallest violation, the one with the best objective is returned,
+//! with the list of the constraints that it violates, and by how much.
+//!
+//! Variable bounds are not relaxed: a problem whose bounds alone are contradictory
+//! is still reported as infeasible.
+//!
+//! ```
+//! use good_lp::elastic::solve_or_explain;
+//! use good_lp::{constraint, default_solver, variables, Solution};
+//!
+//! variables! {vars: 0 <= hours[2] <= 8;}
+//! let mut problem = vars.minimise(hours[0] + 2 * hours[1]);
+//! let demand = problem.add_constraint(constraint!(hours[0] + hours[1] >= 20));
+//! problem.add_constraint(constraint!(hours[1] <= 6));
+//! let explained = solve_or_explain(problem, default_solver)?;
+//! assert!(!explained.is_feasible());
+//! let violation = &explained.violations()[0];
+//! assert_eq!(violation.constraint, demand);
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(violation.amount, 6.);
+//! assert_float_eq(explained.value(hours[0]), 8.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::constraint::ConstraintReference;
+use crate::solvers::{ResolutionError, SolutionStatus, SolveStats};
+use crate::variable::UnsolvedProblem;
+use crate::{variable, Expression, Solution, Solver, SolverModel, Variable};
+
+/// Violations smaller than this are considered to be rounding errors of the solver
+const TOLERANCE: f64 = 1e-9;
+
+/// A constraint that is violated by the solution of the elastic relaxation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The constraint, as returned by [UnsolvedProblem::add_constraint]
+    pub constraint: ConstraintReference,
+    /// The name of the constraint, if it has one
+    pub name: Option<String>,
+    /// By how much the constraint is violated
+    pub amount: f64,
+}
+
+/// The solution returned by [solve_or_explain],
+/// with the constraints it violates if the problem was infeasible
+pub struct ExplainedSolution<T> {
+    solution: T,
+    violations: Vec<Violation>,
+}
+
+impl<T: Solution> ExplainedSolution<T> {
+    /// Whether the solution satisfies all the constraints of the problem
+    pub fn is_feasible(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// The constraints that are violated, in the order in which they were added to the problem
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// The sum of the amounts by which the constraints are violated
+    pub fn total_violation(&self) -> f64 {
+        self.violations
+            .iter()
+            .map(|violation| violation.amount)
+            .sum()
+    }
+
+    /// The solution returned by the solver
+    pub fn inner(&self) -> &T {
+        &self.solution
+    }
+}
+
+impl<T: Solution> Solution for ExplainedSolution<T> {
+    fn value(&self, variable: Variable) -> f64 {
+        self.solution.value(variable)
+    }
+
+    fn status(&self) -> SolutionStatus {
+        self.solution.status()
+    }
+
+    fn best_bound(&self) -> Option<f64> {
+        self.solution.best_bound()
+    }
+
+    fn mip_gap(&self) -> Option<f64> {
+        self.solution.mip_gap()
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.solution.stats()
+    }
+}
+
+/// Solves the problem, or, if it is infeasible, the solution that violates its constraints
+/// as little as possible. See the [module documentation](self).
+///
+/// Other errors, such as an unbounded problem, are returned as they are.
+pub fn solve_or_explain<S>(
+    problem: UnsolvedProblem,
+    solver: S,
+) -> Result<ExplainedSolution<<S::Model as SolverModel>::Solution>, ResolutionError>
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    match problem.clone().using(solver.clone()).solve() {
+        Ok(solution) => {
+            return Ok(ExplainedSolution {
+                solution,
+                violations: vec![],
+            })
+        }
+        Err(ResolutionError::Infeasible) => {}
+        Err(error) => return Err(error),
+    }
+    let UnsolvedProblem {
+        objective,
+        direction,
+        mut variables,
+        constraints,
+        ..
+    } = problem;
+    // Each constraint gets a variable measuring by how much it is violated
+    let mut violations = Vec::with_capacity(constraints.len());
+    let mut elastic_constraints = Vec::with_capacity(constraints.len());
+    for mut constraint in constraints {
+        let above = variables.add(variable().min(0));
+        constraint.expression.add_mul(-1, above);
+        let mut violation = Expression::from(above);
+        if constraint.is_equality {
+            let below = variables.add(variable().min(0));
+            constraint.expression.add_mul(1, below);
+            violation.add_mul(1, below);
+        }
+        violations.push(violation);
+        elastic_constraints.push(constraint);
+    }
+    let total: Expression = violations.iter().sum();
+    let with_constraints = |mut problem: UnsolvedProblem| {
+        for constraint in &elastic_constraints {
+            problem.add_constraint(constraint.clone());
+        }
+        problem
+    };
+    let least_violation = with_constraints(variables.clone().minimise(total.clone()))
+        .using(solver.clone())
+        .solve()?
+        .eval(&total);
+    // Among the least violating solutions, the best one for the original objective
+    let limit = least_violation + TOLERANCE * (1. + least_violation.abs());
+    let solution = with_constraints(variables.optimise(direction, objective))
+        .with(total.leq(limit))
+        .using(solver)
+        .solve()?;
+    let violations = violations
+        .iter()
+        .zip(&elastic_constraints)
+        .enumerate()
+        .filter_map(|(index, (violation, constraint))| {
+            let amount = solution.eval(violation);
+            if amount > TOLERANCE {
+                Some(Violation {
+                    constraint: ConstraintReference { index },
+                    name: constraint.name.clone(),
+                    amount,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    Ok(ExplainedSolution {
+        solution,
+        violations,
+    })
+}
@@ -0,0 +1,132 @@
+//! Exact verification of solutions, with rational arithmetic.
+//!
+//! Solvers work with floating-point numbers, and accept small violations of the constraints.
+//! When a solution has to be trusted without any floating-point doubt,
+//! for instance in finance or in formal verification,
+//! [verify_exact] checks it with exact rational arithmetic:
+//! every coefficient and every value is converted to the rational number
+//! it represents exactly, and the constraints are evaluated without rounding.
+//!
+//! This module is activated by the `exact` cargo feature.
+//!
+//! ```
+//! use good_lp::exact::verify_exact;
+//! use good_lp::{constraint, variables};
+//! use std::collections::HashMap;
+//!
+//! variables! {vars: 0 <= x; 0 <= y;}
+//! let problem = vars.maximise(x + y).with(constraint!(x + y <= 0.3));
+//! let almost: HashMap<_, _> = vec![(x, 0.1), (y, 0.2)].into_iter().collect();
+//! // 0.1 + 0.2 is slightly above 0.3 in floating-point arithmetic
+//! assert!(!verify_exact(&problem, &almost).is_feasible());
+//! let exact: HashMap<_, _> = vec![(x, 0.1), (y, 0.3 - 0.1)].into_iter().collect();
+//! assert!(verify_exact(&problem, &exact).is_feasible());
+//! ```
+use num_rational::BigRational;
+
+use crate::variable::UnsolvedProblem;
+use crate::{Expression, Solution, Variable};
+
+/// A constraint that is not satisfied exactly
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExactViolation {
+    /// The position of the constraint in the problem
+    pub constraint: usize,
+    /// The name of the constraint, if it has one
+    pub name: Option<String>,
+    /// How much the constraint is violated: the amount by which the left hand side
+    /// is above the right hand side, in absolute value for equalities
+    pub excess: BigRational,
+}
+
+/// The result of the exact verification of a solution, returned by [verify_exact]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExactCheck {
+    /// The value of the objective, computed exactly
+    pub objective: BigRational,
+    /// The constraints that are violated
+    pub violations: Vec<ExactViolation>,
+    /// The variables whose value is outside of their bounds
+    pub out_of_bounds: Vec<Variable>,
+    /// The integer variables whose value is not an integer
+    pub not_integer: Vec<Variable>,
+}
+
+impl ExactCheck {
+    /// Whether the solution satisfies all the constraints, bounds and integrality requirements
+    pub fn is_feasible(&self) -> bool {
+        self.violations.is_empty() && self.out_of_bounds.is_empty() && self.not_integer.is_empty()
+    }
+}
+
+/// Converts a finite number to the rational it represents exactly.
+/// Panics if the number is NaN or infinite.
+fn rational(value: f64) -> BigRational {
+    match BigRational::from_float(value) {
+        Some(rational) => rational,
+        None => panic!("{} cannot be represented as a rational number", value),
+    }
+}
+
+fn exact_value<S: Solution>(expression: &Expression, solution: &S) -> BigRational {
+    let mut value = rational(expression.constant);
+    for (&variable, &coefficient) in &expression.linear.coefficients {
+        value += rational(coefficient) * rational(solution.value(variable));
+    }
+    value
+}
+
+/// Checks that the solution satisfies all the constraints of the problem,
+/// the bounds of its variables, and the integrality of its integer variables,
+/// with exact rational arithmetic, without any tolerance.
+///
+/// Panics if a coefficient, or the value of a variable, is NaN or infinite.
+pub fn verify_exact<S: Solution>(problem: &UnsolvedProblem, solution: &S) -> ExactCheck {
+    let zero = rational(0.);
+    let violations = problem
+        .constraints
+        .iter()
+        .enumerate()
+        .filter_map(|(index, constraint)| {
+            let value = exact_value(&constraint.expression, solution);
+            let excess = if constraint.is_equality {
+                if value < zero {
+                    -value
+                } else {
+                    value
+                }
+            } else {
+                value
+            };
+            if excess > zero {
+                Some(ExactViolation {
+                    constraint: index,
+                    name: constraint.name.clone(),
+                    excess,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    let mut out_of_bounds = vec![];
+    let mut not_integer = vec![];
+    for (variable, definition) in problem.variables.iter_variables_with_def() {
+        let value = solution.value(variable);
+        let exact = rational(value);
+        let below = definition.min.is_finite() && exact < rational(definition.min);
+        let above = definition.max.is_finite() && exact > rational(definition.max);
+        if below || above {
+            out_of_bounds.push(variable);
+        }
+        if definition.is_integer && !exact.is_integer() {
+            not_integer.push(variable);
+        }
+    }
+    ExactCheck {
+        objective: exact_value(&problem.objective, solution),
+        violations,
+        out_of_bounds,
+        not_integer,
+    }
+}
@@ -0,0 +1,258 @@
+//! Multi-period capacity expansion: when to build new production capacity.
+//!
+//! Over a planning horizon divided in periods, a growing demand has to be served
+//! by several technologies (plants, machines, warehouses, ...).
+//! Each technology starts with an existing capacity, and can be expanded
+//! by building whole units, which stay available until the end of the horizon.
+//! The goal is to find the build schedule and the production levels
+//! that minimise the total discounted cost of building and producing.
+//!
+//! ```
+//! # // minilp and highs do not support integer variables
+//! # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+//! use good_lp::default_solver;
+//! use good_lp::expansion::{capacity_expansion, Technology};
+//!
+//! let technologies = [
+//!     // Cheap to run, but expensive to build
+//!     Technology { initial_capacity: 50., unit_capacity: 40., build_cost: 1000., production_cost: 1. },
+//!     // Expensive to run, but cheap to build
+//!     Technology { initial_capacity: 0., unit_capacity: 10., build_cost: 50., production_cost: 10. },
+//! ];
+//! let demand = [40., 60., 100.];
+//! let plan = capacity_expansion(&technologies, &demand, 0.05).solve(default_solver)?;
+//! // The small units cover the peak, the demand never justifies a large unit
+//! assert_eq!(plan.builds(0, 2), 0);
+//! assert_eq!(plan.built_before(1, 2), 5);
+//! assert_eq!(plan.capacity(1, 2), 50.);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel, Variable,
+    VariableDefinition,
+};
+
+/// A way to produce, whose capacity can be expanded
+#[derive(Debug, Clone, PartialEq)]
+pub struct Technology {
+    /// The capacity available at the start of the horizon
+    pub initial_capacity: f64,
+    /// The capacity added by each unit that is built
+    pub unit_capacity: f64,
+    /// The cost of building one unit
+    pub build_cost: f64,
+    /// The cost of producing one unit of output
+    pub production_cost: f64,
+}
+
+/// A unit construction decision of an [ExpansionPlan]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Build {
+    /// The index of the technology that is expanded
+    pub technology: usize,
+    /// The period at the start of which the units are available
+    pub period: usize,
+    /// The number of units built
+    pub units: usize,
+}
+
+/// A capacity expansion problem, created by [capacity_expansion]
+pub struct CapacityExpansion {
+    problem: UnsolvedProblem,
+    technologies: Vec<Technology>,
+    discount_factors: Vec<f64>,
+    /// The number of units built, by technology and period
+    builds: Vec<Vec<Variable>>,
+    /// The production, by technology and period
+    production: Vec<Vec<Variable>>,
+}
+
+/// The factor applied to the costs of each period: `1 / (1 + rate)^period`
+fn discount_factors(periods: usize, rate: f64) -> Vec<f64> {
+    (0..periods)
+        .map(|period| (1. + rate).powi(period as i32).recip())
+        .collect()
+}
+
+/// The capacity of a technology in each period, given the units built in each period
+fn cumulative_capacity(technology: &Technology, builds: &[Variable]) -> Vec<Expression> {
+    let mut capacity = Expression::from(technology.initial_capacity);
+    builds
+        .iter()
+        .map(|&built| {
+            capacity.add_mul(technology.unit_capacity, built);
+            capacity.clone()
+        })
+        .collect()
+}
+
+/// Builds the capacity expansion problem for the given demand in each period.
+/// Costs incurred in period `t` are discounted by `1 / (1 + discount_rate)^t`.
+///
+/// Units built in a period are available from that period on.
+pub fn capacity_expansion(
+    technologies: &[Technology],
+    demand: &[f64],
+    discount_rate: f64,
+) -> CapacityExpansion {
+    let mut vars = ProblemVariables::new();
+    let mut named = |prefix: &str, tech: usize, def: VariableDefinition| -> Vec<Variable> {
+        (0..demand.len())
+            .map(|t| vars.add(def.clone().name(format!("{}_{}_{}", prefix, tech, t))))
+            .collect()
+    };
+    let builds: Vec<Vec<Variable>> = (0..technologies.len())
+        .map(|tech| named("build", tech, variable().nonneg_int()))
+        .collect();
+    let production: Vec<Vec<Variable>> = (0..technologies.len())
+        .map(|tech| named("produce", tech, variable().min(0)))
+        .collect();
+    let discount_factors = discount_factors(demand.len(), discount_rate);
+    let mut cost = Expression::with_capacity(2 * technologies.len() * demand.len());
+    for (tech, technology) in technologies.iter().enumerate() {
+        for (t, &factor) in discount_factors.iter().enumerate() {
+            cost.add_mul(factor * technology.build_cost, builds[tech][t]);
+            cost.add_mul(factor * technology.production_cost, production[tech][t]);
+        }
+    }
+    let mut problem = vars.minimise(cost);
+    for (tech, technology) in technologies.iter().enumerate() {
+        let capacity = cumulative_capacity(technology, &builds[tech]);
+        for (t, available) in capacity.into_iter().enumerate() {
+            problem.add_constraint(
+                constraint!(production[tech][t] <= available)
+                    .set_name(format!("capacity_{}_{}", tech, t)),
+            );
+        }
+    }
+    for (t, &needed) in demand.iter().enumerate() {
+        let produced: Expression = production.iter().map(|p| p[t]).sum();
+        problem.add_constraint(constraint!(produced >= needed).set_name(format!("demand_{}", t)));
+    }
+    CapacityExpansion {
+        problem,
+        technologies: technologies.to_vec(),
+        discount_factors,
+        builds,
+        production,
+    }
+}
+
+impl CapacityExpansion {
+    /// The variable representing the number of units of a technology built in a period
+    pub fn build_variable(&self, technology: usize, period: usize) -> Variable {
+        self.builds[technology][period]
+    }
+
+    /// The variable representing the production of a technology in a period
+    pub fn production_variable(&self, technology: usize, period: usize) -> Variable {
+        self.production[technology][period]
+    }
+
+    /// The expression of the capacity of a technology in a period
+    pub fn capacity_expression(&self, technology: usize, period: usize) -> Expression {
+        cumulative_capacity(&self.technologies[technology], &self.builds[technology])
+            .swap_remove(period)
+    }
+
+    /// The underlying problem, to which additional constraints can be added,
+    /// such as a budget or a limit on the number of units built per period
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Finds the cheapest expansion plan
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<ExpansionPlan, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        let builds = self
+            .builds
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&v| solution.value(v).round().max(0.) as usize)
+                    .collect()
+            })
+            .collect();
+        let production = self
+            .production
+            .iter()
+            .map(|row| row.iter().map(|&v| solution.value(v)).collect())
+            .collect();
+        Ok(ExpansionPlan {
+            technologies: self.technologies,
+            discount_factors: self.discount_factors,
+            builds,
+            production,
+        })
+    }
+}
+
+/// The optimal build schedule and production of a [CapacityExpansion] problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpansionPlan {
+    technologies: Vec<Technology>,
+    discount_factors: Vec<f64>,
+    builds: Vec<Vec<usize>>,
+    production: Vec<Vec<f64>>,
+}
+
+impl ExpansionPlan {
+    /// The number of units of a technology built in a period
+    pub fn builds(&self, technology: usize, period: usize) -> usize {
+        self.builds[technology][period]
+    }
+
+    /// The number of units of a technology built up to and including a period
+    pub fn built_before(&self, technology: usize, period: usize) -> usize {
+        self.builds[technology][..=period].iter().sum()
+    }
+
+    /// The capacity of a technology in a period, including the units built in that period
+    pub fn capacity(&self, technology: usize, period: usize) -> f64 {
+        let tech = &self.technologies[technology];
+        tech.initial_capacity + tech.unit_capacity * self.built_before(technology, period) as f64
+    }
+
+    /// The production of a technology in a period
+    pub fn production(&self, technology: usize, period: usize) -> f64 {
+        self.production[technology][period]
+    }
+
+    /// All the construction decisions, sorted by period then by technology
+    pub fn schedule(&self) -> Vec<Build> {
+        let mut schedule: Vec<Build> = self
+            .builds
+            .iter()
+            .enumerate()
+            .flat_map(|(technology, row)| {
+                row.iter().enumerate().filter(|(_, &units)| units > 0).map(
+                    move |(period, &units)| Build {
+                        technology,
+                        period,
+                        units,
+                    },
+                )
+            })
+            .collect();
+        schedule.sort_by_key(|build| (build.period, build.technology));
+        schedule
+    }
+
+    /// The total cost of the plan, with the costs of each period discounted
+    pub fn discounted_cost(&self) -> f64 {
+        let mut cost = 0.;
+        for (tech, technology) in self.technologies.iter().enumerate() {
+            for (t, factor) in self.discount_factors.iter().enumerate() {
+                cost += factor
+                    * (technology.build_cost * self.builds[tech][t] as f64
+                        + technology.production_cost * self.production[tech][t]);
+            }
+        }
+        cost
+    }
+}
@@ -9,6 +9,8 @@ use crate::variable::{FormatWithVars, Variable};
 use crate::{Constraint, Solution};
 
 /// An linear expression without a constant component
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct LinearExpression {
     pub(crate) coefficients: HashMap<Variable, f64>,
 }
@@ -75,6 +77,7 @@ impl FormatWithVars for LinearExpression {
 }
 
 /// Represents an affine expression, such as `2x + 3` or `x + y + z`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Expression {
     pub(crate) linear: LinearExpression,
     pub(crate) constant: f64,
@@ -350,6 +353,27 @@ impl<E: IntoAffineExpression> std::iter::Sum<E> for Expression {
     }
 }
 
+/// The sum of the variables, expressions or numbers, as an [Expression].
+///
+/// This is what `sum(...)` means inside [constraint!](crate::constraint!),
+/// which also accepts a comprehension, `sum(expression for pattern in iterator)`.
+///
+/// ```
+/// use good_lp::{constraint, sum, variable, variables};
+///
+/// let mut vars = variables!();
+/// let x = vars.add_vector(variable().min(0), 3);
+/// let total = sum(&x);
+/// let weighted = constraint!(sum((i + 1) as f64 * x[i] for i in 0..3) <= total.clone() + 5);
+/// ```
+pub fn sum<I>(items: I) -> Expression
+where
+    I: IntoIterator,
+    I::Item: IntoAffineExpression,
+{
+    items.into_iter().sum()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -0,0 +1,164 @@
+//! Verification that a solution satisfies all the requirements of a problem.
+//!
+//! Solvers work with floating point numbers, and accept solutions that violate
+//! the constraints by less than their own tolerances, which differ from one solver to the next.
+//! [check_solution] checks a solution against the bounds, integrality requirements and
+//! constraints of the problem, with a tolerance of your choice,
+//! and lists every requirement that is violated by more than that tolerance.
+//!
+//! ```
+//! use good_lp::feasibility::{check_solution, Requirement};
+//! use good_lp::{constraint, variable, variables};
+//! use std::collections::HashMap;
+//!
+//! let mut vars = variables!();
+//! let x = vars.add(variable().integer().clamp(0, 10));
+//! let y = vars.add(variable().min(0).name("y"));
+//! let mut problem = vars.maximise(x + y);
+//! let budget = problem.add_constraint(constraint!(x + y <= 8));
+//! // Values computed by hand, or by another solver
+//! let solution: HashMap<_, _> = vec![(x, 4.5), (y, 4.)].into_iter().collect();
+//! let report = check_solution(&problem, &solution, 1e-6);
+//! assert!(!report.is_feasible());
+//! assert_eq!(report.violations[0].requirement, Requirement::Integrality(x));
+//! assert_eq!(report.violations[1].requirement, Requirement::Constraint(budget));
+//! assert_eq!(report.max_violation(), 0.5);
+//! ```
+use std::fmt::{Display, Formatter};
+
+use crate::constraint::ConstraintReference;
+use crate::variable::UnsolvedProblem;
+use crate::{Solution, Variable};
+
+/// A requirement of a problem
+#[derive(Debug, Clone, PartialEq)]
+pub enum Requirement {
+    /// The lower bound of a variable
+    LowerBound(Variable),
+    /// The upper bound of a variable
+    UpperBound(Variable),
+    /// A variable has to take an integer value
+    Integrality(Variable),
+    /// A constraint, as returned by [UnsolvedProblem::add_constraint]
+    Constraint(ConstraintReference),
+}
+
+/// A requirement that a solution violates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The requirement that is violated
+    pub requirement: Requirement,
+    /// The name of the variable or of the constraint, if it has one
+    pub name: Option<String>,
+    /// By how much the requirement is violated
+    pub amount: f64,
+}
+
+/// The requirements violated by a solution, returned by [check_solution]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeasibilityReport {
+    /// The violated requirements: the bounds and integrality of the variables in their order,
+    /// then the constraints in the order in which they were added to the problem
+    pub violations: Vec<Violation>,
+    /// The tolerance that was used for the check
+    pub tolerance: f64,
+}
+
+impl FeasibilityReport {
+    /// Whether the solution satisfies all the requirements, within the tolerance
+    pub fn is_feasible(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// The largest amount by which a requirement is violated, or 0 if the solution is feasible
+    pub fn max_violation(&self) -> f64 {
+        self.violations
+            .iter()
+            .map(|violation| violation.amount)
+            .fold(0., f64::max)
+    }
+}
+
+impl Display for Requirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Requirement::LowerBound(variable) => write!(f, "lower bound of {:?}", variable),
+            Requirement::UpperBound(variable) => write!(f, "upper bound of {:?}", variable),
+            Requirement::Integrality(variable) => write!(f, "integrality of {:?}", variable),
+            Requirement::Constraint(constraint) => write!(f, "constraint {}", constraint.index),
+        }
+    }
+}
+
+impl Display for FeasibilityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_feasible() {
+            return write!(
+                f,
+                "The solution is feasible, with a tolerance of {}",
+                self.tolerance
+            );
+        }
+        write!(
+            f,
+            "The solution violates {} requirements, with a tolerance of {}:",
+            self.violations.len(),
+            self.tolerance
+        )?;
+        for violation in &self.violations {
+            write!(f, "\n  {}", violation.requirement)?;
+            if let Some(name) = &violation.name {
+                write!(f, " ({})", name)?;
+            }
+            write!(f, ": violated by {}", violation.amount)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks the solution against the bounds, the integrality requirements and the constraints
+/// of the problem, and reports all the ones that it violates by more than `tolerance`.
+pub fn check_solution<S: Solution>(
+    problem: &UnsolvedProblem,
+    solution: &S,
+    tolerance: f64,
+) -> FeasibilityReport {
+    let mut violations = vec![];
+    for (variable, definition) in problem.variables.iter_variables_with_def() {
+        let value = solution.value(variable);
+        let name = Some(definition.get_name())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        let mut violated = |requirement, amount: f64| {
+            if amount > tolerance {
+                violations.push(Violation {
+                    requirement,
+                    name: name.clone(),
+                    amount,
+                });
+            }
+        };
+        violated(Requirement::LowerBound(variable), definition.min - value);
+        violated(Requirement::UpperBound(variable), value - definition.max);
+        if definition.is_integer {
+            violated(
+                Requirement::Integrality(variable),
+                (value - value.round()).abs(),
+            );
+        }
+    }
+    for (index, constraint) in problem.constraints.iter().enumerate() {
+        let amount = -solution.constraint_slack(constraint);
+        if amount > tolerance {
+            violations.push(Violation {
+                requirement: Requirement::Constraint(ConstraintReference { index }),
+                name: constraint.get_name().map(str::to_string),
+                amount,
+            });
+        }
+    }
+    FeasibilityReport {
+        violations,
+        tolerance,
+    }
+}
@@ -0,0 +1,906 @@
+//! The [CPLEX LP](https://www.ibm.com/docs/en/icos/22.1.0?topic=cplex-lp-file-format-algebraic-representation)
+//! file format: a human-readable algebraic representation of a problem,
+//! convenient to look at when debugging a model.
+//!
+//! ```
+//! use good_lp::{constraint, variables};
+//!
+//! variables! {vars: 0 <= x <= 10; y (integer);}
+//! let problem = vars
+//!     .maximise(3 * x + y)
+//!     .with(constraint!(x + 2 * y <= 14).set_name("capacity"));
+//! let lp = problem.to_lp();
+//! assert!(lp.contains("capacity: x + 2 y <= 14"));
+//!
+//! // Problems can also be read back, and modified before being solved
+//! let parsed = good_lp::formats::lp::read(lp.as_bytes())?;
+//! let x = parsed.variable("x");
+//! let problem = parsed.problem.with(constraint!(x >= 1));
+//! # Ok::<_, good_lp::formats::FormatError>(())
+//! ```
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::formats::{format_number, FormatError, ParsedProblem, ProblemNames};
+use crate::solvers::ObjectiveDirection;
+use crate::variable::{ProblemVariables, UnsolvedProblem, VariableDefinition};
+use crate::{constraint, Constraint, Expression, Variable};
+
+/// The name of the objective function in the exported files
+const OBJECTIVE_NAME: &str = "obj";
+
+/// Lines longer than this are wrapped. CPLEX refuses lines longer than 255 characters.
+const MAX_LINE_LENGTH: usize = 200;
+
+/// Writes the problem in the LP format.
+///
+/// Variables and constraints that have no name are called `v{index}` and `c{index}`.
+/// Characters that are not allowed in LP names are replaced by underscores,
+/// and duplicate names are made unique.
+/// Integer variables with bounds `[0, 1]` are declared as binary,
+/// and other integer variables as general integers.
+pub fn write<W: Write>(problem: &UnsolvedProblem, mut writer: W) -> io::Result<()> {
+    let names = ProblemNames::new(problem, OBJECTIVE_NAME, sanitize);
+    let model_name = problem
+        .metadata
+        .as_ref()
+        .map_or("good_lp", |m| m.name.as_str());
+    writeln!(writer, "\\ Problem name: {}", model_name)?;
+    for line in problem.metadata.iter().flat_map(|m| m.comment_lines()) {
+        writeln!(writer, "\\ {}", line)?;
+    }
+    writeln!(writer)?;
+    match problem.direction {
+        ObjectiveDirection::Maximisation => writeln!(writer, "Maximize")?,
+        ObjectiveDirection::Minimisation => writeln!(writer, "Minimize")?,
+    }
+    let mut objective = linear_terms(&problem.objective, &names.variables);
+    if problem.objective.constant != 0. {
+        objective.push(signed_term(problem.objective.constant, None));
+    }
+    writeln!(
+        writer,
+        " {}",
+        wrap(format!("{}:", OBJECTIVE_NAME), objective)
+    )?;
+
+    writeln!(writer, "Subject To")?;
+    for (constraint, name) in problem.constraints.iter().zip(&names.constraints) {
+        let mut terms = linear_terms(&constraint.expression, &names.variables);
+        if terms.is_empty() {
+            // A constraint needs at least one variable
+            if let Some(first) = names.variables.first() {
+                terms.push(format!("0 {}", first));
+            }
+        }
+        let operator = if constraint.is_equality { "=" } else { "<=" };
+        let rhs = 0. - constraint.expression.constant;
+        terms.push(format!("{} {}", operator, format_number(rhs)));
+        writeln!(writer, " {}", wrap(format!("{}:", name), terms))?;
+    }
+
+    writeln!(writer, "Bounds")?;
+    let mut general = vec![];
+    let mut binary = vec![];
+    for (var, def) in problem.variables.iter_variables_with_def() {
+        let name = &names.variables[var.index()];
+        let (min, max) = (def.min, def.max);
+        if def.is_integer {
+            if min == 0. && max == 1. {
+                binary.push(name.as_str());
+                continue;
+            }
+            general.push(name.as_str());
+        }
+        if min == max {
+            writeln!(writer, " {} = {}", name, format_number(min))?;
+        } else if min == f64::NEG_INFINITY && max == f64::INFINITY {
+            writeln!(writer, " {} free", name)?;
+        } else if max == f64::INFINITY {
+            if min != 0. {
+                writeln!(writer, " {} >= {}", name, format_number(min))?;
+            }
+        } else {
+            let min = if min == f64::NEG_INFINITY {
+                "-inf".to_string()
+            } else {
+                format_number(min)
+            };
+            writeln!(writer, " {} <= {} <= {}", min, name, format_number(max))?;
+        }
+    }
+    for (section, names) in [("General", general), ("Binary", binary)] {
+        if !names.is_empty() {
+            writeln!(writer, "{}", section)?;
+            writeln!(
+                writer,
+                " {}",
+                wrap(String::new(), names.iter().map(|n| n.to_string()).collect())
+            )?;
+        }
+    }
+    writeln!(writer, "End")?;
+    Ok(())
+}
+
+/// Valid names contain only letters, digits and the characters `!"#$%&()/,.;?@_`'{}|~`,
+/// and don't start with a digit or a period.
+fn sanitize(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "!\"#$%&()/,.;?@_`'{}|~".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// The terms of the linear part of an expression, ordered by variable
+fn linear_terms(expression: &Expression, names: &[String]) -> Vec<String> {
+    let mut coefficients: Vec<_> = expression
+        .linear
+        .coefficients
+        .iter()
+        .filter(|(_, &c)| c != 0.)
+        .map(|(&var, &c)| (var.index(), c))
+        .collect();
+    coefficients.sort_by_key(|&(index, _)| index);
+    coefficients
+        .into_iter()
+        .map(|(index, c)| signed_term(c, Some(&names[index])))
+        .collect()
+}
+
+/// Formats `+ 2 x`, `- x`, or `+ 3` if there is no variable
+fn signed_term(coefficient: f64, variable: Option<&str>) -> String {
+    let sign = if coefficient < 0. { '-' } else { '+' };
+    let abs = coefficient.abs();
+    match variable {
+        Some(var) if abs == 1. => format!("{} {}", sign, var),
+        Some(var) => format!("{} {} {}", sign, format_number(abs), var),
+        None => format!("{} {}", sign, format_number(abs)),
+    }
+}
+
+/// Joins the terms after the given label, removing the leading `+`, and wrapping long lines
+fn wrap(label: String, terms: Vec<String>) -> String {
+    let mut result = label;
+    let mut line_length = result.len();
+    for (i, term) in terms.into_iter().enumerate() {
+        let term = match term.strip_prefix("+ ") {
+            Some(positive) if i == 0 => positive.to_string(),
+            _ => term,
+        };
+        if line_length + term.len() + 1 > MAX_LINE_LENGTH {
+            result.push_str("\n   ");
+            line_length = 3;
+        } else if !result.is_empty() {
+            result.push(' ');
+            line_length += 1;
+        }
+        line_length += term.len();
+        result.push_str(&term);
+    }
+    result
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Operator(Operator),
+    Plus,
+    Minus,
+    Colon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    LessOrEqual,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl Operator {
+    /// The operator obtained when swapping both sides
+    fn reversed(self) -> Self {
+        match self {
+            Operator::LessOrEqual => Operator::GreaterOrEqual,
+            Operator::GreaterOrEqual => Operator::LessOrEqual,
+            Operator::Equal => Operator::Equal,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Objective,
+    Constraints,
+    Bounds,
+    Integers,
+    Binaries,
+    End,
+}
+
+/// Recognizes the keyword that starts a section, and returns the rest of the line
+fn section_header(line: &str) -> Option<(Section, &str)> {
+    let lower = line.to_ascii_lowercase();
+    let keywords = [
+        ("maximize", Some(ObjectiveDirection::Maximisation)),
+        ("maximum", Some(ObjectiveDirection::Maximisation)),
+        ("max", Some(ObjectiveDirection::Maximisation)),
+        ("minimize", Some(ObjectiveDirection::Minimisation)),
+        ("minimum", Some(ObjectiveDirection::Minimisation)),
+        ("min", Some(ObjectiveDirection::Minimisation)),
+    ];
+    let sections = [
+        ("subject to", Section::Constraints),
+        ("such that", Section::Constraints),
+        ("s.t.", Section::Constraints),
+        ("st", Section::Constraints),
+        ("bounds", Section::Bounds),
+        ("bound", Section::Bounds),
+        ("generals", Section::Integers),
+        ("general", Section::Integers),
+        ("gen", Section::Integers),
+        ("integers", Section::Integers),
+        ("binaries", Section::Binaries),
+        ("binary", Section::Binaries),
+        ("bin", Section::Binaries),
+        ("end", Section::End),
+    ];
+    let objective = keywords
+        .iter()
+        .map(|&(keyword, _)| (keyword, Section::Objective));
+    for (keyword, section) in objective.chain(sections.iter().copied()) {
+        if let Some(rest) = lower.strip_prefix(keyword) {
+            if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                return Some((section, &line[keyword.len()..]));
+            }
+        }
+    }
+    None
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!\"#$%&()/,.;?@_`'{}|~[]^".contains(c)
+}
+
+/// Splits a line into tokens
+fn tokenize(
+    line: &str,
+    line_number: usize,
+    tokens: &mut Vec<(Token, usize)>,
+) -> Result<(), FormatError> {
+    let mut chars = line.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let token = if c.is_whitespace() {
+            chars.next();
+            continue;
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            let mut previous = ' ';
+            while let Some(&(i, c)) = chars.peek() {
+                let exponent_sign = (c == '+' || c == '-') && (previous == 'e' || previous == 'E');
+                if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign {
+                    end = i + c.len_utf8();
+                    previous = c;
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let number = &line[start..end];
+            Token::Number(number.parse().map_err(|_| {
+                FormatError::parse(line_number, format!("invalid number {:?}", number))
+            })?)
+        } else if c == '<' || c == '>' || c == '=' {
+            chars.next();
+            let mut operator = String::from(c);
+            if let Some(&(_, next)) = chars.peek() {
+                if next == '<' || next == '>' || next == '=' {
+                    operator.push(next);
+                    chars.next();
+                }
+            }
+            Token::Operator(match operator.as_str() {
+                "<" | "<=" | "=<" => Operator::LessOrEqual,
+                ">" | ">=" | "=>" => Operator::GreaterOrEqual,
+                "=" => Operator::Equal,
+                _ => {
+                    return Err(FormatError::parse(
+                        line_number,
+                        format!("invalid operator {:?}", operator),
+                    ))
+                }
+            })
+        } else if c == '+' {
+            chars.next();
+            Token::Plus
+        } else if c == '-' {
+            chars.next();
+            Token::Minus
+        } else if c == ':' {
+            chars.next();
+            Token::Colon
+        } else if is_name_char(c) {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if is_name_char(c) {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            Token::Name(line[start..end].to_string())
+        } else {
+            return Err(FormatError::parse(
+                line_number,
+                format!("unexpected character {:?}", c),
+            ));
+        };
+        tokens.push((token, line_number));
+    }
+    Ok(())
+}
+
+fn is_infinity(name: &str) -> bool {
+    name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity")
+}
+
+/// The variables of the problem being read, in their order of appearance
+#[derive(Default)]
+struct Columns {
+    definitions: Vec<VariableDefinition>,
+    index: HashMap<String, usize>,
+}
+
+impl Columns {
+    fn get(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.index.get(name) {
+            return index;
+        }
+        let index = self.definitions.len();
+        self.index.insert(name.to_string(), index);
+        self.definitions
+            .push(VariableDefinition::new().name(name).min(0));
+        index
+    }
+}
+
+/// A linear expression over the indices of the variables
+#[derive(Default)]
+struct Terms {
+    coefficients: Vec<(usize, f64)>,
+    constant: f64,
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    position: usize,
+    last_line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens
+            .get(self.position + offset)
+            .map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.last_line, |&(_, line)| line)
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, FormatError> {
+        Err(FormatError::parse(self.line(), message))
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    fn at_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    /// Consumes a `name:` label, if there is one
+    fn label(&mut self) -> Option<String> {
+        match (self.peek(), self.peek_at(1)) {
+            (Some(Token::Name(name)), Some(Token::Colon)) => {
+                self.position += 2;
+                Some(name.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Consumes a sequence of signs, and returns -1 if the number of minus signs is odd
+    fn sign(&mut self) -> f64 {
+        let mut sign = 1.;
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {}
+                Some(Token::Minus) => sign = -sign,
+                _ => return sign,
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Consumes a signed number, possibly infinite
+    fn number(&mut self) -> Result<f64, FormatError> {
+        let sign = self.sign();
+        match self.next() {
+            Some(Token::Number(value)) => Ok(sign * value),
+            Some(Token::Name(name)) if is_infinity(name) => Ok(sign * f64::INFINITY),
+            _ => {
+                self.position -= 1;
+                self.error("expected a number")
+            }
+        }
+    }
+
+    /// Whether the next tokens are a signed number
+    fn starts_with_number(&self) -> bool {
+        let mut offset = 0;
+        while let Some(Token::Plus) | Some(Token::Minus) = self.peek_at(offset) {
+            offset += 1;
+        }
+        match self.peek_at(offset) {
+            Some(Token::Number(_)) => true,
+            Some(Token::Name(name)) => is_infinity(name),
+            _ => false,
+        }
+    }
+
+    /// Consumes a linear expression, up to the next operator, label, or the end of the section
+    fn terms(&mut self, columns: &mut Columns) -> Result<Terms, FormatError> {
+        let mut terms = Terms::default();
+        loop {
+            if self.label_ahead() {
+                return Ok(terms);
+            }
+            match self.peek() {
+                None | Some(Token::Operator(_)) => return Ok(terms),
+                Some(Token::Colon) => return self.error("unexpected ':'"),
+                _ => {}
+            }
+            let sign = self.sign();
+            let coefficient = match self.peek() {
+                Some(Token::Number(value)) => {
+                    self.position += 1;
+                    Some(*value)
+                }
+                _ => None,
+            };
+            match self.peek() {
+                Some(Token::Name(name)) if !self.label_ahead() => {
+                    self.position += 1;
+                    let column = columns.get(name);
+                    terms
+                        .coefficients
+                        .push((column, sign * coefficient.unwrap_or(1.)));
+                }
+                _ => match coefficient {
+                    Some(value) => terms.constant += sign * value,
+                    None => return self.error("expected a variable or a number"),
+                },
+            }
+        }
+    }
+
+    fn label_ahead(&self) -> bool {
+        matches!(
+            (self.peek(), self.peek_at(1)),
+            (Some(Token::Name(_)), Some(Token::Colon))
+        )
+    }
+
+    fn operator(&mut self) -> Result<Operator, FormatError> {
+        match self.next() {
+            Some(Token::Operator(operator)) => Ok(*operator),
+            _ => {
+                self.position -= 1;
+                self.error("expected <=, >= or =")
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, FormatError> {
+        match self.next() {
+            Some(Token::Name(name)) => Ok(name),
+            _ => {
+                self.position -= 1;
+                self.error("expected a variable name")
+            }
+        }
+    }
+}
+
+/// A constraint `lower <= terms <= upper`, where infinite bounds are absent
+struct Row {
+    name: Option<String>,
+    terms: Terms,
+    lower: f64,
+    upper: f64,
+}
+
+fn bound(operator: Operator, value: f64) -> (f64, f64) {
+    match operator {
+        Operator::LessOrEqual => (f64::NEG_INFINITY, value),
+        Operator::GreaterOrEqual => (value, f64::INFINITY),
+        Operator::Equal => (value, value),
+    }
+}
+
+fn parse_constraints(parser: &mut Parser, columns: &mut Columns) -> Result<Vec<Row>, FormatError> {
+    let mut rows = vec![];
+    while !parser.at_end() {
+        let name = parser.label();
+        let row = if parser.starts_with_number() && {
+            // A ranged constraint: `lower <= expression <= upper`
+            let mut offset = 0;
+            while let Some(Token::Plus) | Some(Token::Minus) = parser.peek_at(offset) {
+                offset += 1;
+            }
+            matches!(parser.peek_at(offset + 1), Some(Token::Operator(_)))
+        } {
+            let first = parser.number()?;
+            let first_operator = parser.operator()?.reversed();
+            let terms = parser.terms(columns)?;
+            let (mut lower, mut upper) = bound(first_operator, first - terms.constant);
+            if let Some(Token::Operator(_)) = parser.peek() {
+                let operator = parser.operator()?;
+                let (l, u) = bound(operator, parser.number()? - terms.constant);
+                lower = lower.max(l);
+                upper = upper.min(u);
+            }
+            Row {
+                name,
+                terms,
+                lower,
+                upper,
+            }
+        } else {
+            let terms = parser.terms(columns)?;
+            let operator = parser.operator()?;
+            let rhs = parser.number()?;
+            let (lower, upper) = bound(operator, rhs - terms.constant);
+            Row {
+                name,
+                terms,
+                lower,
+                upper,
+            }
+        };
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn parse_bounds(parser: &mut Parser, columns: &mut Columns) -> Result<(), FormatError> {
+    while !parser.at_end() {
+        if parser.starts_with_number() {
+            // value <= x [<= value]
+            let value = parser.number()?;
+            let operator = parser.operator()?.reversed();
+            let column = columns.get(parser.name()?);
+            apply_bound(&mut columns.definitions[column], operator, value);
+            if let Some(Token::Operator(_)) = parser.peek() {
+                let operator = parser.operator()?;
+                let value = parser.number()?;
+                apply_bound(&mut columns.definitions[column], operator, value);
+            }
+        } else {
+            let column = columns.get(parser.name()?);
+            let def = &mut columns.definitions[column];
+            match parser.peek() {
+                Some(Token::Name(free)) if free.eq_ignore_ascii_case("free") => {
+                    parser.position += 1;
+                    def.min = f64::NEG_INFINITY;
+                    def.max = f64::INFINITY;
+                }
+                _ => {
+                    let operator = parser.operator()?;
+                    let value = parser.number()?;
+                    apply_bound(def, operator, value);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies `variable <operator> value`
+fn apply_bound(def: &mut VariableDefinition, operator: Operator, value: f64) {
+    match operator {
+        Operator::LessOrEqual => def.max = value,
+        Operator::GreaterOrEqual => def.min = value,
+        Operator::Equal => {
+            def.min = value;
+            def.max = value;
+        }
+    }
+}
+
+/// Reads a problem in the LP format.
+///
+/// The objective, `Subject To`, `Bounds`, `General` and `Binary` sections are supported,
+/// as well as ranged constraints written `lower <= expression <= upper`.
+/// Semi-continuous variables, special ordered sets and quadratic terms are not supported.
+/// Variables have a lower bound of 0 and no upper bound unless specified otherwise.
+/// A ranged constraint becomes two constraints, the second one being named `{name}_range`.
+pub fn read<R: BufRead>(reader: R) -> Result<ParsedProblem, FormatError> {
+    let mut direction = ObjectiveDirection::Minimisation;
+    let mut section_tokens: Vec<(Section, Vec<(Token, usize)>)> = vec![];
+    let mut last_line = 0;
+    for (line_number, line) in reader.lines().enumerate() {
+        let line_number = line_number + 1;
+        last_line = line_number;
+        let line = line?;
+        let line = match line.find('\\') {
+            Some(comment) => &line[..comment],
+            None => &line[..],
+        };
+        let trimmed = line.trim_start();
+        let content = match section_header(trimmed) {
+            Some((section, rest)) => {
+                if section == Section::Objective {
+                    direction = if trimmed.to_ascii_lowercase().starts_with("max") {
+                        ObjectiveDirection::Maximisation
+                    } else {
+                        ObjectiveDirection::Minimisation
+                    };
+                }
+                section_tokens.push((section, vec![]));
+                rest
+            }
+            None => {
+                let lower = trimmed.to_ascii_lowercase();
+                if lower.starts_with("semi") || lower.starts_with("sos") {
+                    return Err(FormatError::parse(
+                        line_number,
+                        format!("unsupported section {:?}", trimmed),
+                    ));
+                }
+                line
+            }
+        };
+        match section_tokens.last_mut() {
+            Some((Section::End, _)) => break,
+            Some((_, tokens)) => tokenize(content, line_number, tokens)?,
+            None if content.trim().is_empty() => {}
+            None => {
+                return Err(FormatError::parse(
+                    line_number,
+                    "expected the objective to start with Maximize or Minimize",
+                ))
+            }
+        }
+    }
+
+    let mut columns = Columns::default();
+    let mut objective = Terms::default();
+    let mut rows = vec![];
+    let mut integers = vec![];
+    for (section, tokens) in &section_tokens {
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            last_line,
+        };
+        match section {
+            Section::Objective => {
+                parser.label();
+                objective = parser.terms(&mut columns)?;
+                if !parser.at_end() {
+                    return parser.error("unexpected token in the objective");
+                }
+            }
+            Section::Constraints => rows.extend(parse_constraints(&mut parser, &mut columns)?),
+            Section::Bounds => parse_bounds(&mut parser, &mut columns)?,
+            Section::Integers | Section::Binaries => {
+                while !parser.at_end() {
+                    let column = columns.get(parser.name()?);
+                    integers.push((column, *section == Section::Binaries));
+                }
+            }
+            Section::End => {}
+        }
+    }
+    for (column, binary) in integers {
+        let def = &mut columns.definitions[column];
+        def.is_integer = true;
+        if binary {
+            def.min = 0.;
+            def.max = 1.;
+        }
+    }
+
+    let mut variables = ProblemVariables::new();
+    let columns: Vec<Variable> = columns
+        .definitions
+        .into_iter()
+        .map(|def| variables.add(def))
+        .collect();
+    let expression = |terms: &Terms| {
+        let mut expression = Expression::default();
+        for &(column, value) in &terms.coefficients {
+            expression.add_mul(value, columns[column]);
+        }
+        expression
+    };
+    let mut objective_expression = expression(&objective);
+    objective_expression.constant = objective.constant;
+    let mut problem = variables.optimise(direction, objective_expression);
+    for row in rows {
+        let lhs = expression(&row.terms);
+        let constraints: Vec<Constraint> = if row.lower == row.upper {
+            vec![constraint::eq(lhs, row.lower)]
+        } else {
+            let mut constraints = vec![];
+            if row.upper < f64::INFINITY {
+                constraints.push(constraint::leq(lhs.clone(), row.upper));
+            }
+            if row.lower > f64::NEG_INFINITY {
+                constraints.push(constraint::geq(lhs, row.lower));
+            }
+            constraints
+        };
+        for (i, c) in constraints.into_iter().enumerate() {
+            let c = match (&row.name, i) {
+                (Some(name), 0) => c.set_name(name.clone()),
+                (Some(name), _) => c.set_name(format!("{}_range", name)),
+                (None, _) => c,
+            };
+            problem.add_constraint(c);
+        }
+    }
+    Ok(ParsedProblem::new(problem))
+}
+
+impl ProblemVariables {
+    /// Reads a problem from an LP file. See [read] for details.
+    pub fn from_lp<P: AsRef<Path>>(path: P) -> Result<ParsedProblem, FormatError> {
+        read(BufReader::new(File::open(path)?))
+    }
+}
+
+impl UnsolvedProblem {
+    /// Returns the problem, with its constraints, in the LP format.
+    /// See [write] for details.
+    pub fn to_lp(&self) -> String {
+        let mut buffer = Vec::new();
+        write(self, &mut buffer).expect("writing to memory cannot fail");
+        String::from_utf8(buffer).expect("names are valid utf-8")
+    }
+
+    /// Writes the problem, with its constraints, to a file in the LP format.
+    /// See [write] for details.
+    pub fn write_lp<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write(self, &mut writer)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read;
+    use crate::{constraint, variable, variables, Solution, SolverModel};
+
+    #[test]
+    fn export_small_problem() {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("x").clamp(0, 10));
+        let y = vars.add(variable().name("y").integer().min(1));
+        let z = vars.add(variable().name("z z"));
+        let b = vars.add(variable().name("1b").binary());
+        let w = vars.add(variable().max(-1));
+        let problem = vars
+            .minimise(3 * x - y + 2)
+            .with(constraint!(x + 2 * y <= 14).set_name("cap".to_string()))
+            .with(constraint!(z == y - 1 + b + w));
+        let expected = "\
+\\ Problem name: good_lp
+
+Minimize
+ obj: 3 x - y + 2
+Subject To
+ cap: x + 2 y <= 14
+ c1: - y + z_z - _1b - v4 = -1
+Bounds
+ 0 <= x <= 10
+ y >= 1
+ z_z free
+ -inf <= v4 <= -1
+General
+ y
+Binary
+ _1b
+End
+";
+        assert_eq!(problem.to_lp(), expected);
+    }
+
+    #[test]
+    fn long_lines_are_wrapped() {
+        let mut vars = variables!();
+        let xs = vars.add_vector(variable(), 100);
+        let sum: crate::Expression = xs.iter().sum();
+        let lp = vars.minimise(sum).to_lp();
+        assert!(lp.lines().all(|line| line.len() <= super::MAX_LINE_LENGTH));
+        assert!(lp.contains("v99"));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("x").clamp(-1, 10));
+        let y = vars.add(variable().name("y").integer().min(1));
+        let z = vars.add(variable().name("z").max(-2));
+        let b = vars.add(variable().name("b").binary());
+        let w = vars.add(variable().name("w").clamp(3, 3));
+        let problem = vars
+            .maximise(3 * x - y + 2 + b + 0.5 * z + w)
+            .with(constraint!(x + 2e-7 * y <= 14).set_name("cap".to_string()))
+            .with(constraint!(z == y - 1 + w).set_name("link".to_string()))
+            .with(constraint!(x + 1.5 * z >= -3));
+        let read_back = read(problem.to_lp().as_bytes()).unwrap().problem;
+        assert_eq!(read_back.to_lp(), problem.to_lp());
+    }
+
+    #[test]
+    fn cplex_syntax() {
+        let lp = r"\ A comment
+MINIMIZE
+ cost: x + 2y
+   + 0 z \ multi-line objective
+SUBJECT TO
+ lim: -3 <= x + y - 1 <= 4
+ x - 1 >= 0
+ c2: 2 x + y >= 0 c3: z <= 2
+BOUNDS
+ x <= 4
+ -inf <= z <= 1
+END
+";
+        let parsed = read(lp.as_bytes()).unwrap();
+        assert_eq!(parsed.problem.constraints.len(), 5);
+        assert_eq!(parsed.constraints["lim_range"].index, 1);
+        assert_eq!(parsed.constraints["c3"].index, 4);
+        let (x, y) = (parsed.variable("x"), parsed.variable("y"));
+        let solution = parsed.problem.using(crate::default_solver).solve().unwrap();
+        float_eq::assert_float_eq!(solution.value(x), 1., abs <= 1e-6);
+        float_eq::assert_float_eq!(solution.value(y), 0., abs <= 1e-6);
+    }
+
+    #[test]
+    fn parse_errors_have_line_numbers() {
+        let lp = "Maximize\n obj: x\nSubject To\n c: x <= y\nEnd\n";
+        let err = match read(lp.as_bytes()) {
+            Err(err) => err,
+            Ok(_) => panic!("the right hand side should be a number"),
+        };
+        assert!(err.to_string().contains("line 4"), "{}", err);
+    }
+}
@@ -0,0 +1,216 @@
+//! Reading and writing problems in the file formats understood by other optimization tools.
+//!
+//! Exporting a problem is useful to share a model with people using other tools,
+//! or to attach it to a bug report for a solver.
+//! Imported problems can be modified before being solved,
+//! using the variables and constraints of a [ParsedProblem].
+
+pub mod lp;
+pub mod mps;
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use crate::constraint::ConstraintReference;
+use crate::variable::UnsolvedProblem;
+use crate::Variable;
+
+/// A problem read from a file, with its variables and constraints indexed by name
+pub struct ParsedProblem {
+    /// The problem, ready to be solved
+    pub problem: UnsolvedProblem,
+    /// The variables of the problem, by name
+    pub variables: HashMap<String, Variable>,
+    /// The constraints of the problem, by name
+    pub constraints: HashMap<String, ConstraintReference>,
+}
+
+impl ParsedProblem {
+    pub(crate) fn new(problem: UnsolvedProblem) -> Self {
+        let variables = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(var, def)| (def.get_name().to_string(), var))
+            .collect();
+        let constraints = problem
+            .constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, c)| {
+                Some((c.get_name()?.to_string(), ConstraintReference { index }))
+            })
+            .collect();
+        ParsedProblem {
+            problem,
+            variables,
+            constraints,
+        }
+    }
+
+    /// The variable with the given name. Panics if there is no such variable.
+    pub fn variable(&self, name: &str) -> Variable {
+        match self.variables.get(name) {
+            Some(&var) => var,
+            None => panic!("no variable named {:?}", name),
+        }
+    }
+}
+
+/// An error that occurred while reading a problem from a file
+#[derive(Debug)]
+pub enum FormatError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file could be read, but its content is invalid
+    Parse {
+        /// The line number (starting from 1) where the error was found
+        line: usize,
+        /// A description of the error
+        message: String,
+    },
+}
+
+impl FormatError {
+    pub(crate) fn parse<S: Into<String>>(line: usize, message: S) -> Self {
+        FormatError::Parse {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Io(e) => write!(f, "Unable to read the problem: {}", e),
+            FormatError::Parse { line, message } => {
+                write!(f, "Invalid problem file at line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl Error for FormatError {}
+
+impl From<io::Error> for FormatError {
+    fn from(e: io::Error) -> Self {
+        FormatError::Io(e)
+    }
+}
+
+/// Replaces whitespace by underscores,
+/// so that names can be written in files where fields are separated by spaces.
+pub(crate) fn without_whitespace(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect()
+}
+
+/// Returns unique names, made valid for a file format by `sanitize`.
+/// Empty names are replaced by `{default_prefix}{index}`.
+/// Names in `reserved` are never returned.
+pub(crate) fn unique_names<'a, I>(
+    names: I,
+    default_prefix: &str,
+    reserved: &[&str],
+    sanitize: fn(&str) -> String,
+) -> Vec<String>
+where
+    I: IntoIterator<Item = Option<&'a str>>,
+{
+    let mut used: HashSet<String> = reserved.iter().map(|s| s.to_string()).collect();
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let base = match name {
+                Some(name) if !name.is_empty() => sanitize(name),
+                _ => format!("{}{}", default_prefix, index),
+            };
+            let mut name = base.clone();
+            let mut suffix = 1;
+            while used.contains(&name) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            used.insert(name.clone());
+            name
+        })
+        .collect()
+}
+
+/// The names under which the variables and constraints of a problem are exported
+pub(crate) struct ProblemNames {
+    pub(crate) variables: Vec<String>,
+    pub(crate) constraints: Vec<String>,
+}
+
+impl ProblemNames {
+    pub(crate) fn new(
+        problem: &UnsolvedProblem,
+        objective_name: &str,
+        sanitize: fn(&str) -> String,
+    ) -> Self {
+        let variables = unique_names(
+            problem
+                .variables
+                .iter_variables_with_def()
+                .map(|(_, def)| Some(def.name.as_str())),
+            "v",
+            &[],
+            sanitize,
+        );
+        let constraints = unique_names(
+            problem.constraints.iter().map(|c| c.name.as_deref()),
+            "c",
+            &[objective_name],
+            sanitize,
+        );
+        ProblemNames {
+            variables,
+            constraints,
+        }
+    }
+}
+
+/// Formats a number so that it can be parsed back without loss of precision,
+/// using the shortest of the decimal and scientific notations.
+pub(crate) fn format_number(x: f64) -> String {
+    let decimal = x.to_string();
+    let scientific = format!("{:e}", x);
+    if scientific.len() < decimal.len() {
+        scientific
+    } else {
+        decimal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_number, unique_names, without_whitespace};
+
+    #[test]
+    fn names_are_unique() {
+        let names = unique_names(
+            vec![Some("x"), None, Some("x"), Some("a b"), Some("")],
+            "v",
+            &[],
+            without_whitespace,
+        );
+        assert_eq!(names, vec!["x", "v1", "x_1", "a_b", "v4"]);
+        assert_eq!(
+            unique_names(vec![Some("obj")], "c", &["obj"], without_whitespace),
+            vec!["obj_1"]
+        );
+    }
+
+    #[test]
+    fn numbers_roundtrip() {
+        assert_eq!(format_number(2.5), "2.5");
+        assert_eq!(format_number(-3.), "-3");
+        assert_eq!(format_number(1e300), "1e300");
+        assert_eq!(format_number(1.5e-9), "1.5e-9");
+    }
+}
@@ -0,0 +1,728 @@
+//! The [MPS](https://en.wikipedia.org/wiki/MPS_(format)) file format,
+//! supported by virtually every linear programming solver.
+//!
+//! ```
+//! use good_lp::{constraint, variable, variables};
+//!
+//! variables! {vars: 0 <= x <= 10; y (integer);}
+//! let problem = vars
+//!     .maximise(3 * x + y)
+//!     .with(constraint!(x + 2 * y <= 14).set_name("capacity"));
+//! let mps = problem.to_mps();
+//! assert!(mps.contains(" L  capacity"));
+//! ```
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::formats::{format_number, without_whitespace, FormatError, ParsedProblem, ProblemNames};
+use crate::solvers::ObjectiveDirection;
+use crate::variable::{ProblemVariables, UnsolvedProblem, VariableDefinition};
+use crate::{constraint, Expression, Variable};
+
+/// The name of the objective row in the exported files
+const OBJECTIVE_ROW: &str = "obj";
+
+/// The two flavours of the MPS format
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum MpsFormat {
+    /// Fields start at fixed columns. Names are limited to 8 characters
+    /// and numbers to 12 characters.
+    Fixed,
+    /// Fields are separated by whitespace. Names can be of any length.
+    Free,
+}
+
+/// Writes the problem in the given MPS format.
+///
+/// Variables and constraints that have no name are called `v{index}` and `c{index}`.
+/// Whitespace in names is replaced by underscores, and duplicate names are made unique.
+///
+/// In the fixed format, an [io::ErrorKind::InvalidInput] error is returned if a name is
+/// longer than 8 characters.
+pub fn write<W: Write>(
+    problem: &UnsolvedProblem,
+    mut writer: W,
+    format: MpsFormat,
+) -> io::Result<()> {
+    let names = ProblemNames::new(problem, OBJECTIVE_ROW, without_whitespace);
+    if format == MpsFormat::Fixed {
+        if let Some(long) = names
+            .variables
+            .iter()
+            .chain(&names.constraints)
+            .find(|name| name.len() > 8)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is too long to be written in a fixed MPS file", long),
+            ));
+        }
+    }
+    let number = |x: f64| -> String {
+        match format {
+            MpsFormat::Free => format_number(x),
+            MpsFormat::Fixed => fixed_number(x),
+        }
+    };
+
+    let model_name = problem
+        .metadata
+        .as_ref()
+        .map_or_else(|| "good_lp".to_string(), |m| without_whitespace(&m.name));
+    writeln!(writer, "NAME          {}", model_name)?;
+    for line in problem.metadata.iter().flat_map(|m| m.comment_lines()) {
+        writeln!(writer, "* {}", line)?;
+    }
+    if problem.direction == ObjectiveDirection::Maximisation {
+        writeln!(writer, "OBJSENSE")?;
+        writeln!(writer, "    MAX")?;
+    }
+
+    writeln!(writer, "ROWS")?;
+    writeln!(writer, " N  {}", OBJECTIVE_ROW)?;
+    for (constraint, name) in problem.constraints.iter().zip(&names.constraints) {
+        let kind = if constraint.is_equality { "E" } else { "L" };
+        writeln!(writer, " {}  {}", kind, name)?;
+    }
+
+    // The constraints are stored row by row, but the MPS format is column-oriented
+    let mut columns: Vec<Vec<(usize, f64)>> = vec![vec![]; problem.variables.len()];
+    for (row, constraint) in problem.constraints.iter().enumerate() {
+        for (&var, &coefficient) in &constraint.expression.linear.coefficients {
+            if coefficient != 0. {
+                columns[var.index()].push((row, coefficient));
+            }
+        }
+    }
+    for column in columns.iter_mut() {
+        column.sort_by_key(|&(row, _)| row);
+    }
+
+    writeln!(writer, "COLUMNS")?;
+    let mut in_integer_block = false;
+    let mut markers = 0;
+    for (var, def) in problem.variables.iter_variables_with_def() {
+        if def.is_integer != in_integer_block {
+            let marker = if def.is_integer {
+                "'INTORG'"
+            } else {
+                "'INTEND'"
+            };
+            let marker_name = format!("MARKER{}", markers);
+            writeln!(
+                writer,
+                "    {:<8}  'MARKER'                 {}",
+                marker_name, marker
+            )?;
+            markers += 1;
+            in_integer_block = def.is_integer;
+        }
+        let column_name = &names.variables[var.index()];
+        let objective = problem
+            .objective
+            .linear
+            .coefficients
+            .get(&var)
+            .copied()
+            .unwrap_or(0.);
+        let entries = columns[var.index()]
+            .iter()
+            .map(|&(row, coefficient)| (names.constraints[row].as_str(), coefficient));
+        let mut written = false;
+        for (row_name, coefficient) in std::iter::once((OBJECTIVE_ROW, objective))
+            .filter(|&(_, c)| c != 0.)
+            .chain(entries)
+        {
+            write_entry(&mut writer, column_name, row_name, &number(coefficient))?;
+            written = true;
+        }
+        if !written {
+            // A column has to appear at least once to be declared
+            write_entry(&mut writer, column_name, OBJECTIVE_ROW, &number(0.))?;
+        }
+    }
+    if in_integer_block {
+        writeln!(
+            writer,
+            "    {:<8}  'MARKER'                 'INTEND'",
+            format!("MARKER{}", markers)
+        )?;
+    }
+
+    writeln!(writer, "RHS")?;
+    if problem.objective.constant != 0. {
+        // The right hand side of the objective row is the opposite of the objective constant
+        write_entry(
+            &mut writer,
+            "RHS",
+            OBJECTIVE_ROW,
+            &number(-problem.objective.constant),
+        )?;
+    }
+    for (constraint, name) in problem.constraints.iter().zip(&names.constraints) {
+        let rhs = -constraint.expression.constant;
+        if rhs != 0. {
+            write_entry(&mut writer, "RHS", name, &number(rhs))?;
+        }
+    }
+
+    writeln!(writer, "BOUNDS")?;
+    for (var, def) in problem.variables.iter_variables_with_def() {
+        let name = &names.variables[var.index()];
+        let (min, max) = (def.min, def.max);
+        if min == max {
+            write_bound(&mut writer, "FX", name, Some(&number(min)))?;
+            continue;
+        }
+        if min == f64::NEG_INFINITY && max == f64::INFINITY {
+            write_bound(&mut writer, "FR", name, None)?;
+            continue;
+        }
+        if min == f64::NEG_INFINITY {
+            write_bound(&mut writer, "MI", name, None)?;
+        } else if min != 0. {
+            write_bound(&mut writer, "LO", name, Some(&number(min)))?;
+        }
+        if max < f64::INFINITY {
+            write_bound(&mut writer, "UP", name, Some(&number(max)))?;
+        } else if def.is_integer {
+            // Some readers give integer variables an upper bound of 1 by default
+            write_bound(&mut writer, "PL", name, None)?;
+        }
+    }
+    writeln!(writer, "ENDATA")?;
+    Ok(())
+}
+
+/// Writes the branching priorities of the integer variables of the problem
+/// in the ORD format of CPLEX, that solvers read along with an MPS file.
+/// See [VariableDefinition::branching_priority].
+///
+/// Variables are named as in [write].
+/// The ones that have the default priority of 0 are not written.
+pub fn write_priorities<W: Write>(problem: &UnsolvedProblem, mut writer: W) -> io::Result<()> {
+    let names = ProblemNames::new(problem, OBJECTIVE_ROW, without_whitespace);
+    writeln!(writer, "NAME")?;
+    for (var, def) in problem.variables.iter_variables_with_def() {
+        let priority = def.get_branching_priority();
+        if def.is_integer && priority != 0 {
+            let name = &names.variables[var.index()];
+            writeln!(writer, "    {:<8}            {:>12}", name, priority)?;
+        }
+    }
+    writeln!(writer, "ENDATA")
+}
+
+fn write_entry<W: Write>(writer: &mut W, column: &str, row: &str, value: &str) -> io::Result<()> {
+    writeln!(writer, "    {:<8}  {:<8}  {:>12}", column, row, value)
+}
+
+fn write_bound<W: Write>(
+    writer: &mut W,
+    kind: &str,
+    column: &str,
+    value: Option<&str>,
+) -> io::Result<()> {
+    match value {
+        Some(value) => writeln!(writer, " {} BND       {:<8}  {:>12}", kind, column, value),
+        None => writeln!(writer, " {} BND       {}", kind, column),
+    }
+}
+
+/// Numbers in fixed MPS files cannot be longer than 12 characters
+fn fixed_number(x: f64) -> String {
+    let shortest = format_number(x);
+    if shortest.len() <= 12 {
+        return shortest;
+    }
+    (0..12)
+        .rev()
+        .map(|precision| format!("{:.*e}", precision, x))
+        .find(|s| s.len() <= 12)
+        .unwrap_or(shortest)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum RowKind {
+    Objective,
+    Free,
+    LessOrEqual,
+    GreaterOrEqual,
+    Equal,
+}
+
+struct Row {
+    name: String,
+    kind: RowKind,
+    coefficients: Vec<(usize, f64)>,
+    rhs: f64,
+    range: Option<f64>,
+}
+
+fn parse_number(token: &str, line: usize) -> Result<f64, FormatError> {
+    token
+        .parse()
+        .map_err(|_| FormatError::parse(line, format!("invalid number {:?}", token)))
+}
+
+/// Reads a problem in the MPS format.
+///
+/// Both the free and the fixed formats are supported, as long as names don't contain spaces.
+/// The `ROWS`, `COLUMNS`, `RHS`, `RANGES`, `BOUNDS` and `OBJSENSE` sections are supported.
+/// Variables have a lower bound of 0 and no upper bound unless specified otherwise,
+/// and `>=` rows are converted to `<=` constraints. A ranged row becomes two constraints,
+/// the second one being named `{row}_range`.
+/// Semi-continuous bounds (`SC`) are not supported.
+pub fn read<R: BufRead>(reader: R) -> Result<ParsedProblem, FormatError> {
+    let mut direction = ObjectiveDirection::Minimisation;
+    let mut section = String::new();
+    let mut rows: Vec<Row> = vec![];
+    let mut row_index: HashMap<String, usize> = HashMap::new();
+    let mut objective_row: Option<usize> = None;
+    let mut columns: Vec<VariableDefinition> = vec![];
+    let mut column_index: HashMap<String, usize> = HashMap::new();
+    let mut in_integer_block = false;
+
+    for (line_number, line) in reader.lines().enumerate() {
+        let line_number = line_number + 1;
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if !line.starts_with(char::is_whitespace) {
+            section = tokens[0].to_ascii_uppercase();
+            match (section.as_str(), tokens.get(1)) {
+                ("OBJSENSE", Some(sense)) => direction = parse_sense(sense, line_number)?,
+                ("ENDATA", _) => break,
+                ("NAME", _)
+                | ("ROWS", _)
+                | ("COLUMNS", _)
+                | ("RHS", _)
+                | ("RANGES", _)
+                | ("BOUNDS", _)
+                | ("OBJSENSE", _) => {}
+                (other, _) => {
+                    return Err(FormatError::parse(
+                        line_number,
+                        format!("unsupported section {}", other),
+                    ))
+                }
+            }
+            continue;
+        }
+        let find_row = |name: &str| {
+            row_index
+                .get(name)
+                .copied()
+                .ok_or_else(|| FormatError::parse(line_number, format!("unknown row {:?}", name)))
+        };
+        match section.as_str() {
+            "OBJSENSE" => direction = parse_sense(tokens[0], line_number)?,
+            "ROWS" => {
+                if tokens.len() != 2 {
+                    return Err(FormatError::parse(
+                        line_number,
+                        "expected a row type and a name",
+                    ));
+                }
+                let kind = match tokens[0].to_ascii_uppercase().as_str() {
+                    "N" if objective_row.is_none() => {
+                        objective_row = Some(rows.len());
+                        RowKind::Objective
+                    }
+                    "N" => RowKind::Free,
+                    "L" => RowKind::LessOrEqual,
+                    "G" => RowKind::GreaterOrEqual,
+                    "E" => RowKind::Equal,
+                    other => {
+                        return Err(FormatError::parse(
+                            line_number,
+                            format!("invalid row type {:?}", other),
+                        ))
+                    }
+                };
+                row_index.insert(tokens[1].to_string(), rows.len());
+                rows.push(Row {
+                    name: tokens[1].to_string(),
+                    kind,
+                    coefficients: vec![],
+                    rhs: 0.,
+                    range: None,
+                });
+            }
+            "COLUMNS" => {
+                if tokens.len() >= 3 && tokens[1].trim_matches('\'') == "MARKER" {
+                    match tokens[2].trim_matches('\'') {
+                        "INTORG" => in_integer_block = true,
+                        "INTEND" => in_integer_block = false,
+                        other => {
+                            return Err(FormatError::parse(
+                                line_number,
+                                format!("invalid marker {:?}", other),
+                            ))
+                        }
+                    }
+                    continue;
+                }
+                if tokens.len() != 3 && tokens.len() != 5 {
+                    return Err(FormatError::parse(
+                        line_number,
+                        "expected a column name followed by one or two (row, value) pairs",
+                    ));
+                }
+                let column = match column_index.get(tokens[0]) {
+                    Some(&column) => column,
+                    None => {
+                        let column = columns.len();
+                        column_index.insert(tokens[0].to_string(), column);
+                        let mut def = VariableDefinition::new().name(tokens[0]).min(0);
+                        if in_integer_block {
+                            def = def.integer();
+                        }
+                        columns.push(def);
+                        column
+                    }
+                };
+                for pair in tokens[1..].chunks(2) {
+                    let row = find_row(pair[0])?;
+                    let value = parse_number(pair[1], line_number)?;
+                    rows[row].coefficients.push((column, value));
+                }
+            }
+            "RHS" | "RANGES" => {
+                // The name of the right hand side vector is optional in the free format
+                let pairs = if tokens.len() % 2 == 1 {
+                    &tokens[1..]
+                } else {
+                    &tokens[..]
+                };
+                for pair in pairs.chunks(2) {
+                    if pair.len() != 2 {
+                        return Err(FormatError::parse(
+                            line_number,
+                            "expected (row, value) pairs",
+                        ));
+                    }
+                    let row = find_row(pair[0])?;
+                    let value = parse_number(pair[1], line_number)?;
+                    if section == "RHS" {
+                        rows[row].rhs = value;
+                    } else {
+                        rows[row].range = Some(value);
+                    }
+                }
+            }
+            "BOUNDS" => {
+                let kind = tokens[0].to_ascii_uppercase();
+                let has_value = !matches!(kind.as_str(), "FR" | "MI" | "PL" | "BV");
+                let expected = if has_value { 4 } else { 3 };
+                // The name of the bound vector is optional in the free format
+                let rest = if tokens.len() == expected {
+                    &tokens[2..]
+                } else if tokens.len() == expected - 1 {
+                    &tokens[1..]
+                } else if kind == "BV" && tokens.len() == 4 {
+                    &tokens[2..3]
+                } else {
+                    return Err(FormatError::parse(line_number, "invalid bound"));
+                };
+                let column = *column_index.get(rest[0]).ok_or_else(|| {
+                    FormatError::parse(line_number, format!("unknown column {:?}", rest[0]))
+                })?;
+                let value = if has_value {
+                    parse_number(rest[1], line_number)?
+                } else {
+                    0.
+                };
+                let def = &mut columns[column];
+                match kind.as_str() {
+                    "UP" => {
+                        // A negative upper bound on a variable with the default lower bound
+                        // makes the variable unbounded below
+                        if value < 0. && def.min == 0. {
+                            def.min = f64::NEG_INFINITY;
+                        }
+                        def.max = value;
+                    }
+                    "LO" => def.min = value,
+                    "FX" => {
+                        def.min = value;
+                        def.max = value;
+                    }
+                    "FR" => {
+                        def.min = f64::NEG_INFINITY;
+                        def.max = f64::INFINITY;
+                    }
+                    "MI" => def.min = f64::NEG_INFINITY,
+                    "PL" => def.max = f64::INFINITY,
+                    "BV" => {
+                        def.is_integer = true;
+                        def.min = 0.;
+                        def.max = 1.;
+                    }
+                    "LI" => {
+                        def.is_integer = true;
+                        def.min = value;
+                    }
+                    "UI" => {
+                        def.is_integer = true;
+                        def.max = value;
+                    }
+                    other => {
+                        return Err(FormatError::parse(
+                            line_number,
+                            format!("unsupported bound type {:?}", other),
+                        ))
+                    }
+                }
+            }
+            other => {
+                return Err(FormatError::parse(
+                    line_number,
+                    format!("unexpected data in section {:?}", other),
+                ))
+            }
+        }
+    }
+
+    let mut variables = ProblemVariables::new();
+    let columns: Vec<Variable> = columns.into_iter().map(|def| variables.add(def)).collect();
+    let expression = |row: &Row| {
+        let mut expression = Expression::default();
+        for &(column, value) in &row.coefficients {
+            expression.add_mul(value, columns[column]);
+        }
+        expression
+    };
+    let mut objective = Expression::default();
+    if let Some(row) = objective_row {
+        objective = expression(&rows[row]);
+        objective.constant = -rows[row].rhs;
+    }
+    let mut problem = variables.optimise(direction, objective);
+    for row in rows {
+        let (lower, upper) = match (row.kind, row.range) {
+            (RowKind::Objective, _) | (RowKind::Free, _) => continue,
+            (RowKind::LessOrEqual, None) => (None, Some(row.rhs)),
+            (RowKind::GreaterOrEqual, None) => (Some(row.rhs), None),
+            (RowKind::Equal, None) => (Some(row.rhs), Some(row.rhs)),
+            (RowKind::LessOrEqual, Some(r)) => (Some(row.rhs - r.abs()), Some(row.rhs)),
+            (RowKind::GreaterOrEqual, Some(r)) => (Some(row.rhs), Some(row.rhs + r.abs())),
+            (RowKind::Equal, Some(r)) if r < 0. => (Some(row.rhs + r), Some(row.rhs)),
+            (RowKind::Equal, Some(r)) => (Some(row.rhs), Some(row.rhs + r)),
+        };
+        let expression = expression(&row);
+        let constraints = match (lower, upper) {
+            (Some(l), Some(u)) if l == u => vec![constraint::eq(expression, l)],
+            (Some(l), Some(u)) => vec![
+                constraint::leq(expression.clone(), u),
+                constraint::geq(expression, l),
+            ],
+            (Some(l), None) => vec![constraint::geq(expression, l)],
+            (None, Some(u)) => vec![constraint::leq(expression, u)],
+            (None, None) => vec![],
+        };
+        for (i, c) in constraints.into_iter().enumerate() {
+            let name = if i == 0 {
+                row.name.clone()
+            } else {
+                format!("{}_range", row.name)
+            };
+            problem.add_constraint(c.set_name(name));
+        }
+    }
+    Ok(ParsedProblem::new(problem))
+}
+
+fn parse_sense(sense: &str, line: usize) -> Result<ObjectiveDirection, FormatError> {
+    match sense.to_ascii_uppercase().as_str() {
+        "MAX" | "MAXIMIZE" | "MAXIMISE" => Ok(ObjectiveDirection::Maximisation),
+        "MIN" | "MINIMIZE" | "MINIMISE" => Ok(ObjectiveDirection::Minimisation),
+        other => Err(FormatError::parse(
+            line,
+            format!("invalid objective sense {:?}", other),
+        )),
+    }
+}
+
+impl ProblemVariables {
+    /// Reads a problem from an MPS file. See [read] for details.
+    ///
+    /// The variables have the same names as the columns in the file,
+    /// and the constraints the same names as the rows.
+    ///
+    /// ```no_run
+    /// use good_lp::{constraint, default_solver, ProblemVariables, Solution, SolverModel};
+    /// let parsed = ProblemVariables::from_mps("afiro.mps")?;
+    /// let x01 = parsed.variable("X01");
+    /// let solution = parsed.problem.with(constraint!(x01 <= 50)).using(default_solver).solve()?;
+    /// println!("X01 = {}", solution.value(x01));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_mps<P: AsRef<Path>>(path: P) -> Result<ParsedProblem, FormatError> {
+        read(BufReader::new(File::open(path)?))
+    }
+}
+
+impl UnsolvedProblem {
+    /// Returns the problem, with its constraints, in the free MPS format.
+    /// See [write] for details.
+    pub fn to_mps(&self) -> String {
+        let mut buffer = Vec::new();
+        write(self, &mut buffer, MpsFormat::Free).expect("writing to memory cannot fail");
+        String::from_utf8(buffer).expect("names are valid utf-8")
+    }
+
+    /// Writes the problem, with its constraints, to a file in the free MPS format.
+    /// See [write] for details.
+    pub fn write_mps<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write(self, &mut writer, MpsFormat::Free)?;
+        writer.flush()
+    }
+
+    /// Writes the branching priorities of the integer variables to a file in the ORD format,
+    /// to be read along with the file written by [UnsolvedProblem::write_mps].
+    /// See [write_priorities] for details.
+    pub fn write_priorities<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_priorities(self, &mut writer)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read, write, write_priorities, MpsFormat};
+    use crate::{constraint, variable, variables, Solution, SolverModel};
+
+    #[test]
+    fn export_small_problem() {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("x").clamp(0, 10));
+        let y = vars.add(variable().name("y").integer().min(1));
+        let z = vars.add(variable().name("z"));
+        let problem = vars
+            .maximise(3 * x + y + 2)
+            .with(constraint!(x + 2 * y <= 14).set_name("cap".to_string()))
+            .with(constraint!(z == y - 1));
+        let mut out = Vec::new();
+        write(&problem, &mut out, MpsFormat::Fixed).unwrap();
+        let expected = "\
+NAME          good_lp
+OBJSENSE
+    MAX
+ROWS
+ N  obj
+ L  cap
+ E  c1
+COLUMNS
+    x         obj                  3
+    x         cap                  1
+    MARKER0   'MARKER'                 'INTORG'
+    y         obj                  1
+    y         cap                  2
+    y         c1                  -1
+    MARKER1   'MARKER'                 'INTEND'
+    z         c1                   1
+RHS
+    RHS       obj                 -2
+    RHS       cap                 14
+    RHS       c1                  -1
+BOUNDS
+ UP BND       x                   10
+ LO BND       y                    1
+ PL BND       y
+ FR BND       z
+ENDATA
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn export_branching_priorities() {
+        let mut vars = variables!();
+        let open = vars.add(variable().name("open").binary().branching_priority(10));
+        let count = vars.add(variable().integer().branching_priority(-2));
+        let plain = vars.add(variable().name("plain").integer());
+        // Priorities only matter for integer variables
+        let flow = vars.add(variable().name("flow").branching_priority(5));
+        let problem = vars.minimise(open + count + plain + flow);
+        let mut out = Vec::new();
+        write_priorities(&problem, &mut out).unwrap();
+        let expected = "\
+NAME
+    open                          10
+    v1                            -2
+ENDATA
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn fixed_format_rejects_long_names() {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("a_very_long_name"));
+        let problem = vars.minimise(x);
+        assert!(write(&problem, Vec::new(), MpsFormat::Fixed).is_err());
+        assert!(problem.to_mps().contains("a_very_long_name"));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("x").clamp(-1, 10));
+        let y = vars.add(variable().name("y").integer().min(1));
+        let z = vars.add(variable().name("z"));
+        let b = vars.add(variable().name("b").binary());
+        let w = vars.add(variable().name("w").max(-2));
+        let problem = vars
+            .maximise(3 * x - y + 2 + b)
+            .with(constraint!(x + 2 * y <= 14).set_name("cap".to_string()))
+            .with(constraint!(z == y - 1 + w).set_name("link".to_string()))
+            .with(constraint!(x + 1.5 * z >= -3).set_name("low".to_string()));
+        let read_back = read(problem.to_mps().as_bytes()).unwrap().problem;
+        assert_eq!(read_back.to_lp(), problem.to_lp());
+    }
+
+    #[test]
+    fn ranges_and_missing_vector_names() {
+        let mps = "\
+NAME test
+ROWS
+ N cost
+ G lim
+ E eq
+COLUMNS
+ x cost 1 lim 1
+ x eq 1
+ y cost 2 lim 1
+RHS
+ lim 2 eq 1
+RANGES
+ lim 3
+BOUNDS
+ UP x 4
+ENDATA
+";
+        let parsed = read(mps.as_bytes()).unwrap();
+        assert_eq!(parsed.problem.constraints.len(), 3);
+        assert_eq!(parsed.constraints["lim_range"].index, 1);
+        let (x, y) = (parsed.variable("x"), parsed.variable("y"));
+        let solution = parsed.problem.using(crate::default_solver).solve().unwrap();
+        float_eq::assert_float_eq!(solution.value(x), 1., abs <= 1e-6);
+        float_eq::assert_float_eq!(solution.value(y), 1., abs <= 1e-6);
+    }
+
+    #[test]
+    fn parse_errors_have_line_numbers() {
+        let err = match read("ROWS\n N obj\nCOLUMNS\n x unknown 1\n".as_bytes()) {
+            Err(err) => err,
+            Ok(_) => panic!("the row doesn't exist"),
+        };
+        assert!(err.to_string().contains("line 4"), "{}", err);
+    }
+}
@@ -0,0 +1,808 @@
+//! Flow problems on directed graphs.
+//!
+//! A [multi-commodity flow](https://en.wikipedia.org/wiki/Multi-commodity_flow_problem)
+//! problem routes several commodities, each from its source to its sink,
+//! through a network whose arcs have a capacity shared by all commodities,
+//! at the lowest possible cost.
+//!
+//! Two formulations are available:
+//!  - the arc formulation ([MultiCommodityFlow::problem]) has one variable
+//!    per commodity and arc, and one flow conservation constraint per commodity and node;
+//!  - the path formulation ([MultiCommodityFlow::path_problem]) has one variable per path.
+//!    There are exponentially many paths, so it starts with a few of them,
+//!    and new ones are generated by [column generation](https://en.wikipedia.org/wiki/Column_generation):
+//!    [MultiCommodityFlow::price] finds the paths that would improve the solution,
+//!    given the dual values of the current one.
+//!
+//! [MultiCommodityFlow::solve_with] solves the problem with either formulation,
+//! running the whole column generation loop for the path formulation,
+//! so that the two can be compared on the same network.
+//!
+//! ```
+//! use good_lp::graph::MultiCommodityFlow;
+//! use good_lp::default_solver;
+//!
+//! // Two routes from 0 to 2: a cheap one through 1, and a direct, expensive one
+//! let network = MultiCommodityFlow::new(3)
+//!     .arc(0, 1, 10., 1.) // from, to, capacity, cost per unit
+//!     .arc(1, 2, 10., 1.)
+//!     .arc(0, 2, 10., 5.)
+//!     .commodity(0, 2, 8.) // source, sink, demand
+//!     .commodity(1, 2, 4.);
+//! let flow = network.solve(default_solver)?;
+//! // The cheap route cannot carry all of the first commodity
+//! assert_eq!(flow.flow(0, 0), 6.);
+//! assert_eq!(flow.flow(0, 2), 2.);
+//! assert_eq!(flow.cost(), 6. * 2. + 2. * 5. + 4. * 1.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+//!
+//! For routing problems, [subtour_elimination] generates the
+//! [subtour elimination constraints](https://en.wikipedia.org/wiki/Travelling_salesman_problem#Dantzig%E2%80%93Fulkerson%E2%80%93Johnson_formulation)
+//! violated by a solution, to be used with [lazy constraints](crate::lazy).
+use crate::constraint::ConstraintReference;
+use crate::solvers::{DualValues, ResolutionError, SolutionWithDual};
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Constraint, Expression, ProblemVariables, Solution, Solver, SolverModel,
+    Variable,
+};
+
+/// An arc of a [MultiCommodityFlow] network
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Arc {
+    from: usize,
+    to: usize,
+    capacity: f64,
+    cost: f64,
+}
+
+/// A commodity that has to be routed through a [MultiCommodityFlow] network
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Commodity {
+    source: usize,
+    sink: usize,
+    demand: f64,
+}
+
+/// A path, given as the list of the indices of its arcs, in order
+pub type Path = Vec<usize>;
+
+/// A multi-commodity flow problem, built with [MultiCommodityFlow::new]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiCommodityFlow {
+    n_nodes: usize,
+    arcs: Vec<Arc>,
+    commodities: Vec<Commodity>,
+    unmet_demand_cost: Option<f64>,
+}
+
+impl MultiCommodityFlow {
+    /// Creates a network with the given number of nodes, numbered from 0,
+    /// and no arcs or commodities
+    pub fn new(n_nodes: usize) -> Self {
+        MultiCommodityFlow {
+            n_nodes,
+            arcs: vec![],
+            commodities: vec![],
+            unmet_demand_cost: None,
+        }
+    }
+
+    /// Adds an arc, that can carry at most `capacity` units of all commodities together,
+    /// each unit costing `cost`. Arcs are numbered from 0, in the order they are added.
+    pub fn arc(mut self, from: usize, to: usize, capacity: f64, cost: f64) -> Self {
+        assert!(
+            from < self.n_nodes && to < self.n_nodes,
+            "the arc ({}, {}) is not between nodes of the network",
+            from,
+            to
+        );
+        self.arcs.push(Arc {
+            from,
+            to,
+            capacity,
+            cost,
+        });
+        self
+    }
+
+    /// Adds a commodity, of which `demand` units have to go from `source` to `sink`.
+    /// Commodities are numbered from 0, in the order they are added.
+    pub fn commodity(mut self, source: usize, sink: usize, demand: f64) -> Self {
+        assert!(
+            source < self.n_nodes && sink < self.n_nodes,
+            "the commodity ({}, {}) is not between nodes of the network",
+            source,
+            sink
+        );
+        self.commodities.push(Commodity {
+            source,
+            sink,
+            demand,
+        });
+        self
+    }
+
+    /// Allows part of the demand not to be delivered, at the given cost per unit.
+    ///
+    /// The problem is then always feasible, which is required by column generation:
+    /// the first paths of the path formulation are usually not enough to deliver everything.
+    /// The cost should be higher than the cost of any path.
+    pub fn unmet_demand_cost(mut self, cost: f64) -> Self {
+        self.unmet_demand_cost = Some(cost);
+        self
+    }
+
+    /// The number of arcs
+    pub fn n_arcs(&self) -> usize {
+        self.arcs.len()
+    }
+
+    /// The number of commodities
+    pub fn n_commodities(&self) -> usize {
+        self.commodities.len()
+    }
+
+    /// Adds one unmet demand variable per commodity, if unmet demand is allowed
+    fn unmet_variables(&self, vars: &mut ProblemVariables) -> Vec<Option<Variable>> {
+        self.commodities
+            .iter()
+            .enumerate()
+            .map(|(k, commodity)| {
+                self.unmet_demand_cost.map(|_| {
+                    vars.add(
+                        variable()
+                            .clamp(0, commodity.demand)
+                            .name(format!("unmet_{}", k)),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// The cost of the unmet demand
+    fn unmet_cost(&self, unmet: &[Option<Variable>]) -> Expression {
+        let cost = self.unmet_demand_cost.unwrap_or(0.);
+        unmet.iter().flatten().map(|&u| cost * u).sum()
+    }
+
+    /// Builds the arc formulation of the problem, with one variable
+    /// per commodity and arc. Its variables and constraints can be inspected and extended
+    /// before it is solved.
+    pub fn problem(&self) -> FlowProblem {
+        let mut vars = ProblemVariables::new();
+        let flows: Vec<Vec<Variable>> = (0..self.commodities.len())
+            .map(|k| {
+                (0..self.arcs.len())
+                    .map(|a| vars.add(variable().min(0).name(format!("flow_{}_{}", k, a))))
+                    .collect()
+            })
+            .collect();
+        let unmet = self.unmet_variables(&mut vars);
+        let mut objective = self.unmet_cost(&unmet);
+        for commodity_flows in &flows {
+            for (arc, &flow) in self.arcs.iter().zip(commodity_flows) {
+                objective.add_mul(arc.cost, flow);
+            }
+        }
+        let mut problem = vars.minimise(objective);
+
+        for (a, arc) in self.arcs.iter().enumerate() {
+            let total: Expression = flows.iter().map(|f| f[a]).sum();
+            problem.add_constraint(
+                constraint!(total <= arc.capacity).set_name(format!("capacity_{}", a)),
+            );
+        }
+        for (k, commodity) in self.commodities.iter().enumerate() {
+            let mut delivered = Expression::from(commodity.demand);
+            if let Some(u) = unmet[k] {
+                delivered -= u;
+            }
+            for node in 0..self.n_nodes {
+                // Flow going out of the node minus flow coming in
+                let mut balance = Expression::default();
+                for (arc, &flow) in self.arcs.iter().zip(&flows[k]) {
+                    if arc.from == node {
+                        balance += flow;
+                    }
+                    if arc.to == node {
+                        balance -= flow;
+                    }
+                }
+                let net_supply = if commodity.source == commodity.sink {
+                    Expression::default()
+                } else if node == commodity.source {
+                    delivered.clone()
+                } else if node == commodity.sink {
+                    -delivered.clone()
+                } else {
+                    Expression::default()
+                };
+                problem.add_constraint(
+                    constraint!(balance == net_supply)
+                        .set_name(format!("conservation_{}_{}", k, node)),
+                );
+            }
+        }
+        FlowProblem {
+            problem,
+            flows,
+            unmet,
+            costs: self.arcs.iter().map(|a| a.cost).collect(),
+        }
+    }
+
+    /// Finds the cheapest routing of all commodities, using the arc formulation
+    pub fn solve<S: Solver>(
+        &self,
+        solver: S,
+    ) -> Result<FlowSolution, <S::Model as SolverModel>::Error> {
+        self.problem().solve(solver)
+    }
+
+    /// Checks that the path is made of consecutive arcs going from the source to the sink
+    /// of the commodity
+    fn is_path_of(&self, commodity: usize, path: &[usize]) -> bool {
+        let Commodity { source, sink, .. } = self.commodities[commodity];
+        let mut node = source;
+        for &a in path {
+            match self.arcs.get(a) {
+                Some(arc) if arc.from == node => node = arc.to,
+                _ => return false,
+            }
+        }
+        node == sink
+    }
+
+    /// The cost of sending one unit along the path
+    pub fn path_cost(&self, path: &[usize]) -> f64 {
+        path.iter().map(|&a| self.arcs[a].cost).sum()
+    }
+
+    /// Finds the cheapest path from the source to the sink of the commodity,
+    /// when using an arc costs its cost plus its price in `arc_prices`.
+    /// Returns the path and its cost with the prices, or `None` if the sink cannot be reached.
+    ///
+    /// Costs and prices are expected to be non-negative,
+    /// so that the cheapest path has no cycle.
+    pub fn shortest_path(&self, commodity: usize, arc_prices: &[f64]) -> Option<(Path, f64)> {
+        let Commodity { source, sink, .. } = self.commodities[commodity];
+        let weight = |a: usize| self.arcs[a].cost + arc_prices.get(a).copied().unwrap_or(0.);
+        // Bellman-Ford: the distance to each node, and the arc through which it is reached
+        let mut distance = vec![f64::INFINITY; self.n_nodes];
+        let mut previous: Vec<Option<usize>> = vec![None; self.n_nodes];
+        distance[source] = 0.;
+        for _ in 1..self.n_nodes.max(2) {
+            let mut changed = false;
+            for (a, arc) in self.arcs.iter().enumerate() {
+                let through = distance[arc.from] + weight(a);
+                if through < distance[arc.to] && arc.to != source {
+                    distance[arc.to] = through;
+                    previous[arc.to] = Some(a);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        if !distance[sink].is_finite() {
+            return None;
+        }
+        let mut path = vec![];
+        let mut node = sink;
+        while let Some(a) = previous[node] {
+            path.push(a);
+            node = self.arcs[a].from;
+        }
+        path.reverse();
+        Some((path, distance[sink]))
+    }
+
+    /// The cheapest path of each commodity, ignoring capacities.
+    /// These are good initial paths for the path formulation.
+    /// Commodities whose sink cannot be reached have no path.
+    pub fn initial_paths(&self) -> Vec<Vec<Path>> {
+        (0..self.commodities.len())
+            .map(|k| {
+                self.shortest_path(k, &[])
+                    .into_iter()
+                    .map(|(p, _)| p)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The pricing step of column generation: finds, for each commodity,
+    /// the path with the most negative reduced cost, if it is negative.
+    ///
+    /// `arc_prices` are the prices of the capacity of the arcs,
+    /// and `commodity_prices` the prices of delivering one more unit of the commodities,
+    /// in the current solution of the path formulation.
+    /// They are the absolute values of the dual values of the capacity and demand constraints,
+    /// see [PathFlowProblem::solve_with_prices].
+    ///
+    /// The reduced cost of a path is its cost, plus the prices of its arcs,
+    /// minus the price of its commodity. Adding a path with a negative reduced cost
+    /// to the path formulation can lower its optimal cost.
+    /// When there is none, the current solution is optimal for the full problem.
+    pub fn price(
+        &self,
+        arc_prices: &[f64],
+        commodity_prices: &[f64],
+        tolerance: f64,
+    ) -> Vec<(usize, Path)> {
+        (0..self.commodities.len())
+            .filter_map(|k| {
+                let (path, cost) = self.shortest_path(k, arc_prices)?;
+                let reduced_cost = cost - commodity_prices[k];
+                if reduced_cost < -tolerance {
+                    Some((k, path))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Builds the path formulation of the problem, with one variable per given path.
+    /// `paths[k]` are the paths of commodity `k`, that can be created with
+    /// [MultiCommodityFlow::initial_paths], and extended with [MultiCommodityFlow::price].
+    ///
+    /// Panics if a path does not go from the source to the sink of its commodity.
+    pub fn path_problem(&self, paths: &[Vec<Path>]) -> PathFlowProblem {
+        assert_eq!(
+            paths.len(),
+            self.commodities.len(),
+            "there must be one list of paths per commodity"
+        );
+        let mut vars = ProblemVariables::new();
+        let path_variables: Vec<Vec<(Path, Variable)>> = paths
+            .iter()
+            .enumerate()
+            .map(|(k, commodity_paths)| {
+                commodity_paths
+                    .iter()
+                    .enumerate()
+                    .map(|(i, path)| {
+                        assert!(
+                            self.is_path_of(k, path),
+                            "{:?} is not a path of commodity {}",
+                            path,
+                            k
+                        );
+                        let var = vars.add(variable().min(0).name(format!("path_{}_{}", k, i)));
+                        (path.clone(), var)
+                    })
+                    .collect()
+            })
+            .collect();
+        let unmet = self.unmet_variables(&mut vars);
+        let mut objective = self.unmet_cost(&unmet);
+        for (path, var) in path_variables.iter().flatten() {
+            objective.add_mul(self.path_cost(path), *var);
+        }
+        let mut problem = vars.minimise(objective);
+
+        let mut on_arc = vec![Expression::default(); self.arcs.len()];
+        for (path, var) in path_variables.iter().flatten() {
+            for &a in path {
+                on_arc[a] += *var;
+            }
+        }
+        for (a, (arc, total)) in self.arcs.iter().zip(on_arc).enumerate() {
+            problem.add_constraint(
+                constraint!(total <= arc.capacity).set_name(format!("capacity_{}", a)),
+            );
+        }
+        for (k, commodity) in self.commodities.iter().enumerate() {
+            let mut delivered: Expression = path_variables[k].iter().map(|(_, v)| *v).sum();
+            if let Some(u) = unmet[k] {
+                delivered += u;
+            }
+            problem.add_constraint(
+                constraint!(delivered == commodity.demand).set_name(format!("demand_{}", k)),
+            );
+        }
+        PathFlowProblem {
+            problem,
+            layout: PathLayout {
+                paths: path_variables,
+                unmet,
+                costs: self.arcs.iter().map(|a| a.cost).collect(),
+            },
+        }
+    }
+
+    /// Solves the problem with the given formulation.
+    /// Both formulations give the same optimal cost, but not the same resolution time,
+    /// which makes it easy to benchmark them on a given network.
+    ///
+    /// The path formulation uses [column generation](MultiCommodityFlow::column_generation),
+    /// which requires a solver that returns dual values. To solve the arc formulation
+    /// with any solver, use [MultiCommodityFlow::solve].
+    pub fn solve_with<S>(
+        &self,
+        formulation: Formulation,
+        solver: S,
+    ) -> Result<FlowSolution, ResolutionError>
+    where
+        S: Solver + Clone,
+        S::Model: SolverModel<Error = ResolutionError>,
+        for<'a> <S::Model as SolverModel>::Solution: SolutionWithDual<'a>,
+    {
+        match formulation {
+            Formulation::Arc => self.solve(solver),
+            Formulation::Path => Ok(self.column_generation(solver)?.solution),
+        }
+    }
+
+    /// Solves the path formulation by column generation.
+    ///
+    /// It starts with the [cheapest path](MultiCommodityFlow::initial_paths) of each commodity,
+    /// and alternates between solving the path formulation with the current paths,
+    /// and [adding the paths](MultiCommodityFlow::price) that can improve its solution,
+    /// until there are none.
+    ///
+    /// If [unmet demand](MultiCommodityFlow::unmet_demand_cost) is not allowed,
+    /// it is still used while the paths are not enough to deliver everything,
+    /// with a cost higher than the one of any path, and
+    /// [ResolutionError::Infeasible] is returned if some of the demand remains unmet in the end.
+    pub fn column_generation<S>(&self, solver: S) -> Result<ColumnGeneration, ResolutionError>
+    where
+        S: Solver + Clone,
+        S::Model: SolverModel<Error = ResolutionError>,
+        for<'a> <S::Model as SolverModel>::Solution: SolutionWithDual<'a>,
+    {
+        let mut master = self.clone();
+        if master.unmet_demand_cost.is_none() {
+            // No path without cycles is more expensive than all the arcs together
+            let highest_path_cost: f64 = self.arcs.iter().map(|a| a.cost.abs()).sum();
+            master.unmet_demand_cost = Some(1. + 2. * highest_path_cost);
+        }
+        let mut paths = self.initial_paths();
+        let mut iterations = 0;
+        loop {
+            iterations += 1;
+            let (solution, prices) = master
+                .path_problem(&paths)
+                .solve_with_prices(solver.clone())?;
+            let mut added = false;
+            for (k, path) in master.price(&prices.arcs, &prices.commodities, 1e-9) {
+                if !paths[k].contains(&path) {
+                    paths[k].push(path);
+                    added = true;
+                }
+            }
+            if !added {
+                let unmet: f64 = (0..self.commodities.len()).map(|k| solution.unmet(k)).sum();
+                if self.unmet_demand_cost.is_none() && unmet > 1e-6 {
+                    return Err(ResolutionError::Infeasible);
+                }
+                return Ok(ColumnGeneration {
+                    solution,
+                    paths,
+                    iterations,
+                });
+            }
+        }
+    }
+}
+
+/// A formulation of a [MultiCommodityFlow] problem, see [MultiCommodityFlow::solve_with]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Formulation {
+    /// One variable per commodity and arc
+    Arc,
+    /// One variable per path, generated by column generation
+    Path,
+}
+
+/// The result of [MultiCommodityFlow::column_generation]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnGeneration {
+    /// The optimal flow
+    pub solution: FlowSolution,
+    /// The paths of each commodity that were generated
+    pub paths: Vec<Vec<Path>>,
+    /// The number of times the path formulation was solved
+    pub iterations: usize,
+}
+
+/// The arc formulation of a [MultiCommodityFlow] problem
+pub struct FlowProblem {
+    problem: UnsolvedProblem,
+    /// `flows[commodity][arc]`
+    flows: Vec<Vec<Variable>>,
+    unmet: Vec<Option<Variable>>,
+    costs: Vec<f64>,
+}
+
+impl FlowProblem {
+    /// The variable with the flow of the commodity on the arc
+    pub fn flow_variable(&self, commodity: usize, arc: usize) -> Variable {
+        self.flows[commodity][arc]
+    }
+
+    /// The variable with the demand of the commodity that is not delivered,
+    /// if [unmet demand is allowed](MultiCommodityFlow::unmet_demand_cost)
+    pub fn unmet_variable(&self, commodity: usize) -> Option<Variable> {
+        self.unmet[commodity]
+    }
+
+    /// The underlying problem, to which additional constraints can be added
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Finds the cheapest flow
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<FlowSolution, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        let flows = self
+            .flows
+            .iter()
+            .map(|f| f.iter().map(|&v| solution.value(v)).collect())
+            .collect();
+        Ok(FlowSolution {
+            flows,
+            unmet: unmet_values(&self.unmet, &solution),
+            costs: self.costs,
+        })
+    }
+}
+
+fn unmet_values<S: Solution>(unmet: &[Option<Variable>], solution: &S) -> Vec<f64> {
+    unmet
+        .iter()
+        .map(|u| u.map_or(0., |u| solution.value(u)))
+        .collect()
+}
+
+/// The path formulation of a [MultiCommodityFlow] problem,
+/// built with [MultiCommodityFlow::path_problem]
+pub struct PathFlowProblem {
+    problem: UnsolvedProblem,
+    layout: PathLayout,
+}
+
+/// The variables of a [PathFlowProblem]
+struct PathLayout {
+    /// The paths of each commodity, with their variable
+    paths: Vec<Vec<(Path, Variable)>>,
+    unmet: Vec<Option<Variable>>,
+    /// The cost of each arc
+    costs: Vec<f64>,
+}
+
+impl PathLayout {
+    fn flow_solution<S: Solution>(&self, solution: &S) -> FlowSolution {
+        let mut flows = vec![vec![0.; self.costs.len()]; self.paths.len()];
+        for (commodity_flows, paths) in flows.iter_mut().zip(&self.paths) {
+            for (path, var) in paths {
+                let value = solution.value(*var);
+                for &a in path {
+                    commodity_flows[a] += value;
+                }
+            }
+        }
+        FlowSolution {
+            flows,
+            unmet: unmet_values(&self.unmet, solution),
+            costs: self.costs.clone(),
+        }
+    }
+}
+
+/// The prices of the capacity of the arcs and of the demand of the commodities,
+/// used to [price](MultiCommodityFlow::price) new paths
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowPrices {
+    /// The price of each arc
+    pub arcs: Vec<f64>,
+    /// The price of each commodity
+    pub commodities: Vec<f64>,
+}
+
+impl PathFlowProblem {
+    /// The variable with the flow along the given path of the commodity,
+    /// numbered in the order they were given to [MultiCommodityFlow::path_problem]
+    pub fn path_variable(&self, commodity: usize, path: usize) -> Variable {
+        self.layout.paths[commodity][path].1
+    }
+
+    /// The variable with the demand of the commodity that is not delivered,
+    /// if [unmet demand is allowed](MultiCommodityFlow::unmet_demand_cost)
+    pub fn unmet_variable(&self, commodity: usize) -> Option<Variable> {
+        self.layout.unmet[commodity]
+    }
+
+    /// The capacity constraint of the arc, in the model created from this problem.
+    /// Its dual value is the opposite of the price of the arc.
+    pub fn capacity_constraint(&self, arc: usize) -> ConstraintReference {
+        ConstraintReference { index: arc }
+    }
+
+    /// The demand constraint of the commodity, in the model created from this problem
+    pub fn demand_constraint(&self, commodity: usize) -> ConstraintReference {
+        ConstraintReference {
+            index: self.layout.costs.len() + commodity,
+        }
+    }
+
+    /// The underlying problem, to which additional constraints can be added
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Finds the cheapest flow using only the paths of this problem
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<FlowSolution, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        Ok(self.layout.flow_solution(&solution))
+    }
+
+    /// Finds the cheapest flow using only the paths of this problem,
+    /// and the prices to use to [generate new paths](MultiCommodityFlow::price).
+    /// This requires a solver that returns dual values.
+    pub fn solve_with_prices<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<(FlowSolution, FlowPrices), <S::Model as SolverModel>::Error>
+    where
+        for<'a> <S::Model as SolverModel>::Solution: SolutionWithDual<'a>,
+    {
+        let n_arcs = self.layout.costs.len();
+        let n_commodities = self.layout.paths.len();
+        let mut solution = self.problem.using(solver).solve()?;
+        let flow = self.layout.flow_solution(&solution);
+        let dual = solution.compute_dual();
+        let prices = FlowPrices {
+            arcs: (0..n_arcs)
+                .map(|a| dual.dual(ConstraintReference { index: a }).abs())
+                .collect(),
+            commodities: (0..n_commodities)
+                .map(|k| dual.dual(ConstraintReference { index: n_arcs + k }).abs())
+                .collect(),
+        };
+        Ok((flow, prices))
+    }
+}
+
+/// The flow of every commodity on every arc
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowSolution {
+    /// `flows[commodity][arc]`
+    flows: Vec<Vec<f64>>,
+    unmet: Vec<f64>,
+    costs: Vec<f64>,
+}
+
+impl FlowSolution {
+    /// The flow of the commodity on the arc
+    pub fn flow(&self, commodity: usize, arc: usize) -> f64 {
+        self.flows[commodity][arc]
+    }
+
+    /// The flow of all commodities on the arc
+    pub fn arc_flow(&self, arc: usize) -> f64 {
+        self.flows.iter().map(|f| f[arc]).sum()
+    }
+
+    /// The demand of the commodity that is not delivered
+    pub fn unmet(&self, commodity: usize) -> f64 {
+        self.unmet[commodity]
+    }
+
+    /// The cost of the flow on all arcs, not including the cost of unmet demand
+    pub fn cost(&self) -> f64 {
+        self.flows
+            .iter()
+            .flat_map(|f| f.iter().zip(&self.costs).map(|(flow, cost)| flow * cost))
+            .sum()
+    }
+}
+
+/// The subtour elimination constraints violated by a solution of a routing problem
+/// on `nodes` nodes, whose undirected edges are given with their variable:
+/// `(from, to, variable)`, where the variable is 1 when the edge is part of the tour.
+///
+/// The nodes connected by the edges whose value is above 0.5 are grouped into components.
+/// When there are several components, each of them is a subtour,
+/// and the constraint for a component `S` requires the tour to use at most `|S| - 1`
+/// edges inside it. No constraint is returned when the edges form a single tour.
+/// This gives the Dantzig-Fulkerson-Johnson formulation of the
+/// [travelling salesman problem](https://en.wikipedia.org/wiki/Travelling_salesman_problem),
+/// with its exponentially many constraints generated only when they are needed.
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::graph::subtour_elimination;
+/// use good_lp::lazy::solve_with_separation;
+/// use good_lp::{constraint, default_solver, variable, variables, Expression, Solution};
+///
+/// // Two groups of three close cities, 10 units apart
+/// let position = |city: usize| (city / 3) as f64 * 10. + (city % 3) as f64;
+/// let mut vars = variables!();
+/// let mut edges = vec![];
+/// for from in 0..6 {
+///     for to in from + 1..6 {
+///         edges.push((from, to, vars.add(variable().binary())));
+///     }
+/// }
+/// let length: Expression = edges
+///     .iter()
+///     .map(|&(from, to, edge)| (position(from) - position(to)).abs() * edge)
+///     .sum();
+/// let mut problem = vars.minimise(length.clone());
+/// for city in 0..6 {
+///     let degree: Expression = edges
+///         .iter()
+///         .filter(|&&(from, to, _)| from == city || to == city)
+///         .map(|&(_, _, edge)| edge)
+///         .sum();
+///     problem.add_constraint(constraint!(degree == 2));
+/// }
+/// let result = solve_with_separation(problem, default_solver, |solution| {
+///     subtour_elimination(6, &edges, solution)
+/// })?;
+/// // Without the cuts, the solution is made of two triangles
+/// assert!(result.rounds > 1);
+/// assert!(subtour_elimination(6, &edges, &result.solution).is_empty());
+/// // Cities on a line: the shortest tour goes from one end to the other, and back
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(result.solution.eval(&length), 2. * 12.);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn subtour_elimination<S: Solution + ?Sized>(
+    nodes: usize,
+    edges: &[(usize, usize, Variable)],
+    solution: &S,
+) -> Vec<Constraint> {
+    // Union-find of the nodes connected by the selected edges
+    let mut parent: Vec<usize> = (0..nodes).collect();
+    for &(from, to, edge) in edges {
+        if solution.value(edge) > 0.5 {
+            let (a, b) = (root(&mut parent, from), root(&mut parent, to));
+            parent[a] = b;
+        }
+    }
+    let components: Vec<usize> = (0..nodes).map(|node| root(&mut parent, node)).collect();
+    let mut sizes = vec![0; nodes];
+    for &component in &components {
+        sizes[component] += 1;
+    }
+    if sizes.iter().filter(|&&size| size > 0).count() <= 1 {
+        return vec![];
+    }
+    (0..nodes)
+        .filter(|&component| sizes[component] > 0)
+        .map(|component| {
+            let inside: Expression = edges
+                .iter()
+                .filter(|&&(from, to, _)| {
+                    components[from] == component && components[to] == component
+                })
+                .map(|&(_, _, edge)| edge)
+                .sum();
+            let size = sizes[component] as f64;
+            inside.leq(size - 1.)
+        })
+        .collect()
+}
+
+/// The representative of the component of the node, in a union-find structure
+fn root(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
@@ -0,0 +1,147 @@
+//! Lazy constraints: constraints that are only added to a problem when a solution violates them.
+//!
+//! Some families of constraints are too large to be written in full,
+//! such as the subtour elimination constraints of routing problems,
+//! or the optimality cuts of Benders decomposition.
+//! Only a few of them are violated by the optimal solution of the problem without them.
+//!
+//! [LazyConstraints] solves the problem, gives the solution to a separation function
+//! that returns the constraints it violates, adds them to the problem, and solves it again,
+//! until the solution does not violate any constraint.
+//! The final solution is then optimal for the problem with all the constraints.
+//!
+//! This works with all solvers, since it only needs to solve problems.
+//! Solvers that accept lazy constraints in their branch-and-bound
+//! avoid re-solving the problem from scratch, but none of the bindings used by good_lp
+//! expose them.
+//!
+//! ```
+//! use good_lp::lazy::LazyConstraints;
+//! use good_lp::{constraint, default_solver, variables, Solution};
+//!
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+//! let problem = vars.maximise(x + y);
+//! // x + y <= 12 and x - y <= 2, but we only add them when they are violated
+//! let result = LazyConstraints::new(|solution: &dyn Solution| {
+//!     let mut violated = vec![];
+//!     if solution.eval_dyn(x + y) > 12. + 1e-6 {
+//!         violated.push(constraint!(x + y <= 12));
+//!     }
+//!     if solution.eval_dyn(x - y) > 2. + 1e-6 {
+//!         violated.push(constraint!(x - y <= 2));
+//!     }
+//!     violated
+//! })
+//! .solve(problem, default_solver)?;
+//! assert!(result.converged);
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(result.solution.eval(x + y), 12.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::variable::UnsolvedProblem;
+use crate::{Constraint, Solution, Solver, SolverModel};
+
+/// Solves problems with lazy constraints, see the [module documentation](crate::lazy)
+pub struct LazyConstraints<F> {
+    separate: F,
+    max_rounds: Option<usize>,
+}
+
+/// The result of [LazyConstraints::solve]
+pub struct LazySolution<S> {
+    /// The last solution found
+    pub solution: S,
+    /// The lazy constraints that were added, in the order they were returned
+    pub added: Vec<Constraint>,
+    /// The number of times the problem was solved
+    pub rounds: usize,
+    /// Whether the last solution satisfies all the lazy constraints.
+    /// This is false when the maximum number of rounds was reached first.
+    pub converged: bool,
+}
+
+impl<F> LazyConstraints<F>
+where
+    F: FnMut(&dyn Solution) -> Vec<Constraint>,
+{
+    /// Creates a lazy constraint loop with the given separation function,
+    /// which returns the constraints violated by a solution, or nothing if there are none
+    pub fn new(separate: F) -> Self {
+        LazyConstraints {
+            separate,
+            max_rounds: None,
+        }
+    }
+
+    /// Stops after solving the problem this many times, even if constraints are still violated
+    pub fn max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = Some(max_rounds);
+        self
+    }
+
+    /// Solves the problem, adding the lazy constraints until none is violated
+    pub fn solve<S>(
+        &mut self,
+        mut problem: UnsolvedProblem,
+        solver: S,
+    ) -> Result<LazySolution<<S::Model as SolverModel>::Solution>, <S::Model as SolverModel>::Error>
+    where
+        S: Solver + Clone,
+    {
+        let mut added = vec![];
+        let mut rounds = 0;
+        loop {
+            rounds += 1;
+            let solution = problem.clone().using(solver.clone()).solve()?;
+            let violated = (self.separate)(&solution);
+            let converged = violated.is_empty();
+            if converged || matches!(self.max_rounds, Some(max) if rounds >= max) {
+                return Ok(LazySolution {
+                    solution,
+                    added,
+                    rounds,
+                    converged,
+                });
+            }
+            for constraint in violated {
+                problem.add_constraint(constraint.clone());
+                added.push(constraint);
+            }
+        }
+    }
+}
+
+/// Solves the problem, calls `separate` with the solution, adds the constraints it returns,
+/// and solves again, until it returns no constraint.
+/// This is a shorthand for [LazyConstraints] without a limit on the number of rounds.
+///
+/// The bindings of good_lp consume a model when solving it,
+/// so the problem is solved from scratch in every round.
+///
+/// ```
+/// use good_lp::lazy::solve_with_separation;
+/// use good_lp::{constraint, default_solver, variables, Solution};
+///
+/// variables! {vars: 0 <= x <= 10;}
+/// let result = solve_with_separation(vars.maximise(x), default_solver, |solution| {
+///     if solution.value(x) > 4. + 1e-6 {
+///         vec![constraint!(x <= 4)]
+///     } else {
+///         vec![]
+///     }
+/// })?;
+/// assert_eq!(result.rounds, 2);
+/// assert_eq!(result.solution.value(x), 4.);
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn solve_with_separation<S, F>(
+    problem: UnsolvedProblem,
+    solver: S,
+    separate: F,
+) -> Result<LazySolution<<S::Model as SolverModel>::Solution>, <S::Model as SolverModel>::Error>
+where
+    S: Solver + Clone,
+    F: FnMut(&dyn Solution) -> Vec<Constraint>,
+{
+    LazyConstraints::new(separate).solve(problem, solver)
+}
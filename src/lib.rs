@@ -66,7 +66,19 @@
 
 pub use affine_expression_trait::IntoAffineExpression;
 pub use constraint::Constraint;
-pub use expression::Expression;
+pub use containers::{LpVariables, VariableMap, VariableMatrix, VariableVec};
+pub use expression::{sum, Expression};
+/// Derives [LpVariables](trait@LpVariables) for a struct of `f64`, `Vec<f64>` and `HashMap<K, f64>`
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use good_lp_derive::LpVariables;
+#[cfg(any(
+    feature = "coin_cbc",
+    feature = "minilp",
+    feature = "lpsolve",
+    feature = "highs"
+))]
+pub use solvers::auto::auto_solver;
 #[cfg_attr(docsrs, doc(cfg(feature = "minilp")))]
 #[cfg(feature = "coin_cbc")]
 pub use solvers::coin_cbc::coin_cbc;
@@ -98,8 +110,11 @@ pub use solvers::minilp::minilp;
 /// When the "coin_cbc" cargo feature is absent, minilp is used as the default solver
 pub use solvers::minilp::minilp as default_solver;
 pub use solvers::{
-    DualValues, ModelWithSOS1, ResolutionError, Solution, SolutionWithDual, Solver, SolverModel,
-    StaticSolver,
+    DualValues, LpMethod, ModelWithBackend, ModelWithBulkAttributes, ModelWithColumns,
+    ModelWithCrossover, ModelWithLpMethod, ModelWithMipGap, ModelWithPresolve,
+    ModelWithRawParameters, ModelWithSOS1, ModelWithScaling, ModelWithSeed, ModelWithThreads,
+    ModelWithTimeLimit, ModelWithVerbosity, ResolutionError, ScalingMode, Solution, SolutionStatus,
+    SolutionWithDual, SolveStats, Solver, SolverModel, StaticSolver, Verbosity,
 };
 pub use variable::{variable, ProblemVariables, Variable, VariableDefinition};
 
@@ -132,10 +147,53 @@ good_lp = { version = \"*\", features = [\"minilp\"] }
 "
 );
 
+mod containers;
 mod expression;
 #[macro_use]
 pub mod variable;
 mod affine_expression_trait;
+pub mod analysis;
+pub mod anytime;
+pub mod batch;
+pub mod bio;
+pub mod budget;
+pub mod cancellation;
+pub mod checkpoint;
+pub mod classification;
+pub mod coloring;
 pub mod constraint;
+pub mod cuts;
+pub mod dea;
+pub mod decomposition;
+pub mod elastic;
+#[cfg(feature = "exact")]
+#[cfg_attr(docsrs, doc(cfg(feature = "exact")))]
+pub mod exact;
+pub mod expansion;
+pub mod feasibility;
+pub mod formats;
+pub mod graph;
+pub mod lazy;
+pub mod linearize;
+pub mod lot_size;
+pub mod mdp;
+pub mod modeling;
+pub mod network;
+pub mod pool;
+pub mod postprocess;
+pub mod power;
+pub mod prelude;
+pub mod probing;
+pub mod provenance;
+pub mod repair;
+pub mod report;
+pub mod rostering;
+pub mod scalar;
+pub mod scheduling;
+pub mod sharding;
+pub mod snapshot;
 pub mod solvers;
+pub mod stochastic;
+pub mod template;
+pub mod tradeoff;
 mod variables_macro;
@@ -0,0 +1,131 @@
+//! Linear reformulations of products of variables.
+//!
+//! A product of two variables, `x * y`, cannot appear in a linear program.
+//! [product] replaces it with a new variable `z`, and constraints that tie `z` to `x` and `y`:
+//! the [McCormick envelope](https://en.wikipedia.org/wiki/McCormick_envelopes) of the product,
+//! computed from the bounds of the two variables.
+//!
+//! When one of the two variables is binary, the envelope is exact: `z` is equal to `x * y`
+//! in every solution. When both are continuous, the envelope is a relaxation:
+//! `z` can be anywhere between the under- and over-estimators of the product,
+//! and the optimal objective is a bound on the one of the original, nonlinear, problem.
+//! The tighter the bounds of the variables, the tighter the envelope.
+//! For two binary variables, [and_var] writes the same product with three constraints.
+//!
+//! ```
+//! # // minilp and highs do not support integer variables
+//! # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+//! use good_lp::linearize::product;
+//! use good_lp::{default_solver, variable, variables, Solution, SolverModel};
+//!
+//! let mut vars = variables!();
+//! let open = vars.add(variable().binary());
+//! let production = vars.add(variable().clamp(0, 80));
+//! // The production is only sold when the shop is open
+//! let (sold, envelope) = product(&mut vars, open, production);
+//! let mut problem = vars.maximise(2 * sold - 100 * open);
+//! for constraint in envelope {
+//!     problem.add_constraint(constraint);
+//! }
+//! let solution = problem.using(default_solver).solve()?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(solution.value(open), 1.);
+//! assert_float_eq(solution.value(sold), 80.);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::{constraint, variable, Constraint, ProblemVariables, Variable};
+
+/// Adds to `vars` a variable standing for `x * y`,
+/// and returns it with the four constraints of the McCormick envelope of the product.
+/// See the [module documentation](self).
+///
+/// Panics if one of the bounds of `x` or `y` is infinite:
+/// the envelope of a product of unbounded variables does not constrain it.
+pub fn product(
+    vars: &mut ProblemVariables,
+    x: Variable,
+    y: Variable,
+) -> (Variable, Vec<Constraint>) {
+    let (x_min, x_max) = finite_bounds(vars, x);
+    let (y_min, y_max) = finite_bounds(vars, y);
+    let corners = [x_min * y_min, x_min * y_max, x_max * y_min, x_max * y_max];
+    let lowest = corners.iter().copied().fold(f64::INFINITY, f64::min);
+    let highest = corners.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let z = vars.add(variable().clamp(lowest, highest));
+    let constraints = vec![
+        // (x - x_min) * (y - y_min) >= 0
+        constraint!(z >= x_min * y + y_min * x - x_min * y_min),
+        // (x_max - x) * (y_max - y) >= 0
+        constraint!(z >= x_max * y + y_max * x - x_max * y_max),
+        // (x_max - x) * (y - y_min) >= 0
+        constraint!(z <= x_max * y + y_min * x - x_max * y_min),
+        // (x - x_min) * (y_max - y) >= 0
+        constraint!(z <= x_min * y + y_max * x - x_min * y_max),
+    ];
+    (z, constraints)
+}
+
+/// Adds to `vars` a variable standing for `a * b`, the logical and of two binary variables,
+/// and returns it with the three constraints that make it equal to the product:
+/// `z <= a`, `z <= b` and `z >= a + b - 1`.
+///
+/// The new variable is continuous between 0 and 1: the constraints make it an integer
+/// whenever `a` and `b` are, so it does not add work to the branch and bound.
+/// This is how the products of quadratic assignment models are written in linear form.
+///
+/// Panics if `a` or `b` is not a binary variable.
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::linearize::and_var;
+/// use good_lp::{default_solver, variable, variables, Solution, SolverModel};
+///
+/// let mut vars = variables!();
+/// let a = vars.add(variable().binary());
+/// let b = vars.add(variable().binary());
+/// let (both, constraints) = and_var(&mut vars, a, b);
+/// // Each variable is worth 1, but taking both costs 3
+/// let mut problem = vars.maximise(a + b - 3 * both);
+/// for constraint in constraints {
+///     problem.add_constraint(constraint);
+/// }
+/// let solution = problem.using(default_solver).solve()?;
+/// # fn assert_float_eq(x:f64, y:f64) { assert!((x-y).abs() <= 1e-6, "{} != {}", x, y); }
+/// assert_float_eq(solution.value(a) + solution.value(b), 1.);
+/// assert_float_eq(solution.value(both), 0.);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn and_var(
+    vars: &mut ProblemVariables,
+    a: Variable,
+    b: Variable,
+) -> (Variable, Vec<Constraint>) {
+    for &factor in &[a, b] {
+        let definition = vars.definition(factor);
+        assert!(
+            definition.is_integer && definition.min >= 0. && definition.max <= 1.,
+            "both factors of a logical and must be binary variables"
+        );
+    }
+    let z = vars.add(variable().clamp(0, 1));
+    let constraints = vec![
+        constraint!(z <= a),
+        constraint!(z <= b),
+        constraint!(z >= a + b - 1),
+    ];
+    (z, constraints)
+}
+
+fn finite_bounds(vars: &ProblemVariables, variable: Variable) -> (f64, f64) {
+    let definition = vars.definition(variable);
+    assert!(
+        definition.min.is_finite() && definition.max.is_finite(),
+        "both factors of a product must have finite bounds, got [{}, {}]",
+        definition.min,
+        definition.max
+    );
+    (definition.min, definition.max)
+}
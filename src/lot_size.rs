@@ -0,0 +1,87 @@
+//! Rounding of production quantities to whole lots, without losing feasibility.
+//!
+//! Production plans are often computed with continuous quantities,
+//! but goods are made in batches of a fixed size.
+//! Rounding every quantity to the nearest multiple of its lot size,
+//! with [multiple_of](crate::postprocess::multiple_of), can violate
+//! the constraints of the plan, such as a capacity or a demand.
+//!
+//! [round_and_restore] rounds the quantities, then solves a small integer problem
+//! to find the feasible plan made of whole lots that is the closest to the rounded one.
+//! The other variables of the problem are kept as close as possible to their value
+//! in the original solution. See [repair](crate::repair) for how the distance is measured.
+//!
+//! ```
+//! # // minilp and highs do not support integer variables
+//! # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+//! use good_lp::lot_size::round_and_restore;
+//! use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+//! use std::collections::HashMap;
+//!
+//! variables! {vars: 0 <= a <= 100; 0 <= b <= 100;}
+//! // The demand of 130 units must be met, with a cheaper than b
+//! let problem = vars
+//!     .minimise(a + 2 * b)
+//!     .with(constraint!(a + b >= 130))
+//!     .with(constraint!(a <= 90));
+//! let solution = problem.clone().using(default_solver).solve()?;
+//! // a is made in lots of 20 and b in lots of 25.
+//! // The optimum is a = 90, b = 40, and rounding gives a = 100, which is above 90
+//! let lot_sizes: HashMap<_, _> = vec![(a, 20.), (b, 25.)].into_iter().collect();
+//! let plan = round_and_restore(&solution, problem, &lot_sizes, default_solver)?;
+//! # fn assert_float_eq(x:f64, y:f64) { assert!((x-y).abs() <= 1e-6, "{} != {}", x, y); }
+//! assert_float_eq(plan.value(a), 80.);
+//! assert_float_eq(plan.value(b), 50.);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::collections::HashMap;
+
+use crate::postprocess::multiple_of;
+use crate::repair::{min_change, RepairedSolution};
+use crate::variable::UnsolvedProblem;
+use crate::{constraint, variable, Solution, Solver, SolverModel, Variable};
+
+/// Rounds the variables of `lot_sizes` to the nearest multiple of their lot size in `solution`,
+/// and returns the solution of `problem` made of whole lots
+/// that deviates the least from the rounded values.
+///
+/// The variables without a lot size are kept as close as possible to their value in `solution`.
+/// The deviation of the returned solution is measured from the rounded values:
+/// it is 0 when rounding did not make the solution infeasible.
+pub fn round_and_restore<S, T>(
+    solution: &S,
+    mut problem: UnsolvedProblem,
+    lot_sizes: &HashMap<Variable, f64>,
+    solver: T,
+) -> Result<RepairedSolution, <T::Model as SolverModel>::Error>
+where
+    S: Solution,
+    T: Solver,
+{
+    let mut anchor: HashMap<Variable, f64> = problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(var, _)| {
+            let value = solution.value(var);
+            let rounded = match lot_sizes.get(&var) {
+                Some(&lot) => multiple_of(lot)(value),
+                None => value,
+            };
+            (var, rounded)
+        })
+        .collect();
+    let mut lot_counts = Vec::with_capacity(lot_sizes.len());
+    for (&var, &lot) in lot_sizes {
+        let count = problem.variables.add(variable().integer());
+        problem.add_constraint(constraint!(var == lot * count));
+        anchor.insert(count, anchor[&var] / lot);
+        lot_counts.push(count);
+    }
+    let mut repair = min_change(problem, &anchor);
+    for count in lot_counts {
+        // The number of lots follows the quantity, its change is not counted twice
+        repair = repair.weight(count, 0.);
+    }
+    repair.solve(solver)
+}
@@ -0,0 +1,118 @@
+//! Solve [Markov decision processes](https://en.wikipedia.org/wiki/Markov_decision_process)
+//! using linear programming.
+//!
+//! The optimal value function `v` of a discounted MDP is the solution of
+//!
+//! ```text
+//! minimise   sum(v[s] for all s)
+//! such that  v[s] >= reward[s][a] + discount * sum(transition[s][a][t] * v[t] for all t)
+//!            for all states s and actions a
+//! ```
+//!
+//! ```
+//! use good_lp::{default_solver, mdp};
+//! // In state 0, you can either stay and earn 1, or move to state 1 and earn nothing.
+//! // In state 1, you stay and earn 2 forever.
+//! let transition = vec![
+//!     vec![vec![1., 0.], vec![0., 1.]],
+//!     vec![vec![0., 1.]],
+//! ];
+//! let reward = vec![vec![1., 0.], vec![2.]];
+//! let solution = mdp::solve_lp(&transition, &reward, 0.9, default_solver)?;
+//! assert_eq!(solution.policy, vec![1, 0]); // move to state 1, then stay there
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::{constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel};
+
+/// The optimal value function and policy of a Markov decision process
+#[derive(Debug, Clone, PartialEq)]
+pub struct MdpSolution {
+    /// The expected discounted reward obtained when starting from each state
+    pub values: Vec<f64>,
+    /// The index of the action to take in each state
+    pub policy: Vec<usize>,
+}
+
+/// Solves a discounted Markov decision process.
+///
+/// - `transition[s][a][t]` is the probability of going to state `t`
+///   when taking action `a` in state `s`,
+/// - `reward[s][a]` is the immediate reward of taking action `a` in state `s`,
+/// - `discount` is the discount factor, in `[0, 1)`.
+///
+/// Different states can have a different number of available actions.
+///
+/// The policy is read from the constraints that are tight at the optimum:
+/// by complementary slackness, these are the only ones that can have a non-zero dual value
+/// (the state-action occupation measure).
+/// This makes it possible to use any solver, including the ones that don't expose dual values.
+///
+/// Panics if the dimensions of `transition` and `reward` don't match,
+/// or if `discount` is not in `[0, 1)`.
+pub fn solve_lp<S: Solver>(
+    transition: &[Vec<Vec<f64>>],
+    reward: &[Vec<f64>],
+    discount: f64,
+    solver: S,
+) -> Result<MdpSolution, <S::Model as SolverModel>::Error> {
+    let n_states = transition.len();
+    assert_eq!(
+        n_states,
+        reward.len(),
+        "there should be one reward row per state"
+    );
+    assert!(
+        (0. ..1.).contains(&discount),
+        "the discount factor should be in [0, 1), got {}",
+        discount
+    );
+    let mut vars = ProblemVariables::new();
+    let values = vars.add_vector(variable(), n_states);
+    let objective: Expression = values.iter().sum();
+    let mut model = vars.minimise(objective).using(solver);
+    let mut future_values: Vec<Vec<Expression>> = Vec::with_capacity(n_states);
+    for (s, (actions, rewards)) in transition.iter().zip(reward).enumerate() {
+        assert_eq!(
+            actions.len(),
+            rewards.len(),
+            "state {} should have as many rewards as actions",
+            s
+        );
+        assert!(!actions.is_empty(), "state {} has no available action", s);
+        let mut state_future = Vec::with_capacity(actions.len());
+        for (a, (probabilities, &r)) in actions.iter().zip(rewards).enumerate() {
+            assert_eq!(
+                probabilities.len(),
+                n_states,
+                "transition[{}][{}] should have one probability per state",
+                s,
+                a
+            );
+            let mut future = Expression::from(r);
+            for (&value, &p) in values.iter().zip(probabilities) {
+                if p != 0. {
+                    future.add_mul(discount * p, value);
+                }
+            }
+            model.add_constraint(constraint!(values[s] >= future.clone()));
+            state_future.push(future);
+        }
+        future_values.push(state_future);
+    }
+    let solution = model.solve()?;
+    let values: Vec<f64> = values.iter().map(|&v| solution.value(v)).collect();
+    let policy = future_values
+        .iter()
+        .map(|actions| {
+            // The tight constraint is the one with the largest right hand side
+            actions
+                .iter()
+                .map(|future| solution.eval(future))
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(a, _)| a)
+                .expect("every state has an action")
+        })
+        .collect();
+    Ok(MdpSolution { values, policy })
+}
@@ -0,0 +1,215 @@
+//! The assignment and transportation problems.
+use crate::variable::UnsolvedProblem;
+use crate::{constraint, variable, Constraint, Expression, ProblemVariables, Variable};
+
+/// The [assignment problem](https://en.wikipedia.org/wiki/Assignment_problem):
+/// `costs[agent][task]` is the cost of giving the task to the agent.
+/// Each agent does at most one task, each task is done by at most one agent,
+/// and as many tasks as possible are done: all the agents are busy when there are more tasks,
+/// and all the tasks are done when there are more agents.
+///
+/// Panics if the rows of `costs` do not all have the same length.
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::modeling::assignment;
+/// use good_lp::{constraint, default_solver, Solution, SolverModel};
+///
+/// let model = assignment(&[vec![4., 1., 3.], vec![2., 0., 5.], vec![3., 2., 2.]]);
+/// let assigned = model.assigned.clone();
+/// let objective = model.objective.clone();
+/// let mut problem = model.into_problem();
+/// // Without this side constraint, agent 0 would do task 1, for a total cost of 5
+/// problem.add_constraint(constraint!(assigned[0][1] == 0));
+/// let solution = problem.using(default_solver).solve()?;
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(solution.eval(&objective), 6.);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn assignment(costs: &[Vec<f64>]) -> AssignmentModel {
+    let n_tasks = costs.first().map_or(0, Vec::len);
+    assert!(
+        costs.iter().all(|row| row.len() == n_tasks),
+        "all the agents must have a cost for every task"
+    );
+    let mut vars = ProblemVariables::new();
+    let assigned: Vec<Vec<Variable>> = (0..costs.len())
+        .map(|agent| {
+            (0..n_tasks)
+                .map(|task| {
+                    vars.add(
+                        variable()
+                            .binary()
+                            .name(format!("assign_{}_{}", agent, task)),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    let objective = costs
+        .iter()
+        .zip(&assigned)
+        .flat_map(|(row, variables)| row.iter().zip(variables).map(|(&cost, &x)| cost * x))
+        .sum();
+    // The side with the fewest elements is fully assigned
+    let agents_busy = costs.len() <= n_tasks;
+    let agents = assigned
+        .iter()
+        .enumerate()
+        .map(|(agent, variables)| {
+            let tasks: Expression = variables.iter().sum();
+            let constraint = if agents_busy {
+                constraint!(tasks == 1)
+            } else {
+                constraint!(tasks <= 1)
+            };
+            constraint.set_name(format!("agent_{}", agent))
+        })
+        .collect();
+    let tasks = (0..n_tasks)
+        .map(|task| {
+            let agents: Expression = assigned.iter().map(|variables| variables[task]).sum();
+            let constraint = if agents_busy {
+                constraint!(agents <= 1)
+            } else {
+                constraint!(agents == 1)
+            };
+            constraint.set_name(format!("task_{}", task))
+        })
+        .collect();
+    AssignmentModel {
+        vars,
+        assigned,
+        agents,
+        tasks,
+        objective,
+    }
+}
+
+/// An assignment model, built by [assignment]
+pub struct AssignmentModel {
+    /// The variables of the model, to which new variables can be added
+    pub vars: ProblemVariables,
+    /// `assigned[agent][task]` is the binary variable that is 1 when the agent does the task
+    pub assigned: Vec<Vec<Variable>>,
+    /// The constraint on the number of tasks of each agent
+    pub agents: Vec<Constraint>,
+    /// The constraint on the number of agents of each task
+    pub tasks: Vec<Constraint>,
+    /// The total cost of the assignment, to be minimised
+    pub objective: Expression,
+}
+
+impl AssignmentModel {
+    /// The problem minimising the cost of the assignment
+    pub fn into_problem(self) -> UnsolvedProblem {
+        let mut problem = self.vars.minimise(self.objective);
+        for constraint in self.agents.into_iter().chain(self.tasks) {
+            problem.add_constraint(constraint);
+        }
+        problem
+    }
+}
+
+/// The [transportation problem](https://en.wikipedia.org/wiki/Transportation_theory_(mathematics)):
+/// sources ship goods to destinations, `costs[source][destination]` being the cost
+/// of shipping one unit. Each source ships at most its supply,
+/// and each destination receives at least its demand.
+///
+/// The problem is infeasible when the total supply is lower than the total demand.
+/// When they are equal, and all integers, the optimal shipments are integers.
+///
+/// Panics if `costs` does not have one row per source and one column per destination.
+///
+/// ```
+/// use good_lp::modeling::transportation;
+/// use good_lp::{default_solver, Solution, SolverModel};
+///
+/// let model = transportation(&[20., 30.], &[10., 25.], &[vec![1., 3.], vec![2., 1.]]);
+/// let shipped = model.shipped.clone();
+/// let solution = model.into_problem().using(default_solver).solve()?;
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(solution.value(shipped[0][0]), 10.);
+/// assert_float_eq(solution.value(shipped[1][1]), 25.);
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn transportation(
+    supplies: &[f64],
+    demands: &[f64],
+    costs: &[Vec<f64>],
+) -> TransportationModel {
+    assert!(
+        costs.len() == supplies.len() && costs.iter().all(|row| row.len() == demands.len()),
+        "there must be a cost for every source and destination"
+    );
+    let mut vars = ProblemVariables::new();
+    let shipped: Vec<Vec<Variable>> = (0..supplies.len())
+        .map(|source| {
+            (0..demands.len())
+                .map(|destination| {
+                    vars.add(
+                        variable()
+                            .min(0)
+                            .name(format!("ship_{}_{}", source, destination)),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    let objective = costs
+        .iter()
+        .zip(&shipped)
+        .flat_map(|(row, variables)| row.iter().zip(variables).map(|(&cost, &x)| cost * x))
+        .sum();
+    let supply = supplies
+        .iter()
+        .zip(&shipped)
+        .enumerate()
+        .map(|(source, (&supply, variables))| {
+            let sent: Expression = variables.iter().sum();
+            constraint!(sent <= supply).set_name(format!("supply_{}", source))
+        })
+        .collect();
+    let demand = demands
+        .iter()
+        .enumerate()
+        .map(|(destination, &demand)| {
+            let received: Expression = shipped.iter().map(|variables| variables[destination]).sum();
+            constraint!(received >= demand).set_name(format!("demand_{}", destination))
+        })
+        .collect();
+    TransportationModel {
+        vars,
+        shipped,
+        supply,
+        demand,
+        objective,
+    }
+}
+
+/// A transportation model, built by [transportation]
+pub struct TransportationModel {
+    /// The variables of the model, to which new variables can be added
+    pub vars: ProblemVariables,
+    /// `shipped[source][destination]` is the quantity shipped from the source to the destination
+    pub shipped: Vec<Vec<Variable>>,
+    /// The supply constraint of each source
+    pub supply: Vec<Constraint>,
+    /// The demand constraint of each destination
+    pub demand: Vec<Constraint>,
+    /// The total shipping cost, to be minimised
+    pub objective: Expression,
+}
+
+impl TransportationModel {
+    /// The problem minimising the shipping cost
+    pub fn into_problem(self) -> UnsolvedProblem {
+        let mut problem = self.vars.minimise(self.objective);
+        for constraint in self.supply.into_iter().chain(self.demand) {
+            problem.add_constraint(constraint);
+        }
+        problem
+    }
+}
@@ -0,0 +1,13 @@
+//! Builders for the classic combinatorial optimisation models.
+//!
+//! Each builder returns the variables, constraints and objective of its model,
+//! rather than a solution: side constraints can be added before the model
+//! is turned into a problem and solved.
+
+mod assignment;
+mod packing;
+mod sets;
+
+pub use assignment::{assignment, transportation, AssignmentModel, TransportationModel};
+pub use packing::{bin_packing, knapsack, BinPackingModel, KnapsackModel};
+pub use sets::{set_covering, set_packing, set_partitioning, SetModel};
@@ -0,0 +1,175 @@
+//! The knapsack and bin packing problems.
+use crate::variable::UnsolvedProblem;
+use crate::{constraint, variable, Constraint, Expression, ProblemVariables, Variable};
+
+/// The [knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem):
+/// `items` are given as `(weight, value)`, and the most valuable set of items
+/// whose total weight is at most `capacity` is chosen.
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::modeling::knapsack;
+/// use good_lp::{default_solver, Solution, SolverModel};
+///
+/// let model = knapsack(&[(5., 10.), (4., 7.), (4., 7.)], 8.);
+/// let chosen = model.chosen.clone();
+/// let solution = model.into_problem().using(default_solver).solve()?;
+/// // Items 1 and 2 are worth more together than item 0 alone
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(solution.value(chosen[0]), 0.);
+/// assert_float_eq(solution.value(chosen[1]) + solution.value(chosen[2]), 2.);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn knapsack(items: &[(f64, f64)], capacity: f64) -> KnapsackModel {
+    let mut vars = ProblemVariables::new();
+    let chosen: Vec<Variable> = (0..items.len())
+        .map(|item| vars.add(variable().binary().name(format!("chosen_{}", item))))
+        .collect();
+    let weight: Expression = items
+        .iter()
+        .zip(&chosen)
+        .map(|(&(weight, _), &x)| weight * x)
+        .sum();
+    let objective = items
+        .iter()
+        .zip(&chosen)
+        .map(|(&(_, value), &x)| value * x)
+        .sum();
+    KnapsackModel {
+        vars,
+        chosen,
+        capacity: constraint!(weight <= capacity).set_name("capacity".to_string()),
+        objective,
+    }
+}
+
+/// A knapsack model, built by [knapsack]
+pub struct KnapsackModel {
+    /// The variables of the model, to which new variables can be added
+    pub vars: ProblemVariables,
+    /// The binary variable of each item, that is 1 when the item is chosen
+    pub chosen: Vec<Variable>,
+    /// The capacity constraint
+    pub capacity: Constraint,
+    /// The total value of the chosen items, to be maximised
+    pub objective: Expression,
+}
+
+impl KnapsackModel {
+    /// The problem maximising the value of the chosen items
+    pub fn into_problem(self) -> UnsolvedProblem {
+        self.vars.maximise(self.objective).with(self.capacity)
+    }
+}
+
+/// The [bin packing problem](https://en.wikipedia.org/wiki/Bin_packing_problem):
+/// items of the given sizes are packed into as few bins of capacity `bin_capacity` as possible.
+/// At most `max_bins` bins can be used: the problem is infeasible if they are not enough,
+/// and a large `max_bins` makes the problem larger.
+/// The number of items is a safe choice.
+///
+/// All bins are identical, so every packing can be written in many equivalent ways,
+/// that a branch and bound solver explores in vain.
+/// The [symmetry breaking constraints](BinPackingModel::symmetry_breaking)
+/// remove most of them by using the bins in order.
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::modeling::bin_packing;
+/// use good_lp::{default_solver, Solution, SolverModel};
+///
+/// let model = bin_packing(&[6., 5., 4., 3., 2.], 10., 5);
+/// let objective = model.objective.clone();
+/// let solution = model.into_problem().using(default_solver).solve()?;
+/// // 6 + 4 and 5 + 3 + 2
+/// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+/// assert_float_eq(solution.eval(&objective), 2.);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn bin_packing(items: &[f64], bin_capacity: f64, max_bins: usize) -> BinPackingModel {
+    let mut vars = ProblemVariables::new();
+    let used: Vec<Variable> = (0..max_bins)
+        .map(|bin| vars.add(variable().binary().name(format!("used_{}", bin))))
+        .collect();
+    let packed: Vec<Vec<Variable>> = (0..items.len())
+        .map(|item| {
+            (0..max_bins)
+                .map(|bin| vars.add(variable().binary().name(format!("packed_{}_{}", item, bin))))
+                .collect()
+        })
+        .collect();
+    let placement = packed
+        .iter()
+        .enumerate()
+        .map(|(item, bins)| {
+            let placed: Expression = bins.iter().sum();
+            constraint!(placed == 1).set_name(format!("placement_{}", item))
+        })
+        .collect();
+    let capacity = used
+        .iter()
+        .enumerate()
+        .map(|(bin, &bin_used)| {
+            let load: Expression = items
+                .iter()
+                .zip(&packed)
+                .map(|(&size, bins)| size * bins[bin])
+                .sum();
+            constraint!(load <= bin_capacity * bin_used).set_name(format!("capacity_{}", bin))
+        })
+        .collect();
+    let symmetry_breaking = used
+        .windows(2)
+        .enumerate()
+        .map(|(bin, pair)| constraint!(pair[1] <= pair[0]).set_name(format!("symmetry_{}", bin)))
+        .collect();
+    BinPackingModel {
+        objective: used.iter().sum(),
+        vars,
+        packed,
+        used,
+        placement,
+        capacity,
+        symmetry_breaking,
+    }
+}
+
+/// A bin packing model, built by [bin_packing]
+pub struct BinPackingModel {
+    /// The variables of the model, to which new variables can be added
+    pub vars: ProblemVariables,
+    /// `packed[item][bin]` is the binary variable that is 1 when the item is in the bin
+    pub packed: Vec<Vec<Variable>>,
+    /// The binary variable of each bin, that is 1 when the bin is used
+    pub used: Vec<Variable>,
+    /// The constraint putting each item in exactly one bin
+    pub placement: Vec<Constraint>,
+    /// The capacity constraint of each bin, that can only contain items when it is used
+    pub capacity: Vec<Constraint>,
+    /// Constraints requiring a bin to be used only if the previous one is.
+    /// They do not change the optimal number of bins, and are included in the problem
+    /// by [BinPackingModel::into_problem]: clear them to leave them out.
+    pub symmetry_breaking: Vec<Constraint>,
+    /// The number of bins used, to be minimised
+    pub objective: Expression,
+}
+
+impl BinPackingModel {
+    /// The problem minimising the number of bins used
+    pub fn into_problem(self) -> UnsolvedProblem {
+        let mut problem = self.vars.minimise(self.objective);
+        for constraint in self
+            .placement
+            .into_iter()
+            .chain(self.capacity)
+            .chain(self.symmetry_breaking)
+        {
+            problem.add_constraint(constraint);
+        }
+        problem
+    }
+}
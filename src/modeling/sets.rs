@@ -0,0 +1,205 @@
+//! Set covering, partitioning and packing.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::constraint::ConstraintReference;
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{constraint, variable, Constraint, Expression, ProblemVariables, Solution, Variable};
+
+/// How many of the selected sets can contain an element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplicity {
+    /// At least one, in set covering
+    Covering,
+    /// Exactly one, in set partitioning
+    Partitioning,
+    /// At most one, in set packing
+    Packing,
+}
+
+/// The [set covering problem](https://en.wikipedia.org/wiki/Set_cover_problem):
+/// every element has to be in at least one of the selected sets,
+/// and the total cost of the selected sets is minimised.
+///
+/// `sets` are given as `(set, cost, elements)`, where `set` identifies the set,
+/// for instance a name or an index.
+/// Only the elements that are in at least one of the sets have to be covered.
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::modeling::set_covering;
+/// use good_lp::{default_solver, SolverModel};
+///
+/// // Which fire stations to build, to reach every district
+/// let model = set_covering(vec![
+///     ("north", 3., vec!["a", "b"]),
+///     ("center", 4., vec!["a", "b", "c", "d"]),
+///     ("south", 2., vec!["c", "d"]),
+/// ]);
+/// let (problem, _) = model.clone().into_problem();
+/// let solution = problem.using(default_solver).solve()?;
+/// assert_eq!(model.selected(&solution), vec!["center"]);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn set_covering<S, E, I>(sets: impl IntoIterator<Item = (S, f64, I)>) -> SetModel<S, E>
+where
+    E: Eq + Hash + Clone,
+    I: IntoIterator<Item = E>,
+{
+    SetModel::new(sets, Multiplicity::Covering)
+}
+
+/// The set partitioning problem: every element has to be in exactly one of the selected sets,
+/// and the total cost of the selected sets is minimised.
+/// This is the model of crew scheduling, where the elements are flights,
+/// and the sets are the sequences of flights that a crew can operate.
+///
+/// `sets` are given as `(set, cost, elements)`, as in [set_covering].
+///
+/// ```
+/// # // minilp and highs do not support integer variables
+/// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+/// use good_lp::modeling::set_partitioning;
+/// use good_lp::{default_solver, SolverModel};
+///
+/// let model = set_partitioning(vec![
+///     (0, 3., vec![101, 102]),
+///     (1, 3., vec![103, 104]),
+///     (2, 4., vec![101, 102, 103]),
+///     (3, 1., vec![104]),
+/// ]);
+/// let (problem, _) = model.clone().into_problem();
+/// let solution = problem.using(default_solver).solve()?;
+/// assert_eq!(model.selected(&solution), vec![2, 3]);
+/// # }
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+pub fn set_partitioning<S, E, I>(sets: impl IntoIterator<Item = (S, f64, I)>) -> SetModel<S, E>
+where
+    E: Eq + Hash + Clone,
+    I: IntoIterator<Item = E>,
+{
+    SetModel::new(sets, Multiplicity::Partitioning)
+}
+
+/// The [set packing problem](https://en.wikipedia.org/wiki/Set_packing):
+/// every element can be in at most one of the selected sets,
+/// and the total value of the selected sets is maximised.
+///
+/// `sets` are given as `(set, value, elements)`.
+pub fn set_packing<S, E, I>(sets: impl IntoIterator<Item = (S, f64, I)>) -> SetModel<S, E>
+where
+    E: Eq + Hash + Clone,
+    I: IntoIterator<Item = E>,
+{
+    SetModel::new(sets, Multiplicity::Packing)
+}
+
+/// A set covering, partitioning or packing model,
+/// built by [set_covering], [set_partitioning] or [set_packing]
+#[derive(Clone)]
+pub struct SetModel<S, E> {
+    /// The variables of the model, to which new variables can be added
+    pub vars: ProblemVariables,
+    /// Each set, with the binary variable that is 1 when it is selected
+    pub sets: Vec<(S, Variable)>,
+    /// The constraint of each element, in the order in which the elements first appear
+    pub elements: Vec<(E, Constraint)>,
+    /// The total cost of the selected sets, or their total value for set packing
+    pub objective: Expression,
+    /// Whether the objective is minimised or maximised
+    pub direction: ObjectiveDirection,
+}
+
+impl<S, E: Eq + Hash + Clone> SetModel<S, E> {
+    fn new<I: IntoIterator<Item = E>>(
+        sets: impl IntoIterator<Item = (S, f64, I)>,
+        multiplicity: Multiplicity,
+    ) -> Self {
+        let mut vars = ProblemVariables::new();
+        let mut selected = vec![];
+        let mut objective = Expression::default();
+        let mut positions: HashMap<E, usize> = HashMap::new();
+        let mut elements: Vec<(E, Expression)> = vec![];
+        for (index, (set, cost, set_elements)) in sets.into_iter().enumerate() {
+            let variable = vars.add(variable().binary().name(format!("set_{}", index)));
+            objective.add_mul(cost, variable);
+            for element in set_elements {
+                let position = *positions.entry(element.clone()).or_insert_with(|| {
+                    elements.push((element, Expression::default()));
+                    elements.len() - 1
+                });
+                elements[position].1.add_mul(1, variable);
+            }
+            selected.push((set, variable));
+        }
+        let elements = elements
+            .into_iter()
+            .enumerate()
+            .map(|(position, (element, in_sets))| {
+                let constraint = match multiplicity {
+                    Multiplicity::Covering => constraint!(in_sets >= 1),
+                    Multiplicity::Partitioning => constraint!(in_sets == 1),
+                    Multiplicity::Packing => constraint!(in_sets <= 1),
+                };
+                (
+                    element,
+                    constraint.set_name(format!("element_{}", position)),
+                )
+            })
+            .collect();
+        let direction = if multiplicity == Multiplicity::Packing {
+            ObjectiveDirection::Maximisation
+        } else {
+            ObjectiveDirection::Minimisation
+        };
+        SetModel {
+            vars,
+            sets: selected,
+            elements,
+            objective,
+            direction,
+        }
+    }
+
+    /// Makes the variables of the sets continuous between 0 and 1.
+    ///
+    /// The linear relaxation gives a bound on the optimal objective, and its dual values,
+    /// the prices of the elements, are what column generation needs to find new sets
+    /// for the restricted master problem.
+    pub fn relax(mut self) -> Self {
+        for &(_, variable) in &self.sets {
+            self.vars.relax(variable);
+        }
+        self
+    }
+
+    /// The problem, with the reference of the constraint of each element,
+    /// to read its dual value
+    pub fn into_problem(self) -> (UnsolvedProblem, HashMap<E, ConstraintReference>) {
+        let mut problem = self.vars.optimise(self.direction, self.objective);
+        let references = self
+            .elements
+            .into_iter()
+            .map(|(element, constraint)| (element, problem.add_constraint(constraint)))
+            .collect();
+        (problem, references)
+    }
+
+    /// The sets that are selected in the solution, in the order in which they were given.
+    /// In the solution of the [relaxed](SetModel::relax) model,
+    /// the sets whose variable is above 0.5.
+    pub fn selected<Sol: Solution>(&self, solution: &Sol) -> Vec<S>
+    where
+        S: Clone,
+    {
+        self.sets
+            .iter()
+            .filter(|&&(_, variable)| solution.value(variable) > 0.5)
+            .map(|(set, _)| set.clone())
+            .collect()
+    }
+}
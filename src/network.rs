@@ -0,0 +1,333 @@
+//! Single-commodity network flow models: minimum cost flow and maximum flow.
+//!
+//! Most flow models are written by hand the same way: one variable per arc,
+//! bounded by the capacity of the arc, and one conservation constraint per node,
+//! stating that what goes out of the node minus what comes in is its supply.
+//! [MinCostFlow] and [MaxFlow] write this part, and give back the variables,
+//! the constraints and the objective in a [FlowModel], as ordinary good_lp objects
+//! that can be combined with the rest of a larger problem.
+//!
+//! ```
+//! use good_lp::network::MinCostFlow;
+//! use good_lp::default_solver;
+//!
+//! // 4 units go from node 0 to node 3, through node 1 or node 2
+//! let network = MinCostFlow::new()
+//!     .node(4.) // node 0 supplies 4 units
+//!     .node(0.)
+//!     .node(0.)
+//!     .node(-4.) // node 3 consumes them
+//!     .arc(0, 1, 3., 1.) // from, to, capacity, cost per unit
+//!     .arc(0, 2, 5., 3.)
+//!     .arc(1, 3, 5., 1.)
+//!     .arc(2, 3, 5., 1.);
+//! let flow = network.solve(default_solver)?;
+//! // The cheap route through node 1 is full, the last unit goes through node 2
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(flow.flow(0), 3.);
+//! assert_float_eq(flow.flow(1), 1.);
+//! assert_float_eq(flow.objective(), 3. * 2. + 1. * 4.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+//!
+//! To extend the model, build it in existing variables with [MinCostFlow::model],
+//! then add constraints on its flow variables:
+//!
+//! ```
+//! use good_lp::network::MaxFlow;
+//! use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+//!
+//! let network = MaxFlow::new(3, 0, 2)
+//!     .arc(0, 1, 5.)
+//!     .arc(1, 2, 5.)
+//!     .arc(0, 2, 5.);
+//! let mut vars = variables!();
+//! let model = network.model(&mut vars);
+//! let (through_1, direct) = (model.flows[0], model.flows[2]);
+//! let mut problem = model.into_problem(vars);
+//! // The direct arc cannot carry more than the other route
+//! problem.add_constraint(constraint!(direct <= through_1 - 2));
+//! let solution = problem.using(default_solver).solve()?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(solution.value(through_1) + solution.value(direct), 8.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+//!
+//! When all the supplies and capacities are integers, the flow variables of an optimal
+//! [basic solution](https://en.wikipedia.org/wiki/Basic_feasible_solution) are integers,
+//! without requiring integer variables.
+//! For several commodities sharing the capacity of the arcs, see [graph](crate::graph).
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Constraint, Expression, ProblemVariables, Solution, Solver, SolverModel,
+    Variable,
+};
+
+/// An arc of a [MinCostFlow] or [MaxFlow] network
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Arc {
+    from: usize,
+    to: usize,
+    capacity: f64,
+    cost: f64,
+}
+
+/// A minimum cost flow problem, built with [MinCostFlow::new]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MinCostFlow {
+    supplies: Vec<f64>,
+    arcs: Vec<Arc>,
+}
+
+impl MinCostFlow {
+    /// Creates a network without nodes or arcs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node, that produces `supply` units, or consumes them if `supply` is negative.
+    /// Nodes are numbered from 0, in the order they are added.
+    ///
+    /// The supplies of all nodes have to sum to 0 for the problem to be feasible.
+    pub fn node(mut self, supply: f64) -> Self {
+        self.supplies.push(supply);
+        self
+    }
+
+    /// Adds an arc, that can carry at most `capacity` units, each unit costing `cost`.
+    /// The capacity can be infinite. Arcs are numbered from 0, in the order they are added.
+    ///
+    /// Panics if one of the nodes has not been added yet.
+    pub fn arc(mut self, from: usize, to: usize, capacity: f64, cost: f64) -> Self {
+        assert!(
+            from < self.supplies.len() && to < self.supplies.len(),
+            "the arc ({}, {}) is not between nodes of the network",
+            from,
+            to
+        );
+        self.arcs.push(Arc {
+            from,
+            to,
+            capacity,
+            cost,
+        });
+        self
+    }
+
+    /// The number of nodes
+    pub fn n_nodes(&self) -> usize {
+        self.supplies.len()
+    }
+
+    /// The number of arcs
+    pub fn n_arcs(&self) -> usize {
+        self.arcs.len()
+    }
+
+    /// Adds the flow variables to `vars`, and returns them with the conservation
+    /// constraint of every node, and the cost of the flow, to be minimised
+    pub fn model(&self, vars: &mut ProblemVariables) -> FlowModel {
+        let flows = flow_variables(&self.arcs, vars);
+        let conservation = self
+            .supplies
+            .iter()
+            .enumerate()
+            .map(|(node, &supply)| {
+                let balance = balance(&self.arcs, &flows, node);
+                constraint!(balance == supply).set_name(format!("conservation_{}", node))
+            })
+            .collect();
+        let objective = self
+            .arcs
+            .iter()
+            .zip(&flows)
+            .map(|(arc, &flow)| arc.cost * flow)
+            .sum();
+        FlowModel {
+            flows,
+            conservation,
+            objective,
+            direction: ObjectiveDirection::Minimisation,
+        }
+    }
+
+    /// Finds the cheapest flow that satisfies the supplies of all nodes
+    pub fn solve<S: Solver>(
+        &self,
+        solver: S,
+    ) -> Result<NetworkFlow, <S::Model as SolverModel>::Error> {
+        let mut vars = ProblemVariables::new();
+        self.model(&mut vars).solve(vars, solver)
+    }
+}
+
+/// A maximum flow problem, built with [MaxFlow::new]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow {
+    n_nodes: usize,
+    source: usize,
+    sink: usize,
+    arcs: Vec<Arc>,
+}
+
+impl MaxFlow {
+    /// Creates a network with the given number of nodes, numbered from 0, and no arcs.
+    /// As much flow as possible has to go from `source` to `sink`.
+    pub fn new(n_nodes: usize, source: usize, sink: usize) -> Self {
+        assert!(
+            source < n_nodes && sink < n_nodes,
+            "the source and the sink must be nodes of the network"
+        );
+        MaxFlow {
+            n_nodes,
+            source,
+            sink,
+            arcs: vec![],
+        }
+    }
+
+    /// Adds an arc, that can carry at most `capacity` units.
+    /// Arcs are numbered from 0, in the order they are added.
+    pub fn arc(mut self, from: usize, to: usize, capacity: f64) -> Self {
+        assert!(
+            from < self.n_nodes && to < self.n_nodes,
+            "the arc ({}, {}) is not between nodes of the network",
+            from,
+            to
+        );
+        self.arcs.push(Arc {
+            from,
+            to,
+            capacity,
+            cost: 0.,
+        });
+        self
+    }
+
+    /// The number of arcs
+    pub fn n_arcs(&self) -> usize {
+        self.arcs.len()
+    }
+
+    /// Adds the flow variables to `vars`, and returns them with the conservation
+    /// constraint of every node except the source and the sink,
+    /// and the amount of flow leaving the source, to be maximised
+    pub fn model(&self, vars: &mut ProblemVariables) -> FlowModel {
+        let flows = flow_variables(&self.arcs, vars);
+        let conservation = (0..self.n_nodes)
+            .filter(|&node| node != self.source && node != self.sink)
+            .map(|node| {
+                let balance = balance(&self.arcs, &flows, node);
+                constraint!(balance == 0).set_name(format!("conservation_{}", node))
+            })
+            .collect();
+        FlowModel {
+            objective: balance(&self.arcs, &flows, self.source),
+            flows,
+            conservation,
+            direction: ObjectiveDirection::Maximisation,
+        }
+    }
+
+    /// Finds the maximum flow from the source to the sink
+    pub fn solve<S: Solver>(
+        &self,
+        solver: S,
+    ) -> Result<NetworkFlow, <S::Model as SolverModel>::Error> {
+        let mut vars = ProblemVariables::new();
+        self.model(&mut vars).solve(vars, solver)
+    }
+}
+
+/// One variable per arc, between 0 and its capacity
+fn flow_variables(arcs: &[Arc], vars: &mut ProblemVariables) -> Vec<Variable> {
+    arcs.iter()
+        .enumerate()
+        .map(|(a, arc)| {
+            vars.add(
+                variable()
+                    .clamp(0, arc.capacity)
+                    .name(format!("flow_{}", a)),
+            )
+        })
+        .collect()
+}
+
+/// The flow going out of the node minus the flow coming in
+fn balance(arcs: &[Arc], flows: &[Variable], node: usize) -> Expression {
+    let mut balance = Expression::default();
+    for (arc, &flow) in arcs.iter().zip(flows) {
+        if arc.from == node {
+            balance += flow;
+        }
+        if arc.to == node {
+            balance -= flow;
+        }
+    }
+    balance
+}
+
+/// The variables, constraints and objective of a flow problem,
+/// returned by [MinCostFlow::model] and [MaxFlow::model]
+pub struct FlowModel {
+    /// The flow on each arc, in the order in which the arcs were added
+    pub flows: Vec<Variable>,
+    /// The conservation constraints of the nodes
+    pub conservation: Vec<Constraint>,
+    /// The cost of the flow for [MinCostFlow], or the flow leaving the source for [MaxFlow]
+    pub objective: Expression,
+    /// Whether the objective is minimised or maximised
+    pub direction: ObjectiveDirection,
+}
+
+impl FlowModel {
+    /// The problem that optimises the objective under the conservation constraints.
+    /// `vars` must be the variables in which the model was built.
+    pub fn into_problem(self, vars: ProblemVariables) -> UnsolvedProblem {
+        let mut problem = vars.optimise(self.direction, self.objective);
+        for constraint in self.conservation {
+            problem.add_constraint(constraint);
+        }
+        problem
+    }
+
+    /// Solves the problem, and returns the flow on every arc
+    fn solve<S: Solver>(
+        self,
+        vars: ProblemVariables,
+        solver: S,
+    ) -> Result<NetworkFlow, <S::Model as SolverModel>::Error> {
+        let flows = self.flows.clone();
+        let objective = self.objective.clone();
+        let solution = self.into_problem(vars).using(solver).solve()?;
+        Ok(NetworkFlow {
+            flows: flows.iter().map(|&flow| solution.value(flow)).collect(),
+            objective: solution.eval(&objective),
+        })
+    }
+}
+
+/// The optimal flow of a [MinCostFlow] or [MaxFlow] problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkFlow {
+    flows: Vec<f64>,
+    objective: f64,
+}
+
+impl NetworkFlow {
+    /// The flow on the arc
+    pub fn flow(&self, arc: usize) -> f64 {
+        self.flows[arc]
+    }
+
+    /// The flow on every arc
+    pub fn flows(&self) -> &[f64] {
+        &self.flows
+    }
+
+    /// The cost of the flow for [MinCostFlow], or the amount of flow for [MaxFlow]
+    pub fn objective(&self) -> f64 {
+        self.objective
+    }
+}
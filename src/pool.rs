@@ -0,0 +1,464 @@
+//! Enumeration of several good solutions of a problem with binary variables.
+//!
+//! Planners often want to choose between a few different near-optimal plans,
+//! rather than to be given a single optimal one.
+//! None of the solvers supported by good_lp keep a pool of solutions,
+//! so the pool is built by solving the problem repeatedly,
+//! adding a [no-good cut](https://doi.org/10.1016/j.orl.2008.11.005) after each solution.
+//! The cut forbids the exact combination of binary variables of that solution,
+//! so every solution of the pool differs from the others in at least one binary variable.
+//!
+//! Since every resolution finds the best solution that was not forbidden yet,
+//! the solutions are sorted from the best to the worst objective value.
+//!
+//! ```
+//! # // minilp and highs do not support integer variables
+//! # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+//! use good_lp::pool::solution_pool;
+//! use good_lp::{constraint, default_solver, variable, variables, Solution};
+//!
+//! let mut vars = variables!();
+//! let items: Vec<_> = (0..3).map(|_| vars.add(variable().binary())).collect();
+//! let values = [5., 4., 3.];
+//! let value: good_lp::Expression = items.iter().zip(&values).map(|(&x, &v)| v * x).sum();
+//! // Only two items fit in the bag
+//! let size: good_lp::Expression = items.iter().sum();
+//! let problem = vars.maximise(value.clone()).with(constraint!(size <= 2));
+//! let pool = solution_pool(problem)
+//!     .with_solution_limit(3)
+//!     .solve(default_solver)?;
+//! assert_eq!(pool.len(), 3);
+//! assert_eq!(pool.eval(&value).round(), 9.);
+//! let alternatives = pool.alternatives();
+//! assert_eq!(alternatives[0].eval(&value).round(), 8.);
+//! assert_eq!(alternatives[1].eval(&value).round(), 7.);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+//!
+//! Linear problems can also have several optimal solutions, when the objective is parallel
+//! to a face of the feasible polytope. The solver then returns any vertex of that face,
+//! and a small change to the problem can make it return a completely different one.
+//! [alternative_optima] finds the vertices of the optimal face that
+//! minimise and maximise each variable, which shows which values are
+//! imposed by the optimum, and which ones the solver picked arbitrarily.
+//!
+//! ```
+//! use good_lp::pool::alternative_optima;
+//! use good_lp::{constraint, default_solver, variables};
+//!
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10; 0 <= z <= 1;}
+//! // Any split of 12 between x and y is optimal
+//! let problem = vars.maximise(x + y + z).with(constraint!(x + y <= 12));
+//! let optima = alternative_optima(problem).solve(default_solver)?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! let (min_x, max_x) = optima.range(x).unwrap();
+//! assert_float_eq(min_x, 2.);
+//! assert_float_eq(max_x, 10.);
+//! assert_eq!(optima.undetermined(1e-6), vec![x, y]);
+//! assert_eq!(optima.vertices().len(), 2);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+//!
+//! To seed heuristics, or to test downstream systems with realistic plans,
+//! [random_feasible] samples diverse feasible solutions, ignoring the objective.
+//!
+//! ```
+//! use good_lp::pool::random_feasible;
+//! use good_lp::{constraint, default_solver, variables, Solution};
+//!
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+//! let problem = vars.maximise(x).with(constraint!(x + y <= 12));
+//! // Any source of uniform numbers in [0, 1) can be used, such as `rand::random`
+//! let mut state = 7_u64;
+//! let rng = move || {
+//!     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+//!     (state >> 11) as f64 / (1_u64 << 53) as f64
+//! };
+//! let samples = random_feasible(problem, rng, 5, default_solver)?;
+//! assert_eq!(samples.len(), 5);
+//! for sample in &samples {
+//!     assert!(sample.value(x) + sample.value(y) <= 12. + 1e-6);
+//! }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, Expression, IntoAffineExpression, ResolutionError, Solution, Solver, SolverModel,
+    Variable,
+};
+
+/// The relative difference above which two solutions are different vertices
+const VERTEX_TOLERANCE: f64 = 1e-5;
+
+/// A search for several solutions of a problem, created by [solution_pool]
+pub struct SolutionPool {
+    problem: UnsolvedProblem,
+    limit: usize,
+    max_objective_loss: f64,
+}
+
+/// Prepares the enumeration of the solutions of `problem`.
+/// By default, only the optimal solution is returned: use [SolutionPool::with_solution_limit]
+/// to get more.
+pub fn solution_pool(problem: UnsolvedProblem) -> SolutionPool {
+    SolutionPool {
+        problem,
+        limit: 1,
+        max_objective_loss: f64::INFINITY,
+    }
+}
+
+impl SolutionPool {
+    /// Sets the maximum number of solutions to return, including the best one
+    pub fn with_solution_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Only returns the solutions whose objective value is at most `loss`
+    /// worse than the one of the best solution
+    pub fn max_objective_loss(mut self, loss: f64) -> Self {
+        self.max_objective_loss = loss;
+        self
+    }
+
+    /// Solves the problem until the solution limit is reached,
+    /// or no other solution is found.
+    ///
+    /// Returns an error if the problem itself cannot be solved.
+    pub fn solve<S>(self, solver: S) -> Result<PooledSolutions, ResolutionError>
+    where
+        S: Solver + Clone,
+        S::Model: SolverModel<Error = ResolutionError>,
+    {
+        let SolutionPool {
+            mut problem,
+            limit,
+            max_objective_loss,
+        } = self;
+        let variables: Vec<Variable> = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(v, _)| v)
+            .collect();
+        let binaries: Vec<Variable> = problem
+            .variables
+            .iter_variables_with_def()
+            .filter(|(_, def)| def.is_integer && def.min >= 0. && def.max <= 1.)
+            .map(|(v, _)| v)
+            .collect();
+        let objective = problem.objective.clone();
+        let mut solutions: Vec<PooledSolution> = Vec::with_capacity(limit);
+        while solutions.len() < limit.max(1) {
+            let solution = match problem.clone().using(solver.clone()).solve() {
+                Ok(solution) => solution,
+                Err(ResolutionError::Infeasible) if !solutions.is_empty() => break,
+                Err(e) => return Err(e),
+            };
+            let objective_value = solution.eval(&objective);
+            if solutions.is_empty() && max_objective_loss.is_finite() {
+                let threshold = match problem.direction {
+                    ObjectiveDirection::Maximisation => {
+                        constraint!(objective.clone() >= objective_value - max_objective_loss)
+                    }
+                    ObjectiveDirection::Minimisation => {
+                        constraint!(objective.clone() <= objective_value + max_objective_loss)
+                    }
+                };
+                problem.add_constraint(threshold);
+            }
+            solutions.push(PooledSolution {
+                values: variables.iter().map(|&v| solution.value(v)).collect(),
+                objective: objective_value,
+            });
+            if binaries.is_empty() {
+                break;
+            }
+            let mut distance = Expression::with_capacity(binaries.len());
+            for &var in &binaries {
+                if solution.value(var) > 0.5 {
+                    distance.add_mul(-1, var);
+                    distance += 1;
+                } else {
+                    distance.add_mul(1, var);
+                }
+            }
+            problem.add_constraint(constraint!(distance >= 1));
+        }
+        Ok(PooledSolutions { solutions })
+    }
+}
+
+/// One of the solutions found by a [SolutionPool]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PooledSolution {
+    values: Vec<f64>,
+    objective: f64,
+}
+
+impl PooledSolution {
+    /// The value of the objective of the problem in this solution
+    pub fn objective(&self) -> f64 {
+        self.objective
+    }
+}
+
+impl Solution for PooledSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+}
+
+/// The solutions found by a [SolutionPool], from the best to the worst.
+/// It can be used as the best solution directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PooledSolutions {
+    solutions: Vec<PooledSolution>,
+}
+
+impl PooledSolutions {
+    /// The best solution found
+    pub fn best(&self) -> &PooledSolution {
+        &self.solutions[0]
+    }
+
+    /// The solutions other than the best one, from the best to the worst
+    pub fn alternatives(&self) -> &[PooledSolution] {
+        &self.solutions[1..]
+    }
+
+    /// All the solutions, starting with the best one
+    pub fn iter(&self) -> impl Iterator<Item = &PooledSolution> {
+        self.solutions.iter()
+    }
+
+    /// The number of solutions found, including the best one
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    /// Whether no solution was found.
+    /// This is always false for pools returned by [SolutionPool::solve],
+    /// which contain at least the best solution
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+}
+
+impl Solution for PooledSolutions {
+    fn value(&self, variable: Variable) -> f64 {
+        self.best().value(variable)
+    }
+}
+
+/// A search for the alternative optimal solutions of a linear problem,
+/// created by [alternative_optima]
+pub struct AlternativeOptima {
+    problem: UnsolvedProblem,
+    variables: Option<Vec<Variable>>,
+    objective_tolerance: f64,
+}
+
+/// Prepares the search for the optimal solutions of `problem`
+/// that minimise and maximise each of its variables.
+///
+/// By default, all the variables are explored,
+/// and solutions whose objective is within `1e-9` of the optimum are considered optimal.
+/// This tolerance is much smaller than the one used to tell vertices apart,
+/// so that the relaxation of the optimum does not create new vertices.
+pub fn alternative_optima(problem: UnsolvedProblem) -> AlternativeOptima {
+    AlternativeOptima {
+        problem,
+        variables: None,
+        objective_tolerance: 1e-9,
+    }
+}
+
+impl AlternativeOptima {
+    /// Only explores the range of the given variables
+    pub fn variables(mut self, variables: &[Variable]) -> Self {
+        self.variables = Some(variables.to_vec());
+        self
+    }
+
+    /// Considers solutions optimal if their objective is within `tolerance` of the optimum.
+    /// A larger tolerance also explores near-optimal solutions.
+    pub fn objective_tolerance(mut self, tolerance: f64) -> Self {
+        self.objective_tolerance = tolerance;
+        self
+    }
+
+    /// Solves the problem, then minimises and maximises each explored variable
+    /// while keeping the objective optimal.
+    ///
+    /// This solves `1 + 2 * n_variables` problems.
+    pub fn solve<S: Solver + Clone>(
+        self,
+        solver: S,
+    ) -> Result<OptimalRanges, <S::Model as SolverModel>::Error> {
+        let AlternativeOptima {
+            problem,
+            variables,
+            objective_tolerance,
+        } = self;
+        let all_variables: Vec<Variable> = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(v, _)| v)
+            .collect();
+        let explored = variables.unwrap_or_else(|| all_variables.clone());
+        let objective = problem.objective.clone();
+        let optimum = problem
+            .clone()
+            .using(solver.clone())
+            .solve()?
+            .eval(&objective);
+        let mut optimal_face = problem;
+        optimal_face.add_constraint(match optimal_face.direction {
+            ObjectiveDirection::Maximisation => {
+                constraint!(objective.clone() >= optimum - objective_tolerance)
+            }
+            ObjectiveDirection::Minimisation => {
+                constraint!(objective.clone() <= optimum + objective_tolerance)
+            }
+        });
+        let mut ranges = Vec::with_capacity(explored.len());
+        let mut vertices: Vec<PooledSolution> = vec![];
+        for &var in &explored {
+            let mut range = [0.; 2];
+            for (bound, direction) in range.iter_mut().zip([
+                ObjectiveDirection::Minimisation,
+                ObjectiveDirection::Maximisation,
+            ]) {
+                let mut extreme = optimal_face.clone();
+                extreme.objective = var.into_expression();
+                extreme.direction = direction;
+                let solution = extreme.using(solver.clone()).solve()?;
+                *bound = solution.value(var);
+                let vertex = PooledSolution {
+                    values: all_variables.iter().map(|&v| solution.value(v)).collect(),
+                    objective: solution.eval(&objective),
+                };
+                let is_new = vertices.iter().all(|known| {
+                    let mut pairs = known.values.iter().zip(&vertex.values);
+                    pairs.any(|(a, b)| (a - b).abs() > VERTEX_TOLERANCE * a.abs().max(1.))
+                });
+                if is_new {
+                    vertices.push(vertex);
+                }
+            }
+            ranges.push((var, range[0], range[1]));
+        }
+        Ok(OptimalRanges {
+            optimum,
+            ranges,
+            vertices,
+        })
+    }
+}
+
+/// The ranges of values that variables take in the optimal solutions of a problem,
+/// computed by [AlternativeOptima::solve]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimalRanges {
+    optimum: f64,
+    ranges: Vec<(Variable, f64, f64)>,
+    vertices: Vec<PooledSolution>,
+}
+
+impl OptimalRanges {
+    /// The optimal value of the objective
+    pub fn optimum(&self) -> f64 {
+        self.optimum
+    }
+
+    /// The smallest and largest value of the variable in the optimal solutions,
+    /// or `None` if the variable was not explored
+    pub fn range(&self, variable: Variable) -> Option<(f64, f64)> {
+        self.ranges
+            .iter()
+            .find(|(v, _, _)| *v == variable)
+            .map(|&(_, min, max)| (min, max))
+    }
+
+    /// The explored variables whose value differs by more than `tolerance`
+    /// between optimal solutions
+    pub fn undetermined(&self, tolerance: f64) -> Vec<Variable> {
+        self.ranges
+            .iter()
+            .filter(|(_, min, max)| max - min > tolerance)
+            .map(|&(v, _, _)| v)
+            .collect()
+    }
+
+    /// The distinct optimal solutions found while minimising and maximising each variable
+    pub fn vertices(&self) -> &[PooledSolution] {
+        &self.vertices
+    }
+}
+
+/// Samples `n` feasible solutions of `problem`, by optimizing random objectives.
+/// `rng` must return numbers drawn uniformly in `[0, 1)`.
+///
+/// Each random objective leads the solver to a vertex of the feasible region.
+/// When the problem has no integer variables, the returned solutions are random
+/// convex combinations of the vertices found so far, which are feasible too,
+/// and are not limited to the vertices.
+/// Solutions of problems with integer variables are vertices, and can repeat.
+///
+/// The feasible region should be bounded: otherwise, a random objective can make
+/// the problem unbounded, and the error of the solver is returned.
+/// The objective of the problem is only used to compute [PooledSolution::objective].
+pub fn random_feasible<S, R>(
+    problem: UnsolvedProblem,
+    mut rng: R,
+    n: usize,
+    solver: S,
+) -> Result<Vec<PooledSolution>, <S::Model as SolverModel>::Error>
+where
+    S: Solver + Clone,
+    R: FnMut() -> f64,
+{
+    let all_variables: Vec<Variable> = problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(v, _)| v)
+        .collect();
+    let continuous = problem
+        .variables
+        .iter_variables_with_def()
+        .all(|(_, def)| !def.is_integer);
+    let objective = problem.objective.clone();
+    let mut vertices: Vec<Vec<f64>> = Vec::with_capacity(n);
+    let mut samples = Vec::with_capacity(n);
+    for _ in 0..n {
+        let mut random = problem.clone();
+        random.direction = ObjectiveDirection::Minimisation;
+        random.objective = all_variables.iter().map(|&v| (2. * rng() - 1.) * v).sum();
+        let solution = random.using(solver.clone()).solve()?;
+        vertices.push(all_variables.iter().map(|&v| solution.value(v)).collect());
+        let values = if continuous {
+            // The small offset avoids dividing by zero when all the weights are zero
+            let weights: Vec<f64> = vertices.iter().map(|_| rng() + 1e-9).collect();
+            let total: f64 = weights.iter().sum();
+            let mut values = vec![0.; all_variables.len()];
+            for (vertex, weight) in vertices.iter().zip(&weights) {
+                for (value, x) in values.iter_mut().zip(vertex) {
+                    *value += x * weight / total;
+                }
+            }
+            values
+        } else {
+            vertices[vertices.len() - 1].clone()
+        };
+        let mut sample = PooledSolution {
+            values,
+            objective: 0.,
+        };
+        sample.objective = sample.eval(&objective);
+        samples.push(sample);
+    }
+    Ok(samples)
+}
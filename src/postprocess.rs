@@ -0,0 +1,185 @@
+//! Post-processing of the values of a solution, before they are handed to other systems.
+//!
+//! Solvers return floating point values such as `12.340000000000002` or `99.99999999`,
+//! while downstream systems expect amounts in cents, or quantities in whole lots.
+//! A [ValueProcessing] registers a transform for a variable or a group of variables,
+//! which is applied by [Solution::value_processed], or by [ValueProcessing::apply]
+//! for a whole solution.
+//!
+//! Rounding can make a solution infeasible, so [ValueProcessing::check]
+//! verifies the processed values against the bounds and constraints of the problem.
+//!
+//! ```
+//! use good_lp::postprocess::{decimals, multiple_of, ValueProcessing};
+//! use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+//!
+//! variables! {vars: 0 <= price <= 100; 0 <= units <= 1000;}
+//! let problem = vars
+//!     .maximise(price + units)
+//!     .with(constraint!(3 * price <= 10))
+//!     .with(constraint!(units <= 995));
+//! let processing = ValueProcessing::new()
+//!     .transform(price, decimals(2)) // in cents
+//!     .transform(units, multiple_of(25.)); // in lots of 25
+//! let solution = problem.clone().using(default_solver).solve()?;
+//! assert_eq!(solution.value_processed(price, &processing), 3.33);
+//! assert_eq!(solution.value_processed(units, &processing), 1000.);
+//! // Rounding the units up violated a constraint
+//! let check = processing.check(&problem, &solution, 1e-6);
+//! assert_eq!(check.violated_constraints, vec![1]);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::variable::UnsolvedProblem;
+use crate::{Solution, SolutionStatus, SolveStats, Variable};
+
+type Transform = Arc<dyn Fn(f64) -> f64 + Send + Sync>;
+
+/// A set of transforms applied to the values of the variables of a solution.
+/// Variables without a transform keep their value.
+#[derive(Clone, Default)]
+pub struct ValueProcessing {
+    transforms: HashMap<Variable, Transform>,
+}
+
+impl ValueProcessing {
+    /// Creates a processing that does not change any value
+    pub fn new() -> Self {
+        ValueProcessing::default()
+    }
+
+    /// Applies `transform` to the value of `variable`,
+    /// replacing the transform previously registered for it
+    pub fn transform<F>(self, variable: Variable, transform: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        self.transform_all(&[variable], transform)
+    }
+
+    /// Applies `transform` to the values of all the given variables
+    pub fn transform_all<F>(mut self, variables: &[Variable], transform: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        let transform: Transform = Arc::new(transform);
+        for &variable in variables {
+            self.transforms.insert(variable, Arc::clone(&transform));
+        }
+        self
+    }
+
+    /// The processed value of a variable, given its raw value
+    pub fn process(&self, variable: Variable, value: f64) -> f64 {
+        match self.transforms.get(&variable) {
+            Some(transform) => transform(value),
+            None => value,
+        }
+    }
+
+    /// Returns a solution whose values are processed
+    pub fn apply<'a, S: Solution>(&'a self, solution: &'a S) -> ProcessedSolution<'a, S> {
+        ProcessedSolution {
+            solution,
+            processing: self,
+        }
+    }
+
+    /// Checks the processed values of the solution against the bounds and constraints
+    /// of the problem, with the given tolerance.
+    pub fn check<S: Solution>(
+        &self,
+        problem: &UnsolvedProblem,
+        solution: &S,
+        tolerance: f64,
+    ) -> ProcessingCheck {
+        let processed = self.apply(solution);
+        let violated_bounds = problem
+            .variables
+            .iter_variables_with_def()
+            .filter(|(var, def)| {
+                let value = processed.value(*var);
+                value < def.min - tolerance || value > def.max + tolerance
+            })
+            .map(|(var, _)| var)
+            .collect();
+        let violated_constraints = problem
+            .constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !processed.is_satisfied(c, tolerance))
+            .map(|(i, _)| i)
+            .collect();
+        ProcessingCheck {
+            violated_bounds,
+            violated_constraints,
+        }
+    }
+}
+
+/// The result of [ValueProcessing::check]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessingCheck {
+    /// The variables whose processed value is outside of their bounds
+    pub violated_bounds: Vec<Variable>,
+    /// The indices of the constraints of the problem that the processed values violate,
+    /// in the order in which the constraints were added
+    pub violated_constraints: Vec<usize>,
+}
+
+impl ProcessingCheck {
+    /// Whether the processed solution satisfies all the bounds and constraints
+    pub fn is_feasible(&self) -> bool {
+        self.violated_bounds.is_empty() && self.violated_constraints.is_empty()
+    }
+}
+
+/// A solution whose values are transformed by a [ValueProcessing],
+/// created by [ValueProcessing::apply]
+pub struct ProcessedSolution<'a, S> {
+    solution: &'a S,
+    processing: &'a ValueProcessing,
+}
+
+impl<'a, S: Solution> ProcessedSolution<'a, S> {
+    /// The solution before processing
+    pub fn raw(&self) -> &S {
+        self.solution
+    }
+}
+
+impl<'a, S: Solution> Solution for ProcessedSolution<'a, S> {
+    fn value(&self, variable: Variable) -> f64 {
+        self.processing
+            .process(variable, self.solution.value(variable))
+    }
+
+    fn status(&self) -> SolutionStatus {
+        self.solution.status()
+    }
+
+    fn best_bound(&self) -> Option<f64> {
+        self.solution.best_bound()
+    }
+
+    fn mip_gap(&self) -> Option<f64> {
+        self.solution.mip_gap()
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.solution.stats()
+    }
+}
+
+/// Rounds values to the nearest multiple of `step`, such as a lot size
+pub fn multiple_of(step: f64) -> impl Fn(f64) -> f64 + Send + Sync + Copy {
+    move |value| (value / step).round() * step
+}
+
+/// Rounds values to the given number of decimal places, such as 2 for cents
+pub fn decimals(places: i32) -> impl Fn(f64) -> f64 + Send + Sync + Copy {
+    let scale = 10f64.powi(places);
+    move |value| (value * scale).round() / scale
+}
@@ -0,0 +1,226 @@
+//! Power systems: the [DC optimal power flow](https://en.wikipedia.org/wiki/Optimal_power_flow) problem.
+//!
+//! The DC approximation of the power flow equations assumes that the flow through a line
+//! is proportional to the difference between the voltage angles at both ends,
+//! divided by the reactance of the line.
+//! The goal is to find the cheapest generator dispatch that satisfies the demand at each bus,
+//! without overloading any line.
+//!
+//! ```
+//! use good_lp::default_solver;
+//! use good_lp::power::{dc_opf, Bus, Generator, Line};
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//!
+//! let buses = [Bus { demand: 0. }, Bus { demand: 80. }];
+//! let lines = [Line { from: 0, to: 1, reactance: 0.1, capacity: 50. }];
+//! let generators = [
+//!     Generator { bus: 0, min_output: 0., max_output: 100., cost: 10. },
+//!     Generator { bus: 1, min_output: 0., max_output: 100., cost: 30. },
+//! ];
+//! let dispatch = dc_opf(&buses, &lines, &generators).solve(default_solver)?;
+//! // The cheap generator is limited by the capacity of the line
+//! assert_float_eq(dispatch.output(0), 50.);
+//! assert_float_eq(dispatch.output(1), 30.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel, Variable,
+};
+
+/// A node of the network
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bus {
+    /// The power consumed at this bus
+    pub demand: f64,
+}
+
+/// A transmission line between two buses
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    /// The index of the bus the line starts from. Positive flows go from `from` to `to`.
+    pub from: usize,
+    /// The index of the bus the line goes to
+    pub to: usize,
+    /// The reactance of the line. The flow is `(angle[from] - angle[to]) / reactance`.
+    pub reactance: f64,
+    /// The maximum flow through the line, in both directions
+    pub capacity: f64,
+}
+
+/// A generator connected to a bus
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    /// The index of the bus the generator is connected to
+    pub bus: usize,
+    /// The minimum power the generator has to produce
+    pub min_output: f64,
+    /// The maximum power the generator can produce
+    pub max_output: f64,
+    /// The cost of producing one unit of power
+    pub cost: f64,
+}
+
+/// A DC optimal power flow problem, created by [dc_opf]
+pub struct DcOpf {
+    problem: UnsolvedProblem,
+    outputs: Vec<Variable>,
+    angles: Vec<Variable>,
+    flows: Vec<Variable>,
+}
+
+/// Builds the DC optimal power flow problem.
+///
+/// The voltage angle of the first bus is used as the reference and fixed to 0.
+///
+/// Panics if a line or a generator references a bus that doesn't exist.
+pub fn dc_opf(buses: &[Bus], lines: &[Line], generators: &[Generator]) -> DcOpf {
+    let mut vars = ProblemVariables::new();
+    let outputs: Vec<Variable> = generators
+        .iter()
+        .enumerate()
+        .map(|(g, gen)| {
+            vars.add(
+                variable()
+                    .name(format!("output_{}", g))
+                    .clamp(gen.min_output, gen.max_output),
+            )
+        })
+        .collect();
+    let angles: Vec<Variable> = (0..buses.len())
+        .map(|b| {
+            let angle = variable().name(format!("angle_{}", b));
+            vars.add(if b == 0 { angle.clamp(0, 0) } else { angle })
+        })
+        .collect();
+    let flows: Vec<Variable> = lines
+        .iter()
+        .enumerate()
+        .map(|(l, line)| {
+            vars.add(
+                variable()
+                    .name(format!("flow_{}", l))
+                    .clamp(-line.capacity, line.capacity),
+            )
+        })
+        .collect();
+    let cost: Expression = generators
+        .iter()
+        .zip(&outputs)
+        .map(|(gen, &output)| gen.cost * output)
+        .sum();
+    let mut problem = vars.minimise(cost);
+
+    let mut net_injection: Vec<Expression> = buses.iter().map(|bus| (-bus.demand).into()).collect();
+    for (gen, &output) in generators.iter().zip(&outputs) {
+        assert!(
+            gen.bus < buses.len(),
+            "generator connected to unknown bus {}",
+            gen.bus
+        );
+        net_injection[gen.bus] += output;
+    }
+    for (l, (line, &flow)) in lines.iter().zip(&flows).enumerate() {
+        assert!(
+            line.from < buses.len() && line.to < buses.len(),
+            "line {} connects unknown buses",
+            l
+        );
+        net_injection[line.from] -= flow;
+        net_injection[line.to] += flow;
+        let angle_difference = angles[line.from] - angles[line.to];
+        problem.add_constraint(
+            constraint!(line.reactance * flow == angle_difference)
+                .set_name(format!("flow_definition_{}", l)),
+        );
+    }
+    for (b, injection) in net_injection.into_iter().enumerate() {
+        problem.add_constraint(constraint!(injection == 0).set_name(format!("balance_{}", b)));
+    }
+    DcOpf {
+        problem,
+        outputs,
+        angles,
+        flows,
+    }
+}
+
+impl DcOpf {
+    /// The variable representing the output of a generator
+    pub fn output_variable(&self, generator: usize) -> Variable {
+        self.outputs[generator]
+    }
+
+    /// The variable representing the voltage angle at a bus
+    pub fn angle_variable(&self, bus: usize) -> Variable {
+        self.angles[bus]
+    }
+
+    /// The variable representing the flow through a line
+    pub fn flow_variable(&self, line: usize) -> Variable {
+        self.flows[line]
+    }
+
+    /// The underlying problem, to which additional constraints can be added
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Finds the cheapest dispatch
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<DcOpfSolution, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        let values = |vars: &[Variable]| vars.iter().map(|&v| solution.value(v)).collect();
+        Ok(DcOpfSolution {
+            outputs: values(&self.outputs),
+            angles: values(&self.angles),
+            flows: values(&self.flows),
+        })
+    }
+}
+
+/// The optimal dispatch of a [DcOpf] problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct DcOpfSolution {
+    outputs: Vec<f64>,
+    angles: Vec<f64>,
+    flows: Vec<f64>,
+}
+
+impl DcOpfSolution {
+    /// The power produced by a generator
+    pub fn output(&self, generator: usize) -> f64 {
+        self.outputs[generator]
+    }
+
+    /// The voltage angle at a bus
+    pub fn angle(&self, bus: usize) -> f64 {
+        self.angles[bus]
+    }
+
+    /// The power flowing through a line, from its `from` bus to its `to` bus
+    pub fn flow(&self, line: usize) -> f64 {
+        self.flows[line]
+    }
+
+    /// The power produced by every generator
+    pub fn outputs(&self) -> &[f64] {
+        &self.outputs
+    }
+
+    /// The power flowing through every line
+    pub fn flows(&self) -> &[f64] {
+        &self.flows
+    }
+
+    /// The total production cost of the dispatch
+    pub fn total_cost(&self, generators: &[Generator]) -> f64 {
+        generators
+            .iter()
+            .zip(&self.outputs)
+            .map(|(gen, output)| gen.cost * output)
+            .sum()
+    }
+}
@@ -0,0 +1,26 @@
+//! The types, traits and macros that most programs using good_lp need,
+//! to be imported at once with `use good_lp::prelude::*;`
+//!
+//! The prelude only grows with items that are needed in most programs,
+//! and items are never removed from it outside of a major version,
+//! so a glob import of the prelude does not break when good_lp is updated.
+//! Specialised items, such as the modelling helpers of the other modules
+//! or the traits of the optional solver features, are imported from their own module.
+//!
+//! ```
+//! use good_lp::prelude::*;
+//!
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+//! let solution = vars
+//!     .maximise(x + 2 * y)
+//!     .using(default_solver)
+//!     .with(constraint!(x + y <= 12))
+//!     .solve()?;
+//! assert_eq!(solution.value(y), 10.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+pub use crate::{
+    constraint, default_solver, variable, variables, Constraint, Expression, IntoAffineExpression,
+    ModelWithMipGap, ModelWithTimeLimit, ProblemVariables, ResolutionError, Solution,
+    SolutionStatus, Solver, SolverModel, Variable, VariableDefinition,
+};
@@ -0,0 +1,210 @@
+//! Probing: fixing the binary variables whose value is implied by the constraints.
+//!
+//! Each binary variable is tentatively fixed to 0, then to 1, and the bounds of the
+//! other variables are propagated through the constraints.
+//! When one of the two values makes the problem infeasible, the variable
+//! is fixed to the other value for good. Strong solvers do this in their presolve,
+//! but fixing the variables upfront helps the weaker backends,
+//! and gives a smaller problem to every solver.
+//!
+//! ```
+//! use good_lp::probing::probe;
+//! use good_lp::{constraint, variable, variables};
+//!
+//! let mut vars = variables!();
+//! let open = vars.add(variable().binary());
+//! let other = vars.add(variable().binary());
+//! let production = vars.add(variable().clamp(0, 100));
+//! let problem = vars
+//!     .minimise(10 * open + other + production)
+//!     // production is only possible when the plant is open
+//!     .with(constraint!(production <= 100 * open))
+//!     // and at least 30 units have to be produced
+//!     .with(constraint!(production >= 30));
+//! let probed = probe(problem)?;
+//! assert_eq!(probed.fixed(), &[(open, 1.)]);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::variable::UnsolvedProblem;
+use crate::{Constraint, ResolutionError, Variable};
+
+/// Constraints are considered violated when they are violated by more than this amount
+const TOLERANCE: f64 = 1e-9;
+
+/// Bounds propagation stops after this number of passes over the constraints,
+/// because continuous bounds can improve by smaller and smaller amounts forever
+const MAX_PASSES: usize = 20;
+
+/// A problem whose implied binary variables were fixed by [probe]
+pub struct Probed {
+    problem: UnsolvedProblem,
+    fixed: Vec<(Variable, f64)>,
+}
+
+impl Probed {
+    /// The variables that were fixed, with their value, in the order in which they were fixed
+    pub fn fixed(&self) -> &[(Variable, f64)] {
+        &self.fixed
+    }
+
+    /// The problem, in which the bounds of the fixed variables are both equal to their value
+    pub fn into_problem(self) -> UnsolvedProblem {
+        self.problem
+    }
+}
+
+/// Fixes the binary variables of the problem that can only take one value.
+/// See the [module documentation](self).
+///
+/// Returns [ResolutionError::Infeasible] when the propagation proves
+/// that the problem has no solution.
+///
+/// Each binary variable requires two propagations over all the constraints:
+/// on large problems, probing can take longer than solving.
+pub fn probe(mut problem: UnsolvedProblem) -> Result<Probed, ResolutionError> {
+    let mut bounds: Vec<(f64, f64)> = problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(_, definition)| (definition.min, definition.max))
+        .collect();
+    let integers: Vec<bool> = problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(_, definition)| definition.is_integer)
+        .collect();
+    let binaries: Vec<usize> = (0..bounds.len())
+        .filter(|&index| integers[index] && bounds[index] == (0., 1.))
+        .collect();
+    let propagation = Propagation {
+        constraints: &problem.constraints,
+        integers: &integers,
+    };
+    propagation.propagate(&mut bounds)?;
+    let mut fixed = vec![];
+    for index in binaries {
+        let feasible_at = |value: f64| {
+            let mut tentative = bounds.clone();
+            tentative[index] = (value, value);
+            propagation.propagate(&mut tentative).is_ok()
+        };
+        let value = match bounds[index] {
+            // Already fixed by the propagation
+            (min, max) if min == max => min,
+            _ => match (feasible_at(0.), feasible_at(1.)) {
+                (true, true) => continue,
+                (false, false) => return Err(ResolutionError::Infeasible),
+                (true, false) => 0.,
+                (false, true) => 1.,
+            },
+        };
+        bounds[index] = (value, value);
+        propagation.propagate(&mut bounds)?;
+        let variable = Variable::at(index);
+        problem.variables.fix(variable, value);
+        fixed.push((variable, value));
+    }
+    Ok(Probed { problem, fixed })
+}
+
+struct Propagation<'a> {
+    constraints: &'a [Constraint],
+    integers: &'a [bool],
+}
+
+impl Propagation<'_> {
+    /// Tightens the bounds until no constraint can tighten them further,
+    /// or returns an error if a constraint cannot be satisfied within the bounds
+    fn propagate(&self, bounds: &mut [(f64, f64)]) -> Result<(), ResolutionError> {
+        for _ in 0..MAX_PASSES {
+            let mut changed = false;
+            for constraint in self.constraints {
+                changed |= self.tighten(constraint, 1., bounds)?;
+                if constraint.is_equality {
+                    changed |= self.tighten(constraint, -1., bounds)?;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Tightens the bounds with `sign * expression <= 0`, and returns whether a bound changed
+    fn tighten(
+        &self,
+        constraint: &Constraint,
+        sign: f64,
+        bounds: &mut [(f64, f64)],
+    ) -> Result<bool, ResolutionError> {
+        let terms: Vec<(usize, f64)> = constraint
+            .expression
+            .linear
+            .coefficients
+            .iter()
+            .map(|(variable, &coefficient)| (variable.index(), sign * coefficient))
+            .collect();
+        // The smallest possible value of the left hand side, without its infinite terms
+        let mut finite_activity = sign * constraint.expression.constant;
+        let mut infinite_terms = 0;
+        for &(index, coefficient) in &terms {
+            let term = lowest(bounds, index, coefficient);
+            if term.is_finite() {
+                finite_activity += term;
+            } else {
+                infinite_terms += 1;
+            }
+        }
+        if infinite_terms == 0 && finite_activity > TOLERANCE * (1. + finite_activity.abs()) {
+            return Err(ResolutionError::Infeasible);
+        }
+        let mut changed = false;
+        for &(index, coefficient) in &terms {
+            if coefficient == 0. {
+                continue;
+            }
+            let term = lowest(bounds, index, coefficient);
+            // The smallest value of the other terms
+            let others = match (infinite_terms, term.is_finite()) {
+                (0, _) => finite_activity - term,
+                (1, false) => finite_activity,
+                _ => continue,
+            };
+            let limit = -others / coefficient;
+            let (min, max) = &mut bounds[index];
+            if coefficient > 0. {
+                let mut new_max = limit;
+                if self.integers[index] {
+                    new_max = (new_max + TOLERANCE).floor();
+                }
+                if new_max < *max - TOLERANCE * (1. + max.abs()) {
+                    *max = new_max;
+                    changed = true;
+                }
+            } else {
+                let mut new_min = limit;
+                if self.integers[index] {
+                    new_min = (new_min - TOLERANCE).ceil();
+                }
+                if new_min > *min + TOLERANCE * (1. + min.abs()) {
+                    *min = new_min;
+                    changed = true;
+                }
+            }
+            if *min > *max + TOLERANCE * (1. + max.abs()) {
+                return Err(ResolutionError::Infeasible);
+            }
+        }
+        Ok(changed)
+    }
+}
+
+/// The smallest value of `coefficient * variable` within the bounds of the variable
+fn lowest(bounds: &[(f64, f64)], index: usize, coefficient: f64) -> f64 {
+    let (min, max) = bounds[index];
+    if coefficient > 0. {
+        coefficient * min
+    } else {
+        coefficient * max
+    }
+}
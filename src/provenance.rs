@@ -0,0 +1,150 @@
+//! Names, versions and origins of models, recorded in the files they are exported to.
+//!
+//! A model file found in an archive months later is only useful if it can be traced back
+//! to the code that produced it. A [ModelMetadata] attached to a problem with
+//! [UnsolvedProblem::with_metadata] is written as comments at the top of
+//! [LP](crate::formats::lp) and [MPS](crate::formats::mps) exports,
+//! and is serialized with the problem when the `serde` cargo feature is enabled.
+//!
+//! ```
+//! use good_lp::provenance::ModelMetadata;
+//! use good_lp::{constraint, variables};
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! variables! {vars: 0 <= x <= 10;}
+//! let metadata = ModelMetadata::new("production_plan")
+//!     .version("2.1.0")
+//!     .git_hash("4f2a9c1")
+//!     .created_at(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+//! let problem = vars.maximise(x).with(constraint!(x <= 3)).with_metadata(metadata);
+//! let lp = problem.to_lp();
+//! assert!(lp.starts_with("\\ Problem name: production_plan\n"));
+//! assert!(lp.contains("\\ Version: 2.1.0\n"));
+//! assert!(lp.contains("\\ Git hash: 4f2a9c1\n"));
+//! assert!(lp.contains("\\ Created: 2023-11-14T22:13:20Z\n"));
+//! assert!(problem.to_mps().contains("* Version: 2.1.0\n"));
+//! ```
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::variable::UnsolvedProblem;
+
+/// The name, version and origin of a model
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelMetadata {
+    /// The name of the model
+    pub name: String,
+    /// The version of the model, or of the application that built it
+    pub version: Option<String>,
+    /// The hash of the commit of the code that built the model
+    pub git_hash: Option<String>,
+    /// When the model was built
+    pub created: SystemTime,
+}
+
+impl ModelMetadata {
+    /// Creates metadata for a model with the given name, created now
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        ModelMetadata {
+            name: name.into(),
+            version: None,
+            git_hash: None,
+            created: SystemTime::now(),
+        }
+    }
+
+    /// Sets the version of the model
+    pub fn version<S: Into<String>>(mut self, version: S) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the hash of the commit that built the model,
+    /// for instance from an environment variable set by the build script
+    pub fn git_hash<S: Into<String>>(mut self, git_hash: S) -> Self {
+        self.git_hash = Some(git_hash.into());
+        self
+    }
+
+    /// Sets the creation time of the model, instead of the time the metadata was created
+    pub fn created_at(mut self, created: SystemTime) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// The lines describing the model after its name, to be written as comments
+    pub(crate) fn comment_lines(&self) -> Vec<String> {
+        let mut lines = vec![];
+        if let Some(version) = &self.version {
+            lines.push(format!("Version: {}", version));
+        }
+        if let Some(git_hash) = &self.git_hash {
+            lines.push(format!("Git hash: {}", git_hash));
+        }
+        lines.push(format!("Created: {}", rfc3339(self.created)));
+        lines
+    }
+}
+
+impl UnsolvedProblem {
+    /// Attaches metadata to the problem, replacing the previous one
+    pub fn with_metadata(mut self, metadata: ModelMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// The metadata attached to the problem, if any
+    pub fn metadata(&self) -> Option<&ModelMetadata> {
+        self.metadata.as_ref()
+    }
+}
+
+/// Formats a time as a UTC date and time, with a precision of one second,
+/// such as `2023-11-14T22:13:20Z`. Times before 1970 are written as 1970-01-01.
+fn rfc3339(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (days, second_of_day) = (seconds / 86400, seconds % 86400);
+    // Converts a number of days since 1970-01-01 to a date in the Gregorian calendar,
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let shifted = days + 719_468;
+    let era = shifted / 146_097;
+    let day_of_era = shifted % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        second_of_day / 3600,
+        second_of_day % 3600 / 60,
+        second_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::rfc3339;
+
+    #[test]
+    fn dates() {
+        let at = |seconds| rfc3339(UNIX_EPOCH + Duration::from_secs(seconds));
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(1_709_251_199), "2024-02-29T23:59:59Z");
+        assert_eq!(at(4_102_444_800), "2100-01-01T00:00:00Z");
+    }
+}
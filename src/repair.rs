@@ -0,0 +1,261 @@
+//! Repairing a solution after the data of a problem changed.
+//!
+//! In interactive planning tools, users edit a few values of a problem,
+//! and expect the plan to adapt with as few changes as possible,
+//! rather than to be replaced by an unrelated optimal plan.
+//! [min_change] finds the feasible solution of the modified problem
+//! that minimises the weighted deviation from the old solution:
+//! `sum(weight(x) * |x - old(x)|)`.
+//!
+//! The modified problem must be built with its variables in the same order as
+//! the original one, so that each variable keeps its meaning.
+//!
+//! ```
+//! use good_lp::repair::min_change;
+//! use good_lp::{constraint, default_solver, variables, Solution};
+//! use std::collections::HashMap;
+//!
+//! // Three workers share 9 hours of work, and worker 0 was given 5 hours
+//! variables! {vars: 0 <= hours[3] <= 5;}
+//! let old_plan: HashMap<_, _> = hours.iter().copied().zip(vec![5., 3., 1.]).collect();
+//! // The user edits the data: worker 0 can only do 2 hours
+//! let total: good_lp::Expression = hours.iter().sum();
+//! let edited = vars
+//!     .minimise(0)
+//!     .with(constraint!(total == 9))
+//!     .with(constraint!(hours[0] <= 2));
+//! let repaired = min_change(edited, &old_plan)
+//!     .weight(hours[1], 2.) // Worker 1's plan is harder to change
+//!     .solve(default_solver)?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(repaired.value(hours[0]), 2.);
+//! assert_float_eq(repaired.value(hours[1]), 3.);
+//! assert_float_eq(repaired.value(hours[2]), 4.);
+//! assert_float_eq(repaired.deviation(), 6.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+//!
+//! [project] finds the feasible point that is the closest to an arbitrary target,
+//! such as a plan entered by hand, under the [Distance] of choice.
+//!
+//! ```
+//! use good_lp::repair::{project, Distance};
+//! use good_lp::{constraint, default_solver, variables, Solution};
+//! use std::collections::HashMap;
+//!
+//! variables! {vars: 0 <= x; 0 <= y;}
+//! let problem = vars.minimise(x + y).with(constraint!(x + y <= 4));
+//! let target: HashMap<_, _> = vec![(x, 5.), (y, 1.)].into_iter().collect();
+//! // Under the L∞ distance, the largest change is as small as possible
+//! let projected = project(problem, &target, Distance::LInfinity).solve(default_solver)?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(projected.value(x), 4.);
+//! assert_float_eq(projected.value(y), 0.);
+//! assert_float_eq(projected.deviation(), 1.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::collections::HashMap;
+
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{constraint, variable, Expression, Solution, Solver, SolverModel, Variable};
+
+/// How the deviation from the old values is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distance {
+    /// The sum of the weighted changes: `sum(weight(x) * |x - old(x)|)`
+    L1,
+    /// The largest weighted change: `max(weight(x) * |x - old(x)|)`
+    LInfinity,
+}
+
+/// A repair problem, created by [min_change] or [project]
+pub struct Repair {
+    problem: UnsolvedProblem,
+    old_values: Vec<f64>,
+    weights: HashMap<Variable, f64>,
+    objective_weight: f64,
+    distance: Distance,
+}
+
+/// Prepares the search for the solution of `problem` that is the closest to `old_solution`.
+/// `old_solution` must have a value for every variable of the problem.
+///
+/// By default, all variables have a weight of 1, and the objective of the problem is ignored.
+pub fn min_change<S: Solution>(problem: UnsolvedProblem, old_solution: &S) -> Repair {
+    let old_values = problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(var, _)| old_solution.value(var))
+        .collect();
+    Repair {
+        problem,
+        old_values,
+        weights: HashMap::new(),
+        objective_weight: 0.,
+        distance: Distance::L1,
+    }
+}
+
+/// Prepares the search for the feasible solution of `problem` that is the closest to `target`,
+/// under the given distance.
+/// Variables that have no value in `target` can take any value.
+pub fn project(
+    problem: UnsolvedProblem,
+    target: &HashMap<Variable, f64>,
+    distance: Distance,
+) -> Repair {
+    let mut weights = HashMap::new();
+    let old_values = problem
+        .variables
+        .iter_variables_with_def()
+        .map(|(var, _)| match target.get(&var) {
+            Some(&value) => value,
+            None => {
+                weights.insert(var, 0.);
+                0.
+            }
+        })
+        .collect();
+    Repair {
+        problem,
+        old_values,
+        weights,
+        objective_weight: 0.,
+        distance,
+    }
+}
+
+impl Repair {
+    /// Sets the cost of changing the value of the variable by one unit.
+    /// A weight of 0 lets the variable change freely.
+    pub fn weight(mut self, variable: Variable, weight: f64) -> Self {
+        self.weights.insert(variable, weight);
+        self
+    }
+
+    /// Sets how the deviation is measured. The default is [Distance::L1].
+    pub fn distance(mut self, distance: Distance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Also takes the objective of the problem into account, with the given weight
+    /// relative to the deviation. With a small weight, the objective breaks the ties
+    /// between the solutions that are equally close to the old one.
+    pub fn objective_weight(mut self, weight: f64) -> Self {
+        self.objective_weight = weight;
+        self
+    }
+
+    /// Builds the problem that minimises the deviation.
+    /// The second element is the expression of the weighted deviation.
+    pub fn into_problem(self) -> (UnsolvedProblem, Expression) {
+        let Repair {
+            problem,
+            old_values,
+            weights,
+            objective_weight,
+            distance,
+        } = self;
+        let UnsolvedProblem {
+            objective,
+            direction,
+            mut variables,
+            mut constraints,
+            ..
+        } = problem;
+        let originals: Vec<Variable> = variables
+            .iter_variables_with_def()
+            .map(|(v, _)| v)
+            .collect();
+        let largest = match distance {
+            Distance::L1 => None,
+            Distance::LInfinity => Some(variables.add(variable().min(0))),
+        };
+        let mut deviation = Expression::with_capacity(2 * originals.len());
+        for (var, old) in originals.into_iter().zip(old_values) {
+            let weight = weights.get(&var).copied().unwrap_or(1.);
+            if weight == 0. {
+                continue;
+            }
+            let above = variables.add(variable().min(0));
+            let below = variables.add(variable().min(0));
+            constraints.push(constraint!(var - old == above - below));
+            match largest {
+                None => {
+                    deviation.add_mul(weight, above);
+                    deviation.add_mul(weight, below);
+                }
+                Some(largest) => constraints.push(constraint!(weight * (above + below) <= largest)),
+            }
+        }
+        if let Some(largest) = largest {
+            deviation += largest;
+        }
+        let sign = match direction {
+            ObjectiveDirection::Minimisation => 1.,
+            ObjectiveDirection::Maximisation => -1.,
+        };
+        let mut repair_objective = deviation.clone();
+        repair_objective.add_mul(sign * objective_weight, objective);
+        let mut repaired = variables.minimise(repair_objective);
+        repaired.constraints = constraints;
+        (repaired, deviation)
+    }
+
+    /// Finds the closest solution to the old one
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<RepairedSolution, <S::Model as SolverModel>::Error> {
+        let n = self.old_values.len();
+        let old_values = self.old_values.clone();
+        let (problem, deviation) = self.into_problem();
+        let variables: Vec<Variable> = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(v, _)| v)
+            .take(n)
+            .collect();
+        let solution = problem.using(solver).solve()?;
+        Ok(RepairedSolution {
+            values: variables.iter().map(|&v| solution.value(v)).collect(),
+            variables,
+            old_values,
+            deviation: solution.eval(&deviation),
+        })
+    }
+}
+
+/// The solution of a [Repair] problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairedSolution {
+    variables: Vec<Variable>,
+    values: Vec<f64>,
+    old_values: Vec<f64>,
+    deviation: f64,
+}
+
+impl RepairedSolution {
+    /// The weighted deviation from the old solution, measured with the chosen [Distance]
+    pub fn deviation(&self) -> f64 {
+        self.deviation
+    }
+
+    /// The variables whose value changed by more than `tolerance`
+    pub fn changed(&self, tolerance: f64) -> Vec<Variable> {
+        self.variables
+            .iter()
+            .zip(self.values.iter().zip(&self.old_values))
+            .filter(|(_, (new, old))| (*new - *old).abs() > tolerance)
+            .map(|(&var, _)| var)
+            .collect()
+    }
+}
+
+impl Solution for RepairedSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+}
@@ -0,0 +1,485 @@
+//! Human-readable reports on a solved problem, in Markdown or HTML.
+//!
+//! A report contains the status and objective value of the solution,
+//! statistics about the model, the variables that contribute the most to the objective,
+//! the constraints that are binding, the highest dual values if they are given,
+//! and a check that the solution satisfies all the bounds and constraints.
+//! The HTML report is a single self-contained page.
+//!
+//! ```
+//! use good_lp::report::{generate, ReportFormat, ReportOptions};
+//! use good_lp::{constraint, default_solver, variable, variables, SolverModel};
+//!
+//! let mut vars = variables!();
+//! let chairs = vars.add(variable().min(0).name("chairs"));
+//! let tables = vars.add(variable().min(0).name("tables"));
+//! let problem = vars
+//!     .maximise(70 * chairs + 50 * tables)
+//!     .with(constraint!(4 * chairs + 3 * tables <= 240).set_name("carpentry"))
+//!     .with(constraint!(2 * chairs + tables <= 100).set_name("painting"));
+//! let solution = problem.clone().using(default_solver).solve()?;
+//! let options = ReportOptions::default().title("Furniture production");
+//! let report = generate(&problem, &solution, &options);
+//! assert!(report.starts_with("# Furniture production"));
+//! assert!(report.contains("| carpentry |"));
+//! assert!(report.contains("No violation"));
+//!
+//! let html = generate(&problem, &solution, &options.format(ReportFormat::Html));
+//! assert!(html.starts_with("<!DOCTYPE html>"));
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::fmt::Write;
+
+use crate::feasibility::{check_solution, Requirement};
+use crate::formats::unique_names;
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{Constraint, Solution};
+
+/// The format of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportFormat {
+    /// [Markdown](https://commonmark.org/), with tables
+    Markdown,
+    /// A self-contained HTML page
+    Html,
+}
+
+/// What to include in a report, and how to write it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportOptions {
+    format: ReportFormat,
+    title: String,
+    top: usize,
+    tolerance: f64,
+    duals: Option<Vec<f64>>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        ReportOptions {
+            format: ReportFormat::Markdown,
+            title: "Solve report".to_string(),
+            top: 10,
+            tolerance: 1e-6,
+            duals: None,
+        }
+    }
+}
+
+impl ReportOptions {
+    /// The format of the report. Defaults to Markdown.
+    pub fn format(mut self, format: ReportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The title of the report
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// The maximum number of rows of each table. Defaults to 10.
+    pub fn top(mut self, top: usize) -> Self {
+        self.top = top;
+        self
+    }
+
+    /// The tolerance used to decide whether a constraint is binding or violated.
+    /// Defaults to `1e-6`.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The dual value of each constraint, in the order they were added to the problem,
+    /// for solvers that return them. The highest ones are listed in the report.
+    pub fn duals(mut self, duals: Vec<f64>) -> Self {
+        self.duals = Some(duals);
+        self
+    }
+}
+
+/// A section of a report, before it is written in a given format
+struct Section {
+    title: &'static str,
+    text: Vec<String>,
+    headers: &'static [&'static str],
+    rows: Vec<Vec<String>>,
+}
+
+impl Section {
+    fn new(title: &'static str) -> Self {
+        Section {
+            title,
+            text: vec![],
+            headers: &[],
+            rows: vec![],
+        }
+    }
+}
+
+/// Formats a number with at most 6 decimals, without trailing zeros
+fn number(x: f64) -> String {
+    if x.is_infinite() || x.is_nan() {
+        return x.to_string();
+    }
+    let s = format!("{:.6}", x);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// The value of the left-hand side of the constraint, and its right-hand side
+fn sides<S: Solution>(constraint: &Constraint, solution: &S) -> (f64, f64) {
+    let rhs = -constraint.expression.constant;
+    (solution.eval(&constraint.expression) + rhs, rhs)
+}
+
+/// Writes a report on the solution of a problem
+pub fn generate<S: Solution>(
+    problem: &UnsolvedProblem,
+    solution: &S,
+    options: &ReportOptions,
+) -> String {
+    let variable_names = unique_names(
+        problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(_, def)| Some(def.get_name())),
+        "v",
+        &[],
+        str::to_string,
+    );
+    let constraint_names = unique_names(
+        problem.constraints.iter().map(|c| c.get_name()),
+        "c",
+        &[],
+        str::to_string,
+    );
+    let tol = options.tolerance;
+    let sections = vec![
+        summary(problem, solution),
+        model_statistics(problem),
+        objective_breakdown(problem, solution, &variable_names, options.top),
+        binding_constraints(problem, solution, &constraint_names, options),
+        top_duals(&constraint_names, options),
+        violations(problem, solution, &variable_names, &constraint_names, tol),
+    ];
+    let sections: Vec<Section> = sections.into_iter().flatten().collect();
+    match options.format {
+        ReportFormat::Markdown => markdown(&options.title, &sections),
+        ReportFormat::Html => html(&options.title, &sections),
+    }
+}
+
+fn summary<S: Solution>(problem: &UnsolvedProblem, solution: &S) -> Option<Section> {
+    let mut section = Section::new("Summary");
+    section.headers = &["", ""];
+    let direction = match problem.direction {
+        ObjectiveDirection::Maximisation => "maximise",
+        ObjectiveDirection::Minimisation => "minimise",
+    };
+    let mut rows = vec![
+        vec!["Status".to_string(), format!("{:?}", solution.status())],
+        vec!["Direction".to_string(), direction.to_string()],
+        vec![
+            "Objective".to_string(),
+            number(solution.eval(&problem.objective)),
+        ],
+    ];
+    if let Some(bound) = solution.best_bound() {
+        rows.push(vec!["Best bound".to_string(), number(bound)]);
+    }
+    let stats = solution.stats();
+    if let Some(time) = stats.time {
+        rows.push(vec!["Solve time".to_string(), format!("{:?}", time)]);
+    }
+    if let Some(iterations) = stats.iterations {
+        rows.push(vec!["Iterations".to_string(), iterations.to_string()]);
+    }
+    if let Some(nodes) = stats.nodes {
+        rows.push(vec!["Nodes".to_string(), nodes.to_string()]);
+    }
+    section.rows = rows;
+    Some(section)
+}
+
+fn model_statistics(problem: &UnsolvedProblem) -> Option<Section> {
+    let mut section = Section::new("Model");
+    section.headers = &["", ""];
+    let (mut integers, mut binaries) = (0, 0);
+    for (_, def) in problem.variables.iter_variables_with_def() {
+        if def.is_integer {
+            if def.min >= 0. && def.max <= 1. {
+                binaries += 1;
+            } else {
+                integers += 1;
+            }
+        }
+    }
+    let n = problem.variables.len();
+    let equalities = problem.constraints.iter().filter(|c| c.is_equality).count();
+    let nonzeros: usize = problem
+        .constraints
+        .iter()
+        .map(|c| c.expression.linear.coefficients.len())
+        .sum();
+    section.rows = vec![
+        vec!["Variables".to_string(), n.to_string()],
+        vec![
+            "Continuous variables".to_string(),
+            (n - integers - binaries).to_string(),
+        ],
+        vec!["Integer variables".to_string(), integers.to_string()],
+        vec!["Binary variables".to_string(), binaries.to_string()],
+        vec![
+            "Constraints".to_string(),
+            problem.constraints.len().to_string(),
+        ],
+        vec!["Equality constraints".to_string(), equalities.to_string()],
+        vec!["Non-zero coefficients".to_string(), nonzeros.to_string()],
+    ];
+    Some(section)
+}
+
+fn objective_breakdown<S: Solution>(
+    problem: &UnsolvedProblem,
+    solution: &S,
+    names: &[String],
+    top: usize,
+) -> Option<Section> {
+    let mut terms: Vec<(usize, f64, f64, f64)> = problem
+        .objective
+        .linear
+        .coefficients
+        .iter()
+        .map(|(&var, &coefficient)| {
+            let value = solution.value(var);
+            (var.index(), coefficient, value, coefficient * value)
+        })
+        .filter(|&(_, _, _, contribution)| contribution != 0.)
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    terms.sort_by(|a, b| b.3.abs().total_cmp(&a.3.abs()).then(a.0.cmp(&b.0)));
+    let total: f64 = terms.iter().map(|t| t.3.abs()).sum();
+    let mut section = Section::new("Objective breakdown");
+    section.headers = &["Variable", "Coefficient", "Value", "Contribution", "Share"];
+    if problem.objective.constant != 0. {
+        section.text.push(format!(
+            "The objective has a constant term of {}.",
+            number(problem.objective.constant)
+        ));
+    }
+    if terms.len() > top {
+        section.text.push(format!(
+            "The {} largest of {} contributions:",
+            top,
+            terms.len()
+        ));
+    }
+    section.rows = terms
+        .iter()
+        .take(top)
+        .map(|&(i, coefficient, value, contribution)| {
+            vec![
+                names[i].clone(),
+                number(coefficient),
+                number(value),
+                number(contribution),
+                format!("{:.1}%", 100. * contribution.abs() / total),
+            ]
+        })
+        .collect();
+    Some(section)
+}
+
+fn binding_constraints<S: Solution>(
+    problem: &UnsolvedProblem,
+    solution: &S,
+    names: &[String],
+    options: &ReportOptions,
+) -> Option<Section> {
+    let binding: Vec<(usize, f64, f64)> = problem
+        .constraints
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_equality)
+        .filter_map(|(i, c)| {
+            let (lhs, rhs) = sides(c, solution);
+            if (lhs - rhs).abs() <= options.tolerance {
+                Some((i, lhs, rhs))
+            } else {
+                None
+            }
+        })
+        .collect();
+    let mut section = Section::new("Binding constraints");
+    if binding.is_empty() {
+        section
+            .text
+            .push("No inequality constraint is binding.".to_string());
+        return Some(section);
+    }
+    section.headers = &["Constraint", "Activity", "Bound"];
+    section.text.push(format!(
+        "{} of {} inequality constraints are binding.",
+        binding.len(),
+        problem
+            .constraints
+            .iter()
+            .filter(|c| !c.is_equality)
+            .count()
+    ));
+    section.rows = binding
+        .iter()
+        .take(options.top)
+        .map(|&(i, lhs, rhs)| vec![names[i].clone(), number(lhs), number(rhs)])
+        .collect();
+    Some(section)
+}
+
+fn top_duals(names: &[String], options: &ReportOptions) -> Option<Section> {
+    let duals = options.duals.as_ref()?;
+    let mut sorted: Vec<(usize, f64)> = duals.iter().copied().enumerate().collect();
+    sorted.retain(|&(i, d)| i < names.len() && d.abs() > options.tolerance);
+    sorted.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()).then(a.0.cmp(&b.0)));
+    let mut section = Section::new("Dual values");
+    if sorted.is_empty() {
+        section.text.push("All dual values are zero.".to_string());
+        return Some(section);
+    }
+    section.headers = &["Constraint", "Dual value"];
+    section.rows = sorted
+        .iter()
+        .take(options.top)
+        .map(|&(i, d)| vec![names[i].clone(), number(d)])
+        .collect();
+    Some(section)
+}
+
+fn violations<S: Solution>(
+    problem: &UnsolvedProblem,
+    solution: &S,
+    variable_names: &[String],
+    constraint_names: &[String],
+    tolerance: f64,
+) -> Option<Section> {
+    let rows: Vec<Vec<String>> = check_solution(problem, solution, tolerance)
+        .violations
+        .into_iter()
+        .map(|violation| {
+            let (name, kind) = match violation.requirement {
+                Requirement::LowerBound(var) => (&variable_names[var.index()], "lower bound"),
+                Requirement::UpperBound(var) => (&variable_names[var.index()], "upper bound"),
+                Requirement::Integrality(var) => (&variable_names[var.index()], "integrality"),
+                Requirement::Constraint(c) => (&constraint_names[c.index], "constraint"),
+            };
+            vec![name.clone(), kind.to_string(), number(violation.amount)]
+        })
+        .collect();
+    let mut section = Section::new("Violations");
+    if rows.is_empty() {
+        section.text.push(format!(
+            "No violation: all bounds, integrality requirements and constraints \
+            are satisfied, with a tolerance of {}.",
+            tolerance
+        ));
+    } else {
+        section.text.push(format!(
+            "{} violations larger than {}:",
+            rows.len(),
+            tolerance
+        ));
+        section.headers = &["Element", "Kind", "Violation"];
+        section.rows = rows;
+    }
+    Some(section)
+}
+
+/// Escapes the characters that would break a Markdown table cell
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn markdown(title: &str, sections: &[Section]) -> String {
+    let mut out = String::new();
+    writeln!(out, "# {}", title).unwrap();
+    for section in sections {
+        writeln!(out, "\n## {}\n", section.title).unwrap();
+        for text in &section.text {
+            writeln!(out, "{}\n", text).unwrap();
+        }
+        if section.rows.is_empty() {
+            continue;
+        }
+        let headers: Vec<String> = section.headers.iter().map(|h| escape_markdown(h)).collect();
+        writeln!(out, "| {} |", headers.join(" | ")).unwrap();
+        writeln!(out, "|{}", "---|".repeat(headers.len())).unwrap();
+        for row in &section.rows {
+            let cells: Vec<String> = row.iter().map(|c| escape_markdown(c)).collect();
+            writeln!(out, "| {} |", cells.join(" | ")).unwrap();
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html(title: &str, sections: &[Section]) -> String {
+    let mut out = String::new();
+    let title = escape_html(title);
+    writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+    )
+    .unwrap();
+    writeln!(out, "<title>{}</title>", title).unwrap();
+    writeln!(
+        out,
+        "<style>body {{ font-family: sans-serif; margin: 2em; }} \
+        table {{ border-collapse: collapse; }} \
+        td, th {{ border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }}</style>"
+    )
+    .unwrap();
+    writeln!(out, "</head>\n<body>\n<h1>{}</h1>", title).unwrap();
+    for section in sections {
+        writeln!(out, "<h2>{}</h2>", escape_html(section.title)).unwrap();
+        for text in &section.text {
+            writeln!(out, "<p>{}</p>", escape_html(text)).unwrap();
+        }
+        if section.rows.is_empty() {
+            continue;
+        }
+        writeln!(out, "<table>").unwrap();
+        if section.headers.iter().any(|h| !h.is_empty()) {
+            let headers: Vec<String> = section
+                .headers
+                .iter()
+                .map(|h| format!("<th>{}</th>", escape_html(h)))
+                .collect();
+            writeln!(out, "<tr>{}</tr>", headers.concat()).unwrap();
+        }
+        for row in &section.rows {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|c| format!("<td>{}</td>", escape_html(c)))
+                .collect();
+            writeln!(out, "<tr>{}</tr>", cells.concat()).unwrap();
+        }
+        writeln!(out, "</table>").unwrap();
+    }
+    writeln!(out, "</body>\n</html>").unwrap();
+    out
+}
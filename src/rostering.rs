@@ -0,0 +1,301 @@
+//! Crew and shift [rostering](https://en.wikipedia.org/wiki/Nurse_scheduling_problem):
+//! assigning workers to shifts over a planning horizon.
+//!
+//! The problem uses one binary variable per worker, day and shift,
+//! equal to 1 when the worker works that shift on that day.
+//! A worker works at most one shift per day. On top of that, a [Roster] can require:
+//!  - a minimum number of workers on each shift ([Roster::coverage]),
+//!  - a maximum number of consecutive working days ([Roster::max_consecutive_days]),
+//!  - rest periods between two shifts on consecutive days ([Roster::forbid_sequence]),
+//!  - days off ([Roster::unavailable]).
+//!
+//! The objective minimises the total number of assigned shifts,
+//! plus a [Fairness] term that balances the workload between workers.
+//! This is a mixed integer program: it requires a solver that supports integer variables.
+//!
+//! ```no_run
+//! use good_lp::default_solver;
+//! use good_lp::rostering::{Fairness, Roster};
+//! // 3 workers, 4 days, 2 shifts per day (0 = early, 1 = late)
+//! let roster = Roster::new(3, 4, 2)
+//!     .coverage_every_day(0, 1)
+//!     .coverage_every_day(1, 1)
+//!     .max_consecutive_days(3)
+//!     .forbid_sequence(1, 0) // no early shift right after a late one
+//!     .fairness(Fairness::MinimiseMaximum, 1.)
+//!     .solve(default_solver)?;
+//! assert_eq!(roster.total_shifts(), 8);
+//! assert!((0..3).all(|w| roster.shifts_of(w) <= 3));
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::constraint::Constraint;
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Expression, ProblemVariables, Solution, Solver, SolverModel, Variable,
+};
+
+/// How the workload is balanced between workers
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Fairness {
+    /// The workload is not balanced
+    None,
+    /// Minimise the number of shifts of the busiest worker
+    MinimiseMaximum,
+    /// Minimise the difference between the number of shifts of the busiest
+    /// and of the least busy worker
+    MinimiseRange,
+}
+
+/// A rostering problem, built with [Roster::new]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Roster {
+    n_workers: usize,
+    n_days: usize,
+    n_shifts: usize,
+    /// `coverage[day][shift]`
+    coverage: Vec<Vec<usize>>,
+    max_consecutive_days: Option<usize>,
+    forbidden_sequences: Vec<(usize, usize)>,
+    unavailable: Vec<(usize, usize)>,
+    fairness: Fairness,
+    fairness_weight: f64,
+}
+
+/// The number of variables that are equal to 1 among binary variables
+fn count<'a>(variables: impl IntoIterator<Item = &'a Variable>) -> Expression {
+    variables.into_iter().sum()
+}
+
+/// At most `k` of the given binary variables are equal to 1
+fn at_most<'a>(variables: impl IntoIterator<Item = &'a Variable>, k: usize) -> Constraint {
+    constraint!(count(variables) <= k as f64)
+}
+
+/// At least `k` of the given binary variables are equal to 1
+fn at_least<'a>(variables: impl IntoIterator<Item = &'a Variable>, k: usize) -> Constraint {
+    constraint!(count(variables) >= k as f64)
+}
+
+impl Roster {
+    /// Creates a rostering problem with the given number of workers, days, and shifts per day.
+    /// Initially, no shift needs to be covered.
+    pub fn new(n_workers: usize, n_days: usize, n_shifts: usize) -> Self {
+        Roster {
+            n_workers,
+            n_days,
+            n_shifts,
+            coverage: vec![vec![0; n_shifts]; n_days],
+            max_consecutive_days: None,
+            forbidden_sequences: vec![],
+            unavailable: vec![],
+            fairness: Fairness::None,
+            fairness_weight: 0.,
+        }
+    }
+
+    /// Require at least `workers` workers on the given shift of the given day
+    pub fn coverage(mut self, day: usize, shift: usize, workers: usize) -> Self {
+        self.coverage[day][shift] = workers;
+        self
+    }
+
+    /// Require at least `workers` workers on the given shift, every day
+    pub fn coverage_every_day(mut self, shift: usize, workers: usize) -> Self {
+        for day in self.coverage.iter_mut() {
+            day[shift] = workers;
+        }
+        self
+    }
+
+    /// Workers cannot work more than `days` days in a row
+    pub fn max_consecutive_days(mut self, days: usize) -> Self {
+        self.max_consecutive_days = Some(days);
+        self
+    }
+
+    /// A worker cannot work `next_shift` on the day after working `shift`.
+    /// This is used to enforce rest periods, for instance between a night and a morning shift.
+    pub fn forbid_sequence(mut self, shift: usize, next_shift: usize) -> Self {
+        self.forbidden_sequences.push((shift, next_shift));
+        self
+    }
+
+    /// The worker cannot work on the given day
+    pub fn unavailable(mut self, worker: usize, day: usize) -> Self {
+        self.unavailable.push((worker, day));
+        self
+    }
+
+    /// Balance the workload between workers.
+    /// The fairness term, multiplied by `weight`, is added to the total number of assigned shifts.
+    pub fn fairness(mut self, fairness: Fairness, weight: f64) -> Self {
+        self.fairness = fairness;
+        self.fairness_weight = weight;
+        self
+    }
+
+    /// Builds the problem. Its variables and constraints can be inspected and extended
+    /// before it is solved.
+    pub fn problem(&self) -> RosterProblem {
+        let mut vars = ProblemVariables::new();
+        let assignments: Vec<Vec<Vec<Variable>>> = (0..self.n_workers)
+            .map(|w| {
+                (0..self.n_days)
+                    .map(|d| {
+                        (0..self.n_shifts)
+                            .map(|s| {
+                                vars.add(variable().binary().name(format!("x_{}_{}_{}", w, d, s)))
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let workload: Vec<Expression> = assignments
+            .iter()
+            .map(|days| count(days.iter().flatten()))
+            .collect();
+        let mut objective: Expression = workload.iter().sum();
+        let mut fairness_constraints = vec![];
+        if self.fairness != Fairness::None && self.n_workers > 0 {
+            let max = vars.add(variable().min(0).name("max_shifts"));
+            objective.add_mul(self.fairness_weight, max);
+            for load in &workload {
+                fairness_constraints.push(constraint!(load.clone() <= max));
+            }
+            if self.fairness == Fairness::MinimiseRange {
+                let min = vars.add(variable().min(0).name("min_shifts"));
+                objective.add_mul(-self.fairness_weight, min);
+                for load in &workload {
+                    fairness_constraints.push(constraint!(load.clone() >= min));
+                }
+            }
+        }
+        let mut problem = vars.minimise(objective);
+        for c in fairness_constraints {
+            problem.add_constraint(c);
+        }
+
+        for (w, days) in assignments.iter().enumerate() {
+            for (d, shifts) in days.iter().enumerate() {
+                problem
+                    .add_constraint(at_most(shifts, 1).set_name(format!("one_shift_{}_{}", w, d)));
+            }
+            if let Some(k) = self.max_consecutive_days {
+                for (d, window) in days.windows(k + 1).enumerate() {
+                    problem.add_constraint(
+                        at_most(window.iter().flatten(), k)
+                            .set_name(format!("consecutive_{}_{}", w, d)),
+                    );
+                }
+            }
+            for (d, pair) in days.windows(2).enumerate() {
+                for &(shift, next_shift) in &self.forbidden_sequences {
+                    problem.add_constraint(
+                        at_most([&pair[0][shift], &pair[1][next_shift]], 1)
+                            .set_name(format!("rest_{}_{}_{}_{}", w, d, shift, next_shift)),
+                    );
+                }
+            }
+        }
+        for &(w, d) in &self.unavailable {
+            problem.add_constraint(
+                at_most(&assignments[w][d], 0).set_name(format!("unavailable_{}_{}", w, d)),
+            );
+        }
+        for (d, shifts) in self.coverage.iter().enumerate() {
+            for (s, &workers) in shifts.iter().enumerate() {
+                if workers > 0 {
+                    let on_shift = assignments.iter().map(|days| &days[d][s]);
+                    problem.add_constraint(
+                        at_least(on_shift, workers).set_name(format!("coverage_{}_{}", d, s)),
+                    );
+                }
+            }
+        }
+        RosterProblem {
+            problem,
+            assignments,
+        }
+    }
+
+    /// Finds a roster that covers all shifts
+    pub fn solve<S: Solver>(
+        &self,
+        solver: S,
+    ) -> Result<RosterSolution, <S::Model as SolverModel>::Error> {
+        self.problem().solve(solver)
+    }
+}
+
+/// The optimisation problem built from a [Roster]
+pub struct RosterProblem {
+    problem: UnsolvedProblem,
+    /// `assignments[worker][day][shift]`
+    assignments: Vec<Vec<Vec<Variable>>>,
+}
+
+impl RosterProblem {
+    /// The binary variable that is 1 when the worker works the shift on the given day
+    pub fn assignment_variable(&self, worker: usize, day: usize, shift: usize) -> Variable {
+        self.assignments[worker][day][shift]
+    }
+
+    /// The underlying problem, to which additional constraints can be added
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Finds the optimal roster
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<RosterSolution, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        let shifts = self
+            .assignments
+            .iter()
+            .map(|days| {
+                days.iter()
+                    .map(|shifts| shifts.iter().position(|&x| solution.value(x) > 0.5))
+                    .collect()
+            })
+            .collect();
+        Ok(RosterSolution { shifts })
+    }
+}
+
+/// The shift each worker works on each day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RosterSolution {
+    shifts: Vec<Vec<Option<usize>>>,
+}
+
+impl RosterSolution {
+    /// The shift the worker works on the given day, or `None` on a day off
+    pub fn shift(&self, worker: usize, day: usize) -> Option<usize> {
+        self.shifts[worker][day]
+    }
+
+    /// The shift of every worker on every day, indexed by `[worker][day]`
+    pub fn shifts(&self) -> &[Vec<Option<usize>>] {
+        &self.shifts
+    }
+
+    /// The workers on the given shift of the given day
+    pub fn workers_on(&self, day: usize, shift: usize) -> Vec<usize> {
+        (0..self.shifts.len())
+            .filter(|&w| self.shifts[w][day] == Some(shift))
+            .collect()
+    }
+
+    /// The number of shifts assigned to a worker
+    pub fn shifts_of(&self, worker: usize) -> usize {
+        self.shifts[worker].iter().flatten().count()
+    }
+
+    /// The total number of assigned shifts
+    pub fn total_shifts(&self) -> usize {
+        (0..self.shifts.len()).map(|w| self.shifts_of(w)).sum()
+    }
+}
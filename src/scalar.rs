@@ -0,0 +1,365 @@
+//! Expressions with coefficients of another numeric type than `f64`.
+//!
+//! [Expression] stores its coefficients as `f64`, which is what every solver takes.
+//! A [ScalarExpression] keeps its coefficients in the type they were given in,
+//! such as `f32`, a rational number (with the `exact` feature)
+//! or a [Decimal](https://docs.rs/rust_decimal) (with the `decimal` feature),
+//! and all the arithmetic on it is done in that type.
+//! It is converted to `f64` coefficients only when it is given to a problem:
+//! it can be used as an expression in [constraint!](crate::constraint!), as an objective,
+//! or with variables and other expressions.
+//! Terms are created with [ScalarExpression::term], and constants added to a
+//! [ScalarExpression] or compared to it must be of the same type `T`.
+//! [ScalarExpression::to_expression] does the conversion explicitly, and fails
+//! instead of producing an infinite or NaN coefficient
+//! when a value cannot be represented as a finite `f64`.
+//!
+//! ```
+//! use good_lp::scalar::ScalarExpression;
+//! use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+//!
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+//! let cost = ScalarExpression::term(x, 1.5f32) + ScalarExpression::term(y, 2.5f32);
+//! let solution = vars
+//!     .maximise(x + y)
+//!     .using(default_solver)
+//!     .with(constraint!(cost <= 20.))
+//!     .solve()?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(solution.value(x), 10.);
+//! assert_float_eq(solution.value(y), 2.);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{Expression, IntoAffineExpression, Variable};
+
+/// A numeric type that can be used for the coefficients of a [ScalarExpression]
+pub trait Scalar:
+    Clone
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    /// The additive identity
+    fn zero() -> Self;
+
+    /// The multiplicative identity, the coefficient of a lone variable
+    fn one() -> Self;
+
+    /// The nearest `f64`, which is infinite or NaN if the value is too large or not a number
+    fn nearest_f64(&self) -> f64;
+
+    /// The nearest `f64`, or `None` if the value cannot be represented as a finite `f64`
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.nearest_f64()).filter(|value| value.is_finite())
+    }
+}
+
+impl Scalar for f64 {
+    fn zero() -> Self {
+        0.
+    }
+
+    fn one() -> Self {
+        1.
+    }
+
+    fn nearest_f64(&self) -> f64 {
+        *self
+    }
+}
+
+impl Scalar for f32 {
+    fn zero() -> Self {
+        0.
+    }
+
+    fn one() -> Self {
+        1.
+    }
+
+    fn nearest_f64(&self) -> f64 {
+        f64::from(*self)
+    }
+}
+
+impl Scalar for i32 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn nearest_f64(&self) -> f64 {
+        f64::from(*self)
+    }
+}
+
+impl Scalar for i64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn nearest_f64(&self) -> f64 {
+        *self as f64
+    }
+}
+
+#[cfg(feature = "exact")]
+#[cfg_attr(docsrs, doc(cfg(feature = "exact")))]
+impl Scalar for num_rational::Rational64 {
+    fn zero() -> Self {
+        num_rational::Rational64::from_integer(0)
+    }
+
+    fn one() -> Self {
+        num_rational::Rational64::from_integer(1)
+    }
+
+    fn nearest_f64(&self) -> f64 {
+        *self.numer() as f64 / *self.denom() as f64
+    }
+}
+
+#[cfg(feature = "decimal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "decimal")))]
+impl Scalar for rust_decimal::Decimal {
+    fn zero() -> Self {
+        rust_decimal::Decimal::ZERO
+    }
+
+    fn one() -> Self {
+        rust_decimal::Decimal::ONE
+    }
+
+    fn nearest_f64(&self) -> f64 {
+        // Every decimal is within the range of f64
+        rust_decimal::prelude::ToPrimitive::to_f64(self).unwrap_or(f64::NAN)
+    }
+}
+
+/// An affine expression whose coefficients are of type `T`.
+/// See the [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalarExpression<T> {
+    coefficients: HashMap<Variable, T>,
+    constant: T,
+}
+
+impl<T: Scalar> ScalarExpression<T> {
+    /// An expression with no variable
+    pub fn constant(value: T) -> Self {
+        ScalarExpression {
+            coefficients: HashMap::new(),
+            constant: value,
+        }
+    }
+
+    /// `coefficient * variable`
+    pub fn term(variable: Variable, coefficient: T) -> Self {
+        let mut expression = Self::constant(T::zero());
+        expression.add_term(variable, coefficient);
+        expression
+    }
+
+    /// Adds `coefficient * variable` to the expression
+    pub fn add_term(&mut self, variable: Variable, coefficient: T) {
+        let sum = self.coefficient(variable) + coefficient;
+        self.coefficients.insert(variable, sum);
+    }
+
+    /// The coefficient of the variable, zero if it is not in the expression
+    pub fn coefficient(&self, variable: Variable) -> T {
+        self.coefficients
+            .get(&variable)
+            .cloned()
+            .unwrap_or_else(T::zero)
+    }
+
+    /// The constant part of the expression
+    pub fn get_constant(&self) -> &T {
+        &self.constant
+    }
+
+    /// Converts the expression to an [Expression], with `f64` coefficients,
+    /// to use it in a problem.
+    ///
+    /// ```
+    /// use good_lp::scalar::{NotRepresentable, ScalarExpression};
+    /// use good_lp::variables;
+    ///
+    /// variables! {vars: x;}
+    /// let huge = ScalarExpression::term(x, f32::MAX) * 2.;
+    /// assert_eq!(huge.to_expression(), Err(NotRepresentable { variable: Some(x) }));
+    /// ```
+    pub fn to_expression(&self) -> Result<Expression, NotRepresentable> {
+        let convert = |value: &T, variable: Option<Variable>| {
+            value.to_f64().ok_or(NotRepresentable { variable })
+        };
+        let mut expression = Expression::from(convert(&self.constant, None)?);
+        for (&variable, coefficient) in &self.coefficients {
+            expression.add_mul(convert(coefficient, Some(variable))?, variable);
+        }
+        Ok(expression)
+    }
+}
+
+impl<T: Scalar> From<Variable> for ScalarExpression<T> {
+    fn from(variable: Variable) -> Self {
+        Self::term(variable, T::one())
+    }
+}
+
+/// Converts the coefficients to their nearest `f64`
+impl<T: Scalar> IntoAffineExpression for ScalarExpression<T> {
+    type Iter = std::vec::IntoIter<(Variable, f64)>;
+
+    fn linear_coefficients(self) -> Self::Iter {
+        self.coefficients
+            .into_iter()
+            .map(|(variable, coefficient)| (variable, coefficient.nearest_f64()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn constant(&self) -> f64 {
+        self.constant.nearest_f64()
+    }
+}
+
+impl<T: Scalar> Add for ScalarExpression<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        for (variable, coefficient) in rhs.coefficients {
+            self.add_term(variable, coefficient);
+        }
+        self.constant = self.constant + rhs.constant;
+        self
+    }
+}
+
+impl<T: Scalar> Add<T> for ScalarExpression<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: T) -> Self {
+        self.constant = self.constant + rhs;
+        self
+    }
+}
+
+impl<T: Scalar> Add<Variable> for ScalarExpression<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Variable) -> Self {
+        self.add_term(rhs, T::one());
+        self
+    }
+}
+
+/// Mixed with an [Expression], the coefficients are converted to `f64`
+impl<T: Scalar> Add<Expression> for ScalarExpression<T> {
+    type Output = Expression;
+
+    fn add(self, rhs: Expression) -> Expression {
+        rhs + self
+    }
+}
+
+impl<T: Scalar> Neg for ScalarExpression<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ScalarExpression {
+            coefficients: self
+                .coefficients
+                .into_iter()
+                .map(|(variable, coefficient)| (variable, -coefficient))
+                .collect(),
+            constant: -self.constant,
+        }
+    }
+}
+
+impl<T: Scalar> Sub for ScalarExpression<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<T: Scalar> Sub<T> for ScalarExpression<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self {
+        self + -rhs
+    }
+}
+
+impl<T: Scalar> Sub<Variable> for ScalarExpression<T> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Variable) -> Self {
+        self.add_term(rhs, -T::one());
+        self
+    }
+}
+
+impl<T: Scalar> Sub<Expression> for ScalarExpression<T> {
+    type Output = Expression;
+
+    fn sub(self, rhs: Expression) -> Expression {
+        -rhs + self
+    }
+}
+
+impl<T: Scalar> Mul<T> for ScalarExpression<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        ScalarExpression {
+            coefficients: self
+                .coefficients
+                .into_iter()
+                .map(|(variable, coefficient)| (variable, coefficient * rhs.clone()))
+                .collect(),
+            constant: self.constant * rhs,
+        }
+    }
+}
+
+/// A value of a [ScalarExpression] that cannot be represented as a finite `f64`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotRepresentable {
+    /// The variable whose coefficient cannot be represented,
+    /// or `None` if it is the constant of the expression
+    pub variable: Option<Variable>,
+}
+
+impl Display for NotRepresentable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.variable {
+            Some(variable) => write!(
+                f,
+                "The coefficient of {:?} cannot be represented as a finite f64",
+                variable
+            ),
+            None => write!(f, "The constant cannot be represented as a finite f64"),
+        }
+    }
+}
+
+impl Error for NotRepresentable {}
@@ -0,0 +1,190 @@
+//! Disjunctive constraints of machine scheduling: tasks that cannot overlap in time.
+//!
+//! A machine can process one task at a time: for every pair of tasks,
+//! either the first one ends before the second one starts, or the other way around.
+//! This either-or condition is written with one binary variable per pair of tasks,
+//! and two [big-M](https://en.wikipedia.org/wiki/Big_M_method) constraints.
+//! Too large values of M give a weak linear relaxation and numerical trouble,
+//! so [no_overlap] computes the smallest valid M of each constraint
+//! from the time windows of the tasks.
+//!
+//! ```
+//! # // minilp and highs do not support integer variables
+//! # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+//! use good_lp::scheduling::{no_overlap, Task};
+//! use good_lp::{default_solver, variables, Expression, Solution, SolverModel};
+//!
+//! let mut vars = variables!();
+//! let tasks = [Task::new(3.), Task::new(2.), Task::new(1.).release(2.)];
+//! let machine = no_overlap(&mut vars, &tasks);
+//! // Minimise the sum of the completion times
+//! let completion: Expression = (0..tasks.len()).map(|task| machine.end(task)).sum();
+//! let mut problem = vars.minimise(completion.clone());
+//! for constraint in machine.constraints.iter().cloned() {
+//!     problem.add_constraint(constraint);
+//! }
+//! let solution = problem.using(default_solver).solve()?;
+//! // Task 1 first, then task 2 as soon as it is released, then task 0
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(solution.value(machine.starts[1]), 0.);
+//! assert_float_eq(solution.value(machine.starts[2]), 2.);
+//! assert_float_eq(solution.eval(&completion), 2. + 3. + 6.);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::{constraint, variable, Constraint, Expression, ProblemVariables, Variable};
+
+/// A task to be scheduled on a machine, built with [Task::new]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Task {
+    /// How long the task occupies the machine
+    pub duration: f64,
+    /// The earliest time at which the task can start
+    pub release: f64,
+    /// The time by which the task has to end, or infinity
+    pub deadline: f64,
+}
+
+impl Task {
+    /// A task with the given duration, that can start at time 0, and has no deadline
+    pub fn new(duration: f64) -> Self {
+        Task {
+            duration,
+            release: 0.,
+            deadline: f64::INFINITY,
+        }
+    }
+
+    /// Sets the earliest time at which the task can start
+    pub fn release(mut self, release: f64) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Sets the time by which the task has to end
+    pub fn deadline(mut self, deadline: f64) -> Self {
+        self.deadline = deadline;
+        self
+    }
+}
+
+/// The variables and constraints of tasks sharing a machine, created by [no_overlap]
+#[derive(Clone)]
+pub struct NoOverlap {
+    /// The start time of each task
+    pub starts: Vec<Variable>,
+    /// `(first, second, variable)` for every pair of tasks, `first < second`:
+    /// the binary variable is 1 when `first` ends before `second` starts,
+    /// and 0 when `second` ends before `first` starts
+    pub order: Vec<(usize, usize, Variable)>,
+    /// The two sequencing constraints of every pair of tasks
+    pub constraints: Vec<Constraint>,
+    durations: Vec<f64>,
+    horizon: f64,
+}
+
+impl NoOverlap {
+    /// The end time of the task
+    pub fn end(&self, task: usize) -> Expression {
+        self.starts[task] + self.durations[task]
+    }
+
+    /// The time by which all the tasks end in every schedule:
+    /// the latest release date, plus the sum of the durations,
+    /// or the latest deadline if it is earlier
+    pub fn horizon(&self) -> f64 {
+        self.horizon
+    }
+
+    /// Adds a variable for the [makespan](https://en.wikipedia.org/wiki/Makespan),
+    /// the time at which the last task ends, and returns it with the constraints
+    /// that keep it above the end of every task.
+    ///
+    /// When the makespan is minimised, it is equal to the end of the last task.
+    pub fn makespan(&self, vars: &mut ProblemVariables) -> (Variable, Vec<Constraint>) {
+        let makespan = vars.add(variable().clamp(0, self.horizon).name("makespan"));
+        let constraints = (0..self.starts.len())
+            .map(|task| {
+                let end = self.end(task);
+                constraint!(end <= makespan).set_name(format!("makespan_{}", task))
+            })
+            .collect();
+        (makespan, constraints)
+    }
+}
+
+/// Adds to `vars` a start time variable for every task,
+/// and a binary variable for every pair of tasks, deciding which one comes first.
+/// Returns them with the sequencing constraints that prevent the tasks from overlapping.
+///
+/// The start times are bounded by the release dates, the deadlines,
+/// and the [horizon](NoOverlap::horizon), which does not remove any schedule
+/// that processes the tasks as early as possible.
+/// The M of the constraints is the latest end of the first task,
+/// minus the earliest start of the second one.
+///
+/// Panics if a task has a negative duration.
+pub fn no_overlap(vars: &mut ProblemVariables, tasks: &[Task]) -> NoOverlap {
+    assert!(
+        tasks.iter().all(|task| task.duration >= 0.),
+        "task durations cannot be negative"
+    );
+    let latest_release = tasks.iter().map(|task| task.release).fold(0., f64::max);
+    let latest_deadline = tasks.iter().map(|task| task.deadline).fold(0., f64::max);
+    let total_duration: f64 = tasks.iter().map(|task| task.duration).sum();
+    let horizon = (latest_release + total_duration).min(latest_deadline);
+    // The latest start of each task
+    let latest_starts: Vec<f64> = tasks
+        .iter()
+        .map(|task| task.deadline.min(horizon) - task.duration)
+        .collect();
+    let starts: Vec<Variable> = tasks
+        .iter()
+        .zip(&latest_starts)
+        .enumerate()
+        .map(|(index, (task, &latest))| {
+            vars.add(
+                variable()
+                    .clamp(task.release, latest)
+                    .name(format!("start_{}", index)),
+            )
+        })
+        .collect();
+    // The largest value of end(a) - start(b) within the time windows
+    let big_m =
+        |a: usize, b: usize| (latest_starts[a] + tasks[a].duration - tasks[b].release).max(0.);
+    let mut order = vec![];
+    let mut constraints = vec![];
+    for first in 0..tasks.len() {
+        for second in first + 1..tasks.len() {
+            let before = vars.add(
+                variable()
+                    .binary()
+                    .name(format!("before_{}_{}", first, second)),
+            );
+            let (start_first, start_second) = (starts[first], starts[second]);
+            let (duration_first, duration_second) = (tasks[first].duration, tasks[second].duration);
+            let (m_first, m_second) = (big_m(first, second), big_m(second, first));
+            // When `before` is 1, the first task ends before the second one starts
+            constraints.push(
+                constraint!(
+                    start_first + duration_first + m_first * before <= start_second + m_first
+                )
+                .set_name(format!("sequence_{}_{}", first, second)),
+            );
+            // and when it is 0, the second task ends before the first one starts
+            constraints.push(
+                constraint!(start_second + duration_second <= start_first + m_second * before)
+                    .set_name(format!("sequence_{}_{}", second, first)),
+            );
+            order.push((first, second, before));
+        }
+    }
+    NoOverlap {
+        starts,
+        order,
+        constraints,
+        durations: tasks.iter().map(|task| task.duration).collect(),
+        horizon,
+    }
+}
@@ -0,0 +1,253 @@
+//! Solving block-separable problems as several independent problems.
+//!
+//! Two variables are connected when they appear together in a constraint.
+//! When the variables of a problem form several groups that are not connected to each other,
+//! each group can be optimised on its own, and the solutions can be merged.
+//! Solving several small problems in parallel is usually much faster than solving a large one.
+//!
+//! ```
+//! use good_lp::sharding::shard_and_solve;
+//! use good_lp::{constraint, default_solver, variable, variables, Solution};
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//!
+//! let mut vars = variables!();
+//! let a = vars.add_vector(variable().min(0), 2);
+//! let b = vars.add_vector(variable().min(0), 2);
+//! let problem = vars
+//!     .maximise(a[0] + 2 * a[1] + b[0] + b[1])
+//!     .with(constraint!(a[0] + a[1] <= 1))
+//!     .with(constraint!(b[0] + 3 * b[1] <= 3));
+//! let solution = shard_and_solve(problem, default_solver)?;
+//! assert_eq!(solution.shards(), 2);
+//! assert_float_eq(solution.value(a[1]), 1.);
+//! assert_float_eq(solution.value(b[0]), 3.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::collections::HashMap;
+use std::thread;
+
+use crate::solvers::{ResolutionError, Solution, SolutionStatus, Solver, SolverModel};
+use crate::variable::UnsolvedProblem;
+use crate::{Constraint, Expression, ProblemVariables, Variable};
+
+/// One of the independent parts of a problem, created by [split]
+pub struct Shard {
+    /// The part of the problem with only the variables and constraints of this shard
+    pub problem: UnsolvedProblem,
+    /// The variable of the original problem that corresponds to each variable of this shard
+    pub variables: Vec<Variable>,
+}
+
+/// Finds the representative of the set that contains `i`
+fn find(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+fn variables_of(constraint: &Constraint) -> impl Iterator<Item = usize> + '_ {
+    constraint
+        .expression
+        .linear
+        .coefficients
+        .iter()
+        .filter(|(_, &c)| c != 0.)
+        .map(|(var, _)| var.index())
+}
+
+/// Renames the variables of an expression to the ones of a shard
+fn remap(expression: &Expression, variables: &[Option<Variable>]) -> Expression {
+    let mut remapped = Expression::with_capacity(expression.linear.coefficients.len());
+    for (var, &coefficient) in &expression.linear.coefficients {
+        if let Some(new) = variables[var.index()] {
+            remapped.linear.coefficients.insert(new, coefficient);
+        }
+    }
+    remapped
+}
+
+/// Splits a problem into independent problems, one for each connected group of variables.
+///
+/// Variables that don't appear in any constraint are grouped in a single shard.
+/// The constant part of the objective, and the constraints that don't contain any variable,
+/// are kept in the first shard.
+pub fn split(problem: UnsolvedProblem) -> Vec<Shard> {
+    let UnsolvedProblem {
+        objective,
+        direction,
+        variables,
+        constraints,
+        ..
+    } = problem;
+    let n = variables.len();
+    let mut parents: Vec<usize> = (0..n).collect();
+    let mut constrained = vec![false; n];
+    for constraint in &constraints {
+        let mut vars = variables_of(constraint);
+        if let Some(first) = vars.next() {
+            constrained[first] = true;
+            for other in vars {
+                constrained[other] = true;
+                let (a, b) = (find(&mut parents, first), find(&mut parents, other));
+                parents[a] = b;
+            }
+        }
+    }
+
+    // The shard of each variable, numbered in the order of the variables
+    let mut shard_of_root: HashMap<usize, usize> = HashMap::new();
+    let mut free_shard = None;
+    let mut shard_of_variable = Vec::with_capacity(n);
+    for (i, &is_constrained) in constrained.iter().enumerate() {
+        let next = shard_of_root.len() + free_shard.iter().count();
+        let shard = if is_constrained {
+            let root = find(&mut parents, i);
+            *shard_of_root.entry(root).or_insert(next)
+        } else {
+            *free_shard.get_or_insert(next)
+        };
+        shard_of_variable.push(shard);
+    }
+    let shard_count = (shard_of_root.len() + free_shard.iter().count()).max(1);
+
+    let mut shard_variables = vec![ProblemVariables::new(); shard_count];
+    let mut originals = vec![vec![]; shard_count];
+    let mut new_variables: Vec<Vec<Option<Variable>>> = vec![vec![None; n]; shard_count];
+    for ((var, def), &shard) in variables.iter_variables_with_def().zip(&shard_of_variable) {
+        new_variables[shard][var.index()] = Some(shard_variables[shard].add(def.clone()));
+        originals[shard].push(var);
+    }
+    let mut shard_constraints = vec![vec![]; shard_count];
+    for constraint in constraints {
+        let shard = variables_of(&constraint)
+            .next()
+            .map_or(0, |var| shard_of_variable[var]);
+        let mut remapped = Constraint::new(
+            remap(&constraint.expression, &new_variables[shard]),
+            constraint.is_equality,
+        );
+        remapped.expression.constant = constraint.expression.constant;
+        remapped.name = constraint.name;
+        shard_constraints[shard].push(remapped);
+    }
+
+    shard_variables
+        .into_iter()
+        .zip(originals)
+        .zip(shard_constraints)
+        .zip(new_variables)
+        .enumerate()
+        .map(|(i, (((vars, variables), constraints), new_variables))| {
+            let mut shard_objective = remap(&objective, &new_variables);
+            if i == 0 {
+                shard_objective.constant = objective.constant;
+            }
+            let mut problem = vars.optimise(direction, shard_objective);
+            problem.constraints = constraints;
+            Shard { problem, variables }
+        })
+        .collect()
+}
+
+/// The merged solutions of all the shards of a problem, returned by [shard_and_solve]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShardedSolution {
+    values: Vec<f64>,
+    status: SolutionStatus,
+    shards: usize,
+}
+
+impl ShardedSolution {
+    /// The number of independent problems that were solved
+    pub fn shards(&self) -> usize {
+        self.shards
+    }
+}
+
+impl Solution for ShardedSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+
+    /// The status of the first shard that was not solved to optimality, if any
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+/// Solves a shard, and returns the values of its variables
+fn solve_shard<S>(
+    shard: UnsolvedProblem,
+    solver: S,
+) -> Result<(Vec<f64>, SolutionStatus), ResolutionError>
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    let variables: Vec<Variable> = shard
+        .variables
+        .iter_variables_with_def()
+        .map(|(v, _)| v)
+        .collect();
+    let solution = shard.using(solver).solve()?;
+    let values = variables.iter().map(|&v| solution.value(v)).collect();
+    Ok((values, solution.status()))
+}
+
+/// [Splits](split) the problem into independent problems, solves them in parallel,
+/// one thread per shard, and merges their solutions.
+///
+/// Returns the first error if any of the shards cannot be solved:
+/// if a part of the problem is infeasible or unbounded, so is the whole problem.
+pub fn shard_and_solve<S>(
+    problem: UnsolvedProblem,
+    solver: S,
+) -> Result<ShardedSolution, ResolutionError>
+where
+    S: Solver + Clone + Send,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    let n = problem.variables.len();
+    let shards = split(problem);
+    let shard_count = shards.len();
+    let mut values = vec![0.; n];
+    let mut status = SolutionStatus::Optimal;
+    let (problems, variables): (Vec<_>, Vec<_>) =
+        shards.into_iter().map(|s| (s.problem, s.variables)).unzip();
+    let results: Vec<_> = if shard_count == 1 {
+        problems
+            .into_iter()
+            .map(|problem| solve_shard(problem, solver.clone()))
+            .collect()
+    } else {
+        thread::scope(|scope| {
+            let handles: Vec<_> = problems
+                .into_iter()
+                .map(|problem| {
+                    let solver = solver.clone();
+                    scope.spawn(move || solve_shard(problem, solver))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("a solver panicked"))
+                .collect()
+        })
+    };
+    for (result, originals) in results.into_iter().zip(variables) {
+        let (shard_values, shard_status) = result?;
+        for (var, value) in originals.into_iter().zip(shard_values) {
+            values[var.index()] = value;
+        }
+        if status.is_optimal() {
+            status = shard_status;
+        }
+    }
+    Ok(ShardedSolution {
+        values,
+        status,
+        shards: shard_count,
+    })
+}
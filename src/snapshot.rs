@@ -0,0 +1,117 @@
+//! Solver-independent copies of solutions, that can be stored or sent elsewhere.
+//!
+//! Solutions returned by solvers borrow data from the underlying solver library,
+//! and only give access to values through [Variable](crate::Variable)s.
+//! A [SolutionSnapshot] copies the value of every variable, indexed by name,
+//! so that it can be written as CSV or, with the `serde` cargo feature, serialized.
+//!
+//! ```
+//! use good_lp::snapshot::SolutionSnapshot;
+//! use good_lp::{default_solver, variable, variables, SolverModel};
+//!
+//! let mut vars = variables!();
+//! let x = vars.add(variable().name("x").max(3));
+//! let y = vars.add(variable().name("y").max(2));
+//! let objective = x + 2 * y;
+//! let variables = vars.clone();
+//! let solution = vars.maximise(objective.clone()).using(default_solver).solve()?;
+//! let snapshot = SolutionSnapshot::new(&solution, &variables, &objective);
+//! assert_eq!(snapshot.objective, 7.);
+//! assert_eq!(snapshot.value("y"), Some(2.));
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::formats::unique_names;
+use crate::{Expression, ProblemVariables, Solution, SolutionStatus};
+
+/// The status, the objective value and the values of all variables of a solution, by variable name.
+///
+/// Variables without a name are called `v{index}`,
+/// and duplicate names are made unique by adding a suffix.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolutionSnapshot {
+    /// Whether the solution is optimal
+    pub status: SolutionStatus,
+    /// The value of the objective
+    pub objective: f64,
+    /// The value of each variable, by name
+    pub values: BTreeMap<String, f64>,
+}
+
+impl SolutionSnapshot {
+    /// Copies the values of all the given variables from a solution
+    pub fn new<S: Solution>(
+        solution: &S,
+        variables: &ProblemVariables,
+        objective: &Expression,
+    ) -> Self {
+        let names = unique_names(
+            variables
+                .iter_variables_with_def()
+                .map(|(_, def)| Some(def.get_name())),
+            "v",
+            &[],
+            str::to_string,
+        );
+        let values = variables
+            .iter_variables_with_def()
+            .zip(names)
+            .map(|((var, _), name)| (name, solution.value(var)))
+            .collect();
+        SolutionSnapshot {
+            status: solution.status(),
+            objective: solution.eval(objective),
+            values,
+        }
+    }
+
+    /// The value of the variable with the given name
+    pub fn value(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    /// Writes the values as CSV, with a `variable,value` header,
+    /// and one line per variable, sorted by name
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "variable,value")?;
+        for (name, value) in &self.values {
+            writeln!(writer, "{},{}", csv_field(name), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote, or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::SolutionSnapshot;
+    use crate::{variable, variables};
+
+    #[test]
+    fn csv_export() {
+        let mut vars = variables!();
+        let a = vars.add(variable().name("a, b"));
+        let b = vars.add(variable().name("a, b"));
+        let c = vars.add(variable());
+        let solution: HashMap<_, f64> = [(a, 1.), (b, 2.5), (c, -3.)].iter().copied().collect();
+        let snapshot = SolutionSnapshot::new(&solution, &vars, &(a + c));
+        assert_eq!(snapshot.objective, -2.);
+        let mut csv = Vec::new();
+        snapshot.write_csv(&mut csv).unwrap();
+        let expected = "variable,value\n\"a, b\",1\n\"a, b_1\",2.5\nv2,-3\n";
+        assert_eq!(String::from_utf8(csv).unwrap(), expected);
+    }
+}
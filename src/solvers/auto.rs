@@ -0,0 +1,367 @@
+//! A solver that picks one of the compiled-in backends at runtime,
+//! depending on the [classification](crate::classification) of the problem.
+//!
+//! [default_solver](crate::default_solver) is chosen at compile time, from the cargo features only.
+//! [auto_solver] looks at the problem itself: it will not send a problem with integer variables
+//! to a solver that does not support them, and it prefers fast native solvers for large problems.
+//!
+//! ```
+//! use good_lp::{auto_solver, constraint, variable, variables, Solution, SolverModel};
+//!
+//! let mut vars = variables!();
+//! let x = vars.add(variable().max(3));
+//! let y = vars.add(variable().max(3));
+//! let model = vars
+//!     .maximise(x + y)
+//!     .using(auto_solver)
+//!     .with(constraint!(x + 2 * y <= 4));
+//! println!("solving with {}", model.backend().name());
+//! let solution = model.solve()?;
+//! assert!((solution.value(x) - 3.).abs() < 1e-6);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+//!
+//! The choice can be overridden with [AutoSolver::with_selector].
+//! Solvers from the `lp-solvers` feature call an external program,
+//! so they are never selected automatically.
+//! good_lp only models linear expressions, so quadratic terms never need to be taken into account.
+
+use crate::classification::Classification;
+use crate::constraint::ConstraintReference;
+use crate::solvers::{ResolutionError, Solution, SolutionStatus, SolveStats, Solver, SolverModel};
+use crate::variable::UnsolvedProblem;
+use crate::{Constraint, Variable};
+
+/// Linear problems with at most this number of variables and constraints are considered small.
+/// minilp solves them without the overhead of setting up a native solver.
+const SMALL_PROBLEM_SIZE: usize = 100;
+
+/// A solver backend compiled into the crate, that [AutoSolver] can choose
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Backend {
+    /// [coin_cbc](crate::solvers::coin_cbc)
+    #[cfg(feature = "coin_cbc")]
+    CoinCbc,
+    /// [highs](crate::solvers::highs), which does not support integer variables
+    #[cfg(feature = "highs")]
+    Highs,
+    /// [lpsolve](crate::solvers::lpsolve)
+    #[cfg(feature = "lpsolve")]
+    LpSolve,
+    /// [minilp](crate::solvers::minilp), which does not support integer variables
+    #[cfg(feature = "minilp")]
+    MiniLp,
+}
+
+impl Backend {
+    /// All the backends that are compiled in
+    pub fn available() -> Vec<Backend> {
+        vec![
+            #[cfg(feature = "coin_cbc")]
+            Backend::CoinCbc,
+            #[cfg(feature = "highs")]
+            Backend::Highs,
+            #[cfg(feature = "lpsolve")]
+            Backend::LpSolve,
+            #[cfg(feature = "minilp")]
+            Backend::MiniLp,
+        ]
+    }
+
+    /// The name of the cargo feature that enables this backend
+    pub fn name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "coin_cbc")]
+            Backend::CoinCbc => "coin_cbc",
+            #[cfg(feature = "highs")]
+            Backend::Highs => "highs",
+            #[cfg(feature = "lpsolve")]
+            Backend::LpSolve => "lpsolve",
+            #[cfg(feature = "minilp")]
+            Backend::MiniLp => "minilp",
+        }
+    }
+
+    /// Whether this backend can solve problems with integer variables
+    pub fn supports_integers(&self) -> bool {
+        match self {
+            #[cfg(feature = "coin_cbc")]
+            Backend::CoinCbc => true,
+            #[cfg(feature = "highs")]
+            Backend::Highs => false,
+            #[cfg(feature = "lpsolve")]
+            Backend::LpSolve => true,
+            #[cfg(feature = "minilp")]
+            Backend::MiniLp => false,
+        }
+    }
+
+    /// The preference for this backend when solving the given problem: lower is better
+    fn rank(&self, classification: &Classification) -> usize {
+        let small = classification.variables <= SMALL_PROBLEM_SIZE
+            && classification.constraints <= SMALL_PROBLEM_SIZE;
+        let order: &[&str] = if classification.needs_integer_support() {
+            &["coin_cbc", "lpsolve", "minilp", "highs"]
+        } else if small {
+            &["minilp", "highs", "coin_cbc", "lpsolve"]
+        } else {
+            &["highs", "coin_cbc", "lpsolve", "minilp"]
+        };
+        order
+            .iter()
+            .position(|&name| name == self.name())
+            .unwrap_or(order.len())
+    }
+
+    /// The compiled-in backend that is best suited to solve the given problem.
+    ///
+    /// Problems with integer variables go to Cbc, then lp_solve.
+    /// Small linear problems go to minilp, and large ones to HiGHS, then Cbc.
+    pub fn select(classification: &Classification) -> Backend {
+        Backend::available()
+            .into_iter()
+            .min_by_key(|backend| backend.rank(classification))
+            .expect("at least one native solver is compiled in")
+    }
+}
+
+/// The approximate number of bytes a backend allocates per variable, per constraint,
+/// and per non-zero coefficient, including the working copies it makes while solving.
+/// These are orders of magnitude derived from the data structures each solver keeps, not exact values.
+fn memory_costs(backend: Backend) -> (usize, usize, usize) {
+    match backend {
+        #[cfg(feature = "coin_cbc")]
+        Backend::CoinCbc => (160, 160, 48),
+        #[cfg(feature = "highs")]
+        Backend::Highs => (200, 200, 56),
+        // lp_solve is given each constraint as a dense row of coefficients
+        #[cfg(feature = "lpsolve")]
+        Backend::LpSolve => (136, 128, 40),
+        #[cfg(feature = "minilp")]
+        Backend::MiniLp => (96, 96, 64),
+    }
+}
+
+impl UnsolvedProblem {
+    /// The number of non-zero coefficients in the objective and the constraints of the problem
+    pub fn nonzeros(&self) -> usize {
+        let count = |expression: &crate::Expression| {
+            expression
+                .linear
+                .coefficients
+                .values()
+                .filter(|&&c| c != 0.)
+                .count()
+        };
+        count(&self.objective)
+            + self
+                .constraints
+                .iter()
+                .map(|c| count(&c.expression))
+                .sum::<usize>()
+    }
+
+    /// A rough estimate, in bytes, of the memory the given backend will use to solve this problem.
+    ///
+    /// It is computed from the number of variables, constraints and non-zero coefficients,
+    /// so that oversized problems can be rejected or split before they are sent to a solver.
+    /// Constraints added to the model after [UnsolvedProblem::using] are not taken into account.
+    ///
+    /// ```
+    /// use good_lp::solvers::auto::Backend;
+    /// use good_lp::{constraint, variable, variables};
+    ///
+    /// let mut vars = variables!();
+    /// let x = vars.add_vector(variable().min(0), 1000);
+    /// let total: good_lp::Expression = x.iter().sum();
+    /// let problem = vars.maximise(total.clone()).with(constraint!(total <= 10));
+    /// let backend = Backend::select(&problem.classify());
+    /// let bytes = problem.estimated_backend_memory(backend);
+    /// assert!(bytes < 1 << 20, "a small problem needs less than a megabyte");
+    /// ```
+    pub fn estimated_backend_memory(&self, backend: Backend) -> usize {
+        let (per_variable, per_constraint, per_nonzero) = memory_costs(backend);
+        let variables = self.variables.len();
+        let constraints = self.constraints.len();
+        variables * per_variable + constraints * per_constraint + self.nonzeros() * per_nonzero
+    }
+}
+
+/// A function that chooses a backend for a problem, or returns `None` to use the default choice
+type Selector = Box<dyn FnMut(&Classification) -> Option<Backend>>;
+
+/// A [Solver] that chooses the backend at runtime. See [auto_solver].
+#[derive(Default)]
+pub struct AutoSolver {
+    selector: Option<Selector>,
+}
+
+impl AutoSolver {
+    /// A solver that always uses [Backend::select]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the choice of the backend.
+    /// When the selector returns `None`, [Backend::select] is used.
+    ///
+    /// ```
+    /// use good_lp::solvers::auto::{AutoSolver, Backend};
+    /// use good_lp::{variable, variables, SolverModel};
+    ///
+    /// let mut vars = variables!();
+    /// let x = vars.add(variable().max(1));
+    /// // Always use the first compiled-in backend
+    /// let solver = AutoSolver::new().with_selector(|_| Backend::available().first().copied());
+    /// let model = vars.maximise(x).using(solver);
+    /// assert_eq!(model.backend(), Backend::available()[0]);
+    /// ```
+    pub fn with_selector<F>(mut self, selector: F) -> Self
+    where
+        F: FnMut(&Classification) -> Option<Backend> + 'static,
+    {
+        self.selector = Some(Box::new(selector));
+        self
+    }
+
+    /// The backend that will be used to solve the given problem
+    pub fn choose(&mut self, problem: &UnsolvedProblem) -> Backend {
+        let classification = problem.classify();
+        self.selector
+            .as_mut()
+            .and_then(|selector| selector(&classification))
+            .unwrap_or_else(|| Backend::select(&classification))
+    }
+}
+
+impl Solver for AutoSolver {
+    type Model = AutoModel;
+
+    fn create_model(&mut self, problem: UnsolvedProblem) -> AutoModel {
+        match self.choose(&problem) {
+            #[cfg(feature = "coin_cbc")]
+            Backend::CoinCbc => AutoModel::CoinCbc(super::coin_cbc::coin_cbc(problem)),
+            #[cfg(feature = "highs")]
+            Backend::Highs => AutoModel::Highs(super::highs::highs(problem)),
+            #[cfg(feature = "lpsolve")]
+            Backend::LpSolve => AutoModel::LpSolve(super::lpsolve::lp_solve(problem)),
+            #[cfg(feature = "minilp")]
+            Backend::MiniLp => AutoModel::MiniLp(super::minilp::minilp(problem)),
+        }
+    }
+}
+
+/// A solver that picks the best compiled-in backend for the problem,
+/// to be used with [UnsolvedProblem::using].
+/// See the [module documentation](self).
+pub fn auto_solver(to_solve: UnsolvedProblem) -> AutoModel {
+    AutoSolver::new().create_model(to_solve)
+}
+
+/// Calls the same method on the model or solution of whichever backend was chosen
+macro_rules! dispatch {
+    ($enum:ident, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            #[cfg(feature = "coin_cbc")]
+            $enum::CoinCbc($inner) => $body,
+            #[cfg(feature = "highs")]
+            $enum::Highs($inner) => $body,
+            #[cfg(feature = "lpsolve")]
+            $enum::LpSolve($inner) => $body,
+            #[cfg(feature = "minilp")]
+            $enum::MiniLp($inner) => $body,
+        }
+    };
+}
+
+/// The model of the backend chosen by [AutoSolver]
+pub enum AutoModel {
+    /// A Cbc model
+    #[cfg(feature = "coin_cbc")]
+    CoinCbc(super::coin_cbc::CoinCbcProblem),
+    /// A HiGHS model
+    #[cfg(feature = "highs")]
+    Highs(super::highs::HighsProblem),
+    /// An lp_solve model
+    #[cfg(feature = "lpsolve")]
+    LpSolve(super::lpsolve::LpSolveProblem),
+    /// A minilp model
+    #[cfg(feature = "minilp")]
+    MiniLp(super::minilp::MiniLpProblem),
+}
+
+impl AutoModel {
+    /// The backend that was chosen
+    pub fn backend(&self) -> Backend {
+        match self {
+            #[cfg(feature = "coin_cbc")]
+            AutoModel::CoinCbc(_) => Backend::CoinCbc,
+            #[cfg(feature = "highs")]
+            AutoModel::Highs(_) => Backend::Highs,
+            #[cfg(feature = "lpsolve")]
+            AutoModel::LpSolve(_) => Backend::LpSolve,
+            #[cfg(feature = "minilp")]
+            AutoModel::MiniLp(_) => Backend::MiniLp,
+        }
+    }
+}
+
+impl SolverModel for AutoModel {
+    type Solution = AutoSolution;
+    type Error = ResolutionError;
+
+    fn solve(self) -> Result<AutoSolution, ResolutionError> {
+        Ok(match self {
+            #[cfg(feature = "coin_cbc")]
+            AutoModel::CoinCbc(model) => AutoSolution::CoinCbc(model.solve()?),
+            #[cfg(feature = "highs")]
+            AutoModel::Highs(model) => AutoSolution::Highs(model.solve()?),
+            #[cfg(feature = "lpsolve")]
+            AutoModel::LpSolve(model) => AutoSolution::LpSolve(model.solve()?),
+            #[cfg(feature = "minilp")]
+            AutoModel::MiniLp(model) => AutoSolution::MiniLp(model.solve()?),
+        })
+    }
+
+    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        dispatch!(AutoModel, self, model => model.add_constraint(constraint))
+    }
+}
+
+/// The solution returned by the backend chosen by [AutoSolver]
+pub enum AutoSolution {
+    /// A Cbc solution
+    #[cfg(feature = "coin_cbc")]
+    CoinCbc(super::coin_cbc::CoinCbcSolution),
+    /// A HiGHS solution
+    #[cfg(feature = "highs")]
+    Highs(super::highs::HighsSolution),
+    /// An lp_solve solution
+    #[cfg(feature = "lpsolve")]
+    LpSolve(super::lpsolve::LpSolveSolution),
+    /// A minilp solution
+    #[cfg(feature = "minilp")]
+    MiniLp(super::minilp::MiniLpSolution),
+}
+
+impl Solution for AutoSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        dispatch!(AutoSolution, self, solution => solution.value(variable))
+    }
+
+    fn status(&self) -> SolutionStatus {
+        dispatch!(AutoSolution, self, solution => solution.status())
+    }
+
+    fn best_bound(&self) -> Option<f64> {
+        dispatch!(AutoSolution, self, solution => solution.best_bound())
+    }
+
+    fn mip_gap(&self) -> Option<f64> {
+        dispatch!(AutoSolution, self, solution => solution.mip_gap())
+    }
+
+    fn stats(&self) -> SolveStats {
+        dispatch!(AutoSolution, self, solution => solution.stats())
+    }
+}
@@ -2,14 +2,26 @@
 //! This solver is activated using the default `coin_cbc` feature.
 //! You can disable it an enable another solver instead using cargo features.
 use std::convert::TryInto;
+use std::time::{Duration, Instant};
 
-use coin_cbc::{raw::Status, Col, Model, Sense, Solution as CbcSolution};
+use coin_cbc::{
+    raw::{SecondaryStatus, Status},
+    Col, Model, Sense, Solution as CbcSolution,
+};
 
-use crate::solvers::ModelWithSOS1;
+use crate::solvers::{
+    logged, seed_to_i32, ModelWithBackend, ModelWithBulkAttributes, ModelWithColumns,
+    ModelWithMipGap, ModelWithPresolve, ModelWithRawParameters, ModelWithSOS1, ModelWithScaling,
+    ModelWithSeed, ModelWithThreads, ModelWithTimeLimit, ModelWithVerbosity, ScalingMode,
+    Verbosity,
+};
 use crate::variable::{UnsolvedProblem, VariableDefinition};
 use crate::{
-    constraint::ConstraintReference,
-    solvers::{ObjectiveDirection, ResolutionError, Solution, SolverModel},
+    constraint::{ConstraintReference, StrictInequalities},
+    solvers::{
+        relative_gap, ObjectiveDirection, ResolutionError, Solution, SolutionStatus, SolveStats,
+        SolverModel,
+    },
     IntoAffineExpression,
 };
 use crate::{Constraint, Variable};
@@ -21,32 +33,23 @@ pub fn coin_cbc(to_solve: UnsolvedProblem) -> CoinCbcProblem {
         objective,
         direction,
         variables,
+        strict_inequality,
+        ..
     } = to_solve;
     let mut model = Model::default();
+    let bounds: Vec<(f64, f64)> = variables
+        .iter_variables_with_def()
+        .map(|(_, definition)| (definition.min, definition.max))
+        .collect();
     let columns: Vec<Col> = variables
         .into_iter()
-        .map(
-            |VariableDefinition {
-                 min,
-                 max,
-                 is_integer,
-                 ..
-             }| {
-                let col = model.add_col();
-                // Variables are created with a default min of 0
-                model.set_col_lower(col, min);
-                if max < f64::INFINITY {
-                    model.set_col_upper(col, max)
-                }
-                if is_integer {
-                    model.set_integer(col);
-                }
-                col
-            },
-        )
+        .map(|definition| add_col(&mut model, &definition))
         .collect();
+    let objective_constant = objective.constant;
+    let mut objective_coefficients = vec![0.; columns.len()];
     for (var, coeff) in objective.linear.coefficients.into_iter() {
         model.set_obj_coeff(columns[var.index()], coeff);
+        objective_coefficients[var.index()] = coeff;
     }
     model.set_obj_sense(match direction {
         ObjectiveDirection::Maximisation => Sense::Maximize,
@@ -55,15 +58,38 @@ pub fn coin_cbc(to_solve: UnsolvedProblem) -> CoinCbcProblem {
     CoinCbcProblem {
         model,
         columns,
+        bounds,
+        objective_coefficients,
         has_sos: false,
+        objective_constant,
+        strict_inequalities: StrictInequalities::new(strict_inequality),
+    }
+}
+
+fn add_col(model: &mut Model, definition: &VariableDefinition) -> Col {
+    let col = model.add_col();
+    // Variables are created with a default min of 0
+    model.set_col_lower(col, definition.min);
+    if definition.max < f64::INFINITY {
+        model.set_col_upper(col, definition.max)
+    }
+    if definition.is_integer {
+        model.set_integer(col);
     }
+    col
 }
 
 /// A coin-cbc model
 pub struct CoinCbcProblem {
     model: Model,
     columns: Vec<Col>,
+    /// The bounds and objective coefficients of the columns, that Cbc does not give back
+    bounds: Vec<(f64, f64)>,
+    objective_coefficients: Vec<f64>,
     has_sos: bool,
+    /// Cbc ignores the constant part of the objective
+    objective_constant: f64,
+    strict_inequalities: StrictInequalities,
 }
 
 impl CoinCbcProblem {
@@ -73,53 +99,100 @@ impl CoinCbcProblem {
     }
 }
 
+impl ModelWithBackend for CoinCbcProblem {
+    type Backend = Model;
+
+    fn as_backend(&self) -> &Self::Backend {
+        &self.model
+    }
+
+    fn as_backend_mut(&mut self) -> &mut Self::Backend {
+        &mut self.model
+    }
+}
+
 impl SolverModel for CoinCbcProblem {
     type Solution = CoinCbcSolution;
     type Error = ResolutionError;
 
     fn solve(mut self) -> Result<Self::Solution, Self::Error> {
-        // Due to a bug in cbc, SOS constraints are only taken into account
-        // if the model has at least one integer variable.
-        // See: https://github.com/coin-or/Cbc/issues/376
-        if self.has_sos {
-            // We need to add two columns to work around yet another bug
-            // See: https://github.com/coin-or/Cbc/issues/376#issuecomment-803057782
-            let dummy_col1 = self.model.add_col();
-            let dummy_col2 = self.model.add_col();
-            self.model.set_obj_coeff(dummy_col1, 1e-6);
-            self.model.set_obj_coeff(dummy_col2, 1e-6);
-            self.model.set_integer(dummy_col1);
-            let dummy_row = self.model.add_row();
-            self.model.set_weight(dummy_row, dummy_col1, 1.);
-            self.model.set_weight(dummy_row, dummy_col2, 1.);
-            self.model.set_row_upper(dummy_row, 1.);
-        }
+        logged("Cbc", move || {
+            self.strict_inequalities.check()?;
+            // Due to a bug in cbc, SOS constraints are only taken into account
+            // if the model has at least one integer variable.
+            // See: https://github.com/coin-or/Cbc/issues/376
+            if self.has_sos {
+                // We need to add two columns to work around yet another bug
+                // See: https://github.com/coin-or/Cbc/issues/376#issuecomment-803057782
+                let dummy_col1 = self.model.add_col();
+                let dummy_col2 = self.model.add_col();
+                self.model.set_obj_coeff(dummy_col1, 1e-6);
+                self.model.set_obj_coeff(dummy_col2, 1e-6);
+                self.model.set_integer(dummy_col1);
+                let dummy_row = self.model.add_row();
+                self.model.set_weight(dummy_row, dummy_col1, 1.);
+                self.model.set_weight(dummy_row, dummy_col2, 1.);
+                self.model.set_row_upper(dummy_row, 1.);
+            }
 
-        let solution = self.model.solve();
-        let raw = solution.raw();
-        match raw.status() {
-            Status::Stopped => Err(ResolutionError::Other("Stopped")),
-            Status::Abandoned => Err(ResolutionError::Other("Abandoned")),
-            Status::UserEvent => Err(ResolutionError::Other("UserEvent")),
-            Status::Finished // The optimization finished, but may not have found a solution
-            | Status::Unlaunched // The solver didn't have to be launched, presolve handled it
-            => {
-                if raw.is_continuous_unbounded() {
-                    Err(ResolutionError::Unbounded)
-                } else if raw.is_proven_infeasible() {
-                    Err(ResolutionError::Infeasible)
-                } else {
+            let start = Instant::now();
+            let solution = self.model.solve();
+            let raw = solution.raw();
+            let stats = SolveStats {
+                time: Some(start.elapsed()),
+                iterations: Some(raw.iteration_count() as u64),
+                ..SolveStats::default()
+            };
+            // Cbc only keeps an incumbent once it has found a feasible solution
+            let has_solution = raw.best_solution().is_some();
+            match raw.status() {
+                Status::Stopped | Status::UserEvent if has_solution => {
+                    let status = match raw.secondary_status() {
+                        SecondaryStatus::StoppedOnTime => SolutionStatus::TimeLimit,
+                        SecondaryStatus::StoppedOnGap => SolutionStatus::GapLimit,
+                        _ => SolutionStatus::Interrupted,
+                    };
                     let solution_vec = solution.raw().col_solution().into();
                     Ok(CoinCbcSolution {
                         solution,
                         solution_vec,
+                        status,
+                        objective_constant: self.objective_constant,
+                        stats,
                     })
                 }
-            },
-        }
+                Status::Stopped => Err(ResolutionError::Other("Stopped")),
+                Status::Abandoned => Err(ResolutionError::Other("Abandoned")),
+                Status::UserEvent => Err(ResolutionError::Other("UserEvent")),
+                Status::Finished // The optimization finished, but may not have found a solution
+                | Status::Unlaunched // The solver didn't have to be launched, presolve handled it
+                => {
+                    if raw.is_continuous_unbounded() {
+                        Err(ResolutionError::Unbounded)
+                    } else if raw.is_proven_infeasible() {
+                        Err(ResolutionError::Infeasible)
+                    } else {
+                        // Cbc reports a search stopped by the gap tolerance as finished
+                        let status = match raw.secondary_status() {
+                            SecondaryStatus::StoppedOnGap => SolutionStatus::GapLimit,
+                            _ => SolutionStatus::Optimal,
+                        };
+                        let solution_vec = solution.raw().col_solution().into();
+                        Ok(CoinCbcSolution {
+                            solution,
+                            solution_vec,
+                            status,
+                            objective_constant: self.objective_constant,
+                            stats,
+                        })
+                    }
+                },
+            }
+        })
     }
 
     fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let constraint = self.strict_inequalities.apply(constraint);
         let index = self.model.num_rows().try_into().unwrap();
         let row = self.model.add_row();
         let constant = -constraint.expression.constant;
@@ -135,6 +208,63 @@ impl SolverModel for CoinCbcProblem {
     }
 }
 
+impl ModelWithColumns for CoinCbcProblem {
+    fn add_column(
+        &mut self,
+        definition: VariableDefinition,
+        objective_coefficient: f64,
+        coefficients: &[(ConstraintReference, f64)],
+    ) -> Variable {
+        let variable = Variable::at(self.columns.len());
+        let col = add_col(&mut self.model, &definition);
+        self.model.set_obj_coeff(col, objective_coefficient);
+        for (constraint, coefficient) in coefficients {
+            let row = self
+                .model
+                .rows()
+                .nth(constraint.index)
+                .expect("the constraint is not in this model");
+            self.model.set_weight(row, col, *coefficient);
+        }
+        self.columns.push(col);
+        self.bounds.push((definition.min, definition.max));
+        self.objective_coefficients.push(objective_coefficient);
+        variable
+    }
+}
+
+/// The attributes that are read are the ones that were set through good_lp:
+/// changes made directly to the [ModelWithBackend::as_backend_mut] model are not seen.
+impl ModelWithBulkAttributes for CoinCbcProblem {
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)> {
+        variables.iter().map(|v| self.bounds[v.index()]).collect()
+    }
+
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]) {
+        for &(variable, min, max) in bounds {
+            let col = self.columns[variable.index()];
+            self.model.set_col_lower(col, min);
+            self.model.set_col_upper(col, max);
+            self.bounds[variable.index()] = (min, max);
+        }
+    }
+
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64> {
+        variables
+            .iter()
+            .map(|v| self.objective_coefficients[v.index()])
+            .collect()
+    }
+
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]) {
+        for &(variable, coefficient) in coefficients {
+            self.model
+                .set_obj_coeff(self.columns[variable.index()], coefficient);
+            self.objective_coefficients[variable.index()] = coefficient;
+        }
+    }
+}
+
 /// Unfortunately, the current version of cbc silently ignores
 /// sos constraints on continuous variables.
 /// See <https://github.com/coin-or/Cbc/issues/376>
@@ -151,10 +281,87 @@ impl ModelWithSOS1 for CoinCbcProblem {
     }
 }
 
+impl ModelWithTimeLimit for CoinCbcProblem {
+    fn set_time_limit(&mut self, limit: Duration) {
+        let seconds = limit.as_secs_f64().to_string();
+        self.model.set_parameter("seconds", &seconds);
+    }
+}
+
+impl ModelWithMipGap for CoinCbcProblem {
+    fn set_mip_gap(&mut self, relative_gap: f64) {
+        self.model
+            .set_parameter("ratioGap", &relative_gap.to_string());
+    }
+
+    fn set_absolute_gap(&mut self, absolute_gap: f64) {
+        self.model
+            .set_parameter("allowableGap", &absolute_gap.to_string());
+    }
+}
+
+/// Cbc only uses several threads if it was compiled with multi-threading support
+impl ModelWithThreads for CoinCbcProblem {
+    fn set_threads(&mut self, threads: u32) {
+        self.model.set_parameter("threads", &threads.to_string());
+    }
+}
+
+impl ModelWithSeed for CoinCbcProblem {
+    fn set_seed(&mut self, seed: u64) {
+        let seed = seed_to_i32(seed).to_string();
+        // The seed of Clp, used when solving linear relaxations, and the one of Cbc
+        self.model.set_parameter("randomSeed", &seed);
+        self.model.set_parameter("randomCbcSeed", &seed);
+    }
+}
+
+impl ModelWithPresolve for CoinCbcProblem {
+    fn set_presolve(&mut self, presolve: bool) {
+        let value = if presolve { "on" } else { "off" };
+        self.model.set_parameter("presolve", value);
+    }
+}
+
+impl ModelWithScaling for CoinCbcProblem {
+    fn set_scaling(&mut self, scaling: ScalingMode) {
+        let value = match scaling {
+            ScalingMode::Off => "off",
+            ScalingMode::Automatic => "automatic",
+            ScalingMode::Equilibrium => "equilibrium",
+            ScalingMode::Geometric => "geometric",
+        };
+        self.model.set_parameter("scaling", value);
+    }
+}
+
+/// Parameters are the ones of the Cbc command line, such as `cuts` or `maxNodes`
+impl ModelWithRawParameters for CoinCbcProblem {
+    fn set_raw_parameter(&mut self, name: &str, value: &str) {
+        self.model.set_parameter(name, value);
+    }
+}
+
+impl ModelWithVerbosity for CoinCbcProblem {
+    fn set_verbosity(&mut self, verbosity: Verbosity) {
+        // The log level of Cbc, and the one of Clp, which solves the linear relaxations
+        let (cbc, clp) = match verbosity {
+            Verbosity::Silent => ("0", "0"),
+            Verbosity::Summary => ("1", "0"),
+            Verbosity::Full => ("3", "1"),
+        };
+        self.model.set_parameter("logLevel", cbc);
+        self.model.set_parameter("slogLevel", clp);
+    }
+}
+
 /// A coin-cbc problem solution
 pub struct CoinCbcSolution {
     solution: CbcSolution,
     solution_vec: Vec<f64>, // See: rust-or/good_lp#6
+    status: SolutionStatus,
+    objective_constant: f64,
+    stats: SolveStats,
 }
 
 impl CoinCbcSolution {
@@ -169,4 +376,31 @@ impl Solution for CoinCbcSolution {
         // Our indices should always match those of cbc
         self.solution_vec[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+
+    fn best_bound(&self) -> Option<f64> {
+        let raw = self.solution.raw();
+        let bound = raw.best_possible_value();
+        // Cbc doesn't compute a bound when it solves a problem without integer variables
+        if bound.is_finite() && bound.abs() < 1e50 {
+            Some(bound + self.objective_constant)
+        } else if self.status.is_optimal() {
+            Some(raw.obj_value() + self.objective_constant)
+        } else {
+            None
+        }
+    }
+
+    fn mip_gap(&self) -> Option<f64> {
+        let objective = self.solution.raw().obj_value() + self.objective_constant;
+        self.best_bound()
+            .map(|bound| relative_gap(objective, bound))
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.stats.clone()
+    }
 }
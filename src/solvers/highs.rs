@@ -1,12 +1,18 @@
 //! A solver that uses [highs](https://docs.rs/highs), a parallel C++ solver.
 
+use std::convert::TryInto;
+use std::time::{Duration, Instant};
+
 use highs::HighsModelStatus;
 
 use crate::solvers::{
-    ObjectiveDirection, ResolutionError, Solution, SolutionWithDual, SolverModel,
+    logged, seed_to_i32, LpMethod, ModelWithBackend, ModelWithCrossover, ModelWithLpMethod,
+    ModelWithPresolve, ModelWithRawParameters, ModelWithScaling, ModelWithSeed, ModelWithThreads,
+    ModelWithTimeLimit, ModelWithVerbosity, ObjectiveDirection, ResolutionError, ScalingMode,
+    Solution, SolutionStatus, SolutionWithDual, SolveStats, SolverModel, Verbosity,
 };
 use crate::{
-    constraint::ConstraintReference,
+    constraint::{ConstraintReference, StrictInequalities},
     solvers::DualValues,
     variable::{UnsolvedProblem, VariableDefinition},
 };
@@ -24,6 +30,7 @@ pub fn highs(to_solve: UnsolvedProblem) -> HighsProblem {
         ObjectiveDirection::Minimisation => highs::Sense::Minimise,
     };
     let mut columns = Vec::with_capacity(to_solve.variables.len());
+    let mut column_bounds = Vec::with_capacity(to_solve.variables.len());
     for (
         var,
         &VariableDefinition {
@@ -45,26 +52,75 @@ pub fn highs(to_solve: UnsolvedProblem) -> HighsProblem {
             .unwrap_or(&0.);
         let col = highs_problem.add_column(col_factor, min..max);
         columns.push(col);
+        column_bounds.push((min, max));
     }
     HighsProblem {
         sense,
         highs_problem,
         columns,
+        column_bounds,
+        row_bounds: vec![],
+        options: vec![],
+        strict_inequalities: StrictInequalities::new(to_solve.strict_inequality),
     }
 }
 
+/// The value of an option of the HiGHS solver
+#[derive(Debug, Clone, PartialEq)]
+enum HighsOption {
+    Bool(bool),
+    Int(i32),
+    Float(f64),
+    String(String),
+}
+
 /// A HiGHS model
 #[derive(Debug)]
 pub struct HighsProblem {
     sense: highs::Sense,
     highs_problem: highs::RowProblem,
     columns: Vec<highs::Col>,
+    /// The bounds of the columns and of the rows, to check the solutions returned after a limit
+    column_bounds: Vec<(f64, f64)>,
+    row_bounds: Vec<(f64, f64)>,
+    /// Options set on the model before solving it
+    options: Vec<(String, HighsOption)>,
+    strict_inequalities: StrictInequalities,
 }
 
 impl HighsProblem {
     /// Get a highs model for this problem
     pub fn into_inner(self) -> highs::Model {
-        self.highs_problem.optimise(self.sense)
+        let mut model = self.highs_problem.optimise(self.sense);
+        for (name, value) in self.options {
+            match value {
+                HighsOption::Bool(v) => model.set_option(name.as_str(), v),
+                HighsOption::Int(v) => model.set_option(name.as_str(), v),
+                HighsOption::Float(v) => model.set_option(name.as_str(), v),
+                HighsOption::String(v) => model.set_option(name.as_str(), v.as_str()),
+            }
+        }
+        model
+    }
+}
+
+impl HighsProblem {
+    /// Sets an option, replacing its previous value
+    fn set_option(&mut self, name: &str, value: HighsOption) {
+        self.options.retain(|(n, _)| n != name);
+        self.options.push((name.to_string(), value));
+    }
+}
+
+impl ModelWithBackend for HighsProblem {
+    type Backend = highs::RowProblem;
+
+    fn as_backend(&self) -> &Self::Backend {
+        &self.highs_problem
+    }
+
+    fn as_backend_mut(&mut self) -> &mut Self::Backend {
+        &mut self.highs_problem
     }
 }
 
@@ -72,28 +128,59 @@ impl SolverModel for HighsProblem {
     type Solution = HighsSolution;
     type Error = ResolutionError;
 
-    fn solve(self) -> Result<Self::Solution, Self::Error> {
-        let model = self.into_inner();
-        let solved = model.solve();
-        match solved.status() {
-            HighsModelStatus::NotSet => Err(ResolutionError::Other("NotSet")),
-            HighsModelStatus::LoadError => Err(ResolutionError::Other("LoadError")),
-            HighsModelStatus::ModelError => Err(ResolutionError::Other("ModelError")),
-            HighsModelStatus::PresolveError => Err(ResolutionError::Other("PresolveError")),
-            HighsModelStatus::SolveError => Err(ResolutionError::Other("SolveError")),
-            HighsModelStatus::PostsolveError => Err(ResolutionError::Other("PostsolveError")),
-            HighsModelStatus::ModelEmpty => Err(ResolutionError::Other("ModelEmpty")),
-            HighsModelStatus::PrimalInfeasible => Err(ResolutionError::Infeasible),
-            HighsModelStatus::PrimalUnbounded => Err(ResolutionError::Unbounded),
-            _ok_status => Ok(HighsSolution {
-                solution: solved.get_solution(),
-                dual_values: vec![],
-                acquired: false,
-            }),
-        }
+    fn solve(mut self) -> Result<Self::Solution, Self::Error> {
+        logged("HiGHS", move || {
+            self.strict_inequalities.check()?;
+            let column_bounds = std::mem::take(&mut self.column_bounds);
+            let row_bounds = std::mem::take(&mut self.row_bounds);
+            let model = self.into_inner();
+            let start = Instant::now();
+            let solved = model.solve();
+            let stats = SolveStats {
+                time: Some(start.elapsed()),
+                ..SolveStats::default()
+            };
+            match solved.status() {
+                HighsModelStatus::NotSet => Err(ResolutionError::Other("NotSet")),
+                HighsModelStatus::LoadError => Err(ResolutionError::Other("LoadError")),
+                HighsModelStatus::ModelError => Err(ResolutionError::Other("ModelError")),
+                HighsModelStatus::PresolveError => Err(ResolutionError::Other("PresolveError")),
+                HighsModelStatus::SolveError => Err(ResolutionError::Other("SolveError")),
+                HighsModelStatus::PostsolveError => Err(ResolutionError::Other("PostsolveError")),
+                HighsModelStatus::ModelEmpty => Err(ResolutionError::Other("ModelEmpty")),
+                HighsModelStatus::PrimalInfeasible | HighsModelStatus::PrimalDualInfeasible => {
+                    Err(ResolutionError::Infeasible)
+                }
+                // The dual of a feasible problem is infeasible when the problem is unbounded
+                HighsModelStatus::PrimalUnbounded | HighsModelStatus::DualInfeasible => {
+                    Err(ResolutionError::Unbounded)
+                }
+                HighsModelStatus::Optimal => Ok(SolutionStatus::Optimal),
+                HighsModelStatus::ReachedTimeLimit => Ok(SolutionStatus::TimeLimit),
+                HighsModelStatus::ReachedIterationLimit
+                | HighsModelStatus::ReachedDualObjectiveValueUpperBound => {
+                    Ok(SolutionStatus::Interrupted)
+                }
+            }
+            .and_then(|status| {
+                let solution = solved.get_solution();
+                // After a limit, HiGHS returns its current point, which may not be feasible yet
+                if !status.is_optimal() && !is_feasible(&solution, &column_bounds, &row_bounds) {
+                    return Err(ResolutionError::Other("Stopped"));
+                }
+                Ok(HighsSolution {
+                    status,
+                    solution,
+                    dual_values: vec![],
+                    acquired: false,
+                    stats,
+                })
+            })
+        })
     }
 
     fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let constraint = self.strict_inequalities.apply(constraint);
         let index = self.highs_problem.num_rows();
         let upper_bound = -constraint.expression.constant();
         let columns = &self.columns;
@@ -105,19 +192,130 @@ impl SolverModel for HighsProblem {
         if constraint.is_equality {
             self.highs_problem
                 .add_row(upper_bound..=upper_bound, factors);
+            self.row_bounds.push((upper_bound, upper_bound));
         } else {
             self.highs_problem.add_row(..=upper_bound, factors);
+            self.row_bounds.push((f64::NEG_INFINITY, upper_bound));
         }
         ConstraintReference { index }
     }
 }
 
+/// The tolerance of [is_feasible], relative to the bounds larger than 1
+const FEASIBILITY_TOLERANCE: f64 = 1e-6;
+
+/// Whether the values of the columns and of the rows of the solution are within their bounds
+fn is_feasible(
+    solution: &highs::Solution,
+    column_bounds: &[(f64, f64)],
+    row_bounds: &[(f64, f64)],
+) -> bool {
+    let within = |values: &[f64], bounds: &[(f64, f64)]| {
+        values.iter().zip(bounds).all(|(&value, &(min, max))| {
+            value >= min - FEASIBILITY_TOLERANCE * min.abs().max(1.)
+                && value <= max + FEASIBILITY_TOLERANCE * max.abs().max(1.)
+        })
+    };
+    within(solution.columns(), column_bounds) && within(solution.rows(), row_bounds)
+}
+
+impl ModelWithTimeLimit for HighsProblem {
+    fn set_time_limit(&mut self, limit: Duration) {
+        self.set_option("time_limit", HighsOption::Float(limit.as_secs_f64()));
+    }
+}
+
+impl ModelWithSeed for HighsProblem {
+    fn set_seed(&mut self, seed: u64) {
+        self.set_option("random_seed", HighsOption::Int(seed_to_i32(seed)));
+    }
+}
+
+impl ModelWithThreads for HighsProblem {
+    fn set_threads(&mut self, threads: u32) {
+        let threads = threads.try_into().unwrap_or(i32::MAX);
+        self.set_option("threads", HighsOption::Int(threads));
+    }
+}
+
+impl ModelWithLpMethod for HighsProblem {
+    fn set_lp_method(&mut self, method: LpMethod) {
+        // The simplex strategy is ignored when the interior point solver is used
+        let (solver, strategy) = match method {
+            LpMethod::PrimalSimplex => ("simplex", 4),
+            LpMethod::DualSimplex => ("simplex", 1),
+            LpMethod::Barrier => ("ipm", 1),
+        };
+        self.set_option("solver", HighsOption::String(solver.to_string()));
+        self.set_option("simplex_strategy", HighsOption::Int(strategy));
+    }
+}
+
+impl ModelWithCrossover for HighsProblem {
+    fn set_crossover(&mut self, crossover: bool) {
+        self.set_option("run_crossover", HighsOption::Bool(crossover));
+    }
+}
+
+impl ModelWithPresolve for HighsProblem {
+    fn set_presolve(&mut self, presolve: bool) {
+        let value = if presolve { "on" } else { "off" };
+        self.set_option("presolve", HighsOption::String(value.to_string()));
+    }
+}
+
+/// HiGHS equilibration scaling uses geometric means,
+/// so it is used for both [ScalingMode::Equilibrium] and [ScalingMode::Geometric]
+impl ModelWithScaling for HighsProblem {
+    fn set_scaling(&mut self, scaling: ScalingMode) {
+        let strategy = match scaling {
+            ScalingMode::Off => 0,
+            ScalingMode::Automatic => 1,
+            ScalingMode::Equilibrium | ScalingMode::Geometric => 2,
+        };
+        self.set_option("simplex_scale_strategy", HighsOption::Int(strategy));
+    }
+}
+
+/// HiGHS options are typed: values are passed as booleans if they are `true` or `false`,
+/// as integers or floating point numbers if they can be parsed as such,
+/// and as strings otherwise (`presolve=off`).
+/// The values of floating point options must contain a decimal point (`1.0`, not `1`).
+impl ModelWithRawParameters for HighsProblem {
+    fn set_raw_parameter(&mut self, name: &str, value: &str) {
+        let value = if let Ok(b) = value.parse::<bool>() {
+            HighsOption::Bool(b)
+        } else if let Ok(i) = value.parse::<i32>() {
+            HighsOption::Int(i)
+        } else if let Ok(f) = value.parse::<f64>() {
+            HighsOption::Float(f)
+        } else {
+            HighsOption::String(value.to_string())
+        };
+        self.set_option(name, value);
+    }
+}
+
+/// HiGHS has a single level of user output: its log, which ends with a summary of the resolution.
+/// It is used for both [Verbosity::Summary] and [Verbosity::Full].
+/// The `log_dev_level` option only adds messages meant for the developers of HiGHS,
+/// and can still be set with [ModelWithRawParameters].
+impl ModelWithVerbosity for HighsProblem {
+    fn set_verbosity(&mut self, verbosity: Verbosity) {
+        let output = verbosity != Verbosity::Silent;
+        self.set_option("output_flag", HighsOption::Bool(output));
+        self.set_option("log_to_console", HighsOption::Bool(output));
+    }
+}
+
 /// The solution to a highs problem
 #[derive(Debug)]
 pub struct HighsSolution {
     solution: highs::Solution,
     dual_values: Vec<f64>,
     acquired: bool,
+    status: SolutionStatus,
+    stats: SolveStats,
 }
 
 impl HighsSolution {
@@ -131,6 +329,14 @@ impl Solution for HighsSolution {
     fn value(&self, variable: Variable) -> f64 {
         self.solution.columns()[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.stats.clone()
+    }
 }
 
 impl<'a> DualValues for &'a HighsSolution {
@@ -0,0 +1,250 @@
+//! Extraction of the progress of a resolution from the text log of a solver.
+//!
+//! Some backends only report their progress as text, written to the standard output.
+//! When that output is captured (by running the solver binary, or by redirecting the output),
+//! it can be turned into a series of [ProgressEntry] with [LogFormat::parse],
+//! or attached to the statistics of a solution with [SolveStats::with_log].
+//!
+//! ```
+//! use good_lp::solvers::log_parser::LogFormat;
+//!
+//! let log = "\
+//! Cbc0012I Integer solution of 12 found by DiveCoefficient after 8 iterations and 0 nodes (0.01 seconds)
+//! Cbc0010I After 100 nodes, 4 on tree, 12 best solution, best possible 9 (0.25 seconds)
+//! Cbc0001I Search completed - best objective 10, took 250 iterations and 180 nodes (0.40 seconds)";
+//! let progress = LogFormat::Cbc.parse(log);
+//! assert_eq!(progress.len(), 3);
+//! assert_eq!(progress[1].nodes, Some(100));
+//! assert_eq!(progress[1].best_bound, Some(9.));
+//! assert_eq!(progress[1].gap, Some(0.25));
+//! assert_eq!(progress[2].objective, Some(10.));
+//! ```
+use std::time::Duration;
+
+use crate::solvers::{relative_gap, SolveStats};
+
+/// The format of a solver log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// The output of [Cbc](https://github.com/coin-or/Cbc), and of Clp for continuous problems
+    Cbc,
+    /// The output of [lp_solve](http://lpsolve.sourceforge.net/), with a verbosity of at least 4
+    LpSolve,
+}
+
+/// The state of a resolution at one point in time, as reported in the log of the solver.
+///
+/// Every field is optional, because not every line reports every value.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgressEntry {
+    /// The time elapsed since the start of the resolution
+    pub time: Option<Duration>,
+    /// The number of simplex iterations so far
+    pub iterations: Option<u64>,
+    /// The number of branch-and-bound nodes explored so far
+    pub nodes: Option<u64>,
+    /// The objective value of the best solution found so far
+    pub objective: Option<f64>,
+    /// The best bound on the objective value
+    pub best_bound: Option<f64>,
+    /// The relative gap between the objective value and the best bound
+    pub gap: Option<f64>,
+}
+
+impl LogFormat {
+    /// Extracts the progress reported by each relevant line of the log, in order.
+    /// Lines that do not report progress are ignored.
+    pub fn parse(&self, log: &str) -> Vec<ProgressEntry> {
+        log.lines()
+            .filter_map(|line| {
+                let tokens = tokenize(line);
+                match self {
+                    LogFormat::Cbc => parse_cbc_line(&tokens),
+                    LogFormat::LpSolve => parse_lpsolve_line(&tokens),
+                }
+            })
+            .map(|mut entry| {
+                if let (None, Some(objective), Some(bound)) =
+                    (entry.gap, entry.objective, entry.best_bound)
+                {
+                    entry.gap = Some(relative_gap(objective, bound));
+                }
+                entry
+            })
+            .collect()
+    }
+}
+
+impl SolveStats {
+    /// Adds the progress parsed from the log of the solver to these statistics.
+    /// The number of iterations and nodes are taken from the last entry that reports them,
+    /// when the solver did not report them directly.
+    pub fn with_log(mut self, format: LogFormat, log: &str) -> Self {
+        self.progress = format.parse(log);
+        let last =
+            |field: fn(&ProgressEntry) -> Option<u64>| self.progress.iter().rev().find_map(field);
+        let iterations = last(|entry| entry.iterations);
+        let nodes = last(|entry| entry.nodes);
+        self.iterations = self.iterations.or(iterations);
+        self.nodes = self.nodes.or(nodes);
+        self
+    }
+}
+
+fn tokenize(line: &str) -> Vec<&str> {
+    line.split_whitespace()
+        .map(|token| token.trim_matches(|c: char| c == ',' || c == '(' || c == ')' || c == ';'))
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// The number just before the first occurrence of `keyword`
+fn number_before(tokens: &[&str], keyword: &str) -> Option<f64> {
+    let position = tokens.iter().position(|&token| token == keyword)?;
+    tokens.get(position.checked_sub(1)?)?.parse().ok()
+}
+
+/// The number just after the first occurrence of `keyword`
+fn number_after(tokens: &[&str], keyword: &str) -> Option<f64> {
+    let position = tokens.iter().position(|&token| token == keyword)?;
+    tokens.get(position + 1)?.parse().ok()
+}
+
+fn count(value: Option<f64>) -> Option<u64> {
+    value.filter(|v| *v >= 0.).map(|v| v as u64)
+}
+
+fn seconds(value: Option<f64>) -> Option<Duration> {
+    value.filter(|v| *v >= 0.).map(Duration::from_secs_f64)
+}
+
+/// Cbc reports an objective value of 1e50 when no solution was found yet
+fn cbc_objective(value: Option<f64>) -> Option<f64> {
+    value.filter(|v| v.abs() < 1e50)
+}
+
+fn parse_cbc_line(tokens: &[&str]) -> Option<ProgressEntry> {
+    let entry = match *tokens.first()? {
+        // After 100 nodes, 4 on tree, 12 best solution, best possible 9 (0.25 seconds)
+        "Cbc0010I" => ProgressEntry {
+            nodes: count(number_before(tokens, "nodes")),
+            objective: cbc_objective(number_before(tokens, "best")),
+            best_bound: cbc_objective(number_after(tokens, "possible")),
+            time: seconds(number_before(tokens, "seconds")),
+            ..ProgressEntry::default()
+        },
+        // Integer solution of 12 found [by heuristic] after 8 iterations and 0 nodes (0.01 seconds)
+        "Cbc0004I" | "Cbc0012I" => ProgressEntry {
+            objective: number_after(tokens, "of"),
+            iterations: count(number_before(tokens, "iterations")),
+            nodes: count(number_before(tokens, "nodes")),
+            time: seconds(number_before(tokens, "seconds")),
+            ..ProgressEntry::default()
+        },
+        // Search completed - best objective 10, took 250 iterations and 180 nodes (0.40 seconds)
+        "Cbc0001I" => ProgressEntry {
+            objective: cbc_objective(number_after(tokens, "objective")),
+            iterations: count(number_before(tokens, "iterations")),
+            nodes: count(number_before(tokens, "nodes")),
+            time: seconds(number_before(tokens, "seconds")),
+            ..ProgressEntry::default()
+        },
+        // Optimal objective 10 - 12 iterations time 0.002
+        "Clp0032I" => ProgressEntry {
+            objective: number_after(tokens, "objective"),
+            iterations: count(number_before(tokens, "iterations")),
+            time: seconds(number_after(tokens, "time")),
+            ..ProgressEntry::default()
+        },
+        _ => return None,
+    };
+    Some(entry)
+}
+
+fn parse_lpsolve_line(tokens: &[&str]) -> Option<ProgressEntry> {
+    match tokens {
+        // Improved solution being stored at iter 12; objective 10
+        ["Improved", "solution", ..] | ["Found", "improvement", ..] => Some(ProgressEntry {
+            iterations: count(number_after(tokens, "iter")),
+            objective: number_after(tokens, "objective"),
+            ..ProgressEntry::default()
+        }),
+        // Optimal solution 10 after 250 iter, 180 nodes (gap 0.1%).
+        ["Optimal", "solution", ..] | ["Relaxed", "solution", ..] => Some(ProgressEntry {
+            objective: number_after(tokens, "solution"),
+            iterations: count(
+                number_before(tokens, "iter").or_else(|| number_before(tokens, "iter.")),
+            ),
+            nodes: count(number_before(tokens, "nodes")),
+            gap: tokens
+                .iter()
+                .position(|&token| token == "gap")
+                .and_then(|position| tokens.get(position + 1))
+                .and_then(|gap| gap.trim_end_matches(['%', '.', ')']).parse().ok())
+                .map(|percent: f64| percent / 100.),
+            ..ProgressEntry::default()
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clp_summary() {
+        let progress = LogFormat::Cbc.parse(
+            "Clp0006I 0  Obj 0 Primal inf 3 (1)\nClp0032I Optimal objective 12.5 - 3 iterations time 0.002",
+        );
+        assert_eq!(
+            progress,
+            vec![ProgressEntry {
+                objective: Some(12.5),
+                iterations: Some(3),
+                time: Some(Duration::from_secs_f64(0.002)),
+                ..ProgressEntry::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn cbc_without_solution() {
+        let progress = LogFormat::Cbc
+            .parse("Cbc0010I After 0 nodes, 1 on tree, 1e+50 best solution, best possible -3 (0.01 seconds)");
+        assert_eq!(progress[0].objective, None);
+        assert_eq!(progress[0].best_bound, Some(-3.));
+        assert_eq!(progress[0].gap, None);
+    }
+
+    #[test]
+    fn lpsolve_branch_and_bound() {
+        let log = "\
+set_XXXX: Invalid row index 0
+Improved solution being stored at iter 7; objective -2
+Optimal solution                  -2 after          9 iter,         3 nodes (gap 0.5%).";
+        let progress = LogFormat::LpSolve.parse(log);
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[0].iterations, Some(7));
+        assert_eq!(progress[0].objective, Some(-2.));
+        assert_eq!(progress[1].iterations, Some(9));
+        assert_eq!(progress[1].nodes, Some(3));
+        assert_eq!(progress[1].gap, Some(0.005));
+    }
+
+    #[test]
+    fn stats_keep_reported_values() {
+        let stats = SolveStats {
+            iterations: Some(300),
+            ..SolveStats::default()
+        }
+        .with_log(
+            LogFormat::Cbc,
+            "Cbc0001I Search completed - best objective 10, took 250 iterations and 180 nodes (0.40 seconds)",
+        );
+        assert_eq!(stats.iterations, Some(300));
+        assert_eq!(stats.nodes, Some(180));
+        assert_eq!(stats.progress.len(), 1);
+    }
+}
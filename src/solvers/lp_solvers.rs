@@ -1,17 +1,39 @@
 //! This module allows solving problems is external solver binaries.
 //! Contrarily to other solver modules, this one doesn't require linking your program to any solver.
 //! A solver binary will need to be present on the user's computer at runtime.
+//!
+//! Since the solver runs in a separate process, a crash of the solver
+//! (a segmentation fault in its native code, for instance) cannot take down your program:
+//! it is reported as a [ResolutionError], and the resolution can be retried
+//! automatically with [Model::with_retries].
+//!
+//! The lp-solvers crate discards what the solver binaries print.
+//! Wrap a solver in [CapturedOutput] to have good_lp run the binary and capture its output:
+//! each line is reported as an event of the `solve` span with the "tracing" feature,
+//! and to the log crate with the "log" feature.
+//! The progress that Cbc reports in its output is added to the [SolveStats] of the solution.
 
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::AtomicUsize;
+use std::time::{Duration, Instant};
 
-use lp_solvers::lp_format::LpObjective;
+use lp_solvers::lp_format::{LpObjective, LpProblem};
 use lp_solvers::problem::StrExpression;
 pub use lp_solvers::solvers::*;
 use lp_solvers::util::UniqueNameGenerator;
 
-use crate::constraint::ConstraintReference;
-use crate::solvers::ObjectiveDirection;
-use crate::variable::UnsolvedProblem;
+use crate::constraint::{ConstraintReference, StrictInequalities};
+use crate::solvers::log_parser::LogFormat;
+use crate::solvers::{
+    log_solver_output, logged, ModelWithBackend, ModelWithBulkAttributes, ModelWithColumns,
+    ModelWithThreads, ModelWithTimeLimit, ObjectiveDirection, SolutionStatus, SolveStats,
+};
+use crate::variable::{UnsolvedProblem, VariableDefinition};
 use crate::{
     Constraint, Expression, IntoAffineExpression, ResolutionError, Solution, Solver, SolverModel,
     Variable,
@@ -41,6 +63,10 @@ impl<T: lp_solvers::solvers::SolverTrait + Clone> Solver for LpSolver<T> {
             })
             .collect();
         let objective = linear_coefficients_str(&problem.objective, &variables);
+        let mut objective_coefficients = vec![0.; variables.len()];
+        for (var, coefficient) in problem.objective.linear_coefficients() {
+            objective_coefficients[var.index()] = coefficient;
+        }
         Model {
             problem: lp_solvers::problem::Problem {
                 name,
@@ -50,7 +76,230 @@ impl<T: lp_solvers::solvers::SolverTrait + Clone> Solver for LpSolver<T> {
                 constraints: vec![],
             },
             solver: self.0.clone(),
+            retries: 0,
+            names: gen,
+            objective_coefficients,
+            strict_inequalities: StrictInequalities::new(problem.strict_inequality),
+        }
+    }
+}
+
+/// [Cbc](CbcSolver) given the [branching priorities](VariableDefinition::branching_priority)
+/// of the integer variables, in a file it reads with its `priorityIn` command.
+/// Its output is captured, like with [CapturedOutput].
+///
+/// Cbc branches first on the variables with the lowest priority, 1000 by default:
+/// a good_lp priority `p` is given to Cbc as `1000 - p`.
+///
+/// ```no_run
+/// use good_lp::solvers::lp_solvers::{CbcSolver, CbcWithPriorities};
+/// use good_lp::{constraint, variable, variables, SolverModel};
+/// let mut vars = variables!();
+/// let open = vars.add(variable().binary().branching_priority(10));
+/// let amount = vars.add(variable().integer().max(10));
+/// let solution = vars
+///     .maximise(amount - 3 * open)
+///     .using(CbcWithPriorities::new(CbcSolver::new()))
+///     .with(constraint!(amount <= 10 * open))
+///     .solve()?;
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+#[derive(Clone)]
+pub struct CbcWithPriorities {
+    cbc: CbcSolver,
+    /// The lp-solvers names of the variables, and their good_lp priority
+    priorities: Vec<(String, i32)>,
+}
+
+impl CbcWithPriorities {
+    /// Use the given Cbc solver, with the priorities of the variables of the problem
+    pub fn new(cbc: CbcSolver) -> Self {
+        CbcWithPriorities {
+            cbc,
+            priorities: vec![],
+        }
+    }
+
+    /// The content of the priorities file read by Cbc
+    fn priorities_csv(&self) -> String {
+        let mut csv = String::from("name,priority\n");
+        for (name, priority) in &self.priorities {
+            let priority = 1000i32.saturating_sub(*priority);
+            csv.push_str(&format!("{},{}\n", name, priority));
         }
+        csv
+    }
+}
+
+impl Solver for CbcWithPriorities {
+    type Model = Model<CbcWithPriorities>;
+
+    fn create_model(&mut self, problem: UnsolvedProblem) -> Self::Model {
+        let priorities: Vec<Option<i32>> = problem
+            .variables
+            .iter_variables_with_def()
+            .map(|(_, def)| {
+                let priority = def.get_branching_priority();
+                Some(priority).filter(|&p| def.is_integer && p != 0)
+            })
+            .collect();
+        let mut model = LpSolver(self.clone()).create_model(problem);
+        model.solver.priorities = model
+            .problem
+            .variables
+            .iter()
+            .zip(priorities)
+            .filter_map(|(variable, priority)| Some((variable.name.clone(), priority?)))
+            .collect();
+        model
+    }
+}
+
+impl SolverTrait for CbcWithPriorities {
+    fn run<'a, P: LpProblem<'a>>(
+        &self,
+        problem: &'a P,
+    ) -> Result<lp_solvers::solvers::Solution, String> {
+        if self.priorities.is_empty() {
+            return run_captured(&self.cbc, problem);
+        }
+        let path = TempFile::new("priorities", "csv");
+        std::fs::write(&path.0, self.priorities_csv())
+            .map_err(|e| format!("unable to write the branching priorities: {}", e))?;
+        let program = PriorityIn {
+            cbc: &self.cbc,
+            path,
+        };
+        run_captured(&program, problem)
+    }
+}
+
+/// Cbc, reading the priorities file after the problem
+struct PriorityIn<'a> {
+    cbc: &'a CbcSolver,
+    path: TempFile,
+}
+
+impl SolverProgram for PriorityIn<'_> {
+    fn command_name(&self) -> &str {
+        self.cbc.command_name()
+    }
+
+    fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+        let mut arguments = self.cbc.arguments(lp_file, solution_file);
+        // The first argument is the problem file
+        let priority_in = vec!["priorityIn".into(), self.path.0.clone().into_os_string()];
+        arguments.splice(1..1, priority_in);
+        arguments
+    }
+
+    fn preferred_temp_solution_file(&self) -> Option<&Path> {
+        self.cbc.preferred_temp_solution_file()
+    }
+}
+
+impl SolverWithSolutionParsing for PriorityIn<'_> {
+    fn read_specific_solution<'b, P: LpProblem<'b>>(
+        &self,
+        f: &File,
+        problem: Option<&'b P>,
+    ) -> Result<lp_solvers::solvers::Solution, String> {
+        self.cbc.read_specific_solution(f, problem)
+    }
+}
+
+/// A solver binary that good_lp runs itself, to capture what it prints.
+/// See the [module documentation](self).
+///
+/// ```no_run
+/// use good_lp::solvers::lp_solvers::{CapturedOutput, CbcSolver, LpSolver};
+/// use good_lp::{variables, Solution, SolverModel};
+/// variables! {vars: 0 <= x <= 3;}
+/// let solver = LpSolver(CapturedOutput(CbcSolver::new()));
+/// let solution = vars.maximise(x).using(solver).solve()?;
+/// println!("{:?}", solution.stats().progress);
+/// # Ok::<_, good_lp::ResolutionError>(())
+/// ```
+#[derive(Clone)]
+pub struct CapturedOutput<T>(pub T);
+
+impl<T: SolverProgram + SolverWithSolutionParsing> SolverTrait for CapturedOutput<T> {
+    fn run<'a, P: LpProblem<'a>>(
+        &self,
+        problem: &'a P,
+    ) -> Result<lp_solvers::solvers::Solution, String> {
+        run_captured(&self.0, problem)
+    }
+}
+
+thread_local! {
+    /// The output of the last solver binary run by [run_captured] on this thread,
+    /// and the format of its log, if it is known
+    static CAPTURED: RefCell<Option<(Option<LogFormat>, String)>> = RefCell::new(None);
+}
+
+/// Runs the solver binary like lp-solvers does, but keeps what it prints:
+/// it is reported line by line, and kept for the statistics of the solution
+fn run_captured<'a, T, P>(
+    solver: &T,
+    problem: &'a P,
+) -> Result<lp_solvers::solvers::Solution, String>
+where
+    T: SolverProgram + SolverWithSolutionParsing,
+    P: LpProblem<'a>,
+{
+    let command_name = solver.command_name();
+    let problem_file = problem
+        .to_tmp_file()
+        .map_err(|e| format!("unable to write the {} problem file: {}", command_name, e))?;
+    // A solution file chosen by the user is left in place
+    let (solution_path, _solution_file) = match solver.preferred_temp_solution_file() {
+        Some(path) => (path.to_path_buf(), None),
+        None => {
+            let file = TempFile::new("solution", "sol");
+            (file.0.clone(), Some(file))
+        }
+    };
+    let arguments = solver.arguments(problem_file.path(), &solution_path);
+    let output = Command::new(command_name)
+        .args(arguments)
+        .output()
+        .map_err(|e| format!("unable to run {}: {}", command_name, e))?;
+    let mut printed = String::from_utf8_lossy(&output.stdout).into_owned();
+    printed.push_str(&String::from_utf8_lossy(&output.stderr));
+    log_solver_output(command_name, &printed);
+    let is_cbc = Path::new(command_name)
+        .file_stem()
+        .is_some_and(|stem| stem.eq_ignore_ascii_case("cbc"));
+    let format = Some(LogFormat::Cbc).filter(|_| is_cbc);
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some((format, printed)));
+    if !output.status.success() {
+        return Err(format!("{} exited with {}", command_name, output.status));
+    }
+    let file = File::open(&solution_path)
+        .map_err(|e| format!("unable to read the {} solution: {}", command_name, e))?;
+    solver.read_specific_solution(&file, Some(problem))
+}
+
+/// A path in the temporary directory that is unique to this run, removed when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(kind: &str, extension: &str) -> Self {
+        static FILES: AtomicUsize = AtomicUsize::new(0);
+        TempFile(std::env::temp_dir().join(format!(
+            "good_lp_{}_{}_{}.{}",
+            kind,
+            std::process::id(),
+            FILES.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            extension
+        )))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
     }
 }
 
@@ -58,6 +307,42 @@ impl<T: lp_solvers::solvers::SolverTrait + Clone> Solver for LpSolver<T> {
 pub struct Model<T> {
     problem: lp_solvers::problem::Problem,
     solver: T,
+    retries: usize,
+    names: UniqueNameGenerator,
+    /// The coefficients of the variables in the objective, which is stored as a string
+    objective_coefficients: Vec<f64>,
+    strict_inequalities: StrictInequalities,
+}
+
+impl<T> Model<T> {
+    /// Runs the external solver again, up to this number of times,
+    /// if it exits without producing a solution (because it crashed, for instance).
+    /// Infeasible and unbounded problems are not retried.
+    ///
+    /// ```no_run
+    /// use good_lp::solvers::lp_solvers::{CbcSolver, LpSolver};
+    /// use good_lp::{variables, SolverModel};
+    /// variables! {vars: 0 <= x <= 3;}
+    /// let solver = LpSolver(CbcSolver::new());
+    /// let solution = vars.maximise(x).using(solver).with_retries(2).solve()?;
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+}
+
+impl<T> ModelWithBackend for Model<T> {
+    type Backend = lp_solvers::problem::Problem;
+
+    fn as_backend(&self) -> &Self::Backend {
+        &self.problem
+    }
+
+    fn as_backend_mut(&mut self) -> &mut Self::Backend {
+        &mut self.problem
+    }
 }
 
 impl<T: SolverTrait> SolverModel for Model<T> {
@@ -65,23 +350,54 @@ impl<T: SolverTrait> SolverModel for Model<T> {
     type Error = ResolutionError;
 
     fn solve(self) -> Result<Self::Solution, Self::Error> {
-        let map = self.solver.run(&self.problem)?;
-        match map.status {
-            Status::Infeasible => return Err(ResolutionError::Infeasible),
-            Status::Unbounded => return Err(ResolutionError::Unbounded),
-            Status::NotSolved => return Err(ResolutionError::Other("unknown error: not solved")),
-            _ => {}
-        }
-        let solution = self
-            .problem
-            .variables
-            .iter()
-            .map(|v| f64::from(*map.results.get(&v.name).unwrap_or(&0.)))
-            .collect();
-        Ok(LpSolution { solution })
+        logged("lp-solvers", move || {
+            self.strict_inequalities.check()?;
+            let start = Instant::now();
+            CAPTURED.with(|captured| captured.borrow_mut().take());
+            let mut attempt = self.solver.run(&self.problem);
+            for _ in 0..self.retries {
+                if attempt.is_ok() {
+                    break;
+                }
+                attempt = self.solver.run(&self.problem);
+            }
+            let map = attempt?;
+            // This includes writing the problem, and starting the external solver
+            let mut stats = SolveStats {
+                time: Some(start.elapsed()),
+                ..SolveStats::default()
+            };
+            // Only set when the solver was run by good_lp, by CapturedOutput
+            if let Some((Some(format), output)) =
+                CAPTURED.with(|captured| captured.borrow_mut().take())
+            {
+                stats = stats.with_log(format, &output);
+            }
+            let status = match map.status {
+                Status::Infeasible => return Err(ResolutionError::Infeasible),
+                Status::Unbounded => return Err(ResolutionError::Unbounded),
+                Status::NotSolved => {
+                    return Err(ResolutionError::Other("unknown error: not solved"))
+                }
+                Status::SubOptimal => SolutionStatus::Interrupted,
+                Status::Optimal => SolutionStatus::Optimal,
+            };
+            let solution = self
+                .problem
+                .variables
+                .iter()
+                .map(|v| f64::from(*map.results.get(&v.name).unwrap_or(&0.)))
+                .collect();
+            Ok(LpSolution {
+                solution,
+                status,
+                stats,
+            })
+        })
     }
 
     fn add_constraint(&mut self, c: Constraint) -> ConstraintReference {
+        let c = self.strict_inequalities.apply(c);
         let reference = ConstraintReference {
             index: self.problem.constraints.len(),
         };
@@ -100,6 +416,100 @@ impl<T: SolverTrait> SolverModel for Model<T> {
     }
 }
 
+impl<T> ModelWithColumns for Model<T> {
+    fn add_column(
+        &mut self,
+        definition: VariableDefinition,
+        objective_coefficient: f64,
+        coefficients: &[(ConstraintReference, f64)],
+    ) -> Variable {
+        let variable = Variable::at(self.problem.variables.len());
+        let name = self.names.add_variable(&definition.name).to_string();
+        push_term(&mut self.problem.objective, objective_coefficient, &name);
+        self.objective_coefficients.push(objective_coefficient);
+        for (constraint, coefficient) in coefficients {
+            let lhs = &mut self.problem.constraints[constraint.index].lhs;
+            push_term(lhs, *coefficient, &name);
+        }
+        self.problem.variables.push(lp_solvers::problem::Variable {
+            name,
+            is_integer: definition.is_integer,
+            lower_bound: definition.min,
+            upper_bound: definition.max,
+        });
+        variable
+    }
+}
+
+/// The objective coefficients that are read are the ones that were set through good_lp:
+/// changes made directly to the objective of the [ModelWithBackend::as_backend_mut] problem
+/// are not seen, and are overwritten by [ModelWithBulkAttributes::set_objective_coeffs_bulk].
+impl<T> ModelWithBulkAttributes for Model<T> {
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)> {
+        variables
+            .iter()
+            .map(|v| {
+                let variable = &self.problem.variables[v.index()];
+                (variable.lower_bound, variable.upper_bound)
+            })
+            .collect()
+    }
+
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]) {
+        for &(v, min, max) in bounds {
+            let variable = &mut self.problem.variables[v.index()];
+            variable.lower_bound = min;
+            variable.upper_bound = max;
+        }
+    }
+
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64> {
+        variables
+            .iter()
+            .map(|v| self.objective_coefficients[v.index()])
+            .collect()
+    }
+
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]) {
+        for &(variable, coefficient) in coefficients {
+            self.objective_coefficients[variable.index()] = coefficient;
+        }
+        let mut objective = StrExpression(String::new());
+        for (&coefficient, variable) in self
+            .objective_coefficients
+            .iter()
+            .zip(&self.problem.variables)
+        {
+            if coefficient != 0. {
+                push_term(&mut objective, coefficient, &variable.name);
+            }
+        }
+        self.problem.objective = objective;
+    }
+}
+
+/// Only available for external solvers that accept a time limit, in whole seconds
+impl<T: SolverTrait + WithMaxSeconds<T>> ModelWithTimeLimit for Model<T> {
+    fn set_time_limit(&mut self, limit: Duration) {
+        let seconds = limit.as_secs_f64().ceil() as u32;
+        self.solver = self.solver.with_max_seconds(seconds);
+    }
+}
+
+/// Only available for external solvers that accept a number of threads
+impl<T: SolverTrait + WithNbThreads<T>> ModelWithThreads for Model<T> {
+    fn set_threads(&mut self, threads: u32) {
+        self.solver = self.solver.with_nb_threads(threads);
+    }
+}
+
+fn push_term(expression: &mut StrExpression, coefficient: f64, name: &str) {
+    if !expression.0.is_empty() {
+        expression.0.push_str(" + ");
+    }
+    expression.0.push_str(&format!("{} {}", coefficient, name));
+}
+
 fn linear_coefficients_str(
     expr: &Expression,
     variables: &[lp_solvers::problem::Variable],
@@ -115,10 +525,20 @@ fn linear_coefficients_str(
 /// A solution
 pub struct LpSolution {
     solution: Vec<f64>,
+    status: SolutionStatus,
+    stats: SolveStats,
 }
 
 impl Solution for LpSolution {
     fn value(&self, variable: Variable) -> f64 {
         self.solution[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.stats.clone()
+    }
 }
@@ -1,36 +1,27 @@
 //! A solver that uses a [Cbc](https://www.coin-or.org/Cbc/) [native library binding](https://docs.rs/coin_cbc).
 //! This solver is activated using the default `coin_cbc` feature.
 //! You can disable it an enable another solver instead using cargo features.
-use crate::solvers::{ObjectiveDirection, ResolutionError, Solution, SolverModel};
+use crate::solvers::{
+    logged, ModelWithBackend, ModelWithBulkAttributes, ObjectiveDirection, ResolutionError,
+    Solution, SolutionStatus, SolveStats, SolverModel,
+};
 use crate::variable::UnsolvedProblem;
 use crate::{
-    affine_expression_trait::IntoAffineExpression, constraint::ConstraintReference, ModelWithSOS1,
+    affine_expression_trait::IntoAffineExpression,
+    constraint::{ConstraintReference, StrictInequalities},
+    ModelWithSOS1,
 };
 use crate::{Constraint, Variable};
 use lpsolve::{ConstraintType, Problem, SOSType, SolveStatus};
 use std::convert::TryInto;
 use std::ffi::CString;
 use std::os::raw::c_int;
-
-fn expr_to_scatter_vec<E: IntoAffineExpression>(expr: E) -> (Vec<f64>, Vec<c_int>, f64) {
-    let constant = expr.constant();
-    let mut indices: Vec<c_int> = vec![];
-    let mut coefficients = vec![];
-    for (var, coeff) in expr.linear_coefficients() {
-        indices.push(col_num(var));
-        coefficients.push(coeff);
-    }
-    (coefficients, indices, constant)
-}
+use std::time::Instant;
 
 fn to_c(i: usize) -> c_int {
     i.try_into().expect("Too many variables.")
 }
 
-fn col_num(var: Variable) -> c_int {
-    to_c(var.index() + 1)
-}
-
 /// The [lp_solve](http://lpsolve.sourceforge.net/5.5/) open-source solver library.
 /// lp_solve is released under the LGPL license.
 pub fn lp_solve(to_solve: UnsolvedProblem) -> LpSolveProblem {
@@ -38,74 +29,147 @@ pub fn lp_solve(to_solve: UnsolvedProblem) -> LpSolveProblem {
         objective,
         direction,
         variables,
+        strict_inequality,
+        ..
     } = to_solve;
 
-    // It looks like the lp_solve rust binding doesn't expose the set_maxim function
-    let objective = if direction == ObjectiveDirection::Minimisation {
-        objective
-    } else {
-        -objective
-    };
+    let mut objective_coefficients = vec![0.; variables.len()];
+    for (var, coefficient) in objective.linear_coefficients() {
+        objective_coefficients[var.index()] = coefficient;
+    }
 
     let cols = to_c(variables.len());
-    let mut model = Problem::new(0, cols).expect("Unable to create problem");
-    let (obj_coefs, obj_idx, _const) = expr_to_scatter_vec(objective);
-    assert!(model.scatter_objective_function(&obj_coefs, &obj_idx));
+    let model = Problem::new(0, cols).expect("Unable to create problem");
+    let mut problem = LpSolveProblem {
+        problem: model,
+        direction,
+        bounds: Vec::with_capacity(variables.len()),
+        objective_coefficients,
+        strict_inequalities: StrictInequalities::new(strict_inequality),
+    };
+    problem.set_objective();
     for (i, v) in variables.into_iter().enumerate() {
-        let col = to_c(i + 1);
-        assert!(model.set_integer(col, v.is_integer));
-        if v.min.is_finite() || v.max.is_finite() {
-            assert!(model.set_bounds(col, v.min, v.max));
+        assert!(problem.problem.set_integer(to_c(i + 1), v.is_integer));
+        problem.bounds.push((v.min, v.max));
+        problem.set_bounds(i);
+    }
+    problem
+}
+
+/// An lp_solve problem instance
+pub struct LpSolveProblem {
+    problem: Problem,
+    direction: ObjectiveDirection,
+    /// The bounds and objective coefficients of the columns, that the bindings do not give back
+    bounds: Vec<(f64, f64)>,
+    objective_coefficients: Vec<f64>,
+    strict_inequalities: StrictInequalities,
+}
+
+impl LpSolveProblem {
+    /// Sends the objective coefficients to lp_solve, which replaces the whole objective
+    fn set_objective(&mut self) {
+        // It looks like the lp_solve rust binding doesn't expose the set_maxim function
+        let sign = if self.direction == ObjectiveDirection::Minimisation {
+            1.
         } else {
-            assert!(model.set_unbounded(col));
+            -1.
+        };
+        let (obj_coefs, obj_idx): (Vec<f64>, Vec<c_int>) = self
+            .objective_coefficients
+            .iter()
+            .enumerate()
+            .filter(|&(_, &coefficient)| coefficient != 0.)
+            .map(|(i, &coefficient)| (sign * coefficient, to_c(i + 1)))
+            .unzip();
+        assert!(self
+            .problem
+            .scatter_objective_function(&obj_coefs, &obj_idx));
+    }
+
+    /// Sends the bounds of the column at the given index to lp_solve
+    fn set_bounds(&mut self, index: usize) {
+        let col = to_c(index + 1);
+        let (min, max) = self.bounds[index];
+        if min.is_finite() || max.is_finite() {
+            assert!(self.problem.set_bounds(col, min, max));
+        } else {
+            assert!(self.problem.set_unbounded(col));
         }
     }
-    LpSolveProblem(model)
 }
 
-/// An lp_solve problem instance
-pub struct LpSolveProblem(Problem);
+impl ModelWithBackend for LpSolveProblem {
+    type Backend = Problem;
+
+    fn as_backend(&self) -> &Self::Backend {
+        &self.problem
+    }
+
+    fn as_backend_mut(&mut self) -> &mut Self::Backend {
+        &mut self.problem
+    }
+}
 
 impl SolverModel for LpSolveProblem {
     type Solution = LpSolveSolution;
     type Error = ResolutionError;
 
     fn solve(mut self) -> Result<Self::Solution, Self::Error> {
-        use ResolutionError::*;
-        match Problem::solve(&mut self.0) {
-            SolveStatus::Unbounded => Err(Unbounded),
-            SolveStatus::Infeasible => Err(Infeasible),
-            SolveStatus::OutOfMemory => Err(Other("OutOfMemory")),
-            SolveStatus::NotRun => Err(Other("NotRun")),
-            SolveStatus::Degenerate => Err(Other("Degenerate")),
-            SolveStatus::NumericalFailure => Err(Other("NumericalFailure")),
-            SolveStatus::UserAbort => Err(Other("UserAbort")),
-            SolveStatus::Timeout => Err(Other("Timeout")),
-            SolveStatus::ProcFail => Err(Other("ProcFail")),
-            SolveStatus::ProcBreak => Err(Other("ProcBreak")),
-            SolveStatus::NoFeasibleFound => Err(Other("NoFeasibleFound")),
-            _ => {
-                let mut solution = vec![0.; self.0.num_cols() as usize];
-                let truncated = self
-                    .0
-                    .get_solution_variables(&mut solution)
-                    .expect("internal error: invalid solution array length");
-                assert_eq!(
-                    truncated.len(),
-                    solution.len(),
-                    "The solution doesn't have the expected number of variables"
-                );
-                Ok(LpSolveSolution {
-                    problem: self.0,
-                    solution,
-                })
+        logged("lp_solve", move || {
+            use ResolutionError::*;
+            self.strict_inequalities.check()?;
+            let start = Instant::now();
+            let status = Problem::solve(&mut self.problem);
+            let stats = SolveStats {
+                time: Some(start.elapsed()),
+                ..SolveStats::default()
+            };
+            match status {
+                SolveStatus::Unbounded => Err(Unbounded),
+                SolveStatus::Infeasible => Err(Infeasible),
+                SolveStatus::OutOfMemory => Err(Other("OutOfMemory")),
+                SolveStatus::NotRun => Err(Other("NotRun")),
+                SolveStatus::Degenerate => Err(Other("Degenerate")),
+                SolveStatus::NumericalFailure => Err(Other("NumericalFailure")),
+                SolveStatus::UserAbort => Err(Other("UserAbort")),
+                SolveStatus::Timeout => Err(Other("Timeout")),
+                SolveStatus::ProcFail => Err(Other("ProcFail")),
+                SolveStatus::ProcBreak => Err(Other("ProcBreak")),
+                SolveStatus::NoFeasibleFound => Err(Other("NoFeasibleFound")),
+                _ => {
+                    let mut solution = vec![0.; self.problem.num_cols() as usize];
+                    let truncated = self
+                        .problem
+                        .get_solution_variables(&mut solution)
+                        .expect("internal error: invalid solution array length");
+                    assert_eq!(
+                        truncated.len(),
+                        solution.len(),
+                        "The solution doesn't have the expected number of variables"
+                    );
+                    // lp_solve returns a sub-optimal solution when it is stopped early
+                    // after having found a feasible solution
+                    let status = if status == SolveStatus::Suboptimal {
+                        SolutionStatus::Interrupted
+                    } else {
+                        SolutionStatus::Optimal
+                    };
+                    Ok(LpSolveSolution {
+                        problem: self.problem,
+                        solution,
+                        status,
+                        stats,
+                    })
+                }
             }
-        }
+        })
     }
 
     fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
-        let index = self.0.num_rows().try_into().expect("too many rows");
-        let mut coeffs: Vec<f64> = vec![0.; self.0.num_cols() as usize + 1];
+        let constraint = self.strict_inequalities.apply(constraint);
+        let index = self.problem.num_rows().try_into().expect("too many rows");
+        let mut coeffs: Vec<f64> = vec![0.; self.problem.num_cols() as usize + 1];
         let target = -constraint.expression.constant;
         for (var, coeff) in constraint.expression.linear_coefficients() {
             coeffs[var.index() + 1] = coeff;
@@ -115,12 +179,43 @@ impl SolverModel for LpSolveProblem {
         } else {
             ConstraintType::Le
         };
-        let success = self.0.add_constraint(&coeffs, target, constraint_type);
+        let success = self
+            .problem
+            .add_constraint(&coeffs, target, constraint_type);
         assert!(success, "could not add constraint. memory error.");
         ConstraintReference { index }
     }
 }
 
+/// The attributes that are read are the ones that were set through good_lp:
+/// changes made directly to the [ModelWithBackend::as_backend_mut] model are not seen.
+impl ModelWithBulkAttributes for LpSolveProblem {
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)> {
+        variables.iter().map(|v| self.bounds[v.index()]).collect()
+    }
+
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]) {
+        for &(variable, min, max) in bounds {
+            self.bounds[variable.index()] = (min, max);
+            self.set_bounds(variable.index());
+        }
+    }
+
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64> {
+        variables
+            .iter()
+            .map(|v| self.objective_coefficients[v.index()])
+            .collect()
+    }
+
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]) {
+        for &(variable, coefficient) in coefficients {
+            self.objective_coefficients[variable.index()] = coefficient;
+        }
+        self.set_objective();
+    }
+}
+
 impl ModelWithSOS1 for LpSolveProblem {
     fn add_sos1<I: IntoAffineExpression>(&mut self, variables: I) {
         let iter = variables.linear_coefficients().into_iter();
@@ -132,7 +227,7 @@ impl ModelWithSOS1 for LpSolveProblem {
             variables.push(var.index().try_into().expect("too many vars"));
         }
         let name = CString::new("sos").unwrap();
-        self.0
+        self.problem
             .add_sos_constraint(&name, SOSType::Type1, 1, &weights, &variables);
     }
 }
@@ -141,6 +236,8 @@ impl ModelWithSOS1 for LpSolveProblem {
 pub struct LpSolveSolution {
     problem: Problem,
     solution: Vec<f64>,
+    status: SolutionStatus,
+    stats: SolveStats,
 }
 
 impl LpSolveSolution {
@@ -154,4 +251,12 @@ impl Solution for LpSolveSolution {
     fn value(&self, variable: Variable) -> f64 {
         self.solution[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.stats.clone()
+    }
 }
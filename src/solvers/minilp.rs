@@ -1,13 +1,17 @@
 //! A solver that uses [minilp](https://docs.rs/minilp), a pure rust solver.
 
 use std::panic::catch_unwind;
+use std::time::Instant;
 
 use minilp::Error;
 
 use crate::variable::{UnsolvedProblem, VariableDefinition};
 use crate::{
-    constraint::ConstraintReference,
-    solvers::{ObjectiveDirection, ResolutionError, Solution, SolverModel},
+    constraint::{ConstraintReference, StrictInequalities},
+    solvers::{
+        logged, ModelWithBackend, ModelWithVerbosity, ObjectiveDirection, ResolutionError,
+        Solution, SolveStats, SolverModel, Verbosity,
+    },
 };
 use crate::{Constraint, Variable};
 
@@ -18,6 +22,8 @@ pub fn minilp(to_solve: UnsolvedProblem) -> MiniLpProblem {
         objective,
         direction,
         variables,
+        strict_inequality,
+        ..
     } = to_solve;
     let mut problem = minilp::Problem::new(match direction {
         ObjectiveDirection::Maximisation => minilp::OptimizationDirection::Maximize,
@@ -50,6 +56,8 @@ pub fn minilp(to_solve: UnsolvedProblem) -> MiniLpProblem {
         variables,
         integers,
         n_constraints: 0,
+        objective_constant: objective.constant,
+        strict_inequalities: StrictInequalities::new(strict_inequality),
     }
 }
 
@@ -59,6 +67,8 @@ pub struct MiniLpProblem {
     variables: Vec<minilp::Variable>,
     integers: Vec<minilp::Variable>,
     n_constraints: usize,
+    objective_constant: f64,
+    strict_inequalities: StrictInequalities,
 }
 
 impl MiniLpProblem {
@@ -68,24 +78,46 @@ impl MiniLpProblem {
     }
 }
 
+impl ModelWithBackend for MiniLpProblem {
+    type Backend = minilp::Problem;
+
+    fn as_backend(&self) -> &Self::Backend {
+        &self.problem
+    }
+
+    fn as_backend_mut(&mut self) -> &mut Self::Backend {
+        &mut self.problem
+    }
+}
+
 impl SolverModel for MiniLpProblem {
     type Solution = MiniLpSolution;
     type Error = ResolutionError;
 
     fn solve(self) -> Result<Self::Solution, Self::Error> {
-        let mut solution = self.problem.solve()?;
-        for int_var in self.integers {
-            solution = catch_unwind(|| solution.add_gomory_cut(int_var)).map_err(|_| {
-                ResolutionError::Other("minilp does not support integer variables")
-            })??;
-        }
-        Ok(MiniLpSolution {
-            solution,
-            variables: self.variables,
+        logged("minilp", move || {
+            self.strict_inequalities.check()?;
+            let start = Instant::now();
+            let mut solution = self.problem.solve()?;
+            for int_var in self.integers {
+                solution = catch_unwind(|| solution.add_gomory_cut(int_var)).map_err(|_| {
+                    ResolutionError::Other("minilp does not support integer variables")
+                })??;
+            }
+            Ok(MiniLpSolution {
+                solution,
+                variables: self.variables,
+                objective_constant: self.objective_constant,
+                stats: SolveStats {
+                    time: Some(start.elapsed()),
+                    ..SolveStats::default()
+                },
+            })
         })
     }
 
     fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let constraint = self.strict_inequalities.apply(constraint);
         let index = self.n_constraints;
         let op = match constraint.is_equality {
             true => minilp::ComparisonOp::Eq,
@@ -102,6 +134,12 @@ impl SolverModel for MiniLpProblem {
     }
 }
 
+/// minilp never writes to the console: its messages go to the [log](https://docs.rs/log) crate,
+/// so every verbosity level leaves the resolution silent
+impl ModelWithVerbosity for MiniLpProblem {
+    fn set_verbosity(&mut self, _verbosity: Verbosity) {}
+}
+
 impl From<minilp::Error> for ResolutionError {
     fn from(minilp_error: Error) -> Self {
         match minilp_error {
@@ -115,6 +153,8 @@ impl From<minilp::Error> for ResolutionError {
 pub struct MiniLpSolution {
     solution: minilp::Solution,
     variables: Vec<minilp::Variable>,
+    objective_constant: f64,
+    stats: SolveStats,
 }
 
 impl MiniLpSolution {
@@ -128,6 +168,19 @@ impl Solution for MiniLpSolution {
     fn value(&self, variable: Variable) -> f64 {
         self.solution[self.variables[variable.index()]]
     }
+
+    /// minilp only returns optimal solutions
+    fn best_bound(&self) -> Option<f64> {
+        Some(self.solution.objective() + self.objective_constant)
+    }
+
+    fn mip_gap(&self) -> Option<f64> {
+        Some(0.)
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.stats.clone()
+    }
 }
 
 #[cfg(test)]
@@ -149,4 +202,23 @@ mod tests {
             .unwrap();
         assert_eq!((solution.value(x), solution.value(y)), (0.5, 3.))
     }
+
+    #[test]
+    fn reports_the_objective_as_bound() {
+        let mut vars = variables!();
+        let x = vars.add(variable().clamp(0, 2));
+        let solution = vars.maximise(x + 1).using(minilp).solve().unwrap();
+        assert_eq!(solution.best_bound(), Some(3.));
+        assert_eq!(solution.mip_gap(), Some(0.));
+    }
+
+    #[test]
+    fn measures_the_solve_time() {
+        let mut vars = variables!();
+        let x = vars.add(variable().clamp(0, 2));
+        let solution = vars.maximise(x).using(minilp).solve().unwrap();
+        let stats = solution.stats();
+        assert!(stats.time.is_some());
+        assert_eq!(stats.nodes, None);
+    }
 }
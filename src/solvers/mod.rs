@@ -4,11 +4,23 @@
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::time::Duration;
 
-use crate::variable::UnsolvedProblem;
+use crate::postprocess::ValueProcessing;
+use crate::solvers::log_parser::ProgressEntry;
+use crate::variable::{UnsolvedProblem, VariableDefinition};
 use crate::Constraint;
 use crate::{constraint::ConstraintReference, IntoAffineExpression, Variable};
 
+#[cfg(any(
+    feature = "coin_cbc",
+    feature = "minilp",
+    feature = "lpsolve",
+    feature = "highs"
+))]
+pub mod auto;
+
 #[cfg(feature = "coin_cbc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "coin_cbc")))]
 pub mod coin_cbc;
@@ -29,6 +41,17 @@ pub mod highs;
 #[cfg_attr(docsrs, doc(cfg(feature = "lp-solvers")))]
 pub mod lp_solvers;
 
+#[cfg(feature = "neos")]
+#[cfg_attr(docsrs, doc(cfg(feature = "neos")))]
+pub mod neos;
+
+#[cfg(feature = "remote")]
+#[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+pub mod remote;
+
+pub mod log_parser;
+pub mod subprocess;
+
 /// An entity that is able to solve linear problems
 pub trait Solver {
     /// The internal model type used by the solver
@@ -57,6 +80,7 @@ where
 /// Whether to search for the variable values that give the highest
 /// or the lowest value of the objective function.
 #[derive(Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectiveDirection {
     /// Find the highest possible value of the objective
     Maximisation,
@@ -64,6 +88,70 @@ pub enum ObjectiveDirection {
     Minimisation,
 }
 
+/// How the solver stopped after finding a solution, returned by [Solution::status].
+///
+/// Problems without any feasible solution are reported as errors
+/// ([ResolutionError::Infeasible], [ResolutionError::Unbounded]),
+/// but a solver that reaches a limit after having found a feasible solution
+/// returns it, with a status telling that it may not be optimal.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolutionStatus {
+    /// The solution is optimal
+    Optimal,
+    /// The time limit was reached before optimality was proved.
+    /// The solution is the best one found so far.
+    TimeLimit,
+    /// The solver stopped because the solution was proved to be close enough to the optimum
+    GapLimit,
+    /// The solver was stopped before optimality was proved for another reason
+    /// (node, iteration or solution limit, user interruption, ...).
+    /// The solution is the best one found so far.
+    Interrupted,
+}
+
+impl SolutionStatus {
+    /// Whether the solution was proved to be optimal
+    pub fn is_optimal(&self) -> bool {
+        *self == SolutionStatus::Optimal
+    }
+}
+
+/// Statistics about the work done by the solver, returned by [Solution::stats].
+///
+/// Every field is optional, because not all solvers report all statistics.
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveStats {
+    /// The wall-clock time spent solving the problem,
+    /// not including the time spent building the model
+    pub time: Option<Duration>,
+    /// The number of simplex iterations
+    pub iterations: Option<u64>,
+    /// The number of branch-and-bound nodes that were explored
+    pub nodes: Option<u64>,
+    /// The number of rows and columns removed by presolve
+    pub presolve_reductions: Option<u64>,
+    /// The progress of the resolution over time, for solvers that only report it in their log.
+    /// See [SolveStats::with_log].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub progress: Vec<ProgressEntry>,
+}
+
+/// Reduces a seed to the non-negative range of a 32 bit integer, used by solver parameters
+#[cfg(any(feature = "coin_cbc", feature = "highs"))]
+pub(crate) fn seed_to_i32(seed: u64) -> i32 {
+    (seed % (i32::MAX as u64 + 1)) as i32
+}
+
+/// The relative gap between an objective value and a bound, as reported by [Solution::mip_gap]
+pub(crate) fn relative_gap(objective: f64, bound: f64) -> f64 {
+    if objective == bound {
+        return 0.;
+    }
+    (objective - bound).abs() / objective.abs().max(1e-10)
+}
+
 /// Represents an error that occurred when solving a problem.
 ///
 /// # Examples
@@ -144,6 +232,83 @@ pub trait Solution {
     /// Get the optimal value of a variable of the problem
     fn value(&self, variable: Variable) -> f64;
 
+    /// Whether the solution is optimal, or the best one found before the solver stopped.
+    /// Solvers that cannot be stopped early always return [SolutionStatus::Optimal].
+    ///
+    /// ```
+    /// use good_lp::{default_solver, variables, SolutionStatus, Solution, SolverModel};
+    /// variables! {vars: 0 <= x <= 3;}
+    /// let solution = vars.maximise(x).using(default_solver).solve()?;
+    /// assert_eq!(solution.status(), SolutionStatus::Optimal);
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    fn status(&self) -> SolutionStatus {
+        SolutionStatus::Optimal
+    }
+
+    /// The best bound on the objective value proved by the solver:
+    /// no solution can have a better objective than this value.
+    /// It is equal to the objective value when the solution is optimal.
+    ///
+    /// Returns `None` if the solver doesn't report it.
+    fn best_bound(&self) -> Option<f64> {
+        None
+    }
+
+    /// The relative difference between the objective value of the solution and the best bound:
+    /// `|objective - bound| / |objective|`.
+    /// A gap of 0 means that the solution is proved to be optimal.
+    ///
+    /// Returns `None` if the solver doesn't report the bound.
+    ///
+    /// ```
+    /// use good_lp::{default_solver, variable, variables, Solution, SolverModel};
+    /// # // minilp and highs do not support integer variables
+    /// # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+    /// let mut vars = variables!();
+    /// let x = vars.add(variable().integer().max(3.5));
+    /// let solution = vars.maximise(x).using(default_solver).solve()?;
+    /// if let Some(gap) = solution.mip_gap() {
+    ///     assert!(gap < 1e-6);
+    /// }
+    /// # }
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    fn mip_gap(&self) -> Option<f64> {
+        None
+    }
+
+    /// Statistics about the resolution, such as the time it took.
+    ///
+    /// ```
+    /// use good_lp::{default_solver, variables, Solution, SolverModel};
+    /// variables! {vars: 0 <= x <= 3;}
+    /// let solution = vars.maximise(x).using(default_solver).solve()?;
+    /// if let Some(time) = solution.stats().time {
+    ///     println!("solved in {:?}", time);
+    /// }
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    fn stats(&self) -> SolveStats {
+        SolveStats::default()
+    }
+
+    /// The value of a variable after the transform registered for it in `processing`,
+    /// such as rounding to a lot size. See [crate::postprocess].
+    ///
+    /// ```
+    /// use good_lp::postprocess::{decimals, ValueProcessing};
+    /// use good_lp::{variables, Solution};
+    /// use std::collections::HashMap;
+    /// variables! {vars: amount;}
+    /// let solution: HashMap<_, _> = vec![(amount, 12.340000000000002)].into_iter().collect();
+    /// let processing = ValueProcessing::new().transform(amount, decimals(2));
+    /// assert_eq!(solution.value_processed(amount, &processing), 12.34);
+    /// ```
+    fn value_processed(&self, variable: Variable, processing: &ValueProcessing) -> f64 {
+        processing.process(variable, self.value(variable))
+    }
+
     /// ## Example
     ///
     /// ```rust
@@ -163,6 +328,113 @@ pub trait Solution {
     {
         expr.eval_with(self)
     }
+
+    /// How far the constraint is from being violated in this solution:
+    /// the difference between its two sides, positive when an inequality is strictly satisfied,
+    /// and negative when the constraint is violated.
+    /// For an equality, minus the absolute difference between its two sides.
+    ///
+    /// ```
+    /// use good_lp::{constraint, variables, Solution};
+    /// use std::collections::HashMap;
+    /// variables! {vars: a; b;}
+    /// let solution: HashMap<_, _> = vec![(a, 3.), (b, 4.)].into_iter().collect();
+    /// assert_eq!(solution.constraint_slack(&constraint!(a + b <= 10)), 3.);
+    /// assert_eq!(solution.constraint_slack(&constraint!(a >= b)), -1.);
+    /// assert_eq!(solution.constraint_slack(&constraint!(2 * a == b)), -2.);
+    /// ```
+    fn constraint_slack(&self, constraint: &Constraint) -> f64
+    where
+        Self: Sized,
+    {
+        // Constraints are stored as `expression <= 0` or `expression = 0`
+        let value = self.eval(&constraint.expression);
+        if constraint.is_equality {
+            -value.abs()
+        } else {
+            -value
+        }
+    }
+
+    /// Whether the constraint is satisfied by this solution,
+    /// or violated by at most `tolerance`
+    ///
+    /// ```
+    /// use good_lp::{constraint, variables, Solution};
+    /// use std::collections::HashMap;
+    /// variables! {vars: a; b;}
+    /// let solution: HashMap<_, _> = vec![(a, 3.), (b, 4.)].into_iter().collect();
+    /// assert!(solution.is_satisfied(&constraint!(a + b == 7), 0.));
+    /// assert!(!solution.is_satisfied(&constraint!(a >= b), 0.5));
+    /// assert!(solution.is_satisfied(&constraint!(a >= b), 1.));
+    /// ```
+    fn is_satisfied(&self, constraint: &Constraint, tolerance: f64) -> bool
+    where
+        Self: Sized,
+    {
+        self.constraint_slack(constraint) >= -tolerance
+    }
+
+    /// The values of a map of variables, by key
+    ///
+    /// ```
+    /// use good_lp::{default_solver, variable, variables, Solution, SolverModel};
+    /// use std::collections::HashMap;
+    /// let mut vars = variables!();
+    /// let stock: HashMap<&str, _> = vec![("flour", 2.), ("sugar", 1.)]
+    ///     .into_iter()
+    ///     .map(|(item, max)| (item, vars.add(variable().max(max))))
+    ///     .collect();
+    /// let objective: good_lp::Expression = stock.values().sum();
+    /// let solution = vars.maximise(objective).using(default_solver).solve()?;
+    /// let values = solution.extract(&stock);
+    /// assert_eq!(values["flour"], 2.);
+    /// assert_eq!(values["sugar"], 1.);
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    fn extract<K: Eq + Hash + Clone>(&self, variables: &HashMap<K, Variable>) -> HashMap<K, f64>
+    where
+        Self: Sized,
+    {
+        variables
+            .iter()
+            .map(|(key, &variable)| (key.clone(), self.value(variable)))
+            .collect()
+    }
+
+    /// The values of a list of variables, in the same order
+    ///
+    /// ```
+    /// use good_lp::{variables, Solution};
+    /// use std::collections::HashMap;
+    /// variables! {vars: a; b;}
+    /// let solution: HashMap<_, _> = vec![(a, 3.), (b, 4.)].into_iter().collect();
+    /// assert_eq!(solution.extract_vec(&[b, a]), vec![4., 3.]);
+    /// ```
+    fn extract_vec(&self, variables: &[Variable]) -> Vec<f64> {
+        variables
+            .iter()
+            .map(|&variable| self.value(variable))
+            .collect()
+    }
+}
+
+impl dyn Solution + '_ {
+    /// Evaluate an expression with the values of this solution, like [Solution::eval],
+    /// which cannot be called on trait objects.
+    /// It has another name, so that calls to `eval` are not ambiguous
+    ///
+    /// ```
+    /// use good_lp::{variables, Solution};
+    /// use std::collections::HashMap;
+    /// variables! {vars: a; b;}
+    /// let values: HashMap<_, _> = vec![(a, 3.), (b, 4.)].into_iter().collect();
+    /// let solution: &dyn Solution = &values;
+    /// assert_eq!(solution.eval_dyn(a + 2 * b), 11.);
+    /// ```
+    pub fn eval_dyn<E: IntoAffineExpression>(&self, expr: E) -> f64 {
+        expr.eval_with(self)
+    }
 }
 
 /// All `HashMap<Variable, {number}>` implement [Solution].
@@ -230,3 +502,539 @@ pub trait ModelWithSOS1 {
         self
     }
 }
+
+/// A model that can stop the resolution after a given amount of time.
+///
+/// If the solver found a feasible solution before reaching the time limit,
+/// it returns the best one, with a [SolutionStatus::TimeLimit] status.
+/// Otherwise, solving returns an error.
+pub trait ModelWithTimeLimit {
+    /// Sets the maximum wall-clock time that the solver can spend solving the problem
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// use std::time::Duration;
+    /// # // Not all solvers support time limits
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_time_limit(Duration::from_secs(60))
+    ///     .solve().unwrap();
+    /// assert!(solution.status().is_optimal());
+    /// # }
+    /// ```
+    fn set_time_limit(&mut self, limit: Duration);
+
+    /// See [ModelWithTimeLimit::set_time_limit]
+    fn with_time_limit(mut self, limit: Duration) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_time_limit(limit);
+        self
+    }
+}
+
+/// A model for which the solver can stop before proving that the best solution is optimal,
+/// when it is close enough to the best bound on the objective.
+///
+/// When both gaps are set, the solver stops as soon as one of them is reached.
+pub trait ModelWithMipGap {
+    /// Sets the relative gap between the objective of the best solution and the best bound
+    /// at which the solver stops. `0.005` stops when the solution is proved to be within 0.5% of the optimum.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # // Not all solvers support MIP gaps
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x (integer) <= 10;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_mip_gap(0.005)
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 10.);
+    /// # }
+    /// ```
+    fn set_mip_gap(&mut self, relative_gap: f64);
+
+    /// Sets the absolute difference between the objective of the best solution and the best bound
+    /// at which the solver stops.
+    fn set_absolute_gap(&mut self, absolute_gap: f64);
+
+    /// See [ModelWithMipGap::set_mip_gap]
+    fn with_mip_gap(mut self, relative_gap: f64) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_mip_gap(relative_gap);
+        self
+    }
+
+    /// See [ModelWithMipGap::set_absolute_gap]
+    fn with_absolute_gap(mut self, absolute_gap: f64) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_absolute_gap(absolute_gap);
+        self
+    }
+}
+
+/// A model for which the number of threads used by the solver can be set.
+///
+/// Without it, solvers choose the number of threads themselves,
+/// usually from the number of cores or from an environment variable.
+pub trait ModelWithThreads {
+    /// Sets the number of threads the solver can use
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # // Not all solvers are multi-threaded
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_threads(1)
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 2.);
+    /// # }
+    /// ```
+    fn set_threads(&mut self, threads: u32);
+
+    /// See [ModelWithThreads::set_threads]
+    fn with_threads(mut self, threads: u32) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_threads(threads);
+        self
+    }
+}
+
+/// A model for which the seed of the random number generator of the solver can be set.
+///
+/// Solvers use randomness, for instance to break ties or in heuristics.
+/// With the same seed, the same problem and the same solver version,
+/// a resolution is reproducible, which is useful in tests and experiments.
+pub trait ModelWithSeed {
+    /// Sets the seed of the random number generator.
+    /// Solvers use 32 bit seeds, so larger values are reduced to that range.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # // Not all solvers use randomness
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_seed(42)
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 2.);
+    /// # }
+    /// ```
+    fn set_seed(&mut self, seed: u64);
+
+    /// See [ModelWithSeed::set_seed]
+    fn with_seed(mut self, seed: u64) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_seed(seed);
+        self
+    }
+}
+
+/// How much a solver writes to the console while it solves a problem
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Verbosity {
+    /// Nothing is printed
+    Silent,
+    /// A short summary of the resolution is printed
+    Summary,
+    /// Detailed progress information is printed
+    Full,
+}
+
+/// A model for which the amount of console output of the solver can be controlled.
+///
+/// Each solver has its own default: Cbc, for instance, prints a detailed log of its progress,
+/// which can be a problem in applications that use stdout for their own output.
+pub trait ModelWithVerbosity {
+    /// Sets how much the solver prints while it solves the problem
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_verbosity(Verbosity::Silent)
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 2.);
+    /// # }
+    /// ```
+    fn set_verbosity(&mut self, verbosity: Verbosity);
+
+    /// See [ModelWithVerbosity::set_verbosity]
+    fn with_verbosity(mut self, verbosity: Verbosity) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_verbosity(verbosity);
+        self
+    }
+}
+
+/// The algorithm used to solve linear problems, and the linear relaxations of integer problems
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LpMethod {
+    /// The primal simplex algorithm
+    PrimalSimplex,
+    /// The dual simplex algorithm, usually the fastest simplex variant
+    DualSimplex,
+    /// An interior point (barrier) algorithm, often faster on large sparse problems
+    Barrier,
+}
+
+/// A model for which the algorithm used to solve linear problems can be chosen.
+///
+/// Without it, solvers choose the algorithm themselves.
+/// On large problems, the choice between dual simplex and barrier
+/// can change the resolution time by an order of magnitude.
+pub trait ModelWithLpMethod {
+    /// Sets the algorithm used to solve linear problems
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "highs")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(highs)
+    ///     .with_lp_method(LpMethod::Barrier)
+    ///     .solve().unwrap();
+    /// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+    /// assert_float_eq(solution.value(x), 2.);
+    /// # }
+    /// ```
+    fn set_lp_method(&mut self, method: LpMethod);
+
+    /// See [ModelWithLpMethod::set_lp_method]
+    fn with_lp_method(mut self, method: LpMethod) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_lp_method(method);
+        self
+    }
+}
+
+/// A model for which the crossover that follows a barrier algorithm can be disabled.
+///
+/// Barrier (interior point) algorithms end in the interior of the optimal face.
+/// Solvers then run a crossover phase to move the solution to a vertex,
+/// which can take longer than the barrier itself on large problems.
+/// When a vertex solution is not needed, it can be skipped.
+/// Without a crossover, dual values and basis information may be less accurate.
+pub trait ModelWithCrossover {
+    /// Sets whether a crossover is run after a barrier algorithm
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "highs")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(highs)
+    ///     .with_lp_method(LpMethod::Barrier)
+    ///     .with_crossover(false)
+    ///     .solve().unwrap();
+    /// assert!((solution.value(x) - 2.).abs() < 1e-6);
+    /// # }
+    /// ```
+    fn set_crossover(&mut self, crossover: bool);
+
+    /// See [ModelWithCrossover::set_crossover]
+    fn with_crossover(mut self, crossover: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_crossover(crossover);
+        self
+    }
+}
+
+/// A model for which presolve, the simplification of the problem before it is solved,
+/// can be disabled.
+///
+/// Presolve usually makes resolutions much faster,
+/// but it can run into numerical trouble on badly scaled models.
+pub trait ModelWithPresolve {
+    /// Sets whether the problem is presolved
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_presolve(false)
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 2.);
+    /// # }
+    /// ```
+    fn set_presolve(&mut self, presolve: bool);
+
+    /// See [ModelWithPresolve::set_presolve]
+    fn with_presolve(mut self, presolve: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_presolve(presolve);
+        self
+    }
+}
+
+/// How the rows and columns of a problem are scaled before it is solved
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScalingMode {
+    /// The problem is solved as it is
+    Off,
+    /// The solver chooses how to scale the problem. This is the default of all solvers.
+    Automatic,
+    /// Rows and columns are scaled so that their largest coefficient is 1
+    Equilibrium,
+    /// Rows and columns are scaled by the geometric mean of their coefficients
+    Geometric,
+}
+
+/// A model for which the scaling of the problem can be chosen.
+///
+/// Scaling makes coefficients of very different magnitudes easier to handle,
+/// but on some models, turning it off or changing the method avoids numerical issues.
+pub trait ModelWithScaling {
+    /// Sets how the problem is scaled
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_scaling(ScalingMode::Off)
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 2.);
+    /// # }
+    /// ```
+    fn set_scaling(&mut self, scaling: ScalingMode);
+
+    /// See [ModelWithScaling::set_scaling]
+    fn with_scaling(mut self, scaling: ScalingMode) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_scaling(scaling);
+        self
+    }
+}
+
+/// A model on which solver-specific parameters can be set by name,
+/// for the settings that good_lp does not expose through a dedicated method.
+///
+/// The names and values are the ones documented by each solver,
+/// and they are passed to it without being checked:
+/// a parameter that the solver doesn't know may be ignored, or make the resolution fail.
+pub trait ModelWithRawParameters {
+    /// Sets a parameter of the solver, replacing its previous value
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let solution = problem
+    ///     .maximise(x)
+    ///     .using(coin_cbc)
+    ///     .with_raw_parameter("cuts", "off")
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 2.);
+    /// # }
+    /// ```
+    fn set_raw_parameter(&mut self, name: &str, value: &str);
+
+    /// See [ModelWithRawParameters::set_raw_parameter]
+    fn with_raw_parameter(mut self, name: &str, value: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_raw_parameter(name, value);
+        self
+    }
+}
+
+/// A model to which new variables can be added after it was built,
+/// with their coefficients in the constraints that are already in the model.
+///
+/// This is the building block of column generation, where the variables of a problem
+/// are too many to be created upfront, and are only added when they can improve the solution.
+///
+/// It is implemented by the Cbc, lp-solvers, NEOS and remote models.
+/// The minilp and HiGHS bindings build their problems one constraint at a time,
+/// from variables that must already exist, so a new variable cannot be added
+/// to the constraints they contain. The lp_solve model does not implement it yet.
+pub trait ModelWithColumns {
+    /// Adds a variable with the given bounds and integrality,
+    /// the given coefficient in the objective,
+    /// and the given coefficients in existing constraints.
+    /// The variable has a coefficient of zero in the other constraints.
+    ///
+    /// The coefficients are the ones of the variable on the left hand side
+    /// of `expression <= constant`. Constraints written as `a >= b` are stored as `b - a <= 0`,
+    /// so a variable that increases `a` enters them with a negative coefficient.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # // Not all solvers can add variables to an existing model
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let mut model = problem.maximise(x).using(coin_cbc);
+    /// let capacity = model.add_constraint(constraint!(x <= 1));
+    /// // y enters the capacity constraint as 2 * y, and is worth 3 in the objective
+    /// let y = model.add_column(variable().min(0), 3., &[(capacity, 2.)]);
+    /// let solution = model.solve().unwrap();
+    /// assert_eq!(solution.value(x), 0.);
+    /// assert_eq!(solution.value(y), 0.5);
+    /// # }
+    /// ```
+    fn add_column(
+        &mut self,
+        definition: VariableDefinition,
+        objective_coefficient: f64,
+        coefficients: &[(ConstraintReference, f64)],
+    ) -> Variable;
+}
+
+/// A model whose variable bounds and objective coefficients can be read and changed
+/// for many variables at once, without a call to the solver library for each attribute.
+///
+/// It is implemented by the Cbc, lp_solve, lp-solvers, NEOS and remote models,
+/// and by [UnsolvedProblem], to update a problem before it is solved.
+/// The minilp and HiGHS bindings cannot change a variable once it was added to their model,
+/// so the problems to solve with them have to be updated before they are built.
+pub trait ModelWithBulkAttributes {
+    /// The lower and upper bounds of each variable, in the same order
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables! {problem: 0 <= x <= 2; y <= 5;}
+    /// let mut model = problem.maximise(x + 3 * y);
+    /// model.set_bounds_bulk(&[(x, 1., 4.)]);
+    /// model.set_objective_coeffs_bulk(&[(y, -1.)]);
+    /// assert_eq!(
+    ///     model.get_bounds_bulk(&[x, y]),
+    ///     vec![(1., 4.), (f64::NEG_INFINITY, 5.)]
+    /// );
+    /// assert_eq!(model.get_objective_coeffs_bulk(&[x, y]), vec![1., -1.]);
+    /// ```
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)>;
+
+    /// Sets the lower and upper bounds of the variables
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]);
+
+    /// The coefficient of each variable in the objective, in the same order
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64>;
+
+    /// Sets the coefficients of the variables in the objective
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]);
+}
+
+/// An escape hatch to the model of the underlying solver library,
+/// to call the functions that good_lp does not wrap.
+///
+/// good_lp keeps track of the rows and columns it created in the native model:
+/// adding or removing rows or columns directly through it invalidates
+/// the [Variable]s and [ConstraintReference]s of the problem.
+/// Changing parameters, bounds or coefficients is safe.
+pub trait ModelWithBackend {
+    /// The model type of the solver library
+    type Backend;
+
+    /// The native model
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables! {problem: 0 <= x <= 2;}
+    /// let model = problem.maximise(x).using(coin_cbc).with(constraint!(x <= 1));
+    /// assert_eq!(model.as_backend().num_rows(), 1);
+    /// # }
+    /// ```
+    fn as_backend(&self) -> &Self::Backend;
+
+    /// The native model, to modify it. See the warning on [ModelWithBackend].
+    fn as_backend_mut(&mut self) -> &mut Self::Backend;
+}
+
+/// Runs a resolution, and reports its start and its outcome
+/// to the [log](https://docs.rs/log) crate when the "log" feature is enabled,
+/// and as [tracing](https://docs.rs/tracing) events in a `solve` span
+/// when the "tracing" feature is enabled
+pub(crate) fn logged<S: Solution>(
+    backend: &'static str,
+    solve: impl FnOnce() -> Result<S, ResolutionError>,
+) -> Result<S, ResolutionError> {
+    #[cfg(feature = "log")]
+    log::debug!(target: "good_lp", "solving with {}", backend);
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!(target: "good_lp", "solve", backend).entered();
+    let result = solve();
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(solution) => tracing::info!(
+            target: "good_lp",
+            status = ?solution.status(),
+            stats = ?solution.stats(),
+            "resolution finished"
+        ),
+        Err(error) => tracing::warn!(target: "good_lp", %error, "resolution failed"),
+    }
+    #[cfg(feature = "log")]
+    match &result {
+        Ok(solution) => log::info!(
+            target: "good_lp",
+            "{} finished with status {:?}: {:?}",
+            backend,
+            solution.status(),
+            solution.stats()
+        ),
+        Err(error) => log::warn!(target: "good_lp", "{} failed: {}", backend, error),
+    }
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    let _ = backend;
+    result
+}
+
+/// Reports what a solver printed, line by line, as `debug` events of the current `solve` span
+/// when the "tracing" feature is enabled, and to the log crate when the "log" feature is enabled
+#[cfg(feature = "lp-solvers")]
+pub(crate) fn log_solver_output(solver: &str, output: &str) {
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "good_lp::output", solver, "{}", line);
+        #[cfg(feature = "log")]
+        log::debug!(target: "good_lp::output", "{}: {}", solver, line);
+    }
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    let _ = (solver, output);
+}
@@ -0,0 +1,402 @@
+//! A solver that sends problems to the [NEOS server](https://neos-server.org),
+//! a free internet service that solves optimization problems with many solvers,
+//! including commercial ones.
+//!
+//! The problem is written in the MPS format, submitted to NEOS over HTTPS,
+//! and the results are fetched when the job is done. NEOS requires an email address
+//! for every job, and jobs submitted to NEOS are not private:
+//! see the [terms of use](https://neos-server.org/neos/termofuse.html).
+//!
+//! The solution is read from the output of the solver, which has to list the values
+//! of the variables like Cbc does. This is the case of the Cbc solver, used by default.
+//!
+//! ```no_run
+//! use good_lp::solvers::neos::Neos;
+//! use good_lp::{constraint, variables, Solution, SolverModel};
+//!
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+//! let solution = vars
+//!     .maximise(3 * x + 2 * y)
+//!     .using(Neos::new("me@example.com"))
+//!     .with(constraint!(2 * x + 3 * y <= 17))
+//!     .solve()?;
+//! println!("x = {}", solution.value(x));
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::constraint::{ConstraintReference, StrictInequalities};
+use crate::solvers::{
+    ModelWithBulkAttributes, ModelWithColumns, ModelWithTimeLimit, SolutionStatus,
+};
+use crate::variable::{UnsolvedProblem, VariableDefinition};
+use crate::{Constraint, ResolutionError, Solution, Solver, SolverModel, Variable};
+
+/// The address of the XML-RPC interface of NEOS
+pub const NEOS_URL: &str = "https://neos-server.org:3333";
+
+/// A solver on the NEOS server, and the information needed to submit jobs to it
+#[derive(Debug, Clone)]
+pub struct Neos {
+    email: String,
+    category: String,
+    solver: String,
+    url: String,
+    poll_interval: Duration,
+}
+
+impl Neos {
+    /// Uses the Cbc solver of NEOS, with the given email address,
+    /// to which NEOS can send the results of the jobs
+    pub fn new<S: Into<String>>(email: S) -> Self {
+        Neos {
+            email: email.into(),
+            category: "milp".to_string(),
+            solver: "Cbc".to_string(),
+            url: NEOS_URL.to_string(),
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Uses another solver, by its category and name on NEOS, such as `("milp", "Cbc")`.
+    /// The solver must accept MPS input, and list the values of the variables like Cbc.
+    pub fn solver<C: Into<String>, S: Into<String>>(mut self, category: C, solver: S) -> Self {
+        self.category = category.into();
+        self.solver = solver.into();
+        self
+    }
+
+    /// Sends the jobs to another server with the same interface, instead of [NEOS_URL]
+    pub fn url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Sets how often the status of a job is checked. The default is every 5 seconds.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+}
+
+impl Solver for Neos {
+    type Model = NeosProblem;
+
+    fn create_model(&mut self, problem: UnsolvedProblem) -> Self::Model {
+        NeosProblem {
+            strict_inequalities: StrictInequalities::new(problem.strict_inequality),
+            problem,
+            neos: self.clone(),
+            time_limit: None,
+        }
+    }
+}
+
+/// A problem to be sent to NEOS
+pub struct NeosProblem {
+    problem: UnsolvedProblem,
+    strict_inequalities: StrictInequalities,
+    neos: Neos,
+    time_limit: Option<Duration>,
+}
+
+impl NeosProblem {
+    /// The XML document describing the job, as expected by NEOS
+    fn job(&self) -> String {
+        format!(
+            "<document>\n<category>{}</category>\n<solver>{}</solver>\n\
+            <inputMethod>MPS</inputMethod>\n<email>{}</email>\n<MPS>{}</MPS>\n</document>",
+            escape(&self.neos.category),
+            escape(&self.neos.solver),
+            escape(&self.neos.email),
+            escape(&self.problem.to_mps())
+        )
+    }
+
+    /// Sends an XML-RPC request, and returns the values of the response
+    fn call(&self, method: &str, params: &[&str]) -> Result<Vec<String>, ResolutionError> {
+        let mut body = format!(
+            "<?xml version=\"1.0\"?>\n<methodCall><methodName>{}</methodName><params>",
+            method
+        );
+        for param in params {
+            body += &format!(
+                "<param><value><string>{}</string></value></param>",
+                escape(param)
+            );
+        }
+        body += "</params></methodCall>";
+        let response = ureq::post(&self.neos.url)
+            .set("Content-Type", "text/xml")
+            .send_string(&body)
+            .map_err(|e| ResolutionError::Str(format!("NEOS request failed: {}", e)))?
+            .into_string()
+            .map_err(|e| ResolutionError::Str(format!("invalid NEOS response: {}", e)))?;
+        response_values(&response)
+    }
+}
+
+impl SolverModel for NeosProblem {
+    type Solution = NeosSolution;
+    type Error = ResolutionError;
+
+    fn solve(self) -> Result<Self::Solution, Self::Error> {
+        self.strict_inequalities.check()?;
+        let start = Instant::now();
+        let submitted = self.call("submitJob", &[&self.job()])?;
+        let (job, password) = match submitted.as_slice() {
+            [job, password, ..] if job != "0" => (job.clone(), password.clone()),
+            // When the job is refused, NEOS returns 0 and the reason
+            _ => {
+                return Err(ResolutionError::Str(format!(
+                    "NEOS refused the job: {}",
+                    submitted.get(1).map_or("", String::as_str)
+                )))
+            }
+        };
+        loop {
+            let status = self.call("getJobStatus", &[&job, &password])?;
+            match status.first().map(String::as_str) {
+                Some("Done") => break,
+                Some("Running") | Some("Waiting") => {}
+                other => {
+                    return Err(ResolutionError::Str(format!(
+                        "unexpected NEOS job status: {:?}",
+                        other
+                    )))
+                }
+            }
+            if let Some(limit) = self.time_limit {
+                if start.elapsed() > limit {
+                    // The job is only killed on NEOS, no partial solution can be fetched
+                    self.call("killJob", &[&job, &password])?;
+                    return Err(ResolutionError::Other(
+                        "the NEOS job reached the time limit",
+                    ));
+                }
+            }
+            thread::sleep(self.neos.poll_interval);
+        }
+        let results = self.call("getFinalResults", &[&job, &password])?;
+        let output = results
+            .first()
+            .map(|encoded| String::from_utf8_lossy(&base64_decode(encoded)).into_owned())
+            .unwrap_or_default();
+        parse_output(&output, self.problem.variables.len())
+    }
+
+    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let constraint = self.strict_inequalities.apply(constraint);
+        self.problem.add_constraint(constraint)
+    }
+}
+
+impl ModelWithColumns for NeosProblem {
+    fn add_column(
+        &mut self,
+        definition: VariableDefinition,
+        objective_coefficient: f64,
+        coefficients: &[(ConstraintReference, f64)],
+    ) -> Variable {
+        self.problem
+            .add_column(definition, objective_coefficient, coefficients)
+    }
+}
+
+impl ModelWithBulkAttributes for NeosProblem {
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)> {
+        self.problem.get_bounds_bulk(variables)
+    }
+
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]) {
+        self.problem.set_bounds_bulk(bounds)
+    }
+
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64> {
+        self.problem.get_objective_coeffs_bulk(variables)
+    }
+
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]) {
+        self.problem.set_objective_coeffs_bulk(coefficients)
+    }
+}
+
+impl ModelWithTimeLimit for NeosProblem {
+    /// Limits the time spent waiting for the job, including the time spent in the queue of NEOS.
+    /// When it is reached, the job is killed, and solving returns an error.
+    fn set_time_limit(&mut self, limit: Duration) {
+        self.time_limit = Some(limit);
+    }
+}
+
+/// A solution computed by NEOS
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeosSolution {
+    values: Vec<f64>,
+    status: SolutionStatus,
+    output: String,
+}
+
+impl NeosSolution {
+    /// The complete output of the solver, as returned by NEOS
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+}
+
+impl Solution for NeosSolution {
+    fn value(&self, variable: Variable) -> f64 {
+        self.values[variable.index()]
+    }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The values of an XML-RPC response, or its fault
+fn response_values(response: &str) -> Result<Vec<String>, ResolutionError> {
+    let document = roxmltree::Document::parse(response)
+        .map_err(|e| ResolutionError::Str(format!("invalid NEOS response: {}", e)))?;
+    let values = document
+        .descendants()
+        .filter(|node| node.has_tag_name("value"))
+        .filter(is_scalar)
+        .map(|node| {
+            let scalar = node.children().find(|child| child.is_element());
+            scalar
+                .unwrap_or(node)
+                .text()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        })
+        .collect();
+    if document
+        .descendants()
+        .any(|node| node.has_tag_name("fault"))
+    {
+        return Err(ResolutionError::Str(format!("NEOS error: {:?}", values)));
+    }
+    Ok(values)
+}
+
+/// Whether a value is a string, a number or binary data, and not an array or a structure
+fn is_scalar(node: &roxmltree::Node) -> bool {
+    node.children()
+        .filter(|child| child.is_element())
+        .all(|child| {
+            ["string", "int", "i4", "double", "boolean", "base64"]
+                .contains(&child.tag_name().name())
+        })
+}
+
+fn base64_decode(encoded: &str) -> Vec<u8> {
+    let digit = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+    let digits: Vec<u8> = encoded.bytes().filter_map(digit).collect();
+    let mut decoded = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, &d)| bits | (u32::from(d) << (18 - 6 * i)));
+        decoded.extend_from_slice(&bits.to_be_bytes()[1..chunk.len()]);
+    }
+    decoded
+}
+
+/// Reads the solution from the output of Cbc. The solution starts with a line such as
+/// `Optimal - objective value 24.00000000`, followed by a line per variable
+/// that is not zero, with its index, its name and its value.
+fn parse_output(output: &str, variables: usize) -> Result<NeosSolution, ResolutionError> {
+    let mut lines = output
+        .lines()
+        .skip_while(|line| !line.contains("objective value"));
+    let status_line = lines.next().ok_or_else(|| {
+        ResolutionError::Str(format!("no solution in the output of NEOS:\n{}", output))
+    })?;
+    let status = if status_line.starts_with("Optimal") {
+        SolutionStatus::Optimal
+    } else if status_line.starts_with("Infeasible") || status_line.contains("infeasible") {
+        return Err(ResolutionError::Infeasible);
+    } else if status_line.starts_with("Unbounded") {
+        return Err(ResolutionError::Unbounded);
+    } else if status_line.contains("time") {
+        SolutionStatus::TimeLimit
+    } else {
+        SolutionStatus::Interrupted
+    };
+    let mut values = vec![0.; variables];
+    for line in lines {
+        let fields: Vec<&str> = line
+            .split_whitespace()
+            .skip_while(|&field| field == "**")
+            .collect();
+        match fields.as_slice() {
+            [index, _name, value, ..] => match (index.parse::<usize>(), value.parse::<f64>()) {
+                (Ok(index), Ok(value)) if index < variables => values[index] = value,
+                _ => break,
+            },
+            _ => break,
+        }
+    }
+    Ok(NeosSolution {
+        values,
+        status,
+        output: output.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_base64() {
+        assert_eq!(base64_decode("Z29vZF9scA=="), b"good_lp");
+        assert_eq!(base64_decode("T3B0\naW1h bA=="), b"Optimal");
+    }
+
+    #[test]
+    fn reads_xml_rpc_responses() {
+        let response = "<?xml version='1.0'?><methodResponse><params><param><value><array><data>\
+            <value><int>42</int></value><value><string>secret</string></value>\
+            </data></array></value></param></params></methodResponse>";
+        assert_eq!(response_values(response).unwrap(), vec!["42", "secret"]);
+        let fault = "<methodResponse><fault><value><struct><member><name>faultString</name>\
+            <value><string>bad</string></value></member></struct></value></fault></methodResponse>";
+        assert!(response_values(fault).is_err());
+    }
+
+    #[test]
+    fn reads_cbc_solutions() {
+        let output = "Welcome to the CBC MILP Solver\n\
+            Result - Optimal solution found\n\
+            Optimal - objective value 24.00000000\n      \
+            0 x                      8                       3\n      \
+            2 z                    1.5                       0\n\
+            Total time (CPU seconds):       0.01\n";
+        let solution = parse_output(output, 3).unwrap();
+        assert_eq!(solution.values, vec![8., 0., 1.5]);
+        assert_eq!(solution.status, SolutionStatus::Optimal);
+        let infeasible = "Infeasible - objective value 0.00000000\n";
+        assert_eq!(
+            parse_output(infeasible, 1).err(),
+            Some(ResolutionError::Infeasible)
+        );
+    }
+}
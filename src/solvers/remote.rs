@@ -0,0 +1,148 @@
+//! A solver that sends problems to an HTTP server, such as a farm of solver machines
+//! behind a load balancer.
+//!
+//! The protocol is minimal, so that a server can be written with any HTTP framework:
+//! the problem is sent in the body of a `POST` request, in the free MPS format,
+//! and the server answers with the result of [solve_job](crate::batch::solve_job),
+//! once the problem is solved.
+//!
+//! ```no_run
+//! use good_lp::solvers::remote::RemoteSolver;
+//! use good_lp::{constraint, variables, Solution, SolverModel};
+//! use std::time::Duration;
+//!
+//! let solver = RemoteSolver::new("https://solvers.example.com/solve")
+//!     .header("Authorization", "Bearer my-token")
+//!     .timeout(Duration::from_secs(600));
+//! variables! {vars: 0 <= x <= 10;}
+//! let solution = vars
+//!     .maximise(x)
+//!     .using(solver)
+//!     .with(constraint!(x <= 3))
+//!     .solve()?;
+//! println!("x = {}", solution.value(x));
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::time::Duration;
+
+use crate::batch::{decode_result, BatchSolution};
+use crate::constraint::{ConstraintReference, StrictInequalities};
+use crate::variable::{UnsolvedProblem, VariableDefinition};
+use crate::{
+    Constraint, ModelWithBulkAttributes, ModelWithColumns, ResolutionError, Solver, SolverModel,
+    Variable,
+};
+
+/// A solver that posts problems to an HTTP endpoint
+#[derive(Debug, Clone)]
+pub struct RemoteSolver {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+}
+
+impl RemoteSolver {
+    /// Sends the problems to the given URL
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        RemoteSolver {
+            url: url.into(),
+            headers: vec![],
+            timeout: None,
+        }
+    }
+
+    /// Adds a header to the requests, to authenticate them for instance
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the maximum time to wait for the response of the server.
+    /// By default, there is no limit.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl Solver for RemoteSolver {
+    type Model = RemoteProblem;
+
+    fn create_model(&mut self, problem: UnsolvedProblem) -> Self::Model {
+        RemoteProblem {
+            strict_inequalities: StrictInequalities::new(problem.strict_inequality),
+            problem,
+            remote: self.clone(),
+        }
+    }
+}
+
+/// A problem to be sent to a server
+pub struct RemoteProblem {
+    problem: UnsolvedProblem,
+    strict_inequalities: StrictInequalities,
+    remote: RemoteSolver,
+}
+
+impl SolverModel for RemoteProblem {
+    type Solution = BatchSolution;
+    type Error = ResolutionError;
+
+    fn solve(self) -> Result<Self::Solution, Self::Error> {
+        self.strict_inequalities.check()?;
+        let mut request = ureq::post(&self.remote.url).set("Content-Type", "text/plain");
+        for (name, value) in &self.remote.headers {
+            request = request.set(name, value);
+        }
+        if let Some(timeout) = self.remote.timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request
+            .send_string(&self.problem.to_mps())
+            .map_err(|e| ResolutionError::Str(format!("the remote solver failed: {}", e)))?
+            .into_string()
+            .map_err(|e| ResolutionError::Str(format!("invalid remote response: {}", e)))?;
+        let solution = decode_result(response.as_bytes())?;
+        if solution.values.len() != self.problem.variables.len() {
+            return Err(ResolutionError::Other(
+                "the remote solver did not return a value for every variable",
+            ));
+        }
+        Ok(solution)
+    }
+
+    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let constraint = self.strict_inequalities.apply(constraint);
+        self.problem.add_constraint(constraint)
+    }
+}
+
+impl ModelWithColumns for RemoteProblem {
+    fn add_column(
+        &mut self,
+        definition: VariableDefinition,
+        objective_coefficient: f64,
+        coefficients: &[(ConstraintReference, f64)],
+    ) -> Variable {
+        self.problem
+            .add_column(definition, objective_coefficient, coefficients)
+    }
+}
+
+impl ModelWithBulkAttributes for RemoteProblem {
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)> {
+        self.problem.get_bounds_bulk(variables)
+    }
+
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]) {
+        self.problem.set_bounds_bulk(bounds)
+    }
+
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64> {
+        self.problem.get_objective_coeffs_bulk(variables)
+    }
+
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]) {
+        self.problem.set_objective_coeffs_bulk(coefficients)
+    }
+}
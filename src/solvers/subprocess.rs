@@ -0,0 +1,237 @@
+//! A solver that runs each resolution in a child process,
+//! so that a crash of a native solver cannot take down the program.
+//!
+//! The native solvers, such as Cbc or HiGHS, run their C and C++ code in the process
+//! of the program that uses them: a segmentation fault in a solver kills a long-running service.
+//! [SubprocessSolver] starts a child process instead, sends it the problem in the MPS format
+//! on its standard input, and reads back the result of [solve_job](crate::batch::solve_job).
+//! If the child exits without a result, because it crashed, the resolution is retried
+//! as many times as configured with [SubprocessSolver::retries], and then reported as an error.
+//!
+//! The child is usually the program itself: its `main` function calls [serve_subprocess]
+//! first, which solves the problem and exits when the process was started by a
+//! [SubprocessSolver], and does nothing otherwise.
+//!
+//! ```no_run
+//! use good_lp::solvers::subprocess::{serve_subprocess, SubprocessSolver};
+//! use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     // In the child process, this solves the problem and exits
+//!     serve_subprocess(default_solver);
+//!
+//!     let solver = SubprocessSolver::current_exe()?.retries(2);
+//!     variables! {vars: 0 <= x <= 10;}
+//!     let solution = vars
+//!         .maximise(x)
+//!         .using(solver)
+//!         .with(constraint!(x <= 3))
+//!         .solve()?;
+//!     println!("x = {}", solution.value(x));
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Only the problem is sent to the child: the parameters of the resolution,
+//! such as a time limit, are set on the solver given to [serve_subprocess].
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::batch::{decode_result, solve_job, BatchSolution};
+use crate::constraint::{ConstraintReference, StrictInequalities};
+use crate::variable::{UnsolvedProblem, VariableDefinition};
+use crate::{
+    Constraint, ModelWithBulkAttributes, ModelWithColumns, ResolutionError, Solver, SolverModel,
+    Variable,
+};
+
+/// The environment variable that tells a child process where to write its result
+const RESULT_PATH: &str = "GOOD_LP_SUBPROCESS_RESULT";
+
+/// A solver that runs each resolution in a new child process
+#[derive(Debug, Clone)]
+pub struct SubprocessSolver {
+    program: PathBuf,
+    args: Vec<OsString>,
+    envs: Vec<(OsString, OsString)>,
+    retries: usize,
+}
+
+impl SubprocessSolver {
+    /// Runs the resolutions in the given program, which must call [serve_subprocess]
+    pub fn new<P: Into<PathBuf>>(program: P) -> Self {
+        SubprocessSolver {
+            program: program.into(),
+            args: vec![],
+            envs: vec![],
+            retries: 0,
+        }
+    }
+
+    /// Runs the resolutions in the current executable, which must call [serve_subprocess]
+    pub fn current_exe() -> io::Result<Self> {
+        Ok(Self::new(std::env::current_exe()?))
+    }
+
+    /// Adds an argument to the command line of the child process
+    pub fn arg<S: AsRef<OsStr>>(mut self, arg: S) -> Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    /// Sets an environment variable in the child process
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> Self {
+        self.envs
+            .push((key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+        self
+    }
+
+    /// Starts a new child process, up to this number of times,
+    /// when the child exits without a result (because it crashed, for instance).
+    /// Infeasible and unbounded problems are not retried.
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Solves the problem in the MPS format in a child process,
+    /// and returns the result it wrote, or why it did not write any
+    fn run(&self, model: &[u8]) -> Result<Vec<u8>, String> {
+        static RESULTS: AtomicUsize = AtomicUsize::new(0);
+        let result_path = std::env::temp_dir().join(format!(
+            "good_lp_subprocess_{}_{}.txt",
+            process::id(),
+            RESULTS.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .envs(self.envs.iter().map(|(k, v)| (k, v)))
+            .env(RESULT_PATH, &result_path)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("unable to start {}: {}", self.program.display(), e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // The child may have crashed before reading the whole problem
+            let _ = stdin.write_all(model);
+        }
+        let status = child
+            .wait()
+            .map_err(|e| format!("unable to wait for the child process: {}", e))?;
+        let result = fs::read(&result_path);
+        let _ = fs::remove_file(&result_path);
+        match result {
+            Ok(result) if status.success() => Ok(result),
+            _ => Err(format!(
+                "the child process exited without a result: {}",
+                status
+            )),
+        }
+    }
+}
+
+impl Solver for SubprocessSolver {
+    type Model = SubprocessProblem;
+
+    fn create_model(&mut self, problem: UnsolvedProblem) -> Self::Model {
+        SubprocessProblem {
+            strict_inequalities: StrictInequalities::new(problem.strict_inequality),
+            problem,
+            solver: self.clone(),
+        }
+    }
+}
+
+/// A problem to be solved in a child process
+pub struct SubprocessProblem {
+    problem: UnsolvedProblem,
+    strict_inequalities: StrictInequalities,
+    solver: SubprocessSolver,
+}
+
+impl SolverModel for SubprocessProblem {
+    type Solution = BatchSolution;
+    type Error = ResolutionError;
+
+    fn solve(self) -> Result<Self::Solution, Self::Error> {
+        self.strict_inequalities.check()?;
+        let model = self.problem.to_mps();
+        let mut attempt = self.solver.run(model.as_bytes());
+        for _ in 0..self.solver.retries {
+            if attempt.is_ok() {
+                break;
+            }
+            attempt = self.solver.run(model.as_bytes());
+        }
+        let solution = decode_result(&attempt.map_err(ResolutionError::Str)?)?;
+        if solution.values.len() != self.problem.variables.len() {
+            return Err(ResolutionError::Other(
+                "the child process did not return a value for every variable",
+            ));
+        }
+        Ok(solution)
+    }
+
+    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let constraint = self.strict_inequalities.apply(constraint);
+        self.problem.add_constraint(constraint)
+    }
+}
+
+impl ModelWithColumns for SubprocessProblem {
+    fn add_column(
+        &mut self,
+        definition: VariableDefinition,
+        objective_coefficient: f64,
+        coefficients: &[(ConstraintReference, f64)],
+    ) -> Variable {
+        self.problem
+            .add_column(definition, objective_coefficient, coefficients)
+    }
+}
+
+impl ModelWithBulkAttributes for SubprocessProblem {
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)> {
+        self.problem.get_bounds_bulk(variables)
+    }
+
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]) {
+        self.problem.set_bounds_bulk(bounds)
+    }
+
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64> {
+        self.problem.get_objective_coeffs_bulk(variables)
+    }
+
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]) {
+        self.problem.set_objective_coeffs_bulk(coefficients)
+    }
+}
+
+/// When the process was started by a [SubprocessSolver], solves the problem it was sent
+/// with the given solver, writes the result for the parent process, and exits.
+/// Otherwise, returns immediately.
+///
+/// Call it at the start of `main`, before anything that should not happen in the child.
+pub fn serve_subprocess<S>(solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    let result_path = match std::env::var_os(RESULT_PATH) {
+        Some(path) => path,
+        None => return,
+    };
+    let mut model = vec![];
+    if io::stdin().read_to_end(&mut model).is_err() {
+        process::exit(1);
+    }
+    let result = solve_job(&model, solver);
+    if fs::write(result_path, result).is_err() {
+        process::exit(1);
+    }
+    process::exit(0);
+}
@@ -0,0 +1,751 @@
+//! Multi-stage stochastic programming over a scenario tree.
+//!
+//! In a stochastic problem, decisions are taken in stages, and some data
+//! (a demand, a price, ...) is only revealed between stages.
+//! The possible futures are described by a [ScenarioTree]: each node is a state
+//! of the world at a stage, and each path from the root to a leaf is a scenario.
+//!
+//! The [extensive form](extensive_form) of the problem contains a copy of the model
+//! for every scenario. Decisions cannot depend on information that is not known yet,
+//! so the copies of the variables of scenarios that share the same history up to a stage
+//! are constrained to be equal. These non-anticipativity constraints are added automatically.
+//! The objective is the expected value of the objectives of the scenarios.
+//!
+//! When the extensive form is too large, [progressive_hedging] solves the scenarios
+//! separately, and makes their decisions converge iteratively.
+//!
+//! ```
+//! use good_lp::stochastic::{extensive_form, ScenarioTree};
+//! use good_lp::solvers::ObjectiveDirection;
+//! use good_lp::{constraint, default_solver, variable};
+//!
+//! // Capacity is bought today, before knowing whether the demand will be low or high
+//! let mut tree = ScenarioTree::new();
+//! let low = tree.add_child(tree.root(), 0.5);
+//! let high = tree.add_child(tree.root(), 0.5);
+//! let demand = |node| if node == low { 50. } else { 100. };
+//! let model = extensive_form(&tree, ObjectiveDirection::Maximisation, |scenario| {
+//!     let capacity = scenario.add(0, variable().min(0));
+//!     let sales = scenario.add(1, variable().min(0));
+//!     scenario.add_constraint(constraint!(sales <= capacity));
+//!     scenario.add_constraint(constraint!(sales <= demand(scenario.node(1))));
+//!     scenario.add_objective(3 * sales - 2 * capacity);
+//! });
+//! let solution = model.solve(default_solver)?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! // Buying more than 50 only pays off half of the time, which is not enough
+//! assert_float_eq(solution.node_values(tree.root())[0], 50.);
+//! assert_float_eq(solution.node_values(high)[0], 50.);
+//! assert_float_eq(solution.expected_objective(), 50.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::thread;
+
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{
+    constraint, variable, Constraint, Expression, IntoAffineExpression, ProblemVariables,
+    ResolutionError, Solution, Solver, SolverModel, Variable, VariableDefinition,
+};
+
+/// A node of a [ScenarioTree]
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    parent: Option<usize>,
+    stage: usize,
+    /// The probability of the node, given its parent
+    probability: f64,
+    children: Vec<usize>,
+}
+
+/// The possible futures of a stochastic problem. Nodes are identified by their index,
+/// starting with the root, which represents the present at stage 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioTree {
+    nodes: Vec<Node>,
+}
+
+impl Default for ScenarioTree {
+    fn default() -> Self {
+        ScenarioTree {
+            nodes: vec![Node {
+                parent: None,
+                stage: 0,
+                probability: 1.,
+                children: vec![],
+            }],
+        }
+    }
+}
+
+impl ScenarioTree {
+    /// Creates a tree that only contains its root
+    pub fn new() -> Self {
+        ScenarioTree::default()
+    }
+
+    /// The root node, at stage 0
+    pub fn root(&self) -> usize {
+        0
+    }
+
+    /// Adds a node at the stage following the one of `parent`,
+    /// reached with the given probability when `parent` is reached.
+    /// The probabilities of the children of a node should sum to 1.
+    pub fn add_child(&mut self, parent: usize, probability: f64) -> usize {
+        let child = self.nodes.len();
+        let stage = self.nodes[parent].stage + 1;
+        self.nodes[parent].children.push(child);
+        self.nodes.push(Node {
+            parent: Some(parent),
+            stage,
+            probability,
+            children: vec![],
+        });
+        child
+    }
+
+    /// The number of nodes, including the root
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Always false: a tree contains at least its root
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The stage of a node: its distance to the root
+    pub fn stage(&self, node: usize) -> usize {
+        self.nodes[node].stage
+    }
+
+    /// The parent of a node, or `None` for the root
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.nodes[node].parent
+    }
+
+    /// The children of a node
+    pub fn children(&self, node: usize) -> &[usize] {
+        &self.nodes[node].children
+    }
+
+    /// The probability of reaching a node, from the root
+    pub fn probability(&self, node: usize) -> f64 {
+        self.path(node)
+            .iter()
+            .map(|&n| self.nodes[n].probability)
+            .product()
+    }
+
+    /// The nodes from the root to the given node, included
+    pub fn path(&self, node: usize) -> Vec<usize> {
+        let mut path = vec![node];
+        while let Some(parent) = self.nodes[*path.last().unwrap()].parent {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
+
+    /// The leaves of the tree, in the order in which they were added.
+    /// Each leaf is the end of one scenario.
+    pub fn scenarios(&self) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|&n| self.nodes[n].children.is_empty())
+            .collect()
+    }
+}
+
+/// The copy of the model for one scenario, given to the function that builds it.
+/// See [extensive_form].
+pub struct ScenarioModel<'a> {
+    variables: &'a mut ProblemVariables,
+    constraints: Vec<Constraint>,
+    path: Vec<usize>,
+    probability: f64,
+    stage_variables: Vec<Vec<Variable>>,
+    objective: Expression,
+}
+
+impl<'a> ScenarioModel<'a> {
+    pub(crate) fn new(
+        tree: &ScenarioTree,
+        leaf: usize,
+        variables: &'a mut ProblemVariables,
+    ) -> Self {
+        let path = tree.path(leaf);
+        ScenarioModel {
+            variables,
+            constraints: vec![],
+            stage_variables: vec![vec![]; path.len()],
+            probability: tree.probability(leaf),
+            path,
+            objective: Expression::default(),
+        }
+    }
+
+    /// The node through which the scenario goes at the given stage
+    pub fn node(&self, stage: usize) -> usize {
+        self.path[stage]
+    }
+
+    /// The last node of the scenario
+    pub fn leaf(&self) -> usize {
+        *self.path.last().unwrap()
+    }
+
+    /// The number of stages of the scenario
+    pub fn stages(&self) -> usize {
+        self.path.len()
+    }
+
+    /// The probability of the scenario
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// Adds a variable decided at the given stage.
+    /// Every scenario must add the same number of variables to a stage,
+    /// in the same order, so that the copies of a variable can be matched.
+    pub fn add(&mut self, stage: usize, definition: VariableDefinition) -> Variable {
+        let variable = self.variables.add(definition);
+        self.stage_variables[stage].push(variable);
+        variable
+    }
+
+    /// Adds a constraint to this scenario
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// Adds a term to the objective of this scenario
+    pub fn add_objective<E: IntoAffineExpression>(&mut self, term: E) {
+        self.objective += term;
+    }
+
+    pub(crate) fn finish(self) -> ScenarioCopy {
+        ScenarioCopy {
+            probability: self.probability,
+            path: self.path,
+            constraints: self.constraints,
+            stage_variables: self.stage_variables,
+            objective: self.objective,
+        }
+    }
+}
+
+/// The variables, constraints and objective of one scenario
+pub(crate) struct ScenarioCopy {
+    pub(crate) probability: f64,
+    pub(crate) path: Vec<usize>,
+    pub(crate) constraints: Vec<Constraint>,
+    pub(crate) stage_variables: Vec<Vec<Variable>>,
+    pub(crate) objective: Expression,
+}
+
+/// For every node, the index of the first scenario that goes through it
+pub(crate) fn reference_scenarios(tree: &ScenarioTree, scenarios: &[ScenarioCopy]) -> Vec<usize> {
+    let mut reference = vec![usize::MAX; tree.len()];
+    for (s, scenario) in scenarios.iter().enumerate() {
+        for &node in &scenario.path {
+            if reference[node] == usize::MAX {
+                reference[node] = s;
+            }
+        }
+    }
+    reference
+}
+
+/// The extensive form of a stochastic problem, created by [extensive_form]
+pub struct ExtensiveForm {
+    problem: UnsolvedProblem,
+    tree: ScenarioTree,
+    scenarios: Vec<ScenarioCopy>,
+}
+
+/// Builds the extensive form of a stochastic problem.
+///
+/// `build` is called once for every scenario of the tree, in the order of
+/// [ScenarioTree::scenarios], to create the variables, constraints and objective
+/// of a copy of the model. The variables of a stage must only depend on the nodes
+/// of the scenario up to that stage.
+///
+/// Panics if two scenarios that go through the same node
+/// do not add the same number of variables to the stage of that node.
+pub fn extensive_form<F>(
+    tree: &ScenarioTree,
+    direction: ObjectiveDirection,
+    mut build: F,
+) -> ExtensiveForm
+where
+    F: FnMut(&mut ScenarioModel),
+{
+    let mut variables = ProblemVariables::new();
+    let scenarios: Vec<ScenarioCopy> = tree
+        .scenarios()
+        .into_iter()
+        .map(|leaf| {
+            let mut scenario = ScenarioModel::new(tree, leaf, &mut variables);
+            build(&mut scenario);
+            scenario.finish()
+        })
+        .collect();
+    let mut objective = Expression::default();
+    for scenario in &scenarios {
+        objective.add_mul(scenario.probability, &scenario.objective);
+    }
+    let mut problem = variables.optimise(direction, objective);
+    let reference = reference_scenarios(tree, &scenarios);
+    for scenario in &scenarios {
+        for constraint in &scenario.constraints {
+            problem.add_constraint(constraint.clone());
+        }
+        for (stage, &node) in scenario.path.iter().enumerate() {
+            let reference_variables = &scenarios[reference[node]].stage_variables[stage];
+            let copies = &scenario.stage_variables[stage];
+            assert_eq!(
+                reference_variables.len(),
+                copies.len(),
+                "scenarios going through node {} have different numbers of variables at stage {}",
+                node,
+                stage
+            );
+            for (&decision, &copy) in reference_variables.iter().zip(copies) {
+                if decision != copy {
+                    problem.add_constraint(constraint!(decision == copy));
+                }
+            }
+        }
+    }
+    ExtensiveForm {
+        problem,
+        tree: tree.clone(),
+        scenarios,
+    }
+}
+
+impl ExtensiveForm {
+    /// The copies of the variables of a stage in a scenario,
+    /// given by its index in [ScenarioTree::scenarios]
+    pub fn scenario_variables(&self, scenario: usize, stage: usize) -> &[Variable] {
+        &self.scenarios[scenario].stage_variables[stage]
+    }
+
+    /// The underlying problem, to which additional constraints can be added
+    pub fn problem_mut(&mut self) -> &mut UnsolvedProblem {
+        &mut self.problem
+    }
+
+    /// Solves the extensive form
+    pub fn solve<S: Solver>(
+        self,
+        solver: S,
+    ) -> Result<TreeSolution, <S::Model as SolverModel>::Error> {
+        let solution = self.problem.using(solver).solve()?;
+        Ok(TreeSolution::new(&self.tree, &self.scenarios, |_, var| {
+            solution.value(var)
+        }))
+    }
+}
+
+/// The decisions taken at every node of a [ScenarioTree]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeSolution {
+    /// The values of the variables of each node, in the order in which they were added
+    node_values: Vec<Vec<f64>>,
+    /// The objective value of each scenario
+    scenario_objectives: Vec<f64>,
+    /// The probability of each scenario
+    probabilities: Vec<f64>,
+}
+
+impl TreeSolution {
+    /// Reads the values of the variables of each scenario.
+    /// `value` is given the index of a scenario and one of its variables.
+    pub(crate) fn new(
+        tree: &ScenarioTree,
+        scenarios: &[ScenarioCopy],
+        mut value: impl FnMut(usize, Variable) -> f64,
+    ) -> Self {
+        let reference = reference_scenarios(tree, scenarios);
+        let node_values = (0..tree.len())
+            .map(|node| {
+                if reference[node] == usize::MAX {
+                    return vec![];
+                }
+                let s = reference[node];
+                scenarios[s].stage_variables[tree.stage(node)]
+                    .iter()
+                    .map(|&var| value(s, var))
+                    .collect()
+            })
+            .collect();
+        let scenario_objectives = scenarios
+            .iter()
+            .enumerate()
+            .map(|(s, scenario)| {
+                let mut objective = scenario.objective.constant;
+                for (var, coefficient) in scenario.objective.linear.coefficients.iter() {
+                    objective += coefficient * value(s, *var);
+                }
+                objective
+            })
+            .collect();
+        TreeSolution {
+            node_values,
+            scenario_objectives,
+            probabilities: scenarios.iter().map(|s| s.probability).collect(),
+        }
+    }
+
+    /// The values of the variables decided at a node,
+    /// in the order in which they were added to its stage
+    pub fn node_values(&self, node: usize) -> &[f64] {
+        &self.node_values[node]
+    }
+
+    /// The objective value of a scenario, given by its index in [ScenarioTree::scenarios]
+    pub fn scenario_objective(&self, scenario: usize) -> f64 {
+        self.scenario_objectives[scenario]
+    }
+
+    /// The expected value of the objective over all scenarios
+    pub fn expected_objective(&self) -> f64 {
+        self.scenario_objectives
+            .iter()
+            .zip(&self.probabilities)
+            .map(|(objective, probability)| objective * probability)
+            .sum()
+    }
+}
+
+/// The number of segments of the piecewise linear approximation of the proximal term
+const PROXIMAL_SEGMENTS: usize = 8;
+
+/// A progressive hedging loop, created by [progressive_hedging]
+pub struct ProgressiveHedging {
+    tree: ScenarioTree,
+    scenarios: Vec<ScenarioCopy>,
+    /// The problem of each scenario: its variables, with the objective as a minimisation
+    base: Vec<(ProblemVariables, Expression)>,
+    penalty: f64,
+    max_iterations: usize,
+    tolerance: f64,
+}
+
+/// Prepares the resolution of a stochastic problem with
+/// [progressive hedging](https://doi.org/10.1287/moor.16.1.119).
+///
+/// Instead of building the extensive form, every scenario is solved on its own, in parallel.
+/// The non-anticipativity constraints are relaxed: each scenario is penalised,
+/// with a price and a proximal term, for deviating from the average decision of the scenarios
+/// that share its history. The prices are updated after each iteration,
+/// until the decisions of all the scenarios agree.
+///
+/// Since good_lp solves linear problems, the quadratic proximal term of the method
+/// is approximated by a piecewise linear function.
+/// On problems with integer variables, progressive hedging is a heuristic,
+/// which may not converge.
+///
+/// `build` is called once per scenario, like in [extensive_form].
+pub fn progressive_hedging<F>(
+    tree: &ScenarioTree,
+    direction: ObjectiveDirection,
+    mut build: F,
+) -> ProgressiveHedging
+where
+    F: FnMut(&mut ScenarioModel),
+{
+    let sign = match direction {
+        ObjectiveDirection::Minimisation => 1.,
+        ObjectiveDirection::Maximisation => -1.,
+    };
+    let mut scenarios = vec![];
+    let mut base = vec![];
+    for leaf in tree.scenarios() {
+        let mut variables = ProblemVariables::new();
+        let mut scenario = ScenarioModel::new(tree, leaf, &mut variables);
+        build(&mut scenario);
+        let scenario = scenario.finish();
+        base.push((variables, sign * scenario.objective.clone()));
+        scenarios.push(scenario);
+    }
+    ProgressiveHedging {
+        tree: tree.clone(),
+        scenarios,
+        base,
+        penalty: 1.,
+        max_iterations: 100,
+        tolerance: 1e-4,
+    }
+}
+
+/// A variable of a scenario that has to agree with the other scenarios going through its node
+struct SharedDecision {
+    variable: Variable,
+    node: usize,
+    /// The index of the variable among the variables of its stage
+    position: usize,
+}
+
+impl ProgressiveHedging {
+    /// Sets the penalty (often called rho) for deviating from the average decision.
+    /// Large penalties make the scenarios agree quickly, on a decision that may be far from
+    /// optimal, while small penalties need more iterations. The default is 1.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = penalty;
+        self
+    }
+
+    /// Sets the maximum number of iterations. The default is 100.
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Stops when the expected distance between the decisions of the scenarios
+    /// and their average, plus the change of the average, stays below `tolerance`
+    /// for two iterations in a row. The default is `1e-4`.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The decisions of each scenario that are shared with other scenarios
+    fn shared_decisions(&self) -> Vec<Vec<SharedDecision>> {
+        let mut through = vec![0; self.tree.len()];
+        for scenario in &self.scenarios {
+            for &node in &scenario.path {
+                through[node] += 1;
+            }
+        }
+        self.scenarios
+            .iter()
+            .map(|scenario| {
+                let mut shared = vec![];
+                for (stage, &node) in scenario.path.iter().enumerate() {
+                    if through[node] < 2 {
+                        continue;
+                    }
+                    for (position, &variable) in scenario.stage_variables[stage].iter().enumerate()
+                    {
+                        shared.push(SharedDecision {
+                            variable,
+                            node,
+                            position,
+                        });
+                    }
+                }
+                shared
+            })
+            .collect()
+    }
+
+    /// The problem of a scenario, with its prices, and the proximal term
+    /// centered on the averages, with breakpoints up to the given distances
+    fn penalised(
+        &self,
+        scenario: usize,
+        shared: &[SharedDecision],
+        prices: &[f64],
+        averages: &[f64],
+        ranges: &[f64],
+    ) -> UnsolvedProblem {
+        let (variables, objective) = &self.base[scenario];
+        let mut variables = variables.clone();
+        let mut objective = objective.clone();
+        let mut constraints = vec![];
+        let rho = self.penalty;
+        for (i, decision) in shared.iter().enumerate() {
+            objective.add_mul(prices[i], decision.variable);
+            let n = PROXIMAL_SEGMENTS as f64;
+            let breakpoint = |k: usize| ranges[i] * (k as f64 / n).powi(2);
+            let mut deviation = Expression::default();
+            for k in 0..=PROXIMAL_SEGMENTS {
+                let (width, slope) = if k < PROXIMAL_SEGMENTS {
+                    let (low, high) = (breakpoint(k), breakpoint(k + 1));
+                    (high - low, rho * (low + high) / 2.)
+                } else {
+                    (f64::INFINITY, rho * breakpoint(k))
+                };
+                let above = variables.add(variable().clamp(0, width));
+                let below = variables.add(variable().clamp(0, width));
+                deviation += above - below;
+                objective.add_mul(slope, above + below);
+            }
+            constraints.push(constraint!(decision.variable - averages[i] == deviation));
+        }
+        let mut problem = variables.minimise(objective);
+        for constraint in self.scenarios[scenario]
+            .constraints
+            .iter()
+            .chain(&constraints)
+        {
+            problem.add_constraint(constraint.clone());
+        }
+        problem
+    }
+
+    /// Runs progressive hedging, solving the problems of the scenarios in parallel,
+    /// one thread per scenario.
+    ///
+    /// Returns the first error if the problem of a scenario cannot be solved.
+    pub fn solve<S>(self, solver: S) -> Result<HedgingSolution, ResolutionError>
+    where
+        S: Solver + Clone + Send,
+        S::Model: SolverModel<Error = ResolutionError>,
+    {
+        let shared = self.shared_decisions();
+        let n = self.scenarios.len();
+        let problems: Vec<UnsolvedProblem> = (0..n)
+            .map(|s| {
+                let (variables, objective) = &self.base[s];
+                let mut problem = variables.clone().minimise(objective.clone());
+                for constraint in &self.scenarios[s].constraints {
+                    problem.add_constraint(constraint.clone());
+                }
+                problem
+            })
+            .collect();
+        let mut values = solve_all(problems, &solver)?;
+        let mut prices: Vec<Vec<f64>> = shared.iter().map(|d| vec![0.; d.len()]).collect();
+        let mut previous_averages: Option<Vec<Vec<f64>>> = None;
+        let mut gaps = vec![];
+        let mut small_gaps = 0;
+        let mut iterations = 0;
+        let converged = loop {
+            let averages = self.averages(&shared, &values);
+            let mut gap = 0.;
+            for (s, decisions) in shared.iter().enumerate() {
+                for decision in decisions {
+                    let average = averages[decision.node][decision.position];
+                    let value = values[s][decision.variable.index()];
+                    gap += self.scenarios[s].probability * (value - average).abs();
+                }
+            }
+            if let Some(previous) = &previous_averages {
+                for (node, (now, before)) in averages.iter().zip(previous).enumerate() {
+                    let change: f64 = now.iter().zip(before).map(|(a, b)| (a - b).abs()).sum();
+                    gap += self.tree.probability(node) * change;
+                }
+            }
+            gaps.push(gap);
+            small_gaps = if gap <= self.tolerance {
+                small_gaps + 1
+            } else {
+                0
+            };
+            if small_gaps >= 2 {
+                break true;
+            }
+            if iterations >= self.max_iterations {
+                break false;
+            }
+            let mut problems = Vec::with_capacity(n);
+            for (s, decisions) in shared.iter().enumerate() {
+                let mut centers = Vec::with_capacity(decisions.len());
+                let mut ranges = Vec::with_capacity(decisions.len());
+                for (i, decision) in decisions.iter().enumerate() {
+                    let average = averages[decision.node][decision.position];
+                    let deviation = values[s][decision.variable.index()] - average;
+                    prices[s][i] += self.penalty * deviation;
+                    centers.push(average);
+                    let range = 2. * (deviation.abs() + prices[s][i].abs() / self.penalty);
+                    ranges.push(range.max(self.tolerance));
+                }
+                problems.push(self.penalised(s, decisions, &prices[s], &centers, &ranges));
+            }
+            values = solve_all(problems, &solver)?;
+            previous_averages = Some(averages);
+            iterations += 1;
+        };
+        let averages = self.averages(&shared, &values);
+        let mut solution =
+            TreeSolution::new(&self.tree, &self.scenarios, |s, var| values[s][var.index()]);
+        for (node, average) in averages.into_iter().enumerate() {
+            if !average.is_empty() {
+                solution.node_values[node] = average;
+            }
+        }
+        Ok(HedgingSolution {
+            solution,
+            iterations,
+            converged,
+            gaps,
+        })
+    }
+
+    /// The probability-weighted average of the decisions of the scenarios going through each node
+    fn averages(&self, shared: &[Vec<SharedDecision>], values: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let mut sums: Vec<Vec<f64>> = vec![vec![]; self.tree.len()];
+        let mut weights = vec![0.; self.tree.len()];
+        for (s, decisions) in shared.iter().enumerate() {
+            let probability = self.scenarios[s].probability;
+            let mut counted = vec![];
+            for decision in decisions {
+                let sum = &mut sums[decision.node];
+                if sum.len() <= decision.position {
+                    sum.resize(decision.position + 1, 0.);
+                }
+                sum[decision.position] += probability * values[s][decision.variable.index()];
+                if !counted.contains(&decision.node) {
+                    counted.push(decision.node);
+                    weights[decision.node] += probability;
+                }
+            }
+        }
+        for (sum, weight) in sums.iter_mut().zip(weights) {
+            for value in sum.iter_mut() {
+                *value /= weight;
+            }
+        }
+        sums
+    }
+}
+
+/// Solves the problems in parallel, and returns the values of all their variables
+fn solve_all<S>(
+    problems: Vec<UnsolvedProblem>,
+    solver: &S,
+) -> Result<Vec<Vec<f64>>, ResolutionError>
+where
+    S: Solver + Clone + Send,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    thread::scope(|scope| {
+        let handles: Vec<_> = problems
+            .into_iter()
+            .map(|problem| {
+                let solver = solver.clone();
+                scope.spawn(move || {
+                    let variables: Vec<Variable> = problem
+                        .variables
+                        .iter_variables_with_def()
+                        .map(|(v, _)| v)
+                        .collect();
+                    let solution = problem.using(solver).solve()?;
+                    Ok(variables.iter().map(|&v| solution.value(v)).collect())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("a solver panicked"))
+            .collect()
+    })
+}
+
+/// The result of [ProgressiveHedging::solve]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HedgingSolution {
+    /// The decisions at each node: the average of the decisions of the scenarios
+    /// going through it, and the objective value of each scenario
+    pub solution: TreeSolution,
+    /// The number of iterations after the initial resolution of the scenarios
+    pub iterations: usize,
+    /// Whether the scenarios agreed on their decisions before the maximum number of iterations
+    pub converged: bool,
+    /// The convergence measure after each iteration, starting with the initial resolution
+    pub gaps: Vec<f64>,
+}
@@ -0,0 +1,441 @@
+//! Constraints with placeholders, parsed once and instantiated many times.
+//!
+//! Models often contain thousands of constraints with the same shape,
+//! that only differ by the variables and numbers they use.
+//! A [ConstraintTemplate] is written once, in a readable form,
+//! and can then be turned into a [Constraint] for each set of values,
+//! without parsing it or building intermediate expressions again.
+//!
+//! In a template, identifiers like `x` are placeholders for variables,
+//! and names in braces like `{cap}` are placeholders for numbers.
+//!
+//! ```
+//! use good_lp::{default_solver, template, variable, variables, Solution, SolverModel};
+//!
+//! let capacity = template!("load: {w1} x + {w2} y <= {cap}");
+//! let mut vars = variables!();
+//! let xs = vars.add_vector(variable().integer().min(0), 3);
+//! let ys = vars.add_vector(variable().integer().min(0), 3);
+//! let objective: good_lp::Expression = xs.iter().chain(&ys).sum();
+//! let mut model = vars.maximise(objective).using(default_solver);
+//! for i in 0..3 {
+//!     // Variables and parameters are given in the order of their first appearance
+//!     let cap = 10. * (i + 1) as f64;
+//!     model.add_constraint(capacity.instantiate(&[xs[i], ys[i]], &[2., 5., cap]));
+//! }
+//! # // minilp and highs don't support integer variables
+//! # #[cfg(not(any(feature = "minilp", feature = "highs")))] {
+//! let solution = model.solve()?;
+//! assert_eq!(solution.value(xs[2]), 15.);
+//! # }
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::{Constraint, Expression, Variable};
+
+/// An error in the syntax of a template, or in the values used to instantiate it
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    /// The template is invalid
+    Syntax {
+        /// The byte offset in the template where the error was found
+        position: usize,
+        /// A description of the error
+        message: String,
+    },
+    /// No value was given for a placeholder
+    Missing(String),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Syntax { position, message } => {
+                write!(f, "invalid template at position {}: {}", position, message)
+            }
+            TemplateError::Missing(name) => write!(f, "no value given for {:?}", name),
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+/// A coefficient: a number multiplied by some parameters
+#[derive(Debug, Clone, PartialEq)]
+struct Coefficient {
+    factor: f64,
+    parameters: Vec<usize>,
+}
+
+impl Coefficient {
+    fn value(&self, parameters: &[f64]) -> f64 {
+        self.parameters
+            .iter()
+            .fold(self.factor, |value, &p| value * parameters[p])
+    }
+}
+
+/// A term of the normalized expression `sum(terms) + constant (<= or ==) 0`
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    coefficient: Coefficient,
+    /// The index of the variable placeholder, or `None` for a constant term
+    variable: Option<usize>,
+}
+
+/// A constraint with placeholders for variables and numbers, created with [ConstraintTemplate::parse]
+/// or the [template!](crate::template!) macro.
+///
+/// The syntax is `[name:] left (<= | >= | =) right`, where both sides are sums of terms,
+/// and each term is a product of numbers, `{parameter}` placeholders,
+/// and at most one variable placeholder. The `*` between factors is optional.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintTemplate {
+    name: Option<String>,
+    terms: Vec<Term>,
+    is_equality: bool,
+    variables: Vec<String>,
+    parameters: Vec<String>,
+}
+
+impl ConstraintTemplate {
+    /// Parses a template
+    ///
+    /// ```
+    /// use good_lp::template::ConstraintTemplate;
+    /// let template = ConstraintTemplate::parse("2 x - {a} * y >= {b} + z").unwrap();
+    /// assert_eq!(template.variable_names(), ["x", "y", "z"]);
+    /// assert_eq!(template.parameter_names(), ["a", "b"]);
+    /// ```
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        Parser::new(template).template()
+    }
+
+    /// The names of the variable placeholders, in the order expected by [ConstraintTemplate::instantiate]
+    pub fn variable_names(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// The names of the number placeholders, in the order expected by [ConstraintTemplate::instantiate]
+    pub fn parameter_names(&self) -> &[String] {
+        &self.parameters
+    }
+
+    /// Creates a constraint, replacing the placeholders by the given variables and numbers,
+    /// in the order of [ConstraintTemplate::variable_names] and [ConstraintTemplate::parameter_names].
+    ///
+    /// Panics if the number of variables or parameters is not the number of placeholders.
+    pub fn instantiate(&self, variables: &[Variable], parameters: &[f64]) -> Constraint {
+        assert_eq!(
+            variables.len(),
+            self.variables.len(),
+            "the template expects {} variables",
+            self.variables.len()
+        );
+        assert_eq!(
+            parameters.len(),
+            self.parameters.len(),
+            "the template expects {} parameters",
+            self.parameters.len()
+        );
+        let mut expression = Expression::with_capacity(self.variables.len());
+        for term in &self.terms {
+            let value = term.coefficient.value(parameters);
+            match term.variable {
+                Some(v) => {
+                    *expression
+                        .linear
+                        .coefficients
+                        .entry(variables[v])
+                        .or_default() += value
+                }
+                None => expression.constant += value,
+            }
+        }
+        let constraint = Constraint::new(expression, self.is_equality);
+        match &self.name {
+            Some(name) => constraint.set_name(name.clone()),
+            None => constraint,
+        }
+    }
+
+    /// Like [ConstraintTemplate::instantiate], but with values given by placeholder name
+    ///
+    /// ```
+    /// use good_lp::{template, variables};
+    /// variables! {vars: x; y;}
+    /// let constraint = template!("x + y <= {cap}").bind(&[("y", y), ("x", x)], &[("cap", 4.)]);
+    /// assert!(constraint.is_ok());
+    /// ```
+    pub fn bind(
+        &self,
+        variables: &[(&str, Variable)],
+        parameters: &[(&str, f64)],
+    ) -> Result<Constraint, TemplateError> {
+        let variables = lookup(&self.variables, variables)?;
+        let parameters = lookup(&self.parameters, parameters)?;
+        Ok(self.instantiate(&variables, &parameters))
+    }
+}
+
+fn lookup<T: Copy>(names: &[String], values: &[(&str, T)]) -> Result<Vec<T>, TemplateError> {
+    names
+        .iter()
+        .map(|name| {
+            values
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|&(_, value)| value)
+                .ok_or_else(|| TemplateError::Missing(name.clone()))
+        })
+        .collect()
+}
+
+/// Parses a template, and panics if it is invalid. See [ConstraintTemplate].
+///
+/// ```
+/// use good_lp::template;
+/// let template = template!("x + y <= {cap}");
+/// assert_eq!(template.parameter_names(), ["cap"]);
+/// ```
+#[macro_export]
+macro_rules! template {
+    ($template:expr) => {
+        match $crate::template::ConstraintTemplate::parse($template) {
+            Ok(template) => template,
+            Err(e) => panic!("{}", e),
+        }
+    };
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    variables: Vec<String>,
+    parameters: Vec<String>,
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Parser {
+            source,
+            position: 0,
+            variables: vec![],
+            parameters: vec![],
+        }
+    }
+
+    fn error<T, S: Into<String>>(&self, message: S) -> Result<T, TemplateError> {
+        Err(TemplateError::Syntax {
+            position: self.position,
+            message: message.into(),
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes the given text if the input starts with it
+    fn eat(&mut self, text: &str) -> bool {
+        self.skip_spaces();
+        let found = self.rest().starts_with(text);
+        if found {
+            self.position += text.len();
+        }
+        found
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += end;
+        &rest[..end]
+    }
+
+    fn index_of(names: &mut Vec<String>, name: &str) -> usize {
+        names.iter().position(|n| n == name).unwrap_or_else(|| {
+            names.push(name.to_string());
+            names.len() - 1
+        })
+    }
+
+    fn template(mut self) -> Result<ConstraintTemplate, TemplateError> {
+        let name = self.label();
+        let mut terms = self.sum(1.)?;
+        let (is_equality, right_sign) = if self.eat("<=") {
+            (false, -1.)
+        } else if self.eat(">=") {
+            // a >= b is stored as b - a <= 0
+            for term in &mut terms {
+                term.coefficient.factor = -term.coefficient.factor;
+            }
+            (false, 1.)
+        } else if self.eat("==") || self.eat("=") {
+            (true, -1.)
+        } else {
+            return self.error("expected <=, >= or =");
+        };
+        terms.extend(self.sum(right_sign)?);
+        self.skip_spaces();
+        if !self.rest().is_empty() {
+            return self.error("unexpected text after the constraint");
+        }
+        Ok(ConstraintTemplate {
+            name,
+            terms,
+            is_equality,
+            variables: self.variables,
+            parameters: self.parameters,
+        })
+    }
+
+    fn label(&mut self) -> Option<String> {
+        self.skip_spaces();
+        let start = self.position;
+        let name = self.take_while(is_identifier_char);
+        if !name.is_empty() && self.eat(":") {
+            Some(name.to_string())
+        } else {
+            self.position = start;
+            None
+        }
+    }
+
+    fn sum(&mut self, sign: f64) -> Result<Vec<Term>, TemplateError> {
+        let mut terms = vec![];
+        let mut term_sign = if self.eat("-") {
+            -sign
+        } else {
+            self.eat("+");
+            sign
+        };
+        loop {
+            terms.push(self.term(term_sign)?);
+            term_sign = if self.eat("+") {
+                sign
+            } else if self.eat("-") {
+                -sign
+            } else {
+                return Ok(terms);
+            };
+        }
+    }
+
+    fn term(&mut self, sign: f64) -> Result<Term, TemplateError> {
+        let mut coefficient = Coefficient {
+            factor: sign,
+            parameters: vec![],
+        };
+        let mut variable = None;
+        let mut factors = 0;
+        loop {
+            self.skip_spaces();
+            if factors > 0 {
+                self.eat("*");
+                self.skip_spaces();
+            }
+            let next = match self.rest().chars().next() {
+                Some(c) => c,
+                None => break,
+            };
+            if next == '{' {
+                let start = self.position;
+                self.position += 1;
+                let name = self.take_while(is_identifier_char);
+                if name.is_empty() || !self.eat("}") {
+                    self.position = start;
+                    return self.error("expected a parameter name followed by }");
+                }
+                let index = Self::index_of(&mut self.parameters, name);
+                coefficient.parameters.push(index);
+            } else if next.is_ascii_digit() || next == '.' {
+                let start = self.position;
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                match number.parse::<f64>() {
+                    Ok(n) => coefficient.factor *= n,
+                    Err(_) => {
+                        self.position = start;
+                        return self.error(format!("invalid number {:?}", number));
+                    }
+                }
+            } else if is_identifier_char(next) {
+                if variable.is_some() {
+                    return self.error("a term cannot contain more than one variable");
+                }
+                let name = self.take_while(is_identifier_char);
+                variable = Some(Self::index_of(&mut self.variables, name));
+            } else {
+                break;
+            }
+            factors += 1;
+        }
+        if factors == 0 {
+            return self.error("expected a number, a {parameter} or a variable");
+        }
+        Ok(Term {
+            coefficient,
+            variable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConstraintTemplate, TemplateError};
+    use crate::variables;
+
+    #[test]
+    fn normalizes_both_sides() {
+        variables! {vars: x; y;}
+        let template = ConstraintTemplate::parse("c: 2 x + {a} >= y - 3 * {a} * {b}").unwrap();
+        let constraint = template.instantiate(&[x, y], &[2., 5.]);
+        // y - 30 - 2x - 2 <= 0
+        assert_eq!(constraint.get_name(), Some("c"));
+        assert_eq!(constraint.expression.linear.coefficients[&x], -2.);
+        assert_eq!(constraint.expression.linear.coefficients[&y], 1.);
+        assert_eq!(constraint.expression.constant, -32.);
+        assert!(!constraint.is_equality);
+    }
+
+    #[test]
+    fn repeated_variables_are_summed() {
+        variables! {vars: x;}
+        let template = ConstraintTemplate::parse("x + {a} x = 1").unwrap();
+        let constraint = template.instantiate(&[x], &[2.]);
+        assert_eq!(constraint.expression.linear.coefficients[&x], 3.);
+        assert_eq!(constraint.expression.constant, -1.);
+        assert!(constraint.is_equality);
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let error = |template| match ConstraintTemplate::parse(template) {
+            Err(TemplateError::Syntax { position, .. }) => position,
+            _ => panic!("{:?} should be invalid", template),
+        };
+        assert_eq!(error("x + y"), 5);
+        assert_eq!(error("x y <= 1"), 2);
+        assert_eq!(error("x <= {cap"), 5);
+        assert_eq!(error("x <= 1.2.3"), 5);
+    }
+
+    #[test]
+    fn missing_values() {
+        variables! {vars: x;}
+        let template = ConstraintTemplate::parse("x <= {cap}").unwrap();
+        let result = template.bind(&[("x", x)], &[]);
+        assert_eq!(result.err(), Some(TemplateError::Missing("cap".into())));
+    }
+}
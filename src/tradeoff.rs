@@ -0,0 +1,249 @@
+//! Objectives made of weighted groups, to explore the trade-offs between them.
+//!
+//! Many problems balance several goals, such as cost against emissions.
+//! Instead of a single objective, an [ObjectiveGroups] holds named groups,
+//! each with an expression and a weight. The objective is their weighted sum.
+//!
+//! A [WeightedModel] keeps the constraints of the problem, and lets the weights
+//! be changed between resolutions with [WeightedModel::set_group_weight],
+//! which is what an interactive slider needs.
+//! `SolverModel::solve` consumes the solver model, so every resolution creates a new one
+//! from the stored problem, without the constraints having to be built again.
+//!
+//! ```
+//! use good_lp::tradeoff::ObjectiveGroups;
+//! use good_lp::solvers::ObjectiveDirection;
+//! use good_lp::{constraint, default_solver, variables};
+//!
+//! variables! {vars: 0 <= coal; 0 <= wind <= 60;}
+//! let groups = ObjectiveGroups::new()
+//!     .group("cost", 2 * coal + 5 * wind, 1.)
+//!     .group("emissions", 10 * coal, 0.);
+//! let mut model = groups
+//!     .problem(vars, ObjectiveDirection::Minimisation)
+//!     .with(constraint!(coal + wind >= 100))
+//!     .using(default_solver);
+//! let cheapest = model.solve()?;
+//! # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+//! assert_float_eq(cheapest.group_value("emissions"), 1000.);
+//! // Pricing emissions makes wind worth it
+//! model.set_group_weight("emissions", 0.5);
+//! let greener = model.solve()?;
+//! assert_float_eq(greener.group_value("emissions"), 400.);
+//! # Ok::<_, good_lp::ResolutionError>(())
+//! ```
+use crate::solvers::ObjectiveDirection;
+use crate::variable::UnsolvedProblem;
+use crate::{
+    Constraint, Expression, IntoAffineExpression, ProblemVariables, Solution, SolutionStatus,
+    SolveStats, Solver, SolverModel, Variable,
+};
+
+/// A named part of an objective
+#[derive(Debug, Clone, PartialEq)]
+struct Group {
+    name: String,
+    expression: Expression,
+    weight: f64,
+}
+
+/// An objective made of named groups, each with its own weight
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectiveGroups {
+    groups: Vec<Group>,
+}
+
+impl ObjectiveGroups {
+    /// Creates an objective without any group
+    pub fn new() -> Self {
+        ObjectiveGroups::default()
+    }
+
+    /// Adds a group to the objective. Panics if a group with the same name already exists.
+    pub fn group<N: Into<String>, E: IntoAffineExpression>(
+        mut self,
+        name: N,
+        expression: E,
+        weight: f64,
+    ) -> Self {
+        let name = name.into();
+        assert!(
+            self.find(&name).is_none(),
+            "there is already a group named {:?}",
+            name
+        );
+        self.groups.push(Group {
+            name,
+            expression: Expression::from_other_affine(expression),
+            weight,
+        });
+        self
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.groups.iter().position(|group| group.name == name)
+    }
+
+    fn index(&self, name: &str) -> usize {
+        match self.find(name) {
+            Some(index) => index,
+            None => panic!("no objective group named {:?}", name),
+        }
+    }
+
+    /// The weight of a group. Panics if there is no such group.
+    pub fn weight(&self, name: &str) -> f64 {
+        self.groups[self.index(name)].weight
+    }
+
+    /// Changes the weight of a group. Panics if there is no such group.
+    pub fn set_weight(&mut self, name: &str, weight: f64) {
+        let index = self.index(name);
+        self.groups[index].weight = weight;
+    }
+
+    /// The names of the groups, in the order in which they were added
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.groups.iter().map(|group| group.name.as_str())
+    }
+
+    /// The weighted sum of the groups
+    pub fn objective(&self) -> Expression {
+        let mut objective = Expression::default();
+        for group in &self.groups {
+            objective.add_mul(group.weight, &group.expression);
+        }
+        objective
+    }
+
+    /// Creates a problem whose objective is the weighted sum of the groups
+    pub fn problem(
+        self,
+        variables: ProblemVariables,
+        direction: ObjectiveDirection,
+    ) -> WeightedProblem {
+        WeightedProblem {
+            problem: variables.optimise(direction, self.objective()),
+            groups: self,
+        }
+    }
+}
+
+/// A problem whose objective is made of weighted groups, created by [ObjectiveGroups::problem]
+#[derive(Clone)]
+pub struct WeightedProblem {
+    problem: UnsolvedProblem,
+    groups: ObjectiveGroups,
+}
+
+impl WeightedProblem {
+    /// Adds a constraint to the problem
+    pub fn with(mut self, constraint: Constraint) -> Self {
+        self.problem.add_constraint(constraint);
+        self
+    }
+
+    /// Adds a constraint to the problem
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        self.problem.add_constraint(constraint);
+    }
+
+    /// Prepares the problem to be solved, possibly several times, with the given solver
+    pub fn using<S: Solver + Clone>(self, solver: S) -> WeightedModel<S> {
+        WeightedModel {
+            problem: self.problem,
+            groups: self.groups,
+            solver,
+        }
+    }
+}
+
+/// A problem whose group weights can be changed between resolutions,
+/// created by [WeightedProblem::using]
+pub struct WeightedModel<S> {
+    problem: UnsolvedProblem,
+    groups: ObjectiveGroups,
+    solver: S,
+}
+
+impl<S: Solver + Clone> WeightedModel<S> {
+    /// The weight of a group. Panics if there is no such group.
+    pub fn group_weight(&self, name: &str) -> f64 {
+        self.groups.weight(name)
+    }
+
+    /// Changes the weight of a group for the next resolutions.
+    /// Panics if there is no such group.
+    pub fn set_group_weight(&mut self, name: &str, weight: f64) {
+        self.groups.set_weight(name, weight);
+        self.problem.objective = self.groups.objective();
+    }
+
+    /// Adds a constraint, kept for all the next resolutions
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        self.problem.add_constraint(constraint);
+    }
+
+    /// Solves the problem with the current weights
+    pub fn solve(
+        &mut self,
+    ) -> Result<
+        WeightedSolution<<S::Model as SolverModel>::Solution>,
+        <S::Model as SolverModel>::Error,
+    > {
+        let solution = self.problem.clone().using(self.solver.clone()).solve()?;
+        let group_values = self
+            .groups
+            .groups
+            .iter()
+            .map(|group| (group.name.clone(), solution.eval(&group.expression)))
+            .collect();
+        Ok(WeightedSolution {
+            solution,
+            group_values,
+        })
+    }
+}
+
+/// The solution of a [WeightedModel], with the value of each group of the objective
+pub struct WeightedSolution<T> {
+    solution: T,
+    group_values: Vec<(String, f64)>,
+}
+
+impl<T: Solution> WeightedSolution<T> {
+    /// The unweighted value of a group in this solution. Panics if there is no such group.
+    pub fn group_value(&self, name: &str) -> f64 {
+        match self.group_values.iter().find(|(group, _)| group == name) {
+            Some(&(_, value)) => value,
+            None => panic!("no objective group named {:?}", name),
+        }
+    }
+
+    /// The solution returned by the solver
+    pub fn inner(&self) -> &T {
+        &self.solution
+    }
+}
+
+impl<T: Solution> Solution for WeightedSolution<T> {
+    fn value(&self, variable: Variable) -> f64 {
+        self.solution.value(variable)
+    }
+
+    fn status(&self) -> SolutionStatus {
+        self.solution.status()
+    }
+
+    fn best_bound(&self) -> Option<f64> {
+        self.solution.best_bound()
+    }
+
+    fn mip_gap(&self) -> Option<f64> {
+        self.solution.mip_gap()
+    }
+
+    fn stats(&self) -> SolveStats {
+        self.solution.stats()
+    }
+}
@@ -10,8 +10,11 @@ use std::ops::{Div, Mul, Neg, Not, RangeBounds};
 use fnv::FnvHashMap as HashMap;
 
 use crate::affine_expression_trait::IntoAffineExpression;
+use crate::constraint::{ConstraintReference, StrictInequality};
 use crate::expression::{Expression, LinearExpression};
-use crate::solvers::{ObjectiveDirection, Solver};
+use crate::provenance::ModelMetadata;
+use crate::solvers::{ModelWithBulkAttributes, ObjectiveDirection, Solution, Solver, SolverModel};
+use crate::Constraint;
 
 /// A variable in a problem. Use variables to create [expressions](Expression),
 /// to express the [objective](ProblemVariables::optimise)
@@ -35,6 +38,8 @@ use crate::solvers::{ObjectiveDirection, Solver};
 /// assert_eq!(v1, v1_copy);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Variable {
     /// A variable is nothing more than an index into the `variables` field of a ProblemVariables
     /// That's why it can be `Copy`.
@@ -69,7 +74,7 @@ impl IntoAffineExpression for Option<Variable> {
     }
 }
 
-impl<'a> IntoAffineExpression for &'a Variable {
+impl IntoAffineExpression for &Variable {
     type Iter = std::iter::Once<(Variable, f64)>;
 
     #[inline]
@@ -79,8 +84,9 @@ impl<'a> IntoAffineExpression for &'a Variable {
 }
 
 impl Variable {
-    /// No one should use this method outside of [VariableDefinition]
-    fn at(index: usize) -> Self {
+    /// No one should use this method outside of [VariableDefinition],
+    /// and of the models that add variables to an existing problem
+    pub(crate) fn at(index: usize) -> Self {
         Self { index }
     }
 }
@@ -115,11 +121,32 @@ impl FormatWithVars for Variable {
 
 /// Defines the properties of a variable, such as its lower and upper bounds.
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariableDefinition {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_bound",
+            deserialize_with = "deserialize_min"
+        )
+    )]
     pub(crate) min: f64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_bound",
+            deserialize_with = "deserialize_max"
+        )
+    )]
     pub(crate) max: f64,
     pub(crate) name: String,
     pub(crate) is_integer: bool,
+    pub(crate) branching_priority: i32,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) domain: Option<Vec<f64>>,
 }
 
 impl VariableDefinition {
@@ -130,6 +157,8 @@ impl VariableDefinition {
             max: f64::INFINITY,
             name: String::new(),
             is_integer: false,
+            branching_priority: 0,
+            domain: None,
         }
     }
 
@@ -177,6 +206,169 @@ impl VariableDefinition {
         self
     }
 
+    /// Define the variable as a continuous fraction, between 0 and 1
+    ///
+    /// ```
+    /// # use good_lp::variable;
+    /// assert_eq!(variable().fraction(), variable().clamp(0, 1));
+    /// ```
+    pub fn fraction(self) -> Self {
+        self.clamp(0, 1)
+    }
+
+    /// Define the variable as a continuous percentage, between 0 and 100
+    ///
+    /// ```
+    /// # use good_lp::variable;
+    /// assert_eq!(variable().percentage(), variable().clamp(0, 100));
+    /// ```
+    pub fn percentage(self) -> Self {
+        self.clamp(0, 100)
+    }
+
+    /// Define the variable as a non-negative integer, such as a number of items
+    ///
+    /// ```
+    /// # use good_lp::variable;
+    /// assert_eq!(variable().nonneg_int(), variable().integer().min(0));
+    /// ```
+    pub fn nonneg_int(self) -> Self {
+        self.integer().min(0)
+    }
+
+    /// Remove the bounds of the variable, so that it can take any real value.
+    /// This is the default for new variables.
+    ///
+    /// ```
+    /// # use good_lp::variable;
+    /// assert_eq!(variable().min(0).free(), variable());
+    /// ```
+    pub fn free(self) -> Self {
+        self.min(f64::NEG_INFINITY).max(f64::INFINITY)
+    }
+
+    /// Restrict the variable to a finite set of values, such as the diameters of the pipes
+    /// or the sizes of the packs that can be bought.
+    /// The bounds of the variable are set to the smallest and largest values of the set.
+    ///
+    /// When the problem is created, with [ProblemVariables::optimise],
+    /// a binary variable is added for each value of the set that is within the bounds
+    /// of the variable, with the constraints that exactly one of them is 1,
+    /// and that the variable is equal to the value of that one.
+    ///
+    /// **Warning**: the binary variables make the problem a mixed integer problem,
+    /// so a solver that supports integer variables is needed.
+    ///
+    /// ```
+    /// # use good_lp::{variables, variable, default_solver, SolverModel, Solution, constraint};
+    /// let mut problem = variables!();
+    /// let pack = problem.add(variable().integer().domain(&[0, 5, 10, 25]));
+    /// if cfg!(not(any(feature = "minilp", feature = "highs"))) {
+    ///     let solution = problem
+    ///         .minimise(pack)
+    ///         .using(default_solver)
+    ///         .with(constraint!(pack >= 7))
+    ///         .solve()
+    ///         .unwrap();
+    ///     # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+    ///     assert_float_eq(solution.value(pack), 10.);
+    /// }
+    /// ```
+    pub fn domain<N: Into<f64> + Copy>(mut self, values: &[N]) -> Self {
+        let mut values: Vec<f64> = values.iter().map(|&value| value.into()).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        values.dedup();
+        if let (Some(&min), Some(&max)) = (values.first(), values.last()) {
+            self.min = min;
+            self.max = max;
+        }
+        self.domain = Some(values);
+        self
+    }
+
+    /// The values the variable is restricted to, if any. See [VariableDefinition::domain]
+    pub fn get_domain(&self) -> Option<&[f64]> {
+        self.domain.as_deref()
+    }
+
+    /// The values of the domain of the variable that are within its bounds,
+    /// and that are integers if the variable is an integer
+    fn allowed_values(&self) -> Option<Vec<f64>> {
+        let domain = self.domain.as_ref()?;
+        let allowed = domain
+            .iter()
+            .copied()
+            .filter(|&value| self.min <= value && value <= self.max)
+            .filter(|&value| !self.is_integer || value.fract() == 0.)
+            .collect();
+        Some(allowed)
+    }
+
+    /// Checks that the bounds of the variable can be satisfied:
+    /// they must not be NaN, the lower bound must not be above the upper bound,
+    /// and an integer variable must have an integer value between its bounds.
+    /// A variable [restricted to a set of values](VariableDefinition::domain)
+    /// must have one of these values between its bounds.
+    ///
+    /// ```
+    /// # use good_lp::variable;
+    /// # use good_lp::variable::BoundsError;
+    /// assert_eq!(variable().fraction().validate(), Ok(()));
+    /// assert_eq!(
+    ///     variable().min(3).max(1).validate(),
+    ///     Err(BoundsError::Crossed { min: 3., max: 1. })
+    /// );
+    /// assert_eq!(
+    ///     variable().integer().clamp(0.2, 0.8).validate(),
+    ///     Err(BoundsError::NoInteger { min: 0.2, max: 0.8 })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), BoundsError> {
+        let (min, max) = (self.min, self.max);
+        if min.is_nan() || max.is_nan() {
+            Err(BoundsError::NaN)
+        } else if min > max {
+            Err(BoundsError::Crossed { min, max })
+        } else if self.is_integer && min.ceil() > max.floor() {
+            Err(BoundsError::NoInteger { min, max })
+        } else if self
+            .allowed_values()
+            .is_some_and(|values| values.is_empty())
+        {
+            Err(BoundsError::EmptyDomain { min, max })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Set the branching priority of an integer variable.
+    /// When branch-and-bound has to choose a fractional variable to branch on,
+    /// variables with a higher priority are chosen first. The default priority is 0.
+    ///
+    /// This is only a hint: solvers that don't support branching priorities ignore it.
+    /// Priorities are given to the Cbc binary by the `CbcWithPriorities` solver
+    /// of the `lp-solvers` feature, and they are exported with the problem by
+    /// [write_priorities](crate::formats::mps::write_priorities),
+    /// for the solvers that read priority files, such as CPLEX.
+    /// They can be read back with [VariableDefinition::get_branching_priority].
+    ///
+    /// ```
+    /// # use good_lp::{ProblemVariables, variable};
+    /// let mut problem = ProblemVariables::new();
+    /// let open_warehouse = problem.add(variable().binary().branching_priority(10));
+    /// let (_, definition) = problem.iter_variables_with_def().next().unwrap();
+    /// assert_eq!(definition.get_branching_priority(), 10);
+    /// ```
+    pub fn branching_priority(mut self, priority: i32) -> Self {
+        self.branching_priority = priority;
+        self
+    }
+
+    /// The branching priority of the variable. See [VariableDefinition::branching_priority]
+    pub fn get_branching_priority(&self) -> i32 {
+        self.branching_priority
+    }
+
     /// Set the name of the variable. This is useful in particular when displaying the problem
     /// for debugging purposes.
     ///
@@ -191,6 +383,11 @@ impl VariableDefinition {
         self
     }
 
+    /// The name of the variable, or an empty string if it is anonymous
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
     /// Set the lower and/or higher bounds of the variable
     ///
     /// ## Examples
@@ -243,6 +440,58 @@ impl VariableDefinition {
     }
 }
 
+/// The reason why the bounds of a variable cannot be satisfied,
+/// returned by [VariableDefinition::validate]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundsError {
+    /// One of the bounds is NaN
+    NaN,
+    /// The lower bound is above the upper bound
+    Crossed {
+        /// The lower bound
+        min: f64,
+        /// The upper bound
+        max: f64,
+    },
+    /// The variable is an integer, but there is no integer between its bounds
+    NoInteger {
+        /// The lower bound
+        min: f64,
+        /// The upper bound
+        max: f64,
+    },
+    /// The variable is restricted to a set of values, but none of them is between its bounds
+    EmptyDomain {
+        /// The lower bound
+        min: f64,
+        /// The upper bound
+        max: f64,
+    },
+}
+
+impl Display for BoundsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundsError::NaN => write!(f, "a bound of the variable is NaN"),
+            BoundsError::Crossed { min, max } => write!(
+                f,
+                "the lower bound {} is above the upper bound {}",
+                min, max
+            ),
+            BoundsError::NoInteger { min, max } => {
+                write!(f, "there is no integer between {} and {}", min, max)
+            }
+            BoundsError::EmptyDomain { min, max } => write!(
+                f,
+                "no value of the domain of the variable is between {} and {}",
+                min, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoundsError {}
+
 /// Creates an unbounded continuous linear variable
 impl Default for VariableDefinition {
     fn default() -> Self {
@@ -259,7 +508,9 @@ pub fn variable() -> VariableDefinition {
 /// Each problem has a unique type, which prevents using the variables
 /// from one problem inside an other one.
 /// Instances of this type should be created exclusively using the [variables!] macro.
-#[derive(Default)]
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct ProblemVariables {
     variables: Vec<VariableDefinition>,
 }
@@ -327,7 +578,7 @@ impl ProblemVariables {
     /// assert_eq!(solve(ObjectiveDirection::Maximisation), 3.);
     /// ```
     pub fn optimise<E: IntoAffineExpression>(
-        self,
+        mut self,
         direction: ObjectiveDirection,
         objective: E,
     ) -> UnsolvedProblem {
@@ -337,11 +588,47 @@ impl ProblemVariables {
             "There should not be more variables in the objective function than in the problem. \
             You probably used variables from a different problem in this one."
         );
+        let constraints = self.domain_constraints();
         UnsolvedProblem {
             objective,
             direction,
             variables: self,
+            constraints,
+            metadata: None,
+            strict_inequality: None,
+        }
+    }
+
+    /// Adds a binary variable for each allowed value of the variables
+    /// that are [restricted to a set of values](VariableDefinition::domain),
+    /// and returns the constraints that select one of the values
+    fn domain_constraints(&mut self) -> Vec<Constraint> {
+        let mut constraints = vec![];
+        for index in 0..self.variables.len() {
+            let definition = &self.variables[index];
+            let values = match definition.allowed_values() {
+                Some(values) => values,
+                None => continue,
+            };
+            let name = definition.name.clone();
+            // The domain is expanded once, even if the variables are optimised again
+            self.variables[index].domain = None;
+            let restricted = Variable::at(index);
+            let mut selected = Expression::default();
+            let mut value_of_selected = Expression::default();
+            for value in values {
+                let mut binary = variable().binary();
+                if !name.is_empty() {
+                    binary = binary.name(format!("{}_is_{}", name, value));
+                }
+                let binary = self.add(binary);
+                selected.add_mul(1, binary);
+                value_of_selected.add_mul(value, binary);
+            }
+            constraints.push(selected.eq(1));
+            constraints.push(value_of_selected.eq(restricted));
         }
+        constraints
     }
 
     /// Creates an maximization problem with the given objective. Don't solve it immediately
@@ -375,6 +662,23 @@ impl ProblemVariables {
             .map(|(i, def)| (Variable::at(i), def))
     }
 
+    /// Sets both bounds of the variable to the given value
+    pub(crate) fn fix(&mut self, variable: Variable, value: f64) {
+        let definition = &mut self.variables[variable.index()];
+        definition.min = value;
+        definition.max = value;
+    }
+
+    /// The definition of the variable
+    pub(crate) fn definition(&self, variable: Variable) -> &VariableDefinition {
+        &self.variables[variable.index()]
+    }
+
+    /// Makes the variable continuous, keeping its bounds
+    pub(crate) fn relax(&mut self, variable: Variable) {
+        self.variables[variable.index()].is_integer = false;
+    }
+
     /// The number of variables
     pub fn len(&self) -> usize {
         self.variables.len()
@@ -430,18 +734,252 @@ impl IntoIterator for ProblemVariables {
     }
 }
 
-/// A problem without constraints.
+/// A problem that has not been given to a solver yet.
 /// Created with [ProblemVariables::optimise].
+///
+/// Constraints are usually added to the solver model returned by [UnsolvedProblem::using],
+/// but they can also be added here, to get a solver-independent description of the whole problem,
+/// that can for instance be [exported to a file](UnsolvedProblem::to_mps).
+///
+/// With the `serde` cargo feature, problems can be serialized, to be cached or sent to a worker.
+/// Deserialization fails if the objective or a constraint uses a variable
+/// that is not part of the problem.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UnsolvedProblemSnapshot"))]
 pub struct UnsolvedProblem {
     pub(crate) objective: Expression,
     pub(crate) direction: ObjectiveDirection,
     pub(crate) variables: ProblemVariables,
+    pub(crate) constraints: Vec<Constraint>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) metadata: Option<ModelMetadata>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) strict_inequality: Option<StrictInequality>,
 }
 
 impl UnsolvedProblem {
-    /// Create a solver instance and feed it with this problem
-    pub fn using<S: Solver>(self, mut solver: S) -> S::Model {
-        solver.create_model(self)
+    /// Create a solver instance and feed it with this problem,
+    /// including the constraints that were added to it
+    pub fn using<S: Solver>(mut self, mut solver: S) -> S::Model {
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        let start = std::time::Instant::now();
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        let variable_count = self.variables.len();
+        let constraints = std::mem::take(&mut self.constraints);
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        let constraint_count = constraints.len();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            target: "good_lp",
+            "build_model",
+            variables = variable_count,
+            constraints = constraint_count
+        )
+        .entered();
+        let mut model = solver.create_model(self);
+        for constraint in constraints {
+            model.add_constraint(constraint);
+        }
+        #[cfg(feature = "log")]
+        log::debug!(
+            target: "good_lp",
+            "built a model with {} variables and {} constraints in {:?}",
+            variable_count,
+            constraint_count,
+            start.elapsed()
+        );
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "good_lp", elapsed = ?start.elapsed(), "model built");
+        model
+    }
+
+    /// Adds a constraint to the problem, before it is given to a solver
+    ///
+    /// ```
+    /// use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+    /// variables! {vars: x <= 10;}
+    /// let problem = vars.maximise(x).with(constraint!(x <= 3));
+    /// let solution = problem.using(default_solver).solve()?;
+    /// assert_eq!(solution.value(x), 3.);
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    pub fn with(mut self, constraint: Constraint) -> Self {
+        self.add_constraint(constraint);
+        self
+    }
+
+    /// The variables of the problem
+    pub fn variables(&self) -> &ProblemVariables {
+        &self.variables
+    }
+
+    /// Adds a constraint to the problem and returns a reference to it.
+    /// The reference stays valid in the solver model created with [UnsolvedProblem::using].
+    ///
+    /// If the constraint is a strict inequality, and they were forbidden with
+    /// [UnsolvedProblem::set_strict_inequality], the resolution of the problem fails.
+    pub fn add_constraint(&mut self, mut constraint: Constraint) -> ConstraintReference {
+        if let Some(StrictInequality::EpsilonRelax(epsilon)) = self.strict_inequality {
+            constraint = constraint.set_strict_epsilon(epsilon);
+        }
+        let index = self.constraints.len();
+        self.constraints.push(constraint);
+        ConstraintReference { index }
+    }
+
+    /// Adds a variable, with its coefficients in the objective and in existing constraints.
+    /// Used by the models that keep the problem as it is, see [crate::ModelWithColumns].
+    pub(crate) fn add_column(
+        &mut self,
+        definition: VariableDefinition,
+        objective_coefficient: f64,
+        coefficients: &[(ConstraintReference, f64)],
+    ) -> Variable {
+        let variable = self.variables.add(definition);
+        self.objective.add_mul(objective_coefficient, variable);
+        for (constraint, coefficient) in coefficients {
+            self.constraints[constraint.index]
+                .expression
+                .add_mul(*coefficient, variable);
+        }
+        variable
+    }
+
+    /// Sets the margin of all the strict inequalities of the problem,
+    /// including the ones that are added later, to the problem
+    /// or to the solver model created from it.
+    /// See [DEFAULT_STRICT_EPSILON](crate::constraint::DEFAULT_STRICT_EPSILON).
+    ///
+    /// ```
+    /// use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+    /// variables! {vars: x <= 10;}
+    /// let mut problem = vars.maximise(x).with(constraint!(x < 3));
+    /// problem.set_strict_epsilon(0.5);
+    /// let solution = problem.using(default_solver).solve()?;
+    /// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-9, "{} != {}", a, b); }
+    /// assert_float_eq(solution.value(x), 2.5);
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    pub fn set_strict_epsilon(&mut self, epsilon: f64) {
+        self.set_strict_inequality(StrictInequality::EpsilonRelax(epsilon));
+    }
+
+    /// Sets how the strict inequalities of the problem are handled,
+    /// including the ones that are added later, to the problem
+    /// or to the solver model created from it.
+    ///
+    /// When strict inequalities are forbidden, the resolution fails if the problem,
+    /// or its solver model, contains one.
+    /// Use [Constraint::with_strict_policy] to check a single constraint.
+    ///
+    /// ```
+    /// use good_lp::constraint::StrictInequality;
+    /// use good_lp::{constraint, default_solver, variables, SolverModel};
+    /// variables! {vars: x <= 10;}
+    /// let mut problem = vars.minimise(x);
+    /// problem.set_strict_inequality(StrictInequality::Error);
+    /// problem.add_constraint(constraint!(x >= 1)); // fine
+    /// let model = problem.using(default_solver).with(constraint!(x > 1));
+    /// assert!(model.solve().is_err());
+    /// ```
+    pub fn set_strict_inequality(&mut self, policy: StrictInequality) {
+        self.strict_inequality = Some(policy);
+        let constraints = std::mem::take(&mut self.constraints);
+        for constraint in constraints {
+            self.add_constraint(constraint);
+        }
+    }
+
+    /// Fixes the variables for which `predicate` returns true to their value in `solution`,
+    /// by setting both of their bounds to that value. Values of integer variables are rounded.
+    /// Returns the number of variables that were fixed.
+    ///
+    /// This is useful when re-planning: decisions that were already carried out,
+    /// such as the ones of past periods, are locked, and only the others can change.
+    ///
+    /// ```
+    /// use good_lp::{constraint, default_solver, variable, variables, Solution, SolverModel};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut vars = variables!();
+    /// let production: Vec<_> = (0..4)
+    ///     .map(|period| vars.add(variable().clamp(0, 10).name(format!("produce_{}", period))))
+    ///     .collect();
+    /// let total: good_lp::Expression = production.iter().sum();
+    /// let mut problem = vars.minimise(total.clone()).with(constraint!(total.clone() >= 20));
+    /// // The plan carried out so far
+    /// let plan: HashMap<_, _> = production.iter().copied().zip(vec![8., 7., 0., 5.]).collect();
+    /// let today = 2;
+    /// let frozen = problem.freeze_where(&plan, |_, def| {
+    ///     let period: usize = def.get_name()["produce_".len()..].parse().unwrap();
+    ///     period < today
+    /// });
+    /// assert_eq!(frozen, 2);
+    /// let solution = problem.using(default_solver).solve()?;
+    /// # fn assert_float_eq(a:f64, b:f64) { assert!((a-b).abs() <= 1e-6, "{} != {}", a, b); }
+    /// assert_float_eq(solution.value(production[0]), 8.);
+    /// assert_float_eq(solution.value(production[1]), 7.);
+    /// assert_float_eq(solution.eval(&total), 20.);
+    /// # Ok::<_, good_lp::ResolutionError>(())
+    /// ```
+    pub fn freeze_where<S, F>(&mut self, solution: &S, mut predicate: F) -> usize
+    where
+        S: Solution,
+        F: FnMut(Variable, &VariableDefinition) -> bool,
+    {
+        let mut frozen = 0;
+        for (i, def) in self.variables.variables.iter_mut().enumerate() {
+            let var = Variable::at(i);
+            if !predicate(var, def) {
+                continue;
+            }
+            let mut value = solution.value(var);
+            if def.is_integer {
+                value = value.round();
+            }
+            def.min = value;
+            def.max = value;
+            frozen += 1;
+        }
+        frozen
+    }
+}
+
+impl ModelWithBulkAttributes for UnsolvedProblem {
+    fn get_bounds_bulk(&self, variables: &[Variable]) -> Vec<(f64, f64)> {
+        variables
+            .iter()
+            .map(|v| {
+                let definition = &self.variables.variables[v.index()];
+                (definition.min, definition.max)
+            })
+            .collect()
+    }
+
+    fn set_bounds_bulk(&mut self, bounds: &[(Variable, f64, f64)]) {
+        for &(variable, min, max) in bounds {
+            let definition = &mut self.variables.variables[variable.index()];
+            definition.min = min;
+            definition.max = max;
+        }
+    }
+
+    fn get_objective_coeffs_bulk(&self, variables: &[Variable]) -> Vec<f64> {
+        let coefficients = &self.objective.linear.coefficients;
+        variables
+            .iter()
+            .map(|v| coefficients.get(v).copied().unwrap_or(0.))
+            .collect()
+    }
+
+    fn set_objective_coeffs_bulk(&mut self, coefficients: &[(Variable, f64)]) {
+        for &(variable, coefficient) in coefficients {
+            self.objective
+                .linear
+                .coefficients
+                .insert(variable, coefficient);
+        }
     }
 }
 
@@ -521,3 +1059,69 @@ impl Not for Variable {
         1. - self
     }
 }
+
+/// Infinite bounds are serialized as `null`, since many formats (such as JSON)
+/// cannot represent infinite numbers
+#[cfg(feature = "serde")]
+fn serialize_bound<S: serde::Serializer>(bound: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    let finite = Some(*bound).filter(|b| b.is_finite());
+    serde::Serialize::serialize(&finite, serializer)
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_min<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let bound: Option<f64> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(bound.unwrap_or(f64::NEG_INFINITY))
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_max<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let bound: Option<f64> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(bound.unwrap_or(f64::INFINITY))
+}
+
+/// The serialized form of an [UnsolvedProblem], that is validated before being used
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UnsolvedProblemSnapshot {
+    objective: Expression,
+    direction: ObjectiveDirection,
+    variables: ProblemVariables,
+    constraints: Vec<Constraint>,
+    #[serde(default)]
+    metadata: Option<ModelMetadata>,
+    #[serde(default)]
+    strict_inequality: Option<StrictInequality>,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<UnsolvedProblemSnapshot> for UnsolvedProblem {
+    type Error = String;
+
+    fn try_from(snapshot: UnsolvedProblemSnapshot) -> Result<Self, Self::Error> {
+        let n_variables = snapshot.variables.len();
+        let expressions = std::iter::once(&snapshot.objective)
+            .chain(snapshot.constraints.iter().map(|c| &c.expression));
+        for expression in expressions {
+            if let Some(var) = expression
+                .linear
+                .coefficients
+                .keys()
+                .find(|var| var.index >= n_variables)
+            {
+                return Err(format!(
+                    "variable {} is used, but the problem has only {} variables",
+                    var.index, n_variables
+                ));
+            }
+        }
+        Ok(UnsolvedProblem {
+            objective: snapshot.objective,
+            direction: snapshot.direction,
+            variables: snapshot.variables,
+            constraints: snapshot.constraints,
+            metadata: snapshot.metadata,
+            strict_inequality: snapshot.strict_inequality,
+        })
+    }
+}
@@ -30,6 +30,20 @@
 /// let objective = x[0] + x[1] - x[2];
 /// ```
 ///
+/// ### Creating a family of variables indexed by the elements of a set
+///
+/// `x[key in set]` creates one variable per element of `set`, which can be any value
+/// that implements [IntoIterator], and stores them in a [HashMap](std::collections::HashMap)
+/// indexed by the elements.
+///
+/// ```
+/// use good_lp::{constraint, variables};
+/// const FOODS: [&str; 3] = ["bread", "milk", "rice"];
+/// variables!{vars: 0 <= quantity[food in FOODS] <= 10; }
+/// let at_most_two_breads = constraint!(quantity["bread"] <= 2);
+/// assert_eq!(quantity.len(), 3);
+/// ```
+///
 /// ### Creating integer variables
 ///
 /// ```
@@ -122,7 +136,7 @@ macro_rules! variables {
     $(
         $($min:literal <= )?
         $var_name:ident
-        $([$length:expr])?
+        $([$($index:tt)+])?
         $(($qualifier:tt))?
         $(<= $max:expr;)?
         $(>= $postfix_min:expr;)?
@@ -138,10 +152,15 @@ macro_rules! variables {
                                 $(.max($max))*
                                 $(.min($postfix_min))*
                                 $(.$qualifier())*;
-                    $crate::variables!(@add_variable, $vars, var_def, $($length)*)
+                    $crate::variables!(@add_variable, $vars, var_def, $($($index)+)?)
                 };
             )*
         };
+    (@add_variable, $vars:expr, $var:expr, $key:ident in $set:expr) => {
+        ::std::iter::IntoIterator::into_iter($set)
+            .map(|$key| ($key, $vars.add($var.clone())))
+            .collect::<::std::collections::HashMap<_, _>>()
+    };
     (@add_variable, $vars:expr, $var:expr, $length:expr) => {
         $vars.add_vector($var, $length)
     };
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use good_lp::analysis::{describe, histogram};
+use good_lp::{variable, variables, Variable};
+
+fn solution(values: &[f64]) -> (Vec<Variable>, HashMap<Variable, f64>) {
+    let mut vars = variables!();
+    let variables = vars.add_vector(variable(), values.len());
+    let solution = variables
+        .iter()
+        .copied()
+        .zip(values.iter().copied())
+        .collect();
+    (variables, solution)
+}
+
+#[test]
+fn histogram_bins() {
+    let (vars, solution) = solution(&[0., 1., 2., 3., 4., 4., 10.]);
+    let histogram = histogram(&solution, &vars, 5);
+    assert_eq!(histogram.counts, vec![2, 2, 2, 0, 1]);
+    assert_eq!(histogram.bin_width(), 2.);
+    let bins: Vec<_> = histogram.bins().collect();
+    assert_eq!(bins[0], (0., 2.));
+    assert_eq!(bins[4], (8., 10.));
+}
+
+#[test]
+fn histogram_of_equal_values() {
+    let (vars, solution) = solution(&[3., 3., 3.]);
+    let histogram = histogram(&solution, &vars, 4);
+    assert_eq!(histogram.counts, vec![3, 0, 0, 0]);
+}
+
+#[test]
+fn statistics() {
+    let (vars, solution) = solution(&[2., 4., 4., 4., 5., 5., 7., 9.]);
+    let stats = describe(&solution, &vars);
+    assert_eq!(stats.count, 8);
+    assert_eq!(stats.sum, 40.);
+    assert_eq!((stats.min, stats.max), (2., 9.));
+    assert_eq!(stats.mean, 5.);
+    assert_eq!(stats.median, 4.5);
+    assert_eq!(stats.std_dev, 2.);
+    let stats = describe(&solution, &vars[..3]);
+    assert_eq!(stats.median, 4.);
+}
+
+#[test]
+fn compare_solutions_from_several_solvers() {
+    use good_lp::analysis::{compare_solutions, objective_close};
+    use good_lp::Expression;
+
+    let (vars, reference) = solution(&[1., 2., 3.]);
+    let close: HashMap<Variable, f64> = vars.iter().copied().zip(vec![1., 2., 3. + 1e-9]).collect();
+    let different: HashMap<Variable, f64> = vars.iter().copied().zip(vec![1.5, 2., 2.]).collect();
+    let objective: Expression = vars.iter().sum();
+
+    let comparison = compare_solutions(&[&reference, &close], &vars, &objective, 1e-6, 1e-6);
+    assert!(comparison.objectives_close);
+    assert!(comparison.max_deviation < 1e-6);
+
+    let comparison = compare_solutions(
+        &[&reference, &close, &different],
+        &vars,
+        &objective,
+        1e-6,
+        1e-6,
+    );
+    assert!(!comparison.objectives_close);
+    assert_eq!(comparison.objectives[2], 5.5);
+    assert_eq!(comparison.max_deviation, 1.);
+    assert_eq!(comparison.max_deviation_variable, Some(vars[2]));
+    assert_eq!(comparison.max_deviation_solution, Some(2));
+    assert!(objective_close(f64::INFINITY, f64::INFINITY, 0., 0.));
+}
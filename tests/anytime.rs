@@ -0,0 +1,38 @@
+#![cfg(feature = "coin_cbc")]
+use std::time::Duration;
+
+use float_eq::assert_float_eq;
+use good_lp::anytime::solve_anytime;
+use good_lp::{coin_cbc, constraint, variable, variables, ResolutionError, Solution};
+
+#[test]
+fn improvements_are_reported_in_order() {
+    let mut vars = variables!();
+    let items: Vec<_> = (0..12).map(|_| vars.add(variable().binary())).collect();
+    let weights = [23., 31., 29., 44., 53., 38., 63., 85., 89., 82., 71., 57.];
+    let values = [92., 57., 49., 68., 60., 43., 67., 84., 87., 72., 66., 51.];
+    let value: good_lp::Expression = items.iter().zip(&values).map(|(&x, &v)| v * x).sum();
+    let weight: good_lp::Expression = items.iter().zip(&weights).map(|(&x, &w)| w * x).sum();
+    let problem = vars.maximise(value).with(constraint!(weight <= 165));
+    let mut objectives = vec![];
+    let solution = solve_anytime(problem, coin_cbc, Duration::from_secs(20), |incumbent| {
+        objectives.push(incumbent.objective())
+    })
+    .unwrap();
+    assert!(solution.status().is_optimal());
+    assert!(objectives.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_float_eq!(
+        *objectives.last().unwrap(),
+        solution.objective(),
+        abs <= 1e-9
+    );
+}
+
+#[test]
+fn infeasible_problems_are_reported() {
+    let mut vars = variables!();
+    let x = vars.add(variable().integer().clamp(0, 3));
+    let problem = vars.maximise(x).with(constraint!(x >= 5));
+    let result = solve_anytime(problem, coin_cbc, Duration::from_secs(5), |_| {});
+    assert_eq!(result.err(), Some(ResolutionError::Infeasible));
+}
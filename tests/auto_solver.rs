@@ -0,0 +1,73 @@
+use good_lp::solvers::auto::{AutoSolver, Backend};
+use good_lp::{auto_solver, constraint, variable, variables, Solution, SolverModel};
+
+#[test]
+fn solves_a_linear_problem() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 2));
+    let y = vars.add(variable().clamp(1, 3));
+    let solution = vars
+        .maximise(x + y)
+        .using(auto_solver)
+        .with(constraint!(2 * x + y <= 4))
+        .solve()
+        .unwrap();
+    assert!((solution.value(x) - 0.5).abs() < 1e-6);
+    assert!((solution.value(y) - 3.).abs() < 1e-6);
+}
+
+#[test]
+fn integer_problems_go_to_a_mip_solver() {
+    let mut vars = variables!();
+    let x = vars.add(variable().integer().max(2.5));
+    let problem = vars.maximise(x);
+    let backend = AutoSolver::new().choose(&problem);
+    let expected = Backend::available()
+        .into_iter()
+        .find(Backend::supports_integers)
+        .unwrap_or(backend);
+    assert_eq!(backend, expected);
+}
+
+#[test]
+fn the_selector_overrides_the_choice() {
+    let last = *Backend::available().last().unwrap();
+    let mut vars = variables!();
+    let x = vars.add(variable().max(1));
+    let solver = AutoSolver::new().with_selector(move |classification| {
+        assert_eq!(classification.variables, 1);
+        Some(last)
+    });
+    let model = vars.maximise(x).using(solver);
+    assert_eq!(model.backend(), last);
+    let solution = model.solve().unwrap();
+    assert!((solution.value(x) - 1.).abs() < 1e-6);
+}
+
+#[test]
+fn the_default_is_used_when_the_selector_returns_none() {
+    let mut vars = variables!();
+    vars.add(variable().max(1));
+    let problem = vars.maximise(0);
+    let classification = problem.classify();
+    let backend = AutoSolver::new().with_selector(|_| None).choose(&problem);
+    assert_eq!(backend, Backend::select(&classification));
+}
+
+#[test]
+fn memory_estimates_grow_with_the_problem() {
+    let problem = |n: usize| {
+        let mut vars = variables!();
+        let x = vars.add_vector(variable().min(0), n);
+        let total: good_lp::Expression = x.iter().sum();
+        vars.maximise(total.clone()).with(constraint!(total <= 10))
+    };
+    let (small, large) = (problem(10), problem(1000));
+    assert_eq!(small.nonzeros(), 20);
+    for backend in Backend::available() {
+        let small_bytes = small.estimated_backend_memory(backend);
+        let large_bytes = large.estimated_backend_memory(backend);
+        assert!(small_bytes > 0);
+        assert!(large_bytes > 50 * small_bytes, "{:?}", backend);
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use float_eq::assert_float_eq;
+use good_lp::batch::{collect, run_worker, submit, DirectoryQueue, JobQueue};
+use good_lp::{constraint, default_solver, variable, variables, Solution};
+
+#[test]
+fn jobs_are_solved_once() {
+    let directory = std::env::temp_dir().join("good_lp_batch_test");
+    let _ = std::fs::remove_dir_all(&directory);
+    let queue = DirectoryQueue::new(&directory).unwrap();
+    let mut jobs = vec![];
+    let mut variables = vec![];
+    for day in 0..5 {
+        let mut vars = variables!();
+        let unused = vars.add(variable().name("unused").clamp(-1, 1));
+        let stock = vars.add(variable().name("stock").clamp(0, 100));
+        let problem = vars
+            .minimise(stock - unused)
+            .with(constraint!(stock >= day));
+        let job = format!("day-{}", day);
+        submit(&queue, &job, &problem).unwrap();
+        jobs.push(job);
+        variables.push((unused, stock));
+    }
+    assert_eq!(run_worker(&queue, default_solver).unwrap(), 5);
+    assert_eq!(queue.claim().unwrap(), None);
+    let names: Vec<&str> = jobs.iter().map(String::as_str).collect();
+    let results = collect(&queue, &names, Duration::from_millis(10)).unwrap();
+    for (day, (job, (unused, stock))) in jobs.iter().zip(variables).enumerate() {
+        let solution = results[job].as_ref().unwrap();
+        assert_float_eq!(solution.value(unused), 1., abs <= 1e-6);
+        assert_float_eq!(solution.value(stock), day as f64, abs <= 1e-6);
+        assert_float_eq!(solution.objective(), day as f64 - 1., abs <= 1e-6);
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn invalid_models_are_reported() {
+    let directory = std::env::temp_dir().join("good_lp_batch_invalid_test");
+    let _ = std::fs::remove_dir_all(&directory);
+    let queue = DirectoryQueue::new(&directory).unwrap();
+    queue
+        .submit("broken", b"COLUMNS\n    x  OBJ  one\n")
+        .unwrap();
+    assert_eq!(run_worker(&queue, default_solver).unwrap(), 1);
+    let results = collect(&queue, &["broken"], Duration::from_millis(10)).unwrap();
+    assert!(results["broken"].is_err());
+    assert!(queue.submit("../escape", b"").is_err());
+    std::fs::remove_dir_all(&directory).unwrap();
+}
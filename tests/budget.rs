@@ -0,0 +1,52 @@
+use float_eq::assert_float_eq;
+use good_lp::budget::{BudgetAllocation, Channel};
+use good_lp::default_solver;
+
+#[test]
+fn spend_goes_to_the_highest_marginal_returns() {
+    let allocation = BudgetAllocation::new(150.)
+        .channel(Channel::saturating("tv", 1000., 100., 300., 30))
+        .channel(Channel::saturating("radio", 300., 20., 300., 30))
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(allocation.total_spend(), 150., abs <= 1e-6);
+    // At the optimum, the marginal returns of both channels are close
+    let marginal = |max: f64, half: f64, spend: f64| max * half / (spend + half).powi(2);
+    let tv = marginal(1000., 100., allocation.spend(0));
+    let radio = marginal(300., 20., allocation.spend(1));
+    assert!((tv - radio).abs() < 0.5, "{} {}", tv, radio);
+}
+
+#[test]
+fn min_and_max_spend() {
+    let allocation = BudgetAllocation::new(100.)
+        .channel(Channel::new("a", vec![(0., 0.), (100., 300.)]).max_spend(30.))
+        .channel(Channel::new("b", vec![(0., 0.), (100., 100.)]).min_spend(5.))
+        .channel(Channel::new("c", vec![(0., 0.), (100., 10.)]))
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(allocation.spend(0), 30., abs <= 1e-6);
+    assert_float_eq!(allocation.spend(1), 70., abs <= 1e-6);
+    assert_float_eq!(allocation.spend(2), 0., abs <= 1e-6);
+    assert_float_eq!(allocation.response(1), 70., abs <= 1e-6);
+}
+
+#[test]
+fn spend_all_the_budget() {
+    let allocation = BudgetAllocation::new(120.)
+        .channel(Channel::new(
+            "flat",
+            vec![(0., 0.), (50., 50.), (200., 50.)],
+        ))
+        .spend_all()
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(allocation.total_spend(), 120., abs <= 1e-6);
+    assert_float_eq!(allocation.total_response(), 50., abs <= 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "concave")]
+fn convex_curves_are_rejected() {
+    Channel::new("convex", vec![(0., 0.), (10., 1.), (20., 10.)]);
+}
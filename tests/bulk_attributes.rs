@@ -0,0 +1,36 @@
+//! The bounds and objective coefficients changed in bulk on a built model
+//! are the ones used by the solver.
+use float_eq::assert_float_eq;
+use good_lp::{constraint, variables, ModelWithBulkAttributes, Solution, Solver, SolverModel};
+
+#[allow(dead_code)]
+fn bounds_and_costs_reach_the_solver<S>(solver: S)
+where
+    S: Solver,
+    S::Model: ModelWithBulkAttributes,
+{
+    variables! {vars: 0 <= x <= 2; 0 <= y <= 5;}
+    let mut model = vars
+        .maximise(x + 3 * y)
+        .using(solver)
+        .with(constraint!(x + y <= 4));
+    model.set_bounds_bulk(&[(x, 1., 4.), (y, 0., 2.)]);
+    model.set_objective_coeffs_bulk(&[(y, -1.)]);
+    assert_eq!(model.get_bounds_bulk(&[x, y]), vec![(1., 4.), (0., 2.)]);
+    assert_eq!(model.get_objective_coeffs_bulk(&[x, y]), vec![1., -1.]);
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.value(x), 4., abs <= 1e-6);
+    assert_float_eq!(solution.value(y), 0., abs <= 1e-6);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn coin_cbc() {
+    bounds_and_costs_reach_the_solver(good_lp::coin_cbc)
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn lp_solve() {
+    bounds_and_costs_reach_the_solver(good_lp::lp_solve)
+}
@@ -0,0 +1,99 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use good_lp::cancellation::{solve_until_cancelled, solve_with_cancellation, CancellationToken};
+use good_lp::constraint::ConstraintReference;
+use good_lp::{
+    default_solver, variables, Constraint, ModelWithTimeLimit, ResolutionError, Solution,
+    SolutionStatus, SolverModel, Variable,
+};
+
+#[test]
+fn solves_when_not_cancelled() {
+    variables! {vars: 0 <= x <= 3;}
+    let token = CancellationToken::new();
+    let solution = solve_with_cancellation(vars.maximise(x), default_solver, &token).unwrap();
+    assert!((solution.value(x) - 3.).abs() < 1e-6);
+    assert_eq!(solution.status(), SolutionStatus::Optimal);
+}
+
+#[test]
+fn cancel_running_resolution() {
+    variables! {vars: 0 <= x <= 3;}
+    let slow_solver = |problem| {
+        thread::sleep(Duration::from_secs(5));
+        default_solver(problem)
+    };
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        canceller.cancel();
+    });
+    let start = Instant::now();
+    let result = solve_with_cancellation(vars.maximise(x), slow_solver, &token);
+    assert_eq!(
+        result.err(),
+        Some(ResolutionError::Other("the resolution was cancelled"))
+    );
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+/// A model that uses all of its time limit, and never proves that its solution is optimal
+struct Unproved<M> {
+    model: M,
+    limit: Duration,
+}
+
+struct UnprovedSolution<S>(S);
+
+impl<S: Solution> Solution for UnprovedSolution<S> {
+    fn value(&self, variable: Variable) -> f64 {
+        self.0.value(variable)
+    }
+
+    fn status(&self) -> SolutionStatus {
+        SolutionStatus::TimeLimit
+    }
+}
+
+impl<M: SolverModel> SolverModel for Unproved<M> {
+    type Solution = UnprovedSolution<M::Solution>;
+    type Error = M::Error;
+
+    fn solve(self) -> Result<Self::Solution, Self::Error> {
+        thread::sleep(self.limit);
+        self.model.solve().map(UnprovedSolution)
+    }
+
+    fn add_constraint(&mut self, c: Constraint) -> ConstraintReference {
+        self.model.add_constraint(c)
+    }
+}
+
+impl<M> ModelWithTimeLimit for Unproved<M> {
+    fn set_time_limit(&mut self, limit: Duration) {
+        self.limit = limit;
+    }
+}
+
+#[test]
+fn cancel_returns_the_incumbent() {
+    variables! {vars: 0 <= x <= 3;}
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(300));
+        canceller.cancel();
+    });
+    let start = Instant::now();
+    let solver = |problem| Unproved {
+        model: default_solver(problem),
+        limit: Duration::ZERO,
+    };
+    let solution = solve_until_cancelled(vars.maximise(x), solver, &token).unwrap();
+    assert!((solution.value(x) - 3.).abs() < 1e-6);
+    assert_eq!(solution.status(), SolutionStatus::Interrupted);
+    // The round that was running when the token was cancelled is not waited for
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use good_lp::checkpoint::{solve_with_checkpoints, Checkpoint};
+use good_lp::constraint::ConstraintReference;
+use good_lp::{
+    constraint, default_solver, variables, Constraint, ModelWithTimeLimit, Solution, SolverModel,
+};
+
+/// A model that accepts a time limit, and ignores it
+struct Limited<M>(M);
+
+impl<M: SolverModel> SolverModel for Limited<M> {
+    type Solution = M::Solution;
+    type Error = M::Error;
+
+    fn solve(self) -> Result<Self::Solution, Self::Error> {
+        self.0.solve()
+    }
+
+    fn add_constraint(&mut self, c: Constraint) -> ConstraintReference {
+        self.0.add_constraint(c)
+    }
+}
+
+impl<M> ModelWithTimeLimit for Limited<M> {
+    fn set_time_limit(&mut self, _limit: Duration) {}
+}
+
+#[test]
+fn checkpoints_are_saved() {
+    let file = format!("good_lp_checkpoint_test_{}.txt", std::process::id());
+    let path = std::env::temp_dir().join(file);
+    variables! {vars: 0 <= x <= 10;}
+    let problem = vars.maximise(x).with(constraint!(2 * x <= 7));
+    let solver = |problem| Limited(default_solver(problem));
+    let result = solve_with_checkpoints(problem, solver, Duration::from_secs(10), &path).unwrap();
+    assert!(result.save_error.is_none());
+    assert!((result.solution.value(x) - 3.5).abs() < 1e-6);
+    assert_eq!(Checkpoint::load(&path).unwrap().values, vec![3.5]);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn solution_is_returned_when_the_checkpoint_cannot_be_written() {
+    let path = std::env::temp_dir()
+        .join(format!("good_lp_missing_directory_{}", std::process::id()))
+        .join("checkpoint.txt");
+    variables! {vars: 0 <= x <= 10;}
+    let problem = vars.maximise(x).with(constraint!(2 * x <= 7));
+    let solver = |problem| Limited(default_solver(problem));
+    let result = solve_with_checkpoints(problem, solver, Duration::from_secs(10), &path).unwrap();
+    assert!(result.save_error.is_some());
+    assert!((result.solution.value(x) - 3.5).abs() < 1e-6);
+}
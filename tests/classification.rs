@@ -0,0 +1,79 @@
+use good_lp::classification::{ProblemKind, Structure};
+use good_lp::{constraint, variable, variables, Expression};
+
+#[test]
+fn linear_network_flow() {
+    // Flow from node 0 to node 2, through node 1 or directly
+    variables! {vars: 0 <= a <= 5; 0 <= b <= 5; 0 <= c <= 5;}
+    let problem = vars
+        .minimise(a + b + 3 * c)
+        .with(constraint!(a + c == 4)) // leaving the source
+        .with(constraint!(b - a == 0)) // through node 1
+        .with(constraint!(-b - c == -4)); // entering the sink
+    let classification = problem.classify();
+    assert_eq!(classification.kind, ProblemKind::Linear);
+    assert_eq!(classification.structures, vec![Structure::NetworkFlow]);
+    assert!(!classification.needs_integer_support());
+}
+
+#[test]
+fn assignment() {
+    let mut vars = variables!();
+    let x: Vec<Vec<_>> = (0..2)
+        .map(|_| vars.add_vector(variable().binary(), 2))
+        .collect();
+    let cost: Expression = x.iter().flatten().sum();
+    let mut problem = vars.minimise(cost);
+    for (i, row) in x.iter().enumerate() {
+        problem.add_constraint(constraint!(row[0] + row[1] == 1));
+        problem.add_constraint(constraint!(x[0][i] + x[1][i] == 1));
+    }
+    let classification = problem.classify();
+    assert_eq!(classification.kind, ProblemKind::Binary);
+    assert!(classification.has(Structure::SetPartitioning));
+    assert!(classification.has(Structure::Assignment));
+    assert!(!classification.has(Structure::NetworkFlow));
+    assert_eq!(classification.binary_variables, 4);
+}
+
+#[test]
+fn set_covering() {
+    let mut vars = variables!();
+    let x = vars.add_vector(variable().binary(), 3);
+    let problem = vars
+        .minimise(x[0] + x[1] + x[2])
+        .with(constraint!(x[0] + x[1] >= 1))
+        .with(constraint!(x[1] + x[2] >= 1));
+    let classification = problem.classify();
+    assert_eq!(classification.structures, vec![Structure::SetCovering]);
+}
+
+#[test]
+fn mixed_integer() {
+    let mut vars = variables!();
+    let n = vars.add(variable().integer().clamp(0, 10));
+    let y = vars.add(variable().binary());
+    let z = vars.add(variable().min(0));
+    let problem = vars
+        .maximise(n + y + z)
+        .with(constraint!(3 * n + 2 * z <= 7))
+        .with(constraint!(z <= 10 * y));
+    let classification = problem.classify();
+    assert_eq!(classification.kind, ProblemKind::MixedInteger);
+    assert_eq!(classification.integer_variables, 2);
+    assert!(classification.structures.is_empty());
+    assert!(classification.needs_integer_support());
+}
+
+#[test]
+fn integer_knapsack() {
+    let mut vars = variables!();
+    let n = vars.add_vector(variable().integer().min(0), 2);
+    let problem = vars
+        .maximise(3 * n[0] + 2 * n[1])
+        .with(constraint!(5 * n[0] + 3 * n[1] <= 17))
+        .with(constraint!(n[0] + 4 * n[1] <= 9));
+    let classification = problem.classify();
+    assert_eq!(classification.kind, ProblemKind::Integer);
+    assert_eq!(classification.structures, vec![Structure::Knapsack]);
+}
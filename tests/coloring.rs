@@ -0,0 +1,56 @@
+// minilp and highs don't support integer variables
+#![cfg(not(any(feature = "minilp", feature = "highs")))]
+use good_lp::coloring::Coloring;
+use good_lp::default_solver;
+
+fn petersen() -> Vec<(usize, usize)> {
+    let mut edges = vec![];
+    for i in 0..5 {
+        edges.push((i, (i + 1) % 5));
+        edges.push((i, i + 5));
+        edges.push((i + 5, (i + 2) % 5 + 5));
+    }
+    edges
+}
+
+#[test]
+fn petersen_graph_needs_three_colors() {
+    for &cliques in &[false, true] {
+        let coloring = Coloring::new(10, 4)
+            .conflicts(petersen())
+            .clique_constraints(cliques)
+            .solve(default_solver)
+            .unwrap();
+        assert_eq!(coloring.n_colors(), 3);
+        for (u, v) in petersen() {
+            assert_ne!(coloring.color(u), coloring.color(v));
+        }
+    }
+}
+
+#[test]
+fn cliques_need_one_color_per_vertex() {
+    let edges = (0..4).flat_map(|u| (u + 1..4).map(move |v| (u, v)));
+    let coloring = Coloring::new(5, 5)
+        .conflicts(edges)
+        .clique_constraints(true)
+        .solve(default_solver)
+        .unwrap();
+    assert_eq!(coloring.n_colors(), 4);
+    assert_eq!(coloring.color(0), 0);
+}
+
+#[test]
+fn frequencies_are_separated() {
+    let coloring = Coloring::new(3, 6)
+        .separation(0, 1, 2)
+        .separation(1, 2, 2)
+        .conflict(0, 2)
+        .solve(default_solver)
+        .unwrap();
+    let (a, b, c) = (coloring.color(0), coloring.color(1), coloring.color(2));
+    assert!((a as i64 - b as i64).abs() >= 2);
+    assert!((b as i64 - c as i64).abs() >= 2);
+    assert_ne!(a, c);
+    assert_eq!(coloring.span(), 3);
+}
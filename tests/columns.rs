@@ -0,0 +1,24 @@
+#![cfg(feature = "coin_cbc")]
+use float_eq::assert_float_eq;
+use good_lp::{coin_cbc, constraint, variable, variables, ModelWithColumns, Solution, SolverModel};
+
+#[test]
+fn columns_enter_existing_constraints() {
+    // Cover a demand of 3 units of item a and 2 units of item b with patterns,
+    // starting from the patterns that contain a single item.
+    // The demands are written as `demand - production <= 0`,
+    // so the new pattern enters them with negative coefficients.
+    variables! {vars: 0 <= only_a; 0 <= only_b;}
+    let mut model = vars.minimise(only_a + only_b).using(coin_cbc);
+    let demand_a = model.add_constraint(constraint!(3 - only_a <= 0));
+    let demand_b = model.add_constraint(constraint!(2 - only_b <= 0));
+    let both = model.add_column(
+        variable().min(0).integer(),
+        1.5,
+        &[(demand_a, -1.), (demand_b, -1.)],
+    );
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.value(both), 2., abs <= 1e-6);
+    assert_float_eq!(solution.value(only_a), 1., abs <= 1e-6);
+    assert_float_eq!(solution.value(only_b), 0., abs <= 1e-6);
+}
@@ -0,0 +1,49 @@
+use float_eq::assert_float_eq;
+
+use good_lp::dea::{Dea, Orientation, ReturnsToScale};
+use good_lp::default_solver;
+
+fn hospitals() -> Dea {
+    // Each hospital uses doctors and nurses to treat patients
+    let inputs = vec![vec![2., 4.], vec![4., 2.], vec![4., 4.], vec![6., 6.]];
+    let outputs = vec![vec![1.], vec![1.], vec![1.], vec![3.]];
+    Dea::new(inputs, outputs)
+}
+
+#[test]
+fn ccr_input_oriented() {
+    let scores = hospitals().solve(default_solver).unwrap();
+    assert_eq!(scores.len(), 4);
+    assert_float_eq!(scores[0], 1., abs <= 1e-6);
+    assert_float_eq!(scores[1], 1., abs <= 1e-6);
+    assert_float_eq!(scores[2], 0.5, abs <= 1e-6);
+    assert_float_eq!(scores[3], 1., abs <= 1e-6);
+}
+
+#[test]
+fn ccr_orientations_agree() {
+    let input = hospitals().solve(default_solver).unwrap();
+    let output = hospitals()
+        .orientation(Orientation::Output)
+        .solve(default_solver)
+        .unwrap();
+    for (a, b) in input.iter().zip(&output) {
+        assert_float_eq!(a, b, abs <= 1e-6);
+    }
+}
+
+#[test]
+fn bcc_input_oriented() {
+    let dea = Dea::new(
+        vec![vec![2.], vec![4.], vec![5.]],
+        vec![vec![1.], vec![4.], vec![3.]],
+    )
+    .returns_to_scale(ReturnsToScale::Variable);
+    assert_float_eq!(dea.efficiency(0, default_solver).unwrap(), 1., abs <= 1e-6);
+    assert_float_eq!(dea.efficiency(1, default_solver).unwrap(), 1., abs <= 1e-6);
+    assert_float_eq!(
+        dea.efficiency(2, default_solver).unwrap(),
+        2. / 3.,
+        abs <= 1e-6
+    );
+}
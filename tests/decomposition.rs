@@ -0,0 +1,88 @@
+#![cfg(feature = "highs")]
+use float_eq::assert_float_eq;
+use good_lp::constraint::ConstraintReference;
+use good_lp::decomposition::dantzig_wolfe;
+use good_lp::variable::UnsolvedProblem;
+use good_lp::{constraint, highs, variable, variables, Expression, Solution, SolverModel};
+
+/// Three machines with their own capacity, that share two demands.
+/// Returns the problem, its objective, and the capacity constraint of each machine.
+fn machines() -> (UnsolvedProblem, Expression, Vec<ConstraintReference>) {
+    let mut vars = variables!();
+    let definition = variable().clamp(0, 10);
+    let production: Vec<_> = (0..3)
+        .map(|_| vars.add_vector(definition.clone(), 2))
+        .collect();
+    let cost: Expression = production
+        .iter()
+        .enumerate()
+        .map(|(m, p)| (m + 1) as f64 * p[0] + (3 - m) as f64 * p[1])
+        .sum();
+    let mut problem = vars.minimise(cost.clone());
+    let capacities = production
+        .iter()
+        .map(|p| problem.add_constraint(constraint!(2 * p[0] + 3 * p[1] <= 17)))
+        .collect();
+    let first: Expression = production.iter().map(|p| p[0]).sum();
+    let second: Expression = production.iter().map(|p| p[1]).sum();
+    problem.add_constraint(constraint!(first >= 9));
+    problem.add_constraint(constraint!(second >= 7));
+    (problem, cost, capacities)
+}
+
+#[test]
+fn same_optimum_as_the_direct_resolution() {
+    let (problem, cost, capacities) = machines();
+    let direct = problem.clone().using(highs).solve().unwrap();
+    let mut decomposition = dantzig_wolfe(problem);
+    for capacity in capacities {
+        decomposition = decomposition.block(vec![capacity]);
+    }
+    let decomposed = decomposition.solve(highs).unwrap();
+    assert_float_eq!(decomposed.objective(), direct.eval(&cost), abs <= 1e-6);
+    assert!(decomposed.status().is_optimal());
+    assert!(decomposed.iterations() > 1);
+}
+
+#[test]
+fn price_and_branch_finds_an_integer_solution() {
+    // Each site chooses one of two options, and at least two sites choose the first one
+    let mut vars = variables!();
+    let options: Vec<_> = (0..3)
+        .map(|_| vars.add_vector(variable().binary(), 2))
+        .collect();
+    let cost: Expression = options
+        .iter()
+        .enumerate()
+        .map(|(site, o)| (site + 2) as f64 * o[0] + o[1])
+        .sum();
+    let mut problem = vars.minimise(cost.clone());
+    let choices: Vec<_> = options
+        .iter()
+        .map(|o| problem.add_constraint(constraint!(o[0] + o[1] == 1)))
+        .collect();
+    let first: Expression = options.iter().map(|o| o[0]).sum();
+    problem.add_constraint(constraint!(first >= 2));
+    let mut decomposition = dantzig_wolfe(problem);
+    for choice in choices {
+        decomposition = decomposition.block(vec![choice]);
+    }
+    let decomposed = decomposition.solve(highs).unwrap();
+    assert_float_eq!(decomposed.objective(), 2. + 3. + 1., abs <= 1e-6);
+    assert_float_eq!(decomposed.eval(&cost), decomposed.objective(), abs <= 1e-6);
+    assert_float_eq!(decomposed.value(options[2][1]), 1., abs <= 1e-6);
+    assert_eq!(decomposed.columns(), &[2, 2, 2]);
+}
+
+#[test]
+#[should_panic]
+fn blocks_cannot_share_variables() {
+    variables! {vars: 0 <= x <= 1; 0 <= y <= 1;}
+    let mut problem = vars.maximise(x + y);
+    let first = problem.add_constraint(constraint!(x + y <= 1));
+    let second = problem.add_constraint(constraint!(x - y <= 0));
+    let _ = dantzig_wolfe(problem)
+        .block(vec![first])
+        .block(vec![second])
+        .solve(highs);
+}
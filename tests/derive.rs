@@ -0,0 +1,43 @@
+#![cfg(feature = "derive")]
+use std::collections::HashMap;
+
+use float_eq::assert_float_eq;
+use good_lp::{constraint, default_solver, variables, LpVariables, SolverModel};
+
+#[derive(LpVariables)]
+struct Production {
+    #[lp(min = 0, max = 4)]
+    bread: f64,
+    #[lp(min = 0)]
+    shifts: Vec<f64>,
+    #[lp(min = 0, max = 1)]
+    shops: HashMap<String, f64>,
+}
+
+#[test]
+fn values_are_read_back_into_the_struct() {
+    let template = Production {
+        bread: 0.,
+        shifts: vec![0.; 3],
+        shops: vec![("north".to_string(), 0.), ("south".to_string(), 0.)]
+            .into_iter()
+            .collect(),
+    };
+    let mut vars = variables!();
+    let production = template.add_variables(&mut vars);
+    assert_eq!(production.shifts.len(), 3);
+    assert_eq!(production.shops.len(), 2);
+    let shifts: good_lp::Expression = production.shifts.iter().sum();
+    let shops: good_lp::Expression = production.shops.values().sum();
+    let solution = vars
+        .maximise(production.bread + shops - shifts.clone())
+        .using(default_solver)
+        .with(constraint!(shifts >= 2))
+        .solve()
+        .unwrap();
+    let production = Production::from_solution(&production, &solution);
+    assert_float_eq!(production.bread, 4., abs <= 1e-6);
+    assert_float_eq!(production.shifts.iter().sum::<f64>(), 2., abs <= 1e-6);
+    assert_float_eq!(production.shops["north"], 1., abs <= 1e-6);
+    assert_float_eq!(production.shops["south"], 1., abs <= 1e-6);
+}
@@ -0,0 +1,35 @@
+use float_eq::assert_float_eq;
+use good_lp::elastic::solve_or_explain;
+use good_lp::{constraint, default_solver, variables, ResolutionError, Solution};
+
+#[test]
+fn feasible_problem_is_solved_normally() {
+    variables! {vars: 0 <= x <= 10;}
+    let problem = vars.maximise(x).with(constraint!(x <= 4));
+    let explained = solve_or_explain(problem, default_solver).unwrap();
+    assert!(explained.is_feasible());
+    assert_float_eq!(explained.value(x), 4., abs <= 1e-6);
+}
+
+#[test]
+fn violated_equality_is_reported_with_its_name() {
+    variables! {vars: 0 <= x <= 3; 0 <= y <= 3;}
+    let problem = vars
+        .maximise(x - y)
+        .with(constraint!(x + y == 10).set_name("total".to_string()))
+        .with(constraint!(x - y >= 0));
+    let explained = solve_or_explain(problem, default_solver).unwrap();
+    assert_eq!(explained.violations().len(), 1);
+    let violation = &explained.violations()[0];
+    assert_eq!(violation.name.as_deref(), Some("total"));
+    assert_float_eq!(violation.amount, 4., abs <= 1e-6);
+    assert_float_eq!(explained.total_violation(), 4., abs <= 1e-6);
+}
+
+#[test]
+fn unbounded_problem_is_still_an_error() {
+    variables! {vars: 0 <= x;}
+    let problem = vars.maximise(x);
+    let error = solve_or_explain(problem, default_solver).err();
+    assert_eq!(error, Some(ResolutionError::Unbounded));
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "exact")]
+use std::collections::HashMap;
+
+use good_lp::exact::verify_exact;
+use good_lp::{constraint, variable, variables};
+
+#[test]
+fn bounds_and_integrality_are_checked_exactly() {
+    let mut vars = variables!();
+    let x = vars.add(variable().integer().clamp(0, 0.7));
+    let y = vars.add(variable().min(0.1));
+    let problem = vars
+        .minimise(x + y)
+        .with(constraint!(x + y == 1).set_name("total".to_string()));
+    let solution: HashMap<_, _> = vec![(x, 1. + 1e-15), (y, 0.09999999999999999)]
+        .into_iter()
+        .collect();
+    let check = verify_exact(&problem, &solution);
+    assert_eq!(check.out_of_bounds, vec![x, y]);
+    assert_eq!(check.not_integer, vec![x]);
+    assert_eq!(check.violations.len(), 1);
+    assert_eq!(check.violations[0].name.as_deref(), Some("total"));
+    assert!(!check.is_feasible());
+}
@@ -0,0 +1,56 @@
+// minilp and highs do not support integer variables
+#![cfg(not(any(feature = "minilp", feature = "highs")))]
+use float_eq::assert_float_eq;
+use good_lp::default_solver;
+use good_lp::expansion::{capacity_expansion, Build, Technology};
+use good_lp::{constraint, Expression};
+
+fn plant(initial_capacity: f64, build_cost: f64) -> Technology {
+    Technology {
+        initial_capacity,
+        unit_capacity: 10.,
+        build_cost,
+        production_cost: 1.,
+    }
+}
+
+#[test]
+fn builds_as_late_as_possible() {
+    let demand = [10., 15., 25., 30.];
+    let plan = capacity_expansion(&[plant(10., 100.)], &demand, 0.1)
+        .solve(default_solver)
+        .unwrap();
+    assert_eq!(
+        plan.schedule(),
+        vec![
+            Build {
+                technology: 0,
+                period: 1,
+                units: 1
+            },
+            Build {
+                technology: 0,
+                period: 2,
+                units: 1
+            },
+        ]
+    );
+    assert_float_eq!(plan.capacity(0, 3), 30., abs <= 1e-9);
+    assert_float_eq!(plan.production(0, 3), 30., abs <= 1e-6);
+    let expected = 100. / 1.1 + 100. / 1.21 + 10. + 15. / 1.1 + 25. / 1.21 + 30. / 1.331;
+    assert_float_eq!(plan.discounted_cost(), expected, abs <= 1e-6);
+}
+
+#[test]
+fn additional_constraints() {
+    let demand = [0., 20.];
+    let mut expansion = capacity_expansion(&[plant(0., 100.), plant(0., 300.)], &demand, 0.);
+    // The cheap technology can only get one unit in total
+    let cheap_units: Expression = (0..2).map(|t| expansion.build_variable(0, t)).sum();
+    expansion
+        .problem_mut()
+        .add_constraint(constraint!(cheap_units <= 1));
+    let plan = expansion.solve(default_solver).unwrap();
+    assert_eq!(plan.built_before(0, 1), 1);
+    assert_eq!(plan.built_before(1, 1), 1);
+}
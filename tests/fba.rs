@@ -0,0 +1,75 @@
+use float_eq::assert_float_eq;
+
+use good_lp::bio::fba::FluxBalance;
+use good_lp::default_solver;
+
+/// Reactions: 0: uptake of A, 1: A -> B, 2: A -> C, 3: B -> biomass, 4: C -> waste
+fn branched_network() -> FluxBalance {
+    FluxBalance::new(vec![
+        vec![1., -1., -1., 0., 0.], // A
+        vec![0., 1., 0., -1., 0.],  // B
+        vec![0., 0., 1., 0., -1.],  // C
+    ])
+    .bounds(0, 0., 10.)
+    .maximise_reaction(3)
+}
+
+#[test]
+fn optimal_growth() {
+    let solution = branched_network().solve(default_solver).unwrap();
+    assert_float_eq!(solution.objective_value, 10., abs <= 1e-6);
+    assert_float_eq!(solution.fluxes[1], 10., abs <= 1e-6);
+    assert_float_eq!(solution.fluxes[2], 0., abs <= 1e-6);
+}
+
+#[test]
+fn flux_variability() {
+    let fva = branched_network().variability(0.5, default_solver).unwrap();
+    let expected_min = [5., 5., 0., 5., 0.];
+    let expected_max = [10., 10., 5., 10., 5.];
+    for r in 0..5 {
+        assert_float_eq!(fva.minimum[r], expected_min[r], abs <= 1e-6);
+        assert_float_eq!(fva.maximum[r], expected_max[r], abs <= 1e-6);
+    }
+}
+
+#[cfg(feature = "sbml")]
+#[test]
+fn read_fbc_model() {
+    let sbml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<sbml xmlns="http://www.sbml.org/sbml/level3/version1/core" level="3" version="1"
+      xmlns:fbc="http://www.sbml.org/sbml/level3/version1/fbc/version2" fbc:required="false">
+  <model id="toy" fbc:strict="true">
+    <listOfSpecies>
+      <species id="A" compartment="c" boundaryCondition="false"/>
+      <species id="A_ext" compartment="e" boundaryCondition="true"/>
+    </listOfSpecies>
+    <listOfParameters>
+      <parameter id="zero" value="0" constant="true"/>
+      <parameter id="max_uptake" value="4" constant="true"/>
+      <parameter id="inf" value="INF" constant="true"/>
+    </listOfParameters>
+    <listOfReactions>
+      <reaction id="EX_A" reversible="false" fbc:lowerFluxBound="zero" fbc:upperFluxBound="max_uptake">
+        <listOfReactants><speciesReference species="A_ext" stoichiometry="1"/></listOfReactants>
+        <listOfProducts><speciesReference species="A" stoichiometry="1"/></listOfProducts>
+      </reaction>
+      <reaction id="BIOMASS" reversible="false" fbc:lowerFluxBound="zero" fbc:upperFluxBound="inf">
+        <listOfReactants><speciesReference species="A" stoichiometry="2"/></listOfReactants>
+      </reaction>
+    </listOfReactions>
+    <fbc:listOfObjectives fbc:activeObjective="obj">
+      <fbc:objective fbc:id="obj" fbc:type="maximize">
+        <fbc:listOfFluxObjectives>
+          <fbc:fluxObjective fbc:reaction="BIOMASS" fbc:coefficient="1"/>
+        </fbc:listOfFluxObjectives>
+      </fbc:objective>
+    </fbc:listOfObjectives>
+  </model>
+</sbml>"#;
+    let fba = FluxBalance::from_sbml(sbml).unwrap();
+    assert_eq!(fba.n_metabolites(), 1);
+    assert_eq!(fba.get_reaction_names(), &["EX_A", "BIOMASS"]);
+    let solution = fba.solve(default_solver).unwrap();
+    assert_float_eq!(solution.objective_value, 2., abs <= 1e-6);
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use float_eq::assert_float_eq;
+use good_lp::feasibility::{check_solution, Requirement};
+use good_lp::{constraint, default_solver, variable, variables, SolverModel};
+
+#[test]
+fn solver_solutions_are_feasible() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 4));
+    let y = vars.add(variable().min(1));
+    let problem = vars
+        .maximise(x + 2 * y)
+        .with(constraint!(x + y <= 6))
+        .with(constraint!(x - y == -2));
+    let solution = problem.clone().using(default_solver).solve().unwrap();
+    let report = check_solution(&problem, &solution, 1e-6);
+    assert!(report.is_feasible(), "{}", report);
+    assert_eq!(report.max_violation(), 0.);
+}
+
+#[test]
+fn every_violation_is_reported() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 4).name("x"));
+    let y = vars.add(variable().min(1));
+    let mut problem = vars.maximise(x + y);
+    problem.add_constraint(constraint!(x + y <= 10));
+    let equal = problem.add_constraint(constraint!(x == y).set_name("equal".to_string()));
+    let solution: HashMap<_, _> = vec![(x, 5.), (y, 0.5)].into_iter().collect();
+    let report = check_solution(&problem, &solution, 1e-6);
+    let violated: Vec<_> = report
+        .violations
+        .iter()
+        .map(|violation| violation.requirement.clone())
+        .collect();
+    assert_eq!(
+        violated,
+        vec![
+            Requirement::UpperBound(x),
+            Requirement::LowerBound(y),
+            Requirement::Constraint(equal),
+        ]
+    );
+    assert_eq!(report.violations[0].name.as_deref(), Some("x"));
+    assert_eq!(report.violations[1].name, None);
+    assert_eq!(report.violations[2].name.as_deref(), Some("equal"));
+    assert_float_eq!(report.violations[1].amount, 0.5, abs <= 1e-9);
+    assert_float_eq!(report.max_violation(), 4.5, abs <= 1e-9);
+    assert!(report.to_string().contains("(equal): violated by 4.5"));
+    // A larger tolerance hides the smallest violations
+    assert_eq!(check_solution(&problem, &solution, 2.).violations.len(), 1);
+}
@@ -0,0 +1,139 @@
+use float_eq::assert_float_eq;
+use good_lp::graph::{subtour_elimination, MultiCommodityFlow};
+use good_lp::{default_solver, variable, variables};
+use std::collections::HashMap;
+
+/// Two commodities competing for the cheap arcs of a small network
+fn network() -> MultiCommodityFlow {
+    MultiCommodityFlow::new(4)
+        .arc(0, 1, 5., 1.) // 0
+        .arc(1, 3, 5., 1.) // 1
+        .arc(0, 2, 10., 2.) // 2
+        .arc(2, 3, 10., 2.) // 3
+        .arc(1, 2, 10., 1.) // 4
+        .commodity(0, 3, 8.)
+        .commodity(1, 3, 3.)
+}
+
+#[test]
+fn arc_formulation() {
+    let flow = network().solve(default_solver).unwrap();
+    // Arc 1 is the bottleneck: both commodities want it
+    assert_float_eq!(flow.arc_flow(1), 5., abs <= 1e-6);
+    assert_float_eq!(flow.flow(0, 0) + flow.flow(0, 2), 8., abs <= 1e-6);
+    assert_float_eq!(flow.cost(), 31., abs <= 1e-6);
+}
+
+#[test]
+fn path_formulation_with_all_paths() {
+    let network = network();
+    let paths = vec![
+        vec![vec![0, 1], vec![2, 3], vec![0, 4, 3]],
+        vec![vec![1], vec![4, 3]],
+    ];
+    let flow = network.path_problem(&paths).solve(default_solver).unwrap();
+    let by_arcs = network.solve(default_solver).unwrap();
+    assert_float_eq!(flow.cost(), by_arcs.cost(), abs <= 1e-6);
+    assert_float_eq!(flow.arc_flow(1), 5., abs <= 1e-6);
+}
+
+#[test]
+fn initial_paths_are_the_cheapest() {
+    assert_eq!(
+        network().initial_paths(),
+        vec![vec![vec![0, 1]], vec![vec![1]]]
+    );
+}
+
+#[test]
+fn unmet_demand() {
+    let network = network().unmet_demand_cost(100.);
+    // The initial paths can only carry 5 units together
+    let problem = network.path_problem(&network.initial_paths());
+    let flow = problem.solve(default_solver).unwrap();
+    assert_float_eq!(flow.unmet(0) + flow.unmet(1), 6., abs <= 1e-6);
+}
+
+#[test]
+fn pricing() {
+    let network = network();
+    // Arc 1 is saturated and expensive: the other routes become attractive
+    let arc_prices = [0., 3., 0., 0., 0.];
+    assert_eq!(
+        network.shortest_path(0, &arc_prices),
+        Some((vec![2, 3], 4.))
+    );
+    let new_paths = network.price(&arc_prices, &[5., 4.], 1e-9);
+    assert_eq!(new_paths, vec![(0, vec![2, 3]), (1, vec![4, 3])]);
+    // With low commodity prices, no path is worth adding
+    assert!(network.price(&arc_prices, &[4., 3.], 1e-9).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "is not a path of commodity 1")]
+fn invalid_path() {
+    network().path_problem(&[vec![vec![0, 1]], vec![vec![0, 1]]]);
+}
+
+#[test]
+#[cfg(feature = "highs")]
+fn formulations_agree() {
+    use good_lp::graph::Formulation;
+    let network = network();
+    let by_arcs = network
+        .solve_with(Formulation::Arc, good_lp::highs)
+        .unwrap();
+    let by_paths = network
+        .solve_with(Formulation::Path, good_lp::highs)
+        .unwrap();
+    assert_float_eq!(by_arcs.cost(), by_paths.cost(), abs <= 1e-6);
+}
+
+#[test]
+#[cfg(feature = "highs")]
+fn column_generation_adds_paths() {
+    let generated = network().column_generation(good_lp::highs).unwrap();
+    // The initial paths both use arc 1, which is not enough
+    assert!(generated.iterations > 1);
+    assert!(generated.paths.iter().map(Vec::len).sum::<usize>() > 2);
+    assert_float_eq!(generated.solution.cost(), 31., abs <= 1e-6);
+}
+
+#[test]
+#[cfg(feature = "highs")]
+fn column_generation_infeasible() {
+    // Nothing can go beyond the capacity of the only arc
+    let network = MultiCommodityFlow::new(2)
+        .arc(0, 1, 1., 1.)
+        .commodity(0, 1, 2.);
+    assert_eq!(
+        network.column_generation(good_lp::highs),
+        Err(good_lp::ResolutionError::Infeasible)
+    );
+}
+
+#[test]
+fn subtour_elimination_cuts_each_subtour() {
+    let mut vars = variables!();
+    let mut edges = vec![];
+    for from in 0..6 {
+        for to in from + 1..6 {
+            edges.push((from, to, vars.add(variable().binary())));
+        }
+    }
+    // Two triangles: 0-1-2 and 3-4-5
+    let in_triangle = |from: usize, to: usize| from / 3 == to / 3;
+    let triangles: HashMap<_, _> = edges
+        .iter()
+        .map(|&(from, to, edge)| (edge, if in_triangle(from, to) { 1. } else { 0. }))
+        .collect();
+    let cuts = subtour_elimination(6, &edges, &triangles);
+    assert_eq!(cuts.len(), 2);
+    // A single tour: 0-1-2-3-4-5-0
+    let in_tour = |from: usize, to: usize| to == from + 1 || (from, to) == (0, 5);
+    let tour: HashMap<_, _> = edges
+        .iter()
+        .map(|&(from, to, edge)| (edge, if in_tour(from, to) { 1. } else { 0. }))
+        .collect();
+    assert!(subtour_elimination(6, &edges, &tour).is_empty());
+}
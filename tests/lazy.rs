@@ -0,0 +1,96 @@
+use float_eq::assert_float_eq;
+use good_lp::lazy::LazyConstraints;
+use good_lp::{constraint, default_solver, variable, variables, Expression, Solution};
+
+#[test]
+fn adds_only_violated_constraints() {
+    let mut vars = variables!();
+    let x = vars.add_vector(variable().clamp(0, 1), 4);
+    let total: Expression = x.iter().sum();
+    let problem = vars.maximise(total);
+    // One constraint per pair: x[i] + x[j] <= 1, generated for the most violated pair only
+    let result = LazyConstraints::new(|solution: &dyn Solution| {
+        let mut best = None;
+        let mut best_excess = 1e-6;
+        for i in 0..4 {
+            for j in i + 1..4 {
+                let excess = solution.value(x[i]) + solution.value(x[j]) - 1.;
+                if excess > best_excess {
+                    best = Some((i, j));
+                    best_excess = excess;
+                }
+            }
+        }
+        best.map(|(i, j)| constraint!(x[i] + x[j] <= 1))
+            .into_iter()
+            .collect()
+    })
+    .solve(problem, default_solver)
+    .unwrap();
+    assert!(result.converged);
+    assert_eq!(result.rounds, result.added.len() + 1);
+    assert!(result.added.len() < 6);
+    let objective: f64 = x.iter().map(|&v| result.solution.value(v)).sum();
+    assert_float_eq!(objective, 2., abs <= 1e-6);
+}
+
+#[test]
+fn stops_after_max_rounds() {
+    variables! {vars: 0 <= x <= 100;}
+    let problem = vars.maximise(x);
+    let mut bound = 100.;
+    // Each round only tightens the bound a little
+    let result = LazyConstraints::new(|solution: &dyn Solution| {
+        if solution.value(x) > 10. {
+            bound -= 10.;
+            vec![constraint!(x <= bound)]
+        } else {
+            vec![]
+        }
+    })
+    .max_rounds(3)
+    .solve(problem, default_solver)
+    .unwrap();
+    assert!(!result.converged);
+    assert_eq!(result.rounds, 3);
+    assert_float_eq!(result.solution.value(x), 80., abs <= 1e-6);
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn integer_problem() {
+    variables! {vars: 0 <= x (integer) <= 10; 0 <= y (integer) <= 10;}
+    let problem = vars.maximise(x + y);
+    let result = LazyConstraints::new(|solution: &dyn Solution| {
+        if solution.eval_dyn(2 * x + 3 * y) > 12. + 1e-6 {
+            vec![constraint!(2 * x + 3 * y <= 12)]
+        } else {
+            vec![]
+        }
+    })
+    .solve(problem, default_solver)
+    .unwrap();
+    assert!(result.converged);
+    assert_float_eq!(result.solution.eval(x + y), 6., abs <= 1e-6);
+}
+
+#[test]
+fn separation_helper_adds_cuts_until_none_is_returned() {
+    variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+    // The cuts x + y <= 15 - k, for k = 0, 1, 2, 3
+    let mut k = 0;
+    let result =
+        good_lp::lazy::solve_with_separation(vars.maximise(x + y), default_solver, |solution| {
+            if k < 4 && solution.eval_dyn(x + y) > 15. - k as f64 + 1e-6 {
+                let cut = constraint!(x + y <= 15 - k);
+                k += 1;
+                vec![cut]
+            } else {
+                vec![]
+            }
+        })
+        .unwrap();
+    assert!(result.converged);
+    assert_eq!(result.added.len(), 4);
+    assert_float_eq!(result.solution.eval(x + y), 12., abs <= 1e-6);
+}
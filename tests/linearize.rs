@@ -0,0 +1,91 @@
+use float_eq::assert_float_eq;
+use good_lp::linearize::{and_var, product};
+use good_lp::{constraint, default_solver, variable, variables, Solution, SolverModel};
+
+#[test]
+fn envelope_of_continuous_variables_is_a_relaxation() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 2));
+    let y = vars.add(variable().clamp(0, 3));
+    let (z, envelope) = product(&mut vars, x, y);
+    let mut problem = vars.maximise(z).with(constraint!(x + y <= 3));
+    for constraint in envelope {
+        problem.add_constraint(constraint);
+    }
+    let solution = problem.using(default_solver).solve().unwrap();
+    // z <= 2y and z <= 3x: the envelope allows 3.6, the product is at most 1.5 * 1.5
+    assert_float_eq!(solution.value(z), 3.6, abs <= 1e-6);
+    assert!(solution.value(z) >= 1.5 * 1.5);
+}
+
+#[test]
+fn envelope_is_exact_at_the_bounds() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(-1, 2));
+    let y = vars.add(variable().clamp(3, 4));
+    let (z, envelope) = product(&mut vars, x, y);
+    let mut problem = vars
+        .minimise(z)
+        .with(constraint!(x == -1))
+        .with(constraint!(y == 4));
+    for constraint in envelope {
+        problem.add_constraint(constraint);
+    }
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert_float_eq!(solution.value(z), -4., abs <= 1e-6);
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn product_with_a_binary_is_exact() {
+    let mut vars = variables!();
+    let on = vars.add(variable().binary());
+    let power = vars.add(variable().clamp(0, 80));
+    let (z, envelope) = product(&mut vars, on, power);
+    let mut problem = vars
+        .maximise(z)
+        .with(constraint!(on == 0))
+        .with(constraint!(power == 80));
+    for constraint in envelope {
+        problem.add_constraint(constraint);
+    }
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert_float_eq!(solution.value(z), 0., abs <= 1e-6);
+}
+
+#[test]
+#[should_panic]
+fn unbounded_factors_cannot_be_linearized() {
+    let mut vars = variables!();
+    let x = vars.add(variable().min(0));
+    let y = vars.add(variable().clamp(0, 1));
+    product(&mut vars, x, y);
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn and_var_is_one_when_both_are() {
+    let mut vars = variables!();
+    let a = vars.add(variable().binary());
+    let b = vars.add(variable().binary());
+    let (both, constraints) = and_var(&mut vars, a, b);
+    assert_eq!(constraints.len(), 3);
+    // Taking both is rewarded, but the reward goes through the product only
+    let mut problem = vars.maximise(both - 0.1 * a - 0.1 * b);
+    for constraint in constraints {
+        problem.add_constraint(constraint);
+    }
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert_float_eq!(solution.value(a), 1., abs <= 1e-6);
+    assert_float_eq!(solution.value(b), 1., abs <= 1e-6);
+    assert_float_eq!(solution.value(both), 1., abs <= 1e-6);
+}
+
+#[test]
+#[should_panic]
+fn and_var_requires_binary_variables() {
+    let mut vars = variables!();
+    let a = vars.add(variable().binary());
+    let b = vars.add(variable().clamp(0, 1));
+    and_var(&mut vars, a, b);
+}
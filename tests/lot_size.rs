@@ -0,0 +1,30 @@
+// minilp and highs do not support integer variables
+#![cfg(not(any(feature = "minilp", feature = "highs")))]
+use float_eq::assert_float_eq;
+use good_lp::lot_size::round_and_restore;
+use good_lp::{constraint, default_solver, variables, Solution};
+use std::collections::HashMap;
+
+#[test]
+fn feasible_rounding_is_kept() {
+    variables! {vars: 0 <= a <= 100; 0 <= b <= 100;}
+    let problem = vars.maximise(a + b).with(constraint!(a + b <= 150));
+    let solution: HashMap<_, _> = vec![(a, 61.), (b, 33.)].into_iter().collect();
+    let lot_sizes: HashMap<_, _> = vec![(a, 10.)].into_iter().collect();
+    let plan = round_and_restore(&solution, problem, &lot_sizes, default_solver).unwrap();
+    assert_float_eq!(plan.value(a), 60., abs <= 1e-6);
+    assert_float_eq!(plan.value(b), 33., abs <= 1e-6);
+    assert_float_eq!(plan.deviation(), 0., abs <= 1e-6);
+}
+
+#[test]
+fn unlotted_variables_absorb_the_rounding() {
+    variables! {vars: 0 <= a <= 100; 0 <= b <= 100;}
+    let problem = vars.minimise(a + b).with(constraint!(a + b >= 47));
+    let solution: HashMap<_, _> = vec![(a, 42.), (b, 5.)].into_iter().collect();
+    let lot_sizes: HashMap<_, _> = vec![(a, 10.)].into_iter().collect();
+    let plan = round_and_restore(&solution, problem, &lot_sizes, default_solver).unwrap();
+    assert_float_eq!(plan.value(a), 40., abs <= 1e-6);
+    assert_float_eq!(plan.value(b), 7., abs <= 1e-6);
+    assert_eq!(plan.changed(1e-6), vec![b]);
+}
@@ -0,0 +1,25 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{default_solver, mdp};
+
+#[test]
+fn machine_replacement() {
+    // A machine is either new (state 0) or worn (state 1).
+    // A new machine earns 10 and wears out with probability 0.5.
+    // A worn machine earns 4 and breaks down completely (state 2) with probability 0.5,
+    // unless it is replaced for a cost of 3 (action 1), which makes it new again.
+    // A broken machine earns nothing and must be replaced for a cost of 8.
+    let transition = vec![
+        vec![vec![0.5, 0.5, 0.]],
+        vec![vec![0., 0.5, 0.5], vec![1., 0., 0.]],
+        vec![vec![1., 0., 0.]],
+    ];
+    let reward = vec![vec![10.], vec![4., -3.], vec![-8.]];
+    let solution = mdp::solve_lp(&transition, &reward, 0.9, default_solver).unwrap();
+    assert_eq!(solution.policy, vec![0, 1, 0]);
+    // With this policy, v0 = 10 + 0.9 * (v0 + v1) / 2 and v1 = -3 + 0.9 * v0
+    let v0 = (10. - 0.45 * 3.) / (1. - 0.45 - 0.45 * 0.9);
+    assert_float_eq!(solution.values[0], v0, abs <= 1e-6);
+    assert_float_eq!(solution.values[1], -3. + 0.9 * v0, abs <= 1e-6);
+    assert_float_eq!(solution.values[2], -8. + 0.9 * v0, abs <= 1e-6);
+}
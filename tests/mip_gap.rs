@@ -0,0 +1,24 @@
+#![cfg(feature = "coin_cbc")]
+use good_lp::{coin_cbc, constraint, variable, variables, ModelWithMipGap};
+use good_lp::{Solution, SolverModel};
+
+#[test]
+fn a_large_gap_still_returns_a_feasible_solution() {
+    let mut vars = variables!();
+    let items = vars.add_vector(variable().binary(), 6);
+    let weights = [12., 7., 11., 8., 9., 6.];
+    let values = [24., 13., 23., 15., 16., 10.];
+    let weight: good_lp::Expression = items.iter().zip(&weights).map(|(&x, &w)| w * x).sum();
+    let value: good_lp::Expression = items.iter().zip(&values).map(|(&x, &v)| v * x).sum();
+    let solution = vars
+        .maximise(value.clone())
+        .using(coin_cbc)
+        .with(constraint!(weight.clone() <= 26))
+        .with_mip_gap(0.5)
+        .with_absolute_gap(10.)
+        .solve()
+        .unwrap();
+    assert!(solution.eval(&weight) <= 26. + 1e-6);
+    // The optimum is 51: the solution is at most 50% worse
+    assert!(solution.eval(&value) >= 51. * 0.5 - 1e-6);
+}
@@ -0,0 +1,124 @@
+use float_eq::assert_float_eq;
+use good_lp::modeling::transportation;
+use good_lp::{default_solver, ResolutionError, Solution, SolverModel};
+// Only used by the tests with integer variables, which minilp and highs do not support
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+use good_lp::{
+    constraint,
+    modeling::{assignment, bin_packing, knapsack, set_packing},
+};
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn assignment_with_more_agents_than_tasks() {
+    let model = assignment(&[vec![5., 1.], vec![1., 5.], vec![2., 2.]]);
+    assert_eq!((model.agents.len(), model.tasks.len()), (3, 2));
+    let assigned = model.assigned.clone();
+    let solution = model.into_problem().using(default_solver).solve().unwrap();
+    // Both tasks are done, by the cheapest agents, and agent 2 does nothing
+    assert_float_eq!(solution.value(assigned[0][1]), 1., abs <= 1e-6);
+    assert_float_eq!(solution.value(assigned[1][0]), 1., abs <= 1e-6);
+    assert_float_eq!(
+        solution.value(assigned[2][0]) + solution.value(assigned[2][1]),
+        0.,
+        abs <= 1e-6
+    );
+}
+
+#[test]
+fn transportation_uses_the_cheapest_routes() {
+    let model = transportation(&[10., 10.], &[15.], &[vec![1.], vec![2.]]);
+    let objective = model.objective.clone();
+    let solution = model.into_problem().using(default_solver).solve().unwrap();
+    assert_float_eq!(solution.eval(&objective), 10. + 2. * 5., abs <= 1e-6);
+}
+
+#[test]
+fn transportation_without_enough_supply() {
+    let model = transportation(&[10.], &[15.], &[vec![1.]]);
+    let shipped = model.shipped[0][0];
+    assert_eq!(
+        model
+            .into_problem()
+            .using(default_solver)
+            .solve()
+            .map(|solution| solution.value(shipped)),
+        Err(ResolutionError::Infeasible)
+    );
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn knapsack_with_side_constraint() {
+    let model = knapsack(&[(3., 4.), (3., 4.), (4., 5.)], 7.);
+    let chosen = model.chosen.clone();
+    // Items 0 and 1 cannot both be chosen
+    let conflict = constraint!(chosen[0] + chosen[1] <= 1);
+    let objective = model.objective.clone();
+    let solution = model
+        .into_problem()
+        .with(conflict)
+        .using(default_solver)
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.eval(&objective), 9., abs <= 1e-6);
+    assert_float_eq!(solution.value(chosen[2]), 1., abs <= 1e-6);
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn bin_packing_without_symmetry_breaking() {
+    let mut model = bin_packing(&[4., 4., 4., 4.], 8., 4);
+    assert_eq!(model.symmetry_breaking.len(), 3);
+    model.symmetry_breaking.clear();
+    let objective = model.objective.clone();
+    let packed = model.packed.clone();
+    let solution = model.into_problem().using(default_solver).solve().unwrap();
+    assert_float_eq!(solution.eval(&objective), 2., abs <= 1e-6);
+    for bins in &packed {
+        let placed: f64 = bins.iter().map(|&x| solution.value(x)).sum();
+        assert_float_eq!(placed, 1., abs <= 1e-6);
+    }
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn bin_packing_with_too_few_bins() {
+    let model = bin_packing(&[6., 6., 6.], 10., 2);
+    let used = model.used[0];
+    assert_eq!(
+        model
+            .into_problem()
+            .using(default_solver)
+            .solve()
+            .map(|solution| solution.value(used)),
+        Err(ResolutionError::Infeasible)
+    );
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn set_packing_selects_disjoint_sets() {
+    let model = set_packing(vec![
+        ('a', 3., vec![1, 2]),
+        ('b', 2., vec![2, 3]),
+        ('c', 2., vec![3, 4]),
+    ]);
+    assert_eq!(model.elements.len(), 4);
+    let (problem, _) = model.clone().into_problem();
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert_eq!(model.selected(&solution), vec!['a', 'c']);
+}
+
+#[test]
+#[cfg(feature = "highs")]
+fn relaxed_set_covering_prices_the_elements() {
+    use good_lp::modeling::set_covering;
+    use good_lp::solvers::{DualValues, SolutionWithDual};
+    let model = set_covering(vec![("cheap", 2., vec!["x"]), ("expensive", 3., vec!["x"])]).relax();
+    let (problem, references) = model.into_problem();
+    let mut solution = problem.using(good_lp::highs).solve().unwrap();
+    // One more unit of coverage of x costs 2
+    let price = solution.compute_dual().dual(references["x"]);
+    assert_float_eq!(price.abs(), 2., abs <= 1e-6);
+}
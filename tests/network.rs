@@ -0,0 +1,45 @@
+use float_eq::assert_float_eq;
+use good_lp::network::{MaxFlow, MinCostFlow};
+use good_lp::{constraint, default_solver, variables, ResolutionError, Solution, SolverModel};
+
+#[test]
+fn max_flow_is_limited_by_the_minimum_cut() {
+    // The two arcs entering node 3 form the minimum cut, of capacity 2 + 3
+    let network = MaxFlow::new(4, 0, 3)
+        .arc(0, 1, 10.)
+        .arc(0, 2, 10.)
+        .arc(1, 2, 1.)
+        .arc(1, 3, 2.)
+        .arc(2, 3, 3.);
+    let flow = network.solve(default_solver).unwrap();
+    assert_float_eq!(flow.objective(), 5., abs <= 1e-6);
+    assert_float_eq!(flow.flow(3) + flow.flow(4), 5., abs <= 1e-6);
+}
+
+#[test]
+fn unbalanced_supplies_are_infeasible() {
+    let network = MinCostFlow::new().node(2.).node(-1.).arc(0, 1, 5., 1.);
+    assert_eq!(
+        network.solve(default_solver).map(|flow| flow.objective()),
+        Err(ResolutionError::Infeasible)
+    );
+}
+
+#[test]
+fn min_cost_flow_with_side_constraint() {
+    let network = MinCostFlow::new()
+        .node(4.)
+        .node(-4.)
+        .arc(0, 1, f64::INFINITY, 1.)
+        .arc(0, 1, f64::INFINITY, 2.);
+    let mut vars = variables!();
+    let model = network.model(&mut vars);
+    assert_eq!(model.conservation.len(), 2);
+    let (cheap, expensive) = (model.flows[0], model.flows[1]);
+    let mut problem = model.into_problem(vars);
+    // At most half of the flow on the cheap arc
+    problem.add_constraint(constraint!(cheap <= expensive));
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert_float_eq!(solution.value(cheap), 2., abs <= 1e-6);
+    assert_float_eq!(solution.value(expensive), 2., abs <= 1e-6);
+}
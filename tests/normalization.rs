@@ -0,0 +1,43 @@
+//! Every backend receives constraints with merged duplicate terms,
+//! and with the same sign convention, so they all find the same solution.
+use float_eq::assert_float_eq;
+use good_lp::{constraint, variables, Solution, SolverModel, StaticSolver};
+
+fn duplicate_and_cancelled_terms<S: StaticSolver>(solver: S) {
+    variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+    let solution = vars
+        .maximise(x + x + y)
+        .using(solver)
+        // 3 x <= 6, y has no coefficient
+        .with(constraint!(x + 2 * x + y - y <= 6))
+        // y <= 4
+        .with(constraint!(8 - y >= y))
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 2., abs <= 1e-6);
+    assert_float_eq!(solution.value(y), 4., abs <= 1e-6);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn coin_cbc() {
+    duplicate_and_cancelled_terms(good_lp::coin_cbc)
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn minilp() {
+    duplicate_and_cancelled_terms(good_lp::minilp)
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn lp_solve() {
+    duplicate_and_cancelled_terms(good_lp::lp_solve)
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn highs() {
+    duplicate_and_cancelled_terms(good_lp::highs)
+}
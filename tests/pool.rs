@@ -0,0 +1,141 @@
+use good_lp::pool::{alternative_optima, random_feasible};
+use good_lp::{constraint, default_solver, variable, variables, Solution};
+// Only used by the tests with integer variables, which minilp and highs do not support
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+use good_lp::{pool::solution_pool, Expression};
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn enumerates_distinct_solutions_in_objective_order() {
+    let mut vars = variables!();
+    let x = vars.add_vector(variable().binary(), 4);
+    let total: Expression = x.iter().sum();
+    let weighted: Expression = x
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64 + 1.) * v)
+        .sum();
+    let problem = vars
+        .maximise(weighted.clone())
+        .with(constraint!(total == 2));
+    let pool = solution_pool(problem)
+        .with_solution_limit(4)
+        .solve(default_solver)
+        .unwrap();
+    assert_eq!(pool.len(), 4);
+    let objectives: Vec<f64> = pool.iter().map(|s| s.objective().round()).collect();
+    assert_eq!(objectives, vec![7., 6., 5., 5.]);
+    let chosen: Vec<Vec<bool>> = pool
+        .iter()
+        .map(|s| x.iter().map(|&v| s.value(v) > 0.5).collect())
+        .collect();
+    for (i, a) in chosen.iter().enumerate() {
+        assert!(chosen[i + 1..].iter().all(|b| a != b));
+    }
+    assert_eq!(pool.eval(&weighted).round(), 7.);
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn stops_at_objective_loss() {
+    let mut vars = variables!();
+    let x = vars.add_vector(variable().binary(), 3);
+    let weighted: Expression = x
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64 + 1.) * v)
+        .sum();
+    let pool = solution_pool(vars.minimise(weighted))
+        .with_solution_limit(10)
+        .max_objective_loss(1.5)
+        .solve(default_solver)
+        .unwrap();
+    // 0, then {x0} = 1
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.alternatives()[0].objective().round(), 1.);
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn stops_when_all_solutions_are_found() {
+    let mut vars = variables!();
+    let a = vars.add(variable().binary());
+    let b = vars.add(variable().binary());
+    let problem = vars.maximise(a + b).with(constraint!(a + b <= 1));
+    let pool = solution_pool(problem)
+        .with_solution_limit(10)
+        .solve(default_solver)
+        .unwrap();
+    assert_eq!(pool.len(), 3);
+}
+
+#[test]
+fn unique_optimum_has_no_alternatives() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 4));
+    let y = vars.add(variable().clamp(0, 4));
+    let problem = vars.maximise(2 * x + y).with(constraint!(x + y <= 5));
+    let optima = alternative_optima(problem).solve(default_solver).unwrap();
+    assert_eq!(optima.optimum().round(), 9.);
+    assert!(optima.undetermined(1e-6).is_empty());
+    assert_eq!(optima.vertices().len(), 1);
+}
+
+#[test]
+fn tolerance_explores_near_optimal_solutions() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 4));
+    let y = vars.add(variable().clamp(0, 4));
+    let problem = vars.maximise(2 * x + y).with(constraint!(x + y <= 5));
+    let optima = alternative_optima(problem)
+        .variables(&[x])
+        .objective_tolerance(1.)
+        .solve(default_solver)
+        .unwrap();
+    let (min_x, max_x) = optima.range(x).unwrap();
+    assert!((min_x - 3.).abs() < 1e-6, "{}", min_x);
+    assert!((max_x - 4.).abs() < 1e-6, "{}", max_x);
+    assert_eq!(optima.range(y), None);
+}
+
+/// A deterministic source of numbers in [0, 1)
+fn lcg(seed: u64) -> impl FnMut() -> f64 {
+    let mut state = seed;
+    move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+#[test]
+fn random_samples_are_feasible_and_diverse() {
+    variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+    let problem = vars.minimise(x).with(constraint!(x + y >= 5));
+    let samples = random_feasible(problem, lcg(42), 10, default_solver).unwrap();
+    assert_eq!(samples.len(), 10);
+    for sample in &samples {
+        assert!(sample.value(x) + sample.value(y) >= 5. - 1e-6);
+        assert_eq!(sample.objective(), sample.value(x));
+    }
+    let first = &samples[0];
+    assert!(samples
+        .iter()
+        .any(|sample| (sample.value(x) - first.value(x)).abs() > 1e-3));
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn random_integer_samples_are_vertices() {
+    let mut vars = variables!();
+    let x = vars.add_vector(variable().integer().clamp(0, 3), 3);
+    let total: Expression = x.iter().sum();
+    let problem = vars.maximise(total.clone()).with(constraint!(total <= 4));
+    let samples = random_feasible(problem, lcg(1), 5, default_solver).unwrap();
+    for sample in &samples {
+        let values: Vec<f64> = x.iter().map(|&v| sample.value(v)).collect();
+        assert!(values.iter().all(|v| (v - v.round()).abs() < 1e-6));
+        assert!(values.iter().sum::<f64>() <= 4. + 1e-6);
+    }
+}
@@ -0,0 +1,22 @@
+use good_lp::postprocess::{multiple_of, ValueProcessing};
+use good_lp::{constraint, variables, Solution};
+use std::collections::HashMap;
+
+#[test]
+fn group_transform_and_bounds_check() {
+    variables! {vars: 0 <= a <= 10; 0 <= b <= 10; c;}
+    let problem = vars.minimise(a + b + c).with(constraint!(a + b >= 7));
+    let solution: HashMap<_, _> = vec![(a, 3.4), (b, 9.6), (c, 0.3)].into_iter().collect();
+    let processing = ValueProcessing::new().transform_all(&[a, b], multiple_of(5.));
+    let processed = processing.apply(&solution);
+    assert_eq!(processed.value(a), 5.);
+    assert_eq!(processed.value(b), 10.);
+    assert_eq!(processed.value(c), 0.3);
+    assert_eq!(processed.raw().value(a), 3.4);
+    assert!(processing.check(&problem, &solution, 1e-9).is_feasible());
+
+    let processing = processing.transform(b, |value| value.ceil() + 1.);
+    let check = processing.check(&problem, &solution, 1e-9);
+    assert_eq!(check.violated_bounds, vec![b]);
+    assert!(check.violated_constraints.is_empty());
+}
@@ -0,0 +1,54 @@
+use float_eq::assert_float_eq;
+
+use good_lp::default_solver;
+use good_lp::power::{dc_opf, Bus, Generator, Line};
+
+fn line(from: usize, to: usize, capacity: f64) -> Line {
+    Line {
+        from,
+        to,
+        reactance: 1.,
+        capacity,
+    }
+}
+
+fn generator(bus: usize, cost: f64) -> Generator {
+    Generator {
+        bus,
+        min_output: 0.,
+        max_output: 200.,
+        cost,
+    }
+}
+
+#[test]
+fn flows_follow_impedances() {
+    // A triangle: the direct path 0 -> 2 has half the impedance of the path 0 -> 1 -> 2
+    let buses = [Bus { demand: 0. }, Bus { demand: 0. }, Bus { demand: 90. }];
+    let lines = [line(0, 1, 100.), line(1, 2, 100.), line(0, 2, 100.)];
+    let generators = [generator(0, 1.)];
+    let dispatch = dc_opf(&buses, &lines, &generators)
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(dispatch.output(0), 90., abs <= 1e-6);
+    assert_float_eq!(dispatch.flow(0), 30., abs <= 1e-6);
+    assert_float_eq!(dispatch.flow(1), 30., abs <= 1e-6);
+    assert_float_eq!(dispatch.flow(2), 60., abs <= 1e-6);
+    assert_float_eq!(dispatch.angle(0), 0., abs <= 1e-6);
+    assert_float_eq!(dispatch.angle(2), -60., abs <= 1e-6);
+}
+
+#[test]
+fn congestion_requires_expensive_generation() {
+    // Limiting the direct line to 40 also limits the whole transfer,
+    // because flows split 2:1 between the two paths
+    let buses = [Bus { demand: 0. }, Bus { demand: 0. }, Bus { demand: 90. }];
+    let lines = [line(0, 1, 100.), line(1, 2, 100.), line(0, 2, 40.)];
+    let generators = [generator(0, 1.), generator(2, 10.)];
+    let dispatch = dc_opf(&buses, &lines, &generators)
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(dispatch.output(0), 60., abs <= 1e-6);
+    assert_float_eq!(dispatch.output(1), 30., abs <= 1e-6);
+    assert_float_eq!(dispatch.total_cost(&generators), 360., abs <= 1e-6);
+}
@@ -0,0 +1,68 @@
+use good_lp::probing::probe;
+use good_lp::{constraint, variable, variables, ResolutionError};
+
+#[test]
+fn binary_is_fixed_to_zero() {
+    let mut vars = variables!();
+    let express = vars.add(variable().binary());
+    let delay = vars.add(variable().clamp(0, 10));
+    let problem = vars
+        .minimise(delay - express)
+        // the express option is only possible without delay
+        .with(constraint!(delay <= 10 - 10 * express))
+        .with(constraint!(delay >= 5));
+    let probed = probe(problem).unwrap();
+    assert_eq!(probed.fixed(), &[(express, 0.)]);
+}
+
+#[test]
+fn implications_are_chained() {
+    let mut vars = variables!();
+    let a = vars.add(variable().binary());
+    let b = vars.add(variable().binary());
+    let c = vars.add(variable().binary());
+    let problem = vars
+        .maximise(a + b + c)
+        .with(constraint!(a + b >= 2))
+        .with(constraint!(b + c <= 1));
+    let probed = probe(problem).unwrap();
+    assert_eq!(probed.fixed(), &[(a, 1.), (b, 1.), (c, 0.)]);
+}
+
+#[test]
+fn contradictions_are_infeasible() {
+    let mut vars = variables!();
+    let a = vars.add(variable().binary());
+    let b = vars.add(variable().binary());
+    let problem = vars.maximise(a + b).with(constraint!(a + b >= 3));
+    assert_eq!(probe(problem).err(), Some(ResolutionError::Infeasible));
+}
+
+#[test]
+fn free_binaries_are_kept() {
+    let mut vars = variables!();
+    let a = vars.add(variable().binary());
+    let b = vars.add(variable().binary());
+    let problem = vars.maximise(a + b).with(constraint!(a + b <= 1));
+    assert!(probe(problem).unwrap().fixed().is_empty());
+}
+
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+#[test]
+fn probed_problem_has_the_same_optimum() {
+    use good_lp::{default_solver, Solution, SolverModel};
+    let mut vars = variables!();
+    let open: Vec<_> = vars.add_vector(variable().binary(), 3);
+    let production = vars.add(variable().clamp(0, 100));
+    let problem = vars
+        .minimise(10 * open[0] + 5 * open[1] + open[2] + production)
+        .with(constraint!(production <= 100 * open[0]))
+        .with(constraint!(production >= 30))
+        .with(constraint!(open[1] + open[2] >= 1));
+    let direct = problem.clone().using(default_solver).solve().unwrap();
+    let probed = probe(problem).unwrap();
+    assert_eq!(probed.fixed(), &[(open[0], 1.)]);
+    let solution = probed.into_problem().using(default_solver).solve().unwrap();
+    assert_eq!(solution.value(open[0]), 1.);
+    assert_eq!(direct.value(open[2]), solution.value(open[2]));
+}
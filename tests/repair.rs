@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use float_eq::assert_float_eq;
+use good_lp::repair::{min_change, project, Distance};
+use good_lp::{constraint, default_solver, variable, variables, Expression, Solution};
+
+#[test]
+fn unchanged_when_still_feasible() {
+    let mut vars = variables!();
+    let x = vars.add_vector(variable().clamp(0, 10), 3);
+    let old: HashMap<_, _> = x.iter().copied().zip(vec![1., 2., 3.]).collect();
+    let total: Expression = x.iter().sum();
+    let problem = vars.maximise(total.clone()).with(constraint!(total <= 20));
+    let repaired = min_change(problem, &old).solve(default_solver).unwrap();
+    assert_float_eq!(repaired.deviation(), 0., abs <= 1e-6);
+    assert!(repaired.changed(1e-6).is_empty());
+}
+
+#[test]
+fn zero_weight_variables_absorb_the_change() {
+    let mut vars = variables!();
+    let x = vars.add_vector(variable().clamp(0, 10), 3);
+    let old: HashMap<_, _> = x.iter().copied().zip(vec![4., 4., 4.]).collect();
+    let total: Expression = x.iter().sum();
+    let problem = vars.minimise(0).with(constraint!(total == 15));
+    let repaired = min_change(problem, &old)
+        .weight(x[2], 0.)
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(repaired.value(x[2]), 7., abs <= 1e-6);
+    assert_eq!(repaired.changed(1e-6), vec![x[2]]);
+    assert_float_eq!(repaired.deviation(), 0., abs <= 1e-6);
+}
+
+#[test]
+fn objective_breaks_ties() {
+    let mut vars = variables!();
+    let a = vars.add(variable().clamp(0, 10));
+    let b = vars.add(variable().clamp(0, 10));
+    let old: HashMap<_, _> = vec![(a, 5.), (b, 5.)].into_iter().collect();
+    // One of the two has to decrease by 2, the objective prefers to keep a
+    let problem = vars.maximise(2 * a + b).with(constraint!(a + b <= 8));
+    let repaired = min_change(problem, &old)
+        .objective_weight(0.01)
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(repaired.value(a), 5., abs <= 1e-6);
+    assert_float_eq!(repaired.value(b), 3., abs <= 1e-6);
+    assert_float_eq!(repaired.deviation(), 2., abs <= 1e-6);
+}
+
+#[test]
+fn projection_ignores_variables_without_target() {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 10));
+    let y = vars.add(variable().clamp(0, 10));
+    let z = vars.add(variable().clamp(0, 10));
+    let problem = vars
+        .minimise(z)
+        .with(constraint!(x + y <= 6))
+        .with(constraint!(z >= x));
+    let target: HashMap<_, _> = vec![(x, 8.), (y, 2.)].into_iter().collect();
+    let l1 = project(problem.clone(), &target, Distance::L1)
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(l1.deviation(), 4., abs <= 1e-6);
+    assert!(l1.value(z) >= l1.value(x) - 1e-6);
+    let linf = project(problem, &target, Distance::LInfinity)
+        .solve(default_solver)
+        .unwrap();
+    assert_float_eq!(linf.value(x), 6., abs <= 1e-6);
+    assert_float_eq!(linf.value(y), 0., abs <= 1e-6);
+    assert_float_eq!(linf.deviation(), 2., abs <= 1e-6);
+}
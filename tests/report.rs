@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use good_lp::report::{generate, ReportFormat, ReportOptions};
+use good_lp::{constraint, variable, variables, Variable};
+
+fn problem() -> (good_lp::variable::UnsolvedProblem, Variable, Variable) {
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 4).name("x"));
+    let n = vars.add(variable().integer().clamp(0, 10).name("n|<count>"));
+    let problem = vars
+        .maximise(3 * x + n)
+        .with(constraint!(x + n <= 10).set_name("capacity"))
+        .with(constraint!(x - n <= 3).set_name("balance"));
+    (problem, x, n)
+}
+
+#[test]
+fn reports_violations() {
+    let (problem, x, n) = problem();
+    // Not a real solution: x is above its bound, n is fractional, and capacity is exceeded
+    let solution: HashMap<Variable, f64> = vec![(x, 5.), (n, 6.5)].into_iter().collect();
+    let report = generate(&problem, &solution, &ReportOptions::default());
+    assert!(report.contains("3 violations"), "{}", report);
+    assert!(report.contains("| x | upper bound | 1 |"));
+    assert!(report.contains("| n\\|<count> | integrality | 0.5 |"));
+    assert!(report.contains("| capacity | constraint | 1.5 |"));
+}
+
+#[test]
+fn binding_constraints_and_breakdown() {
+    let (problem, x, n) = problem();
+    let solution: HashMap<Variable, f64> = vec![(x, 4.), (n, 6.)].into_iter().collect();
+    let report = generate(&problem, &solution, &ReportOptions::default().top(1));
+    assert!(report.contains("No violation"));
+    assert!(report.contains("1 of 2 inequality constraints are binding."));
+    assert!(report.contains("| capacity | 10 | 10 |"));
+    // Only the largest contribution is listed
+    assert!(report.contains("The 1 largest of 2 contributions:"));
+    assert!(report.contains("| x | 3 | 4 | 12 | 66.7% |"));
+}
+
+#[test]
+fn duals_and_html() {
+    let (problem, x, n) = problem();
+    let solution: HashMap<Variable, f64> = vec![(x, 4.), (n, 6.)].into_iter().collect();
+    let options = ReportOptions::default()
+        .format(ReportFormat::Html)
+        .title("A & B")
+        .duals(vec![-1., 0.]);
+    let report = generate(&problem, &solution, &options);
+    assert!(report.contains("<h1>A &amp; B</h1>"));
+    assert!(report.contains("<tr><td>capacity</td><td>-1</td></tr>"));
+    assert!(report.contains("n|&lt;count&gt;"));
+    assert!(!report.contains("<td>balance</td><td>0</td>"));
+}
@@ -0,0 +1,64 @@
+// minilp and highs don't support integer variables
+#![cfg(not(any(feature = "minilp", feature = "highs")))]
+use good_lp::default_solver;
+use good_lp::rostering::{Fairness, Roster};
+
+#[test]
+fn coverage_is_met() {
+    let roster = Roster::new(4, 5, 3)
+        .coverage_every_day(0, 2)
+        .coverage_every_day(1, 1)
+        .coverage(4, 2, 1)
+        .solve(default_solver)
+        .unwrap();
+    for day in 0..5 {
+        assert_eq!(roster.workers_on(day, 0).len(), 2);
+        assert_eq!(roster.workers_on(day, 1).len(), 1);
+    }
+    assert_eq!(roster.workers_on(4, 2).len(), 1);
+    assert_eq!(roster.total_shifts(), 16);
+}
+
+#[test]
+fn consecutive_days_and_rest_periods() {
+    let roster = Roster::new(3, 6, 2)
+        .coverage_every_day(0, 1)
+        .coverage_every_day(1, 1)
+        .max_consecutive_days(2)
+        .forbid_sequence(1, 0)
+        .unavailable(0, 0)
+        .solve(default_solver)
+        .unwrap();
+    assert_eq!(roster.shift(0, 0), None);
+    for worker in 0..3 {
+        let shifts = &roster.shifts()[worker];
+        for window in shifts.windows(3) {
+            assert!(window.iter().any(Option::is_none), "{:?}", shifts);
+        }
+        for pair in shifts.windows(2) {
+            assert_ne!((pair[0], pair[1]), (Some(1), Some(0)), "{:?}", shifts);
+        }
+    }
+}
+
+#[test]
+fn fairness_balances_workload() {
+    let roster = Roster::new(3, 3, 1)
+        .coverage_every_day(0, 2)
+        .fairness(Fairness::MinimiseRange, 1.)
+        .solve(default_solver)
+        .unwrap();
+    assert_eq!(
+        (0..3).map(|w| roster.shifts_of(w)).collect::<Vec<_>>(),
+        vec![2, 2, 2]
+    );
+}
+
+#[test]
+fn infeasible_coverage() {
+    let result = Roster::new(2, 3, 1)
+        .coverage_every_day(0, 2)
+        .max_consecutive_days(2)
+        .solve(default_solver);
+    assert!(result.is_err());
+}
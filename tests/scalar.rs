@@ -0,0 +1,88 @@
+use float_eq::assert_float_eq;
+use good_lp::scalar::{NotRepresentable, ScalarExpression};
+use good_lp::{constraint, default_solver, variables, Solution, SolverModel};
+
+#[test]
+fn arithmetic_stays_in_the_scalar_type() {
+    variables! {vars: x; y;}
+    let e = ScalarExpression::term(x, 3) - ScalarExpression::from(y) * 2
+        + ScalarExpression::constant(7);
+    assert_eq!(e.coefficient(x), 3);
+    assert_eq!(e.coefficient(y), -2);
+    assert_eq!(*e.get_constant(), 7);
+    let cancelled = e.clone() - ScalarExpression::term(x, 3);
+    assert_eq!(cancelled.coefficient(x), 0);
+    let expression = e.to_expression().unwrap();
+    let values: std::collections::HashMap<_, _> = vec![(x, 1.), (y, 2.)].into_iter().collect();
+    assert_float_eq!(values.eval(&expression), 6., abs <= 1e-9);
+}
+
+#[test]
+fn f32_coefficients_are_solved() {
+    variables! {vars: 0 <= x <= 4;}
+    let limit = ScalarExpression::term(x, 0.5f32) - 1.5f32;
+    let solution = vars
+        .maximise(x)
+        .using(default_solver)
+        .with(constraint!(limit <= 0.))
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 3., abs <= 1e-6);
+}
+
+#[test]
+fn scalar_expressions_are_used_as_expressions() {
+    variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+    let cost = ScalarExpression::term(x, 2) + y;
+    let solution = vars
+        .maximise(cost.clone() - y)
+        .using(default_solver)
+        .with(constraint!(cost.clone() <= 12))
+        .with(constraint!(y >= ScalarExpression::constant(4)))
+        .with(constraint!(x <= cost - x + 0.5 * y))
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 4., abs <= 1e-6);
+    assert_float_eq!(solution.value(y), 4., abs <= 1e-6);
+}
+
+#[test]
+fn infinite_constants_are_rejected() {
+    let constant = ScalarExpression::constant(f64::INFINITY);
+    assert_eq!(
+        constant.to_expression().unwrap_err(),
+        NotRepresentable { variable: None }
+    );
+}
+
+#[test]
+#[cfg(feature = "exact")]
+fn rational_coefficients() {
+    use num_rational::Rational64;
+    variables! {vars: x;}
+    let third = ScalarExpression::term(x, Rational64::new(1, 3));
+    let sum = third.clone() + third.clone() + third;
+    assert_eq!(sum.coefficient(x), Rational64::from_integer(1));
+    let values: std::collections::HashMap<_, _> = vec![(x, 2.)].into_iter().collect();
+    assert_float_eq!(values.eval(sum.to_expression().unwrap()), 2., abs <= 1e-9);
+}
+
+#[test]
+#[cfg(feature = "decimal")]
+fn decimal_coefficients() {
+    use rust_decimal::Decimal;
+    variables! {vars: x <= 100;}
+    let tenth = Decimal::new(1, 1);
+    let price = ScalarExpression::term(x, tenth) + ScalarExpression::term(x, Decimal::new(2, 1));
+    // 0.1 + 0.2 is exactly 0.3 in decimal arithmetic
+    assert_eq!(price.coefficient(x), Decimal::new(3, 1));
+    let values: std::collections::HashMap<_, _> = vec![(x, 10.)].into_iter().collect();
+    assert_float_eq!(values.eval(price.to_expression().unwrap()), 3., abs <= 1e-9);
+    let solution = vars
+        .maximise(x)
+        .using(default_solver)
+        .with(constraint!(price <= Decimal::new(15, 1)))
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 5., abs <= 1e-6);
+}
@@ -0,0 +1,40 @@
+// minilp and highs do not support integer variables
+#![cfg(not(any(feature = "minilp", feature = "highs")))]
+use float_eq::assert_float_eq;
+use good_lp::scheduling::{no_overlap, Task};
+use good_lp::{default_solver, variables, Solution, SolverModel};
+
+#[test]
+fn makespan_with_release_dates_and_deadlines() {
+    let mut vars = variables!();
+    let tasks = [
+        Task::new(4.),
+        Task::new(2.).release(5.),
+        Task::new(3.).deadline(3.),
+    ];
+    let machine = no_overlap(&mut vars, &tasks);
+    assert_eq!(machine.order.len(), 3);
+    assert_eq!(machine.constraints.len(), 6);
+    let (makespan, makespan_constraints) = machine.makespan(&mut vars);
+    let mut problem = vars.minimise(makespan);
+    for constraint in machine.constraints.iter().chain(&makespan_constraints) {
+        problem.add_constraint(constraint.clone());
+    }
+    let solution = problem.using(default_solver).solve().unwrap();
+    // Task 2 has to be done first, then task 0 ends at 7, which leaves task 1 waiting
+    assert_float_eq!(solution.value(machine.starts[2]), 0., abs <= 1e-6);
+    assert_float_eq!(solution.value(machine.starts[0]), 3., abs <= 1e-6);
+    assert_float_eq!(solution.value(makespan), 9., abs <= 1e-6);
+}
+
+#[test]
+fn tasks_overlapping_every_schedule_are_infeasible() {
+    let mut vars = variables!();
+    let tasks = [Task::new(2.).deadline(2.), Task::new(2.).deadline(3.)];
+    let machine = no_overlap(&mut vars, &tasks);
+    let mut problem = vars.minimise(machine.starts[0]);
+    for constraint in machine.constraints.iter().cloned() {
+        problem.add_constraint(constraint);
+    }
+    assert!(problem.using(default_solver).solve().is_err());
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "coin_cbc")]
+use good_lp::{coin_cbc, constraint, variable, variables, ModelWithSeed, Solution, SolverModel};
+
+#[test]
+fn same_seed_same_solution() {
+    let solve = |seed| {
+        let mut vars = variables!();
+        let x = vars.add_vector(variable().binary(), 8);
+        let total: good_lp::Expression = x.iter().sum();
+        let solution = vars
+            .maximise(total.clone())
+            .using(coin_cbc)
+            .with(constraint!(total <= 3))
+            .with_seed(seed)
+            .solve()
+            .unwrap();
+        x.iter().map(|&v| solution.value(v)).collect::<Vec<_>>()
+    };
+    let first = solve(u64::MAX);
+    assert_eq!(first.iter().sum::<f64>(), 3.);
+    assert_eq!(first, solve(u64::MAX));
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "serde")]
+
+use float_eq::assert_float_eq;
+
+use good_lp::snapshot::SolutionSnapshot;
+use good_lp::variable::UnsolvedProblem;
+use good_lp::{
+    constraint, default_solver, variable, variables, Constraint, Expression, ProblemVariables,
+    Solution, SolverModel,
+};
+
+#[test]
+fn expressions_and_constraints_roundtrip() {
+    variables! {vars: x; y;}
+    let expression: Expression = 2 * x - y + 3;
+    let json = serde_json::to_string(&expression).unwrap();
+    let read: Expression = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, expression);
+
+    let constraint = constraint!(x + y <= 4).set_name("capacity".to_string());
+    let json = serde_json::to_string(&constraint).unwrap();
+    let read: Constraint = serde_json::from_str(&json).unwrap();
+    assert!(read == constraint);
+    assert_eq!(read.get_name(), Some("capacity"));
+    assert_eq!(vars.len(), 2);
+}
+
+#[test]
+fn variables_roundtrip() {
+    let mut vars = variables!();
+    vars.add(variable().name("x").integer().clamp(1, 5));
+    vars.add(variable().name("y").min(-2));
+    vars.add(variable().name("z"));
+    let json = serde_json::to_string(&vars).unwrap();
+    let read: ProblemVariables = serde_json::from_str(&json).unwrap();
+    let definitions: Vec<_> = read.into_iter().collect();
+    let expected: Vec<_> = vars.into_iter().collect();
+    assert_eq!(definitions, expected);
+}
+
+#[test]
+fn problem_can_be_solved_after_deserialization() {
+    variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+    let problem = vars
+        .maximise(x + 2 * y)
+        .with(constraint!(x + y <= 12))
+        .with(constraint!(y <= 2 * x));
+    let json = serde_json::to_string(&problem).unwrap();
+    let read: UnsolvedProblem = serde_json::from_str(&json).unwrap();
+    let solution = read.using(default_solver).solve().unwrap();
+    assert_float_eq!(solution.value(x), 4., abs <= 1e-6);
+    assert_float_eq!(solution.value(y), 8., abs <= 1e-6);
+}
+
+#[test]
+fn unknown_variables_are_rejected() {
+    variables! {vars: x;}
+    let mut json = serde_json::to_value(vars.minimise(x)).unwrap();
+    json["variables"] = serde_json::json!([]);
+    let result: Result<UnsolvedProblem, _> = serde_json::from_value(json);
+    assert!(result.is_err());
+}
+
+#[test]
+fn solution_snapshot_roundtrip() {
+    let mut vars = variables!();
+    let x = vars.add(variable().name("x").max(3));
+    let objective: Expression = 2 * x + 1;
+    let variables = vars.clone();
+    let solution = vars
+        .maximise(objective.clone())
+        .using(default_solver)
+        .solve()
+        .unwrap();
+    let snapshot = SolutionSnapshot::new(&solution, &variables, &objective);
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(
+        json,
+        r#"{"status":"Optimal","objective":7.0,"values":{"x":3.0}}"#
+    );
+    let read: SolutionSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, snapshot);
+}
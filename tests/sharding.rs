@@ -0,0 +1,63 @@
+use good_lp::sharding::{shard_and_solve, split};
+use good_lp::{constraint, default_solver, variable, variables, Solution, SolverModel};
+
+#[test]
+fn independent_blocks_are_split() {
+    let mut vars = variables!();
+    let a = vars.add(variable().max(3));
+    let b = vars.add(variable().max(3));
+    let c = vars.add(variable().max(3));
+    let free = vars.add(variable().max(3));
+    let d = vars.add(variable().max(3));
+    let problem = vars
+        .maximise(a + b + c + free + d + 10)
+        .with(constraint!(a + c <= 4))
+        .with(constraint!(b + d <= 5))
+        .with(constraint!(c <= 2));
+    let shards = split(problem);
+    let variables: Vec<_> = shards.iter().map(|s| s.variables.clone()).collect();
+    assert_eq!(variables, vec![vec![a, c], vec![b, d], vec![free]]);
+    assert_eq!(shards[0].problem.classify().constraints, 2);
+    assert_eq!(shards[1].problem.classify().constraints, 1);
+    assert_eq!(shards[2].problem.classify().constraints, 0);
+}
+
+#[test]
+fn sharded_solution_matches_the_whole_problem() {
+    let build = || {
+        let mut vars = variables!();
+        let x = vars.add_vector(variable().min(0), 6);
+        let objective = 3 * x[0] + 2 * x[1] + x[2] + 4 * x[3] + x[4] + 5 * x[5] - 7;
+        let problem = vars
+            .maximise(objective.clone())
+            .with(constraint!(x[0] + x[1] <= 4))
+            .with(constraint!(x[0] - x[1] <= 1))
+            .with(constraint!(x[2] + 2 * x[3] <= 6))
+            .with(constraint!(x[3] <= 2))
+            .with(constraint!(x[4] + x[5] <= 1));
+        (problem, x, objective)
+    };
+    let (problem, x, objective) = build();
+    let sharded = shard_and_solve(problem, default_solver).unwrap();
+    assert_eq!(sharded.shards(), 3);
+    let (problem, _, _) = build();
+    let whole = problem.using(default_solver).solve().unwrap();
+    assert!((sharded.eval(&objective) - whole.eval(&objective)).abs() < 1e-6);
+    for &var in &x {
+        assert!((sharded.value(var) - whole.value(var)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn an_infeasible_shard_makes_the_problem_infeasible() {
+    let mut vars = variables!();
+    let x = vars.add(variable());
+    let y = vars.add(variable());
+    let problem = vars
+        .maximise(x + y)
+        .with(constraint!(x <= 1))
+        .with(constraint!(y >= 2))
+        .with(constraint!(y <= 1));
+    let result = shard_and_solve(problem, default_solver);
+    assert_eq!(result.err(), Some(good_lp::ResolutionError::Infeasible));
+}
@@ -0,0 +1,87 @@
+use float_eq::assert_float_eq;
+use good_lp::solvers::ObjectiveDirection;
+use good_lp::stochastic::{extensive_form, progressive_hedging, ScenarioTree};
+use good_lp::{constraint, default_solver, variable};
+
+/// A binary tree with three stages: two scenarios after the first period, four after the second
+fn binary_tree() -> (ScenarioTree, Vec<usize>) {
+    let mut tree = ScenarioTree::new();
+    let mut leaves = vec![];
+    for _ in 0..2 {
+        let middle = tree.add_child(tree.root(), 0.5);
+        for _ in 0..2 {
+            leaves.push(tree.add_child(middle, 0.5));
+        }
+    }
+    (tree, leaves)
+}
+
+#[test]
+fn tree_structure() {
+    let (tree, leaves) = binary_tree();
+    assert_eq!(tree.len(), 7);
+    assert_eq!(tree.scenarios(), leaves);
+    assert_eq!(tree.path(leaves[3]), vec![0, 4, 6]);
+    assert_eq!(tree.stage(leaves[0]), 2);
+    assert_float_eq!(tree.probability(leaves[2]), 0.25, abs <= 1e-12);
+}
+
+#[test]
+fn decisions_only_depend_on_the_past() {
+    // Inventory: buy at each stage, cover a demand revealed at stages 1 and 2.
+    // Buying early is cheaper than buying late.
+    let (tree, leaves) = binary_tree();
+    let demand = |node: usize| match node {
+        1 => 10.,
+        4 => 20.,
+        n if leaves.contains(&n) => (n % 3) as f64 * 10.,
+        _ => 0.,
+    };
+    let model = extensive_form(&tree, ObjectiveDirection::Minimisation, |scenario| {
+        let mut stock = good_lp::Expression::default();
+        for stage in 0..scenario.stages() {
+            let cost = (stage + 1) as f64;
+            let buy = scenario.add(stage, variable().min(0));
+            stock += buy;
+            stock -= demand(scenario.node(stage));
+            scenario.add_constraint(constraint!(stock.clone() >= 0));
+            scenario.add_objective(cost * buy);
+        }
+    });
+    assert_eq!(model.scenario_variables(0, 0).len(), 1);
+    let solution = model.solve(default_solver).unwrap();
+    // All four scenarios share the root decision, pairs of scenarios share the middle one
+    let root = solution.node_values(tree.root())[0];
+    assert!(root >= 10. - 1e-6);
+    for &leaf in &leaves {
+        let path = tree.path(leaf);
+        let bought: f64 = path.iter().map(|&n| solution.node_values(n)[0]).sum();
+        let needed: f64 = path.iter().map(|&n| demand(n)).sum();
+        assert!(bought >= needed - 1e-6);
+    }
+    let expected: f64 = (0..4).map(|s| 0.25 * solution.scenario_objective(s)).sum();
+    assert_float_eq!(solution.expected_objective(), expected, abs <= 1e-6);
+}
+
+#[test]
+fn progressive_hedging_agrees_with_the_extensive_form() {
+    let mut tree = ScenarioTree::new();
+    let low = tree.add_child(tree.root(), 0.5);
+    tree.add_child(tree.root(), 0.5);
+    let demand = |node| if node == low { 50. } else { 100. };
+    let hedging = progressive_hedging(&tree, ObjectiveDirection::Maximisation, |scenario| {
+        let capacity = scenario.add(0, variable().min(0));
+        let sales = scenario.add(1, variable().min(0));
+        scenario.add_constraint(constraint!(sales <= capacity));
+        scenario.add_constraint(constraint!(sales <= demand(scenario.node(1))));
+        scenario.add_objective(3 * sales - 2 * capacity);
+    })
+    .penalty(0.5)
+    .max_iterations(200)
+    .tolerance(1e-3);
+    let result = hedging.solve(default_solver).unwrap();
+    assert!(result.converged, "gaps: {:?}", result.gaps);
+    assert_eq!(result.gaps.len(), result.iterations + 1);
+    assert_float_eq!(result.solution.node_values(tree.root())[0], 50., abs <= 0.1);
+    assert_float_eq!(result.solution.expected_objective(), 50., abs <= 0.5);
+}
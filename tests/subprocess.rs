@@ -0,0 +1,86 @@
+use good_lp::solvers::subprocess::{serve_subprocess, SubprocessSolver};
+use good_lp::{constraint, default_solver, variables, ResolutionError, Solution, SolverModel};
+
+/// The child process of the other tests, which run this test binary again
+#[test]
+fn child() {
+    serve_subprocess(default_solver);
+}
+
+/// A child process that crashes the first time it is started
+#[test]
+fn flaky_child() {
+    if let Some(marker) = std::env::var_os("FLAKY_MARKER") {
+        if std::fs::metadata(&marker).is_err() {
+            std::fs::write(&marker, "crashed").unwrap();
+            std::process::abort();
+        }
+    }
+    serve_subprocess(default_solver);
+}
+
+/// A child process that always crashes
+#[test]
+fn crashing_child() {
+    if std::env::var_os("CRASH").is_some() {
+        std::process::abort();
+    }
+}
+
+fn solver(test: &str) -> SubprocessSolver {
+    SubprocessSolver::current_exe()
+        .unwrap()
+        .arg(test)
+        .arg("--exact")
+        .arg("--quiet")
+}
+
+#[test]
+fn solves_in_a_child_process() {
+    variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+    let solution = vars
+        .maximise(x + y)
+        .using(solver("child"))
+        .with(constraint!(x + 2 * y <= 12))
+        .with(constraint!(x <= 4))
+        .solve()
+        .unwrap();
+    assert!((solution.value(x) - 4.).abs() < 1e-6);
+    assert!((solution.value(y) - 4.).abs() < 1e-6);
+}
+
+#[test]
+fn infeasible_problems_are_reported() {
+    variables! {vars: 0 <= x <= 10;}
+    let result = vars
+        .maximise(x)
+        .using(solver("child"))
+        .with(constraint!(x >= 11))
+        .solve();
+    assert_eq!(result.err(), Some(ResolutionError::Infeasible));
+}
+
+#[test]
+fn crashes_are_retried() {
+    let marker = std::env::temp_dir().join(format!("good_lp_flaky_{}", std::process::id()));
+    let _ = std::fs::remove_file(&marker);
+    variables! {vars: 0 <= x <= 10;}
+    let problem = vars.maximise(x).with(constraint!(x <= 3));
+    let flaky = solver("flaky_child").env("FLAKY_MARKER", &marker);
+    let error = problem.clone().using(flaky.clone()).solve().err().unwrap();
+    assert!(error.to_string().contains("without a result"), "{}", error);
+    let _ = std::fs::remove_file(&marker);
+    let solution = problem.using(flaky.retries(1)).solve().unwrap();
+    assert!((solution.value(x) - 3.).abs() < 1e-6);
+    std::fs::remove_file(&marker).unwrap();
+}
+
+#[test]
+fn crashes_are_reported() {
+    variables! {vars: 0 <= x <= 10;}
+    let result = vars
+        .maximise(x)
+        .using(solver("crashing_child").env("CRASH", "1").retries(2))
+        .solve();
+    assert!(result.is_err());
+}
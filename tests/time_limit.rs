@@ -0,0 +1,21 @@
+#![cfg(feature = "coin_cbc")]
+use std::time::Duration;
+
+use good_lp::{coin_cbc, constraint, variable, variables, ModelWithTimeLimit, Solution};
+use good_lp::{SolutionStatus, SolverModel};
+
+#[test]
+fn solves_before_the_time_limit() {
+    let mut vars = variables!();
+    let x = vars.add(variable().integer().max(10));
+    let y = vars.add(variable().integer().max(10));
+    let solution = vars
+        .maximise(3 * x + 2 * y)
+        .using(coin_cbc)
+        .with(constraint!(2 * x + 3 * y <= 17))
+        .with_time_limit(Duration::from_secs(30))
+        .solve()
+        .unwrap();
+    assert_eq!(solution.status(), SolutionStatus::Optimal);
+    assert_eq!(solution.eval(3 * x + 2 * y), 24.);
+}
@@ -0,0 +1,77 @@
+#![cfg(all(feature = "tracing", feature = "minilp"))]
+//! Model building and resolutions are reported as tracing spans and events.
+use std::sync::{Arc, Mutex};
+
+use good_lp::{constraint, variables, SolverModel};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records the names of the spans that are created, and the level and target of the events
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut records = self.0.lock().unwrap();
+        records.push(format!("span {}", span.metadata().name()));
+        Id::from_u64(records.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let record = format!("{} {}", metadata.level(), metadata.target());
+        self.0.lock().unwrap().push(record);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+fn traced(maximum: f64) -> Vec<String> {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        variables! {vars: 0 <= x <= 2;}
+        let _ = vars
+            .maximise(x)
+            .using(good_lp::minilp)
+            .with(constraint!(x <= maximum))
+            .solve();
+    });
+    let records = recorder.0.lock().unwrap();
+    records.clone()
+}
+
+#[test]
+fn build_and_solve_are_traced() {
+    assert_eq!(
+        traced(1.),
+        [
+            "span build_model",
+            "DEBUG good_lp",
+            "span solve",
+            "INFO good_lp"
+        ]
+    );
+}
+
+#[test]
+fn failures_are_warnings() {
+    assert_eq!(
+        traced(-1.),
+        [
+            "span build_model",
+            "DEBUG good_lp",
+            "span solve",
+            "WARN good_lp"
+        ]
+    );
+}
@@ -0,0 +1,33 @@
+use float_eq::assert_float_eq;
+use good_lp::solvers::ObjectiveDirection;
+use good_lp::tradeoff::ObjectiveGroups;
+use good_lp::{constraint, default_solver, variables, Solution};
+
+#[test]
+fn weights_apply_to_the_next_resolutions() {
+    variables! {vars: 0 <= a <= 10; 0 <= b <= 10;}
+    let groups = ObjectiveGroups::new().group("a", a, 2.).group("b", b, 1.);
+    assert_eq!(groups.names().collect::<Vec<_>>(), vec!["a", "b"]);
+    let mut model = groups
+        .problem(vars, ObjectiveDirection::Maximisation)
+        .with(constraint!(a + b <= 10))
+        .using(default_solver);
+    assert_float_eq!(model.solve().unwrap().value(a), 10., abs <= 1e-6);
+    model.set_group_weight("b", 3.);
+    assert_eq!(model.group_weight("b"), 3.);
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.group_value("b"), 10., abs <= 1e-6);
+    assert_float_eq!(solution.group_value("a"), 0., abs <= 1e-6);
+    // The weights are kept until they are changed again
+    model.add_constraint(constraint!(b <= 4));
+    assert_float_eq!(model.solve().unwrap().value(a), 6., abs <= 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "no objective group named \"cost\"")]
+fn unknown_group() {
+    variables! {vars: 0 <= a <= 10;}
+    let mut groups = ObjectiveGroups::new().group("a", a, 1.);
+    groups.set_weight("cost", 1.);
+    drop(vars);
+}
@@ -51,3 +51,182 @@ fn debug_format() {
         expr_str
     )
 }
+
+#[test]
+fn freeze_where() {
+    use good_lp::{constraint, default_solver, variable, Solution, SolverModel};
+    use std::collections::HashMap;
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 10).name("x"));
+    let y = vars.add(variable().clamp(0, 10).name("y"));
+    let previous: HashMap<_, _> = vec![(x, 2.), (y, 3.)].into_iter().collect();
+    let mut problem = vars.maximise(x + y).with(constraint!(x + y <= 15));
+    assert_eq!(
+        problem.freeze_where(&previous, |_, def| def.get_name() == "x"),
+        1
+    );
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert!((solution.value(x) - 2.).abs() < 1e-6);
+    assert!((solution.value(y) - 10.).abs() < 1e-6);
+}
+
+#[test]
+fn bounds_presets_are_valid() {
+    use good_lp::variable;
+    use good_lp::variable::BoundsError;
+    for def in [
+        variable().fraction(),
+        variable().percentage(),
+        variable().nonneg_int(),
+        variable().binary(),
+        variable().free(),
+        variable().integer().clamp(1.5, 2.5),
+    ] {
+        assert_eq!(def.validate(), Ok(()));
+    }
+    assert_eq!(variable().min(f64::NAN).validate(), Err(BoundsError::NaN));
+    assert_eq!(
+        variable().percentage().max(-1).validate(),
+        Err(BoundsError::Crossed { min: 0., max: -1. })
+    );
+}
+
+#[test]
+fn domain_is_sorted_and_bounds_the_variable() {
+    use good_lp::variable;
+    use good_lp::variable::BoundsError;
+    let def = variable().integer().domain(&[25, 0, 10, 5, 10]);
+    assert_eq!(def.get_domain(), Some(&[0., 5., 10., 25.][..]));
+    assert_eq!(def.validate(), Ok(()));
+    assert_eq!(
+        variable().integer().domain(&[0.5, 1.5]).validate(),
+        Err(BoundsError::EmptyDomain { min: 0.5, max: 1.5 })
+    );
+    assert_eq!(
+        variable().domain(&[2, 4]).max(3).min(3).validate(),
+        Err(BoundsError::EmptyDomain { min: 3., max: 3. })
+    );
+}
+
+#[test]
+#[cfg(not(any(feature = "minilp", feature = "highs")))]
+fn domain_selects_a_single_value() {
+    use good_lp::{constraint, default_solver, variable, Solution, SolverModel};
+    let mut vars = variables!();
+    let diameter = vars.add(variable().domain(&[0.5, 0.8, 1.2]).name("diameter"));
+    let problem = vars.minimise(diameter).with(constraint!(diameter >= 0.6));
+    assert_eq!(problem.variables().len(), 4);
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert!((solution.value(diameter) - 0.8).abs() < 1e-6);
+}
+
+#[test]
+fn strict_inequality_policy_applies_to_existing_constraints() {
+    use good_lp::constraint::StrictInequality;
+    use good_lp::{constraint, default_solver, Solution, SolverModel};
+    variables! {vars: 0 <= x <= 10;}
+    let mut problem = vars.minimise(x).with(constraint!(x > 2));
+    problem.set_strict_inequality(StrictInequality::EpsilonRelax(0.25));
+    problem.add_constraint(constraint!(x > 1));
+    let solution = problem.using(default_solver).solve().unwrap();
+    assert!((solution.value(x) - 2.25).abs() < 1e-6);
+}
+
+#[test]
+fn strict_inequalities_can_be_forbidden() {
+    use good_lp::constraint::StrictInequality;
+    use good_lp::{constraint, default_solver, SolverModel};
+    variables! {vars: 0 <= x <= 10;}
+    let mut problem = vars.minimise(x).with(constraint!(x < 2));
+    problem.set_strict_inequality(StrictInequality::Error);
+    let error = problem.using(default_solver).solve().err().unwrap();
+    assert!(error.to_string().contains("is not allowed"), "{}", error);
+}
+
+#[test]
+fn strict_inequality_policy_applies_to_the_model() {
+    use good_lp::constraint::StrictInequality;
+    use good_lp::{constraint, default_solver, Solution, SolverModel};
+    variables! {vars: 0 <= x <= 10;}
+    let mut problem = vars.minimise(x);
+    problem.set_strict_epsilon(0.5);
+    let model = problem.using(default_solver).with(constraint!(x > 1));
+    let solution = model.solve().unwrap();
+    assert!((solution.value(x) - 1.5).abs() < 1e-6);
+
+    variables! {vars: 0 <= y <= 10;}
+    let mut problem = vars.minimise(y).with(constraint!(y >= 1));
+    problem.set_strict_inequality(StrictInequality::Error);
+    let model = problem
+        .using(default_solver)
+        .with(constraint!(y > 1).set_name("above".to_string()));
+    let error = model.solve().err().unwrap();
+    assert!(error.to_string().contains("\"above\""), "{}", error);
+}
+
+#[test]
+fn indexed_families() {
+    use good_lp::{default_solver, Solution, SolverModel};
+    let sizes = [2, 3];
+    let n = 2;
+    variables! {vars:
+        0 <= pick[size in sizes.iter().copied()] <= 1;
+        0 <= spare[n] <= 4;
+        total;
+    }
+    assert_eq!(pick.len(), 2);
+    assert_eq!(spare.len(), 2);
+    let objective: Expression = pick.iter().map(|(&size, &v)| size * v).sum();
+    let solution = vars
+        .maximise(objective + spare[0] + spare[1] - total)
+        .using(default_solver)
+        .with(good_lp::constraint!(total >= 0))
+        .solve()
+        .unwrap();
+    assert_eq!(solution.value(pick[&3]), 1.);
+    assert_eq!(solution.value(spare[1]), 4.);
+}
+
+#[test]
+fn matrix_rows_and_columns() {
+    use good_lp::{default_solver, variable, Solution, SolverModel};
+    let mut vars = variables!();
+    let x = vars.add_matrix(variable().clamp(0, 1), 2, 3);
+    assert_eq!((x.n_rows(), x.n_columns()), (2, 3));
+    assert_eq!(x.row(1)[2], x[(1, 2)]);
+    assert_eq!(x.column(2)[1], x[(1, 2)]);
+    let solution = vars
+        .maximise(x.sum())
+        .using(default_solver)
+        .with(good_lp::constraint!(x.row_sum(0) <= 1))
+        .with(good_lp::constraint!(x.column_sum(2) <= 0))
+        .solve()
+        .unwrap();
+    let values = x.values(&solution);
+    assert!((values[0].iter().sum::<f64>() - 1.).abs() < 1e-6);
+    assert!((values[1][2]).abs() < 1e-6);
+    assert!((solution.eval(x.sum()) - 3.).abs() < 1e-6);
+}
+
+#[test]
+fn map_keeps_the_order_of_the_keys() {
+    use good_lp::variable;
+    let mut vars = variables!();
+    let x = vars.add_map(variable(), vec!["b", "a", "c"]);
+    assert_eq!(x.keys(), &["b", "a", "c"]);
+    assert_eq!(
+        x.iter().map(|(&k, _)| k).collect::<Vec<_>>(),
+        ["b", "a", "c"]
+    );
+    assert_eq!(x.get("a"), Some(&x["a"]));
+    assert_eq!(x.get("d"), None);
+    assert_eq!(x.sum(), x["a"] + x["b"] + x["c"]);
+}
+
+#[test]
+#[should_panic(expected = "a key appears twice")]
+fn map_with_duplicate_keys() {
+    use good_lp::variable;
+    let mut vars = variables!();
+    vars.add_map(variable(), vec![1, 2, 1]);
+}
@@ -0,0 +1,46 @@
+use good_lp::{variables, ModelWithVerbosity, Solution, SolverModel, Verbosity};
+
+#[test]
+#[cfg(feature = "coin_cbc")]
+fn silent_cbc() {
+    variables! {vars: 0 <= x <= 2; 0 <= y (integer) <= 3;}
+    for &verbosity in &[Verbosity::Silent, Verbosity::Summary, Verbosity::Full] {
+        let solution = vars
+            .clone()
+            .maximise(x + y)
+            .using(good_lp::coin_cbc)
+            .with_verbosity(verbosity)
+            .solve()
+            .unwrap();
+        assert_eq!(solution.value(x) + solution.value(y), 5.);
+    }
+}
+
+#[test]
+#[cfg(feature = "minilp")]
+fn silent_minilp() {
+    variables! {vars: 0 <= x <= 2;}
+    let solution = vars
+        .maximise(x)
+        .using(good_lp::minilp)
+        .with_verbosity(Verbosity::Silent)
+        .solve()
+        .unwrap();
+    assert_eq!(solution.value(x), 2.);
+}
+
+#[test]
+#[cfg(feature = "highs")]
+fn silent_highs() {
+    variables! {vars: 0 <= x <= 2; 0 <= y <= 3;}
+    for &verbosity in &[Verbosity::Silent, Verbosity::Summary, Verbosity::Full] {
+        let solution = vars
+            .clone()
+            .maximise(x + y)
+            .using(good_lp::highs)
+            .with_verbosity(verbosity)
+            .solve()
+            .unwrap();
+        assert_eq!(solution.value(x) + solution.value(y), 5.);
+    }
+}